@@ -1,8 +1,8 @@
 use avro_schema::schema::{
-    BytesLogical, Field as AvroField, Fixed, FixedLogical, IntLogical, LongLogical, Record,
+    BytesLogical, Enum, Field as AvroField, Fixed, FixedLogical, IntLogical, LongLogical, Record,
     Schema as AvroSchema,
 };
-use polars_error::{PolarsResult, polars_bail};
+use polars_error::{PolarsResult, polars_bail, polars_ensure};
 
 use crate::datatypes::*;
 
@@ -23,10 +23,55 @@ pub fn to_record(schema: &ArrowSchema, name: String) -> PolarsResult<Record> {
 }
 
 fn field_to_field(field: &Field, name_counter: &mut i32) -> PolarsResult<AvroField> {
-    let schema = type_to_schema(field.dtype(), field.is_nullable, name_counter)?;
+    let schema = field_to_schema(field, name_counter)?;
     Ok(AvroField::new(field.name.to_string(), schema))
 }
 
+/// Like [`type_to_schema`], but also maps Polars enums, which are only recognizable from the
+/// field metadata, to Avro enums.
+fn field_to_schema(field: &Field, name_counter: &mut i32) -> PolarsResult<AvroSchema> {
+    let Some(encoded) = field
+        .metadata
+        .as_ref()
+        .and_then(|md| md.get(DTYPE_ENUM_VALUES))
+    else {
+        return type_to_schema(field.dtype(), field.is_nullable, name_counter);
+    };
+
+    // The categories are encoded as `<len in ascii>;<payload>`.
+    let mut encoded = encoded.as_str();
+    let mut symbols = Vec::new();
+    while let Some(pos) = encoded.find(';') {
+        let len = encoded[..pos].parse::<usize>().unwrap();
+        let (symbol, remainder) = encoded[pos + 1..].split_at(len);
+        polars_ensure!(
+            is_avro_name(symbol),
+            InvalidOperation: "cannot write Enum category {:?} of column {:?} as an Avro enum \
+            symbol, symbols must match [A-Za-z_][A-Za-z0-9_]*; consider casting the column to \
+            String",
+            symbol,
+            field.name,
+        );
+        symbols.push(symbol.to_string());
+        encoded = remainder;
+    }
+
+    let schema = AvroSchema::Enum(Enum::new(_get_field_name(name_counter).as_str(), symbols));
+    Ok(if field.is_nullable {
+        AvroSchema::Union(vec![AvroSchema::Null, schema])
+    } else {
+        schema
+    })
+}
+
+fn is_avro_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 fn type_to_schema(
     dtype: &ArrowDataType,
     is_nullable: bool,
@@ -60,11 +105,7 @@ fn _type_to_schema(dtype: &ArrowDataType, name_counter: &mut i32) -> PolarsResul
         ArrowDataType::Utf8 => AvroSchema::String(None),
         ArrowDataType::LargeUtf8 => AvroSchema::String(None),
         ArrowDataType::LargeList(inner) | ArrowDataType::List(inner) => {
-            AvroSchema::Array(Box::new(type_to_schema(
-                &inner.dtype,
-                inner.is_nullable,
-                name_counter,
-            )?))
+            AvroSchema::Array(Box::new(field_to_schema(inner, name_counter)?))
         },
         ArrowDataType::Struct(fields) => AvroSchema::Record(Record::new(
             _get_field_name(name_counter),
@@ -82,6 +123,18 @@ fn _type_to_schema(dtype: &ArrowDataType, name_counter: &mut i32) -> PolarsResul
         ArrowDataType::Timestamp(TimeUnit::Microsecond, None) => {
             AvroSchema::Long(Some(LongLogical::LocalTimestampMicros))
         },
+        // Avro timestamps with a time zone are instants in UTC.
+        ArrowDataType::Timestamp(TimeUnit::Millisecond, Some(_)) => {
+            AvroSchema::Long(Some(LongLogical::TimestampMillis))
+        },
+        ArrowDataType::Timestamp(TimeUnit::Microsecond, Some(_)) => {
+            AvroSchema::Long(Some(LongLogical::TimestampMicros))
+        },
+        // Truncating to microseconds would silently lose data.
+        ArrowDataType::Timestamp(TimeUnit::Nanosecond, _) => polars_bail!(
+            InvalidOperation: "cannot write nanosecond timestamps to Avro; \
+            consider casting the column to Datetime(\"us\") first"
+        ),
         ArrowDataType::Interval(IntervalUnit::MonthDayNano) => {
             let mut fixed = Fixed::new("", 12);
             fixed.logical = Some(FixedLogical::Duration);
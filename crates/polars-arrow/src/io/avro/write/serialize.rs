@@ -4,7 +4,7 @@ use avro_schema::write::encode;
 use super::super::super::iterator::*;
 use crate::array::*;
 use crate::bitmap::utils::ZipValidity;
-use crate::datatypes::{ArrowDataType, IntervalUnit, PhysicalType, PrimitiveType};
+use crate::datatypes::{ArrowDataType, IntegerType, IntervalUnit, PhysicalType, PrimitiveType};
 use crate::offset::Offset;
 use crate::types::months_days_ns;
 
@@ -270,6 +270,41 @@ pub fn new_serializer<'a>(array: &'a dyn Array, schema: &AvroSchema) -> BoxSeria
             fixed_size_binary_required(array.as_any().downcast_ref().unwrap())
         },
 
+        (PhysicalType::Dictionary(IntegerType::UInt32), AvroSchema::Union(_)) => {
+            // The keys of enums index into their symbols.
+            let keys = array
+                .as_any()
+                .downcast_ref::<DictionaryArray<u32>>()
+                .unwrap()
+                .keys();
+            Box::new(BufStreamingIterator::new(
+                keys.iter(),
+                |x, buf| {
+                    if let Some(x) = x {
+                        buf.push(IS_VALID);
+                        encode::zigzag_encode(*x as i64, buf).unwrap();
+                    } else {
+                        buf.push(IS_NULL);
+                    }
+                },
+                vec![],
+            ))
+        },
+        (PhysicalType::Dictionary(IntegerType::UInt32), AvroSchema::Enum(_)) => {
+            let keys = array
+                .as_any()
+                .downcast_ref::<DictionaryArray<u32>>()
+                .unwrap()
+                .keys();
+            Box::new(BufStreamingIterator::new(
+                keys.values().iter(),
+                |x, buf| {
+                    encode::zigzag_encode(*x as i64, buf).unwrap();
+                },
+                vec![],
+            ))
+        },
+
         (PhysicalType::Primitive(PrimitiveType::Int32), AvroSchema::Union(_)) => {
             let values = array
                 .as_any()
@@ -521,6 +556,7 @@ pub fn can_serialize(dtype: &ArrowDataType) -> bool {
             | LargeUtf8
             | LargeBinary
             | Interval(IntervalUnit::MonthDayNano)
+            | Dictionary(IntegerType::UInt32, _, _)
     )
 }
 
@@ -35,6 +35,9 @@ pub enum QuantileMethod {
     Midpoint,
     Linear,
     Equiprobable,
+    /// Estimate the quantile. Engines that keep all values of a window or group
+    /// may compute it exactly, the same as [`QuantileMethod::Nearest`].
+    Approximate,
 }
 
 #[deprecated(note = "use QuantileMethod instead")]
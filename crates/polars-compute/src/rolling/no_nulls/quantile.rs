@@ -83,7 +83,7 @@ impl<
                     Some((mid + mid_plus_1) / (T::one() + T::one()))
                 };
             },
-            Nearest => {
+            Nearest | Approximate => {
                 let idx = ((length as f64) * self.prob) as usize;
                 std::cmp::min(idx, length - 1)
             },
@@ -205,7 +205,7 @@ where
         (true, _) => v_old, // If we hit the break exactly interpolation shouldn't matter
         (_, Lower) => v_old,
         (_, Higher) => vk,
-        (_, Nearest) => {
+        (_, Nearest | Approximate) => {
             if s - h > h - s_old {
                 v_old
             } else {
@@ -632,7 +632,7 @@ where
                     <<M as LenGet>::Item>::finish(proportion, vi, vj)
                 }
             },
-            Nearest => {
+            Nearest | Approximate => {
                 let idx = (valid_length_f * self.quantile) as usize;
                 let idx = std::cmp::min(idx, valid_length - 1);
                 self.inner.get(idx + null_count)
@@ -19,7 +19,7 @@ fn quantile_idx(
     let nonnull_count = (length - null_count) as f64;
     let float_idx = (nonnull_count - 1.0) * quantile + null_count as f64;
     let mut base_idx = match method {
-        QuantileMethod::Nearest => {
+        QuantileMethod::Nearest | QuantileMethod::Approximate => {
             let idx = float_idx.round() as usize;
             return (idx, 0.0, idx);
         },
@@ -56,8 +56,9 @@ fn midpoint_interpol<T: Float>(lower: T, upper: T) -> T {
     }
 }
 
+/// Get the quantile of the values in the slice, reordering them in the process.
 // Uses quickselect instead of sorting all data
-fn quantile_slice<T: ToPrimitive + TotalOrd + Copy>(
+pub fn quantile_slice<T: ToPrimitive + TotalOrd + Copy>(
     vals: &mut [T],
     quantile: f64,
     method: QuantileMethod,
@@ -81,6 +81,8 @@ impl Display for ErrString {
 #[derive(Debug, Clone)]
 pub enum PolarsError {
     AssertionError(ErrString),
+    /// The query was cancelled through its [`signals::CancellationToken`].
+    Cancelled(ErrString),
     ColumnNotFound(ErrString),
     ComputeError(ErrString),
     Duplicate(ErrString),
@@ -122,6 +124,7 @@ impl Display for PolarsError {
             | SQLSyntax(msg) => write!(f, "{msg}"),
 
             AssertionError(msg) => write!(f, "assertion failed: {msg}"),
+            Cancelled(msg) => write!(f, "cancelled: {msg}"),
             ColumnNotFound(msg) => write!(f, "not found: {msg}"),
             Duplicate(msg) => write!(f, "duplicate: {msg}"),
             IO { error, msg } => match msg {
@@ -240,6 +243,7 @@ impl PolarsError {
         use PolarsError::*;
         match self {
             AssertionError(msg) => AssertionError(func(msg).into()),
+            Cancelled(msg) => Cancelled(func(msg).into()),
             ColumnNotFound(msg) => ColumnNotFound(func(msg).into()),
             ComputeError(msg) => ComputeError(func(msg).into()),
             Duplicate(msg) => Duplicate(func(msg).into()),
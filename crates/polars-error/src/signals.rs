@@ -1,6 +1,15 @@
 use std::any::Any;
+use std::future::Future;
 use std::panic::{UnwindSafe, catch_unwind};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::{PolarsResult, polars_bail};
 
 /// Python hooks SIGINT to instead generate a KeyboardInterrupt exception.
 /// So we do the same to try and abort long-running computations and return to
@@ -113,3 +122,130 @@ fn unregister_catcher() {
         })
         .ok();
 }
+
+/// A token to cooperatively cancel a single query, optionally with a deadline
+/// after which the query is cancelled on its own.
+///
+/// Unlike the keyboard interrupt this only affects the queries the token is
+/// passed to. Those check it regularly with [`CancellationToken::check`] and
+/// return [`PolarsError::Cancelled`](crate::PolarsError::Cancelled) once it is
+/// cancelled. Clones of a token share their state.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    inner: Arc<CancellationState>,
+}
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    deadline: Option<Instant>,
+    // The tasks waiting for the token to be cancelled, by their registration id.
+    wakers: Mutex<Vec<(u64, Waker)>>,
+    next_waker_id: AtomicU64,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a token that is cancelled once `deadline` has passed.
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            inner: Arc::new(CancellationState {
+                deadline: Some(deadline),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Creates a token that is cancelled once `timeout` has elapsed from now.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.inner.deadline
+    }
+
+    /// Cancels the queries this token was passed to at the earliest
+    /// convenience.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+        for (_, waker) in self.inner.wakers.lock().drain(..) {
+            waker.wake();
+        }
+    }
+
+    /// Resolves once [`CancellationToken::cancel`] is called, this does not
+    /// wait for the deadline.
+    pub fn cancelled(&self) -> WaitForCancel<'_> {
+        WaitForCancel {
+            token: self,
+            waker_id: None,
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+            || self.inner.deadline.is_some_and(|d| Instant::now() >= d)
+    }
+
+    /// Returns an error if the token is cancelled or its deadline has passed.
+    #[inline]
+    pub fn check(&self) -> PolarsResult<()> {
+        if self.inner.cancelled.load(Ordering::Relaxed) {
+            polars_bail!(Cancelled: "query was cancelled");
+        }
+        if self.inner.deadline.is_some_and(|d| Instant::now() >= d) {
+            polars_bail!(Cancelled: "query exceeded its deadline");
+        }
+        Ok(())
+    }
+}
+
+/// Future returned by [`CancellationToken::cancelled`].
+pub struct WaitForCancel<'a> {
+    token: &'a CancellationToken,
+    waker_id: Option<u64>,
+}
+
+impl Future for WaitForCancel<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let inner = &self.token.inner;
+        if inner.cancelled.load(Ordering::Relaxed) {
+            return Poll::Ready(());
+        }
+
+        let mut wakers = inner.wakers.lock();
+        // Check again while holding the lock, cancel() sets the flag before
+        // taking the wakers.
+        if inner.cancelled.load(Ordering::Relaxed) {
+            return Poll::Ready(());
+        }
+        match self.waker_id {
+            Some(id) => {
+                if let Some((_, waker)) = wakers.iter_mut().find(|(i, _)| *i == id) {
+                    waker.clone_from(cx.waker());
+                }
+            },
+            None => {
+                let id = inner.next_waker_id.fetch_add(1, Ordering::Relaxed);
+                wakers.push((id, cx.waker().clone()));
+                drop(wakers);
+                self.waker_id = Some(id);
+            },
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for WaitForCancel<'_> {
+    fn drop(&mut self) {
+        if let Some(id) = self.waker_id {
+            self.token.inner.wakers.lock().retain(|(i, _)| *i != id);
+        }
+    }
+}
@@ -5,9 +5,11 @@ use polars_utils::arena::{Arena, Node};
 use super::*;
 use crate::reduce::count::CountReduce;
 use crate::reduce::first_last::{new_first_reduction, new_last_reduction};
+use crate::reduce::implode::new_implode_reduction;
 use crate::reduce::len::LenReduce;
 use crate::reduce::mean::new_mean_reduction;
 use crate::reduce::min_max::{new_max_reduction, new_min_reduction};
+use crate::reduce::quantile::{new_median_reduction, new_quantile_reduction};
 use crate::reduce::sum::new_sum_reduction;
 use crate::reduce::var_std::new_var_std_reduction;
 
@@ -47,11 +49,28 @@ pub fn into_reduction(
                 let count = Box::new(CountReduce::new(*include_nulls)) as Box<_>;
                 (count, *input)
             },
-            IRAggExpr::Quantile { .. } => todo!(),
-            IRAggExpr::Median(_) => todo!(),
+            IRAggExpr::Quantile {
+                expr,
+                quantile,
+                method,
+            } => {
+                let quantile = match expr_arena.get(*quantile) {
+                    AExpr::Literal(lv) => lv.to_any_value().and_then(|av| av.extract::<f64>()),
+                    _ => None,
+                };
+                let quantile = quantile.ok_or_else(
+                    || polars_err!(ComputeError: "quantile must be a scalar literal in a streaming reduction"),
+                )?;
+                (
+                    new_quantile_reduction(get_dt(*expr)?, quantile, *method)?,
+                    *expr,
+                )
+            },
+            IRAggExpr::Median(input) => (new_median_reduction(get_dt(*input)?)?, *input),
             IRAggExpr::NUnique(_) => todo!(),
-            IRAggExpr::Implode(_) => todo!(),
-            IRAggExpr::AggGroups(_) => todo!(),
+            IRAggExpr::Implode(input) => (new_implode_reduction(get_dt(*input)?), *input),
+            // The input is replaced by a row index column during lowering.
+            IRAggExpr::AggGroups(input) => (new_implode_reduction(get_dt(*input)?), *input),
         },
         AExpr::Len => {
             if let Some(first_column) = schema.iter_names().next() {
@@ -116,6 +116,22 @@ impl GroupedReduction for CountReduce {
         Ok(ca.into_series())
     }
 
+    fn has_spillable_state(&self) -> bool {
+        true
+    }
+
+    fn take_state(&mut self) -> PolarsResult<Vec<Series>> {
+        Ok(vec![natives_to_series(core::mem::take(&mut self.counts))])
+    }
+
+    fn new_from_state(&self, state: &[Series]) -> PolarsResult<Box<dyn GroupedReduction>> {
+        Ok(Box::new(Self {
+            counts: series_to_natives(&state[0])?,
+            evicted_counts: Vec::new(),
+            include_nulls: self.include_nulls,
+        }))
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -0,0 +1,209 @@
+#![allow(unsafe_op_in_unsafe_fn)]
+use arrow::legacy::prelude::LargeListArray;
+use arrow::offset::Offsets;
+use polars_core::error::constants::LENGTH_LIMIT_MSG;
+
+use super::*;
+
+/// Collects all values of each group into a list, in the order they were
+/// originally in.
+pub fn new_implode_reduction(dtype: DataType) -> Box<dyn GroupedReduction> {
+    Box::new(ImplodeReduction::new(dtype))
+}
+
+/// A group's values as (seq_id, index into the values buffer).
+type GroupValues = Vec<(u64, IdxSize)>;
+
+pub struct ImplodeReduction {
+    in_dtype: DataType,
+    // All values that were added to this reduction, one chunk per update.
+    values: Series,
+    groups: Vec<GroupValues>,
+    evicted_groups: Vec<GroupValues>,
+}
+
+impl ImplodeReduction {
+    fn new(in_dtype: DataType) -> Self {
+        Self {
+            values: Series::new_empty(PlSmallStr::EMPTY, &in_dtype),
+            in_dtype,
+            groups: Vec::new(),
+            evicted_groups: Vec::new(),
+        }
+    }
+
+    /// Appends the given values to the buffer, returning the index of the
+    /// first one.
+    fn append_values(&mut self, values: &Series) -> PolarsResult<IdxSize> {
+        let offset = self.values.len();
+        polars_ensure!(
+            offset + values.len() <= IdxSize::MAX as usize,
+            ComputeError: "{}", LENGTH_LIMIT_MSG
+        );
+        self.values.append(values)?;
+        Ok(offset as IdxSize)
+    }
+
+    /// Gathers the values of the given groups into a new reduction.
+    fn gather(&self, groups: Vec<GroupValues>) -> Self {
+        let idxs: Vec<IdxSize> = groups.iter().flatten().map(|(_, i)| *i).collect();
+        let values = unsafe { self.values.take_slice_unchecked(&idxs) };
+        let mut next_idx = 0;
+        let groups = groups
+            .into_iter()
+            .map(|grp| {
+                grp.into_iter()
+                    .map(|(seq, _)| {
+                        next_idx += 1;
+                        (seq, next_idx - 1)
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            in_dtype: self.in_dtype.clone(),
+            values,
+            groups,
+            evicted_groups: Vec::new(),
+        }
+    }
+}
+
+impl GroupedReduction for ImplodeReduction {
+    fn new_empty(&self) -> Box<dyn GroupedReduction> {
+        Box::new(Self::new(self.in_dtype.clone()))
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.groups.reserve(additional);
+    }
+
+    fn resize(&mut self, num_groups: IdxSize) {
+        self.groups.resize_with(num_groups as usize, Vec::new);
+    }
+
+    fn update_group(
+        &mut self,
+        values: &Column,
+        group_idx: IdxSize,
+        seq_id: u64,
+    ) -> PolarsResult<()> {
+        assert!(values.dtype() == &self.in_dtype);
+        let offset = self.append_values(values.as_materialized_series())?;
+        let grp = &mut self.groups[group_idx as usize];
+        grp.extend((offset..offset + values.len() as IdxSize).map(|i| (seq_id, i)));
+        Ok(())
+    }
+
+    unsafe fn update_groups_while_evicting(
+        &mut self,
+        values: &Column,
+        subset: &[IdxSize],
+        group_idxs: &[EvictIdx],
+        seq_id: u64,
+    ) -> PolarsResult<()> {
+        assert!(values.dtype() == &self.in_dtype);
+        assert!(subset.len() == group_idxs.len());
+        let values = values.as_materialized_series().take_slice_unchecked(subset);
+        let offset = self.append_values(&values)?;
+        // SAFETY: indices are in-bounds guaranteed by trait.
+        for (i, g) in group_idxs.iter().enumerate() {
+            let grp = self.groups.get_unchecked_mut(g.idx());
+            if g.should_evict() {
+                self.evicted_groups.push(core::mem::take(grp));
+            }
+            grp.push((seq_id, offset + i as IdxSize));
+        }
+        Ok(())
+    }
+
+    unsafe fn combine_subset(
+        &mut self,
+        other: &dyn GroupedReduction,
+        subset: &[IdxSize],
+        group_idxs: &[IdxSize],
+    ) -> PolarsResult<()> {
+        let other = other.as_any().downcast_ref::<Self>().unwrap();
+        assert!(self.in_dtype == other.in_dtype);
+        assert!(subset.len() == group_idxs.len());
+
+        // SAFETY: indices are in-bounds guaranteed by trait.
+        let other_idxs: Vec<IdxSize> = subset
+            .iter()
+            .flat_map(|i| other.groups.get_unchecked(*i as usize))
+            .map(|(_, idx)| *idx)
+            .collect();
+        let values = other.values.take_slice_unchecked(&other_idxs);
+        let mut next_idx = self.append_values(&values)?;
+        for (i, g) in subset.iter().zip(group_idxs) {
+            let grp = self.groups.get_unchecked_mut(*g as usize);
+            for (seq, _) in other.groups.get_unchecked(*i as usize) {
+                grp.push((*seq, next_idx));
+                next_idx += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn take_evictions(&mut self) -> Box<dyn GroupedReduction> {
+        let evicted_groups = core::mem::take(&mut self.evicted_groups);
+        let evicted = self.gather(evicted_groups);
+
+        // The values of the evicted groups are still in our buffer, compact it
+        // once the majority of it is no longer referenced.
+        let num_live: usize = self.groups.iter().map(|grp| grp.len()).sum();
+        if 2 * num_live < self.values.len() {
+            let groups = core::mem::take(&mut self.groups);
+            *self = self.gather(groups);
+        }
+
+        Box::new(evicted)
+    }
+
+    fn finalize(&mut self) -> PolarsResult<Series> {
+        // Values of a group may have been added out of order, but each update
+        // has values of a single morsel in order, so a stable sort on the
+        // sequence id restores the original order.
+        let mut groups = core::mem::take(&mut self.groups);
+        let mut offsets = Vec::with_capacity(groups.len() + 1);
+        offsets.push(0i64);
+        let mut idxs = Vec::with_capacity(self.values.len());
+        for grp in &mut groups {
+            grp.sort_by_key(|(seq, _)| *seq);
+            idxs.extend(grp.iter().map(|(_, i)| *i));
+            offsets.push(idxs.len() as i64);
+        }
+        drop(groups);
+
+        let values = core::mem::replace(
+            &mut self.values,
+            Series::new_empty(PlSmallStr::EMPTY, &self.in_dtype),
+        );
+        let values = unsafe { values.take_slice_unchecked(&idxs) }.rechunk();
+        let arr = values.chunks()[0].clone();
+        let dtype = LargeListArray::default_datatype(arr.dtype().clone());
+        // SAFETY: offsets are monotonically increasing.
+        let arr = LargeListArray::new(
+            dtype,
+            unsafe { Offsets::new_unchecked(offsets) }.into(),
+            arr,
+            None,
+        );
+        let out = unsafe {
+            ListChunked::from_chunks_and_dtype(
+                PlSmallStr::EMPTY,
+                vec![Box::new(arr)],
+                DataType::List(Box::new(self.in_dtype.clone())),
+            )
+        };
+        Ok(out.into_series())
+    }
+
+    fn is_order_sensitive(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
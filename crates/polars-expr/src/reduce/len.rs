@@ -87,6 +87,21 @@ impl GroupedReduction for LenReduce {
         Ok(ca.into_series())
     }
 
+    fn has_spillable_state(&self) -> bool {
+        true
+    }
+
+    fn take_state(&mut self) -> PolarsResult<Vec<Series>> {
+        Ok(vec![natives_to_series(core::mem::take(&mut self.groups))])
+    }
+
+    fn new_from_state(&self, state: &[Series]) -> PolarsResult<Box<dyn GroupedReduction>> {
+        Ok(Box::new(Self {
+            groups: series_to_natives(&state[0])?,
+            evictions: Vec::new(),
+        }))
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
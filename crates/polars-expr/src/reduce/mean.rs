@@ -120,6 +120,20 @@ where
         assert!(m.is_none());
         Ok(finish_output(v, dtype))
     }
+
+    fn has_spillable_state(&self) -> bool {
+        true
+    }
+
+    fn values_to_state(&self, v: Vec<Self::Value>) -> PolarsResult<Vec<Series>> {
+        let (sums, counts): (Vec<_>, Vec<_>) = v.into_iter().unzip();
+        Ok(vec![natives_to_series(sums), counts_to_series(counts)])
+    }
+
+    fn values_from_state(&self, state: &[Series]) -> PolarsResult<Vec<Self::Value>> {
+        let sums = series_to_natives::<f64>(&state[0])?.into_iter();
+        Ok(sums.zip(series_to_counts(&state[1])?).collect())
+    }
 }
 
 #[derive(Clone)]
@@ -165,4 +179,18 @@ impl Reducer for BoolMeanReducer {
             .collect_ca(PlSmallStr::EMPTY);
         Ok(ca.into_series())
     }
+
+    fn has_spillable_state(&self) -> bool {
+        true
+    }
+
+    fn values_to_state(&self, v: Vec<Self::Value>) -> PolarsResult<Vec<Series>> {
+        let (sums, counts): (Vec<_>, Vec<_>) = v.into_iter().unzip();
+        Ok(vec![counts_to_series(sums), counts_to_series(counts)])
+    }
+
+    fn values_from_state(&self, state: &[Series]) -> PolarsResult<Vec<Self::Value>> {
+        let sums = series_to_counts(&state[0])?;
+        Ok(sums.zip(series_to_counts(&state[1])?).collect())
+    }
 }
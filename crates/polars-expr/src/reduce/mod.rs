@@ -2,9 +2,11 @@
 mod convert;
 mod count;
 mod first_last;
+mod implode;
 mod len;
 mod mean;
 mod min_max;
+mod quantile;
 mod sum;
 mod var_std;
 
@@ -12,7 +14,7 @@ use std::any::Any;
 use std::borrow::Cow;
 use std::marker::PhantomData;
 
-use arrow::array::{Array, PrimitiveArray, StaticArray};
+use arrow::array::{Array, BooleanArray, PrimitiveArray, StaticArray};
 use arrow::bitmap::{Bitmap, BitmapBuilder, MutableBitmap};
 pub use convert::into_reduction;
 pub use min_max::{new_max_reduction, new_min_reduction};
@@ -103,6 +105,33 @@ pub trait GroupedReduction: Any + Send + Sync {
     /// After this operation the number of groups is reset to 0.
     fn finalize(&mut self) -> PolarsResult<Series>;
 
+    /// Whether the result depends on the order of the values within a group
+    /// beyond what the seq_id can resolve. Such reductions must receive all
+    /// values of a group from the same morsel in a single update, in order.
+    fn is_order_sensitive(&self) -> bool {
+        false
+    }
+
+    /// Whether the state of the groups can be taken out as Series by
+    /// [`GroupedReduction::take_state`], e.g. to spill it to disk.
+    fn has_spillable_state(&self) -> bool {
+        false
+    }
+
+    /// Returns the state of every group as Series of equal length, from which
+    /// [`GroupedReduction::new_from_state`] restores the groups.
+    ///
+    /// After this operation the number of groups is reset to 0.
+    fn take_state(&mut self) -> PolarsResult<Vec<Series>> {
+        polars_bail!(InvalidOperation: "reduction state can't be taken")
+    }
+
+    /// Returns a new reduction holding the groups of a state returned by
+    /// [`GroupedReduction::take_state`].
+    fn new_from_state(&self, _state: &[Series]) -> PolarsResult<Box<dyn GroupedReduction>> {
+        polars_bail!(InvalidOperation: "reduction state can't be restored")
+    }
+
     /// Returns this GroupedReduction as a dyn Any.
     fn as_any(&self) -> &dyn Any;
 }
@@ -131,6 +160,53 @@ pub trait Reducer: Send + Sync + Clone + 'static {
         m: Option<Bitmap>,
         dtype: &DataType,
     ) -> PolarsResult<Series>;
+
+    /// Whether the values can be converted to and from Series, see
+    /// [`GroupedReduction::take_state`]. Must return true iff
+    /// [`Reducer::values_to_state`] and [`Reducer::values_from_state`] are
+    /// implemented.
+    fn has_spillable_state(&self) -> bool {
+        false
+    }
+
+    fn values_to_state(&self, _v: Vec<Self::Value>) -> PolarsResult<Vec<Series>> {
+        polars_bail!(InvalidOperation: "reduction state can't be taken")
+    }
+
+    fn values_from_state(&self, _state: &[Series]) -> PolarsResult<Vec<Self::Value>> {
+        polars_bail!(InvalidOperation: "reduction state can't be restored")
+    }
+}
+
+/// Converts the values into a Series of their physical type, used for the
+/// spillable state of reductions.
+fn natives_to_series<N: NumericNative>(v: Vec<N>) -> Series {
+    let arr = Box::new(PrimitiveArray::from_vec(v));
+    let dtype = N::PolarsType::get_static_dtype();
+    unsafe { Series::from_chunks_and_dtype_unchecked(PlSmallStr::EMPTY, vec![arr], &dtype) }
+}
+
+/// The inverse of [`natives_to_series`].
+fn series_to_natives<N: NumericNative>(s: &Series) -> PolarsResult<Vec<N>> {
+    polars_ensure!(
+        s.dtype() == &N::PolarsType::get_static_dtype() && !s.has_nulls(),
+        ComputeError: "invalid reduction state of type {}", s.dtype()
+    );
+    Ok(s.chunks()
+        .iter()
+        .flat_map(|arr| {
+            let arr = arr.as_any().downcast_ref::<PrimitiveArray<N>>().unwrap();
+            arr.values().iter().copied()
+        })
+        .collect())
+}
+
+fn counts_to_series(v: impl IntoIterator<Item = usize>) -> Series {
+    natives_to_series(v.into_iter().map(|c| c as u64).collect())
+}
+
+fn series_to_counts(s: &Series) -> PolarsResult<impl Iterator<Item = usize>> {
+    Ok(series_to_natives::<u64>(s)?.into_iter().map(|c| c as usize))
 }
 
 pub trait NumericReduction: Send + Sync + 'static {
@@ -204,6 +280,18 @@ impl<R: NumericReduction> Reducer for NumReducer<R> {
         let arr = Box::new(PrimitiveArray::<Self::Value>::from_vec(v).with_validity(m));
         Ok(unsafe { Series::from_chunks_and_dtype_unchecked(PlSmallStr::EMPTY, vec![arr], dtype) })
     }
+
+    fn has_spillable_state(&self) -> bool {
+        true
+    }
+
+    fn values_to_state(&self, v: Vec<Self::Value>) -> PolarsResult<Vec<Series>> {
+        Ok(vec![natives_to_series(v)])
+    }
+
+    fn values_from_state(&self, state: &[Series]) -> PolarsResult<Vec<Self::Value>> {
+        series_to_natives(&state[0])
+    }
 }
 
 pub struct VecGroupedReduction<R: Reducer> {
@@ -336,6 +424,24 @@ where
         self.reducer.finish(v, None, &self.in_dtype)
     }
 
+    fn has_spillable_state(&self) -> bool {
+        self.reducer.has_spillable_state()
+    }
+
+    fn take_state(&mut self) -> PolarsResult<Vec<Series>> {
+        let v = core::mem::take(&mut self.values);
+        self.reducer.values_to_state(v)
+    }
+
+    fn new_from_state(&self, state: &[Series]) -> PolarsResult<Box<dyn GroupedReduction>> {
+        Ok(Box::new(Self {
+            values: self.reducer.values_from_state(state)?,
+            evicted_values: Vec::new(),
+            in_dtype: self.in_dtype.clone(),
+            reducer: self.reducer.clone(),
+        }))
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -475,6 +581,36 @@ where
         self.reducer.finish(v, Some(m.freeze()), &self.in_dtype)
     }
 
+    fn has_spillable_state(&self) -> bool {
+        self.reducer.has_spillable_state()
+    }
+
+    fn take_state(&mut self) -> PolarsResult<Vec<Series>> {
+        let v = core::mem::take(&mut self.values);
+        let m = core::mem::take(&mut self.mask);
+        let mut state = self.reducer.values_to_state(v)?;
+        let mask = BooleanArray::new(ArrowDataType::Boolean, m.freeze(), None);
+        state.push(BooleanChunked::with_chunk(PlSmallStr::EMPTY, mask).into_series());
+        Ok(state)
+    }
+
+    fn new_from_state(&self, state: &[Series]) -> PolarsResult<Box<dyn GroupedReduction>> {
+        let (mask, values) = state.split_last().unwrap();
+        let mask = mask.bool()?;
+        polars_ensure!(!mask.has_nulls(), ComputeError: "invalid reduction state mask");
+        Ok(Box::new(Self {
+            values: self.reducer.values_from_state(values)?,
+            mask: mask
+                .downcast_iter()
+                .flat_map(|arr| arr.values().iter())
+                .collect(),
+            evicted_values: Vec::new(),
+            evicted_mask: BitmapBuilder::new(),
+            in_dtype: self.in_dtype.clone(),
+            reducer: self.reducer.clone(),
+        }))
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -554,6 +690,27 @@ impl GroupedReduction for NullGroupedReduction {
         ))
     }
 
+    fn has_spillable_state(&self) -> bool {
+        true
+    }
+
+    fn take_state(&mut self) -> PolarsResult<Vec<Series>> {
+        let num_groups = core::mem::replace(&mut self.num_groups, 0) as usize;
+        Ok(vec![Series::full_null(
+            PlSmallStr::EMPTY,
+            num_groups,
+            &DataType::Null,
+        )])
+    }
+
+    fn new_from_state(&self, state: &[Series]) -> PolarsResult<Box<dyn GroupedReduction>> {
+        Ok(Box::new(Self {
+            num_groups: state[0].len() as IdxSize,
+            num_evictions: 0,
+            dtype: self.dtype.clone(),
+        }))
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
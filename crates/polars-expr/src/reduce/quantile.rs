@@ -0,0 +1,300 @@
+use std::marker::PhantomData;
+
+use arrow::legacy::prelude::LargeListArray;
+use arrow::offset::Offsets;
+use num_traits::AsPrimitive;
+use polars_compute::rolling::QuantileMethod;
+use polars_core::with_match_physical_numeric_polars_type;
+use polars_utils::quantile_sketch::QuantileSketch;
+
+use super::*;
+
+pub fn new_median_reduction(dtype: DataType) -> PolarsResult<Box<dyn GroupedReduction>> {
+    polars_ensure!(supports_quantile(&dtype), opq = median, dtype);
+    Ok(new_quantile_reduction_impl(dtype, None))
+}
+
+pub fn new_quantile_reduction(
+    dtype: DataType,
+    quantile: f64,
+    method: QuantileMethod,
+) -> PolarsResult<Box<dyn GroupedReduction>> {
+    polars_ensure!(supports_quantile(&dtype), opq = quantile, dtype);
+    polars_ensure!(
+        (0.0..=1.0).contains(&quantile),
+        ComputeError: "`quantile` should be between 0.0 and 1.0",
+    );
+    Ok(new_quantile_reduction_impl(dtype, Some((quantile, method))))
+}
+
+fn supports_quantile(dtype: &DataType) -> bool {
+    dtype.is_primitive_numeric()
+        || dtype.is_temporal()
+        || dtype.is_decimal()
+        || dtype.is_bool()
+        || dtype.is_null()
+}
+
+fn new_quantile_reduction_impl(
+    dtype: DataType,
+    quantile: Option<(f64, QuantileMethod)>,
+) -> Box<dyn GroupedReduction> {
+    use DataType::*;
+    use VecGroupedReduction as VGR;
+
+    // Approximate quantiles of numeric columns use a mergeable sketch with
+    // bounded memory per group, others are computed exactly.
+    if let (Some((quantile, QuantileMethod::Approximate)), true) =
+        (quantile, dtype.is_primitive_numeric())
+    {
+        let out_dtype = if dtype == Float32 { Float32 } else { Float64 };
+        return with_match_physical_numeric_polars_type!(dtype.to_physical(), |$T| {
+            Box::new(VGR::new(dtype, ApproxQuantileReducer::<$T> {
+                quantile,
+                out_dtype,
+                _phantom: PhantomData,
+            }))
+        });
+    }
+
+    match dtype {
+        Null => Box::new(NullGroupedReduction::new(Null)),
+        // Booleans are stored as integers, the same as the in-memory engine
+        // does for the median.
+        Boolean => Box::new(VGR::new(
+            dtype,
+            QuantileReducer::<Int32Type> {
+                quantile,
+                storage_dtype: Int32,
+                _phantom: PhantomData,
+            },
+        )),
+        _ => {
+            with_match_physical_numeric_polars_type!(dtype.to_physical(), |$T| {
+                Box::new(VGR::new(dtype.clone(), QuantileReducer::<$T> {
+                    quantile,
+                    storage_dtype: dtype,
+                    _phantom: PhantomData,
+                }))
+            })
+        },
+    }
+}
+
+/// Computes the exact quantile, this keeps all non-null values of each group.
+struct QuantileReducer<T> {
+    // None for the median.
+    quantile: Option<(f64, QuantileMethod)>,
+    // The logical type the stored physical values belong to.
+    storage_dtype: DataType,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> Clone for QuantileReducer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            quantile: self.quantile,
+            storage_dtype: self.storage_dtype.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> QuantileReducer<T> {
+    fn reduce(&self, s: &Series) -> PolarsResult<Scalar> {
+        match self.quantile {
+            None => s.median_reduce(),
+            Some((quantile, method)) => s.quantile_reduce(quantile, method),
+        }
+    }
+}
+
+impl<T> Reducer for QuantileReducer<T>
+where
+    T: PolarsNumericType,
+    T::Native: TotalOrd,
+    ChunkedArray<T>: IntoSeries,
+{
+    type Dtype = T;
+    type Value = Vec<T::Native>;
+
+    fn init(&self) -> Self::Value {
+        Vec::new()
+    }
+
+    fn cast_series<'a>(&self, s: &'a Series) -> Cow<'a, Series> {
+        if s.dtype().is_bool() {
+            Cow::Owned(s.cast(&DataType::Int32).unwrap())
+        } else {
+            s.to_physical_repr()
+        }
+    }
+
+    fn combine(&self, a: &mut Self::Value, b: &Self::Value) {
+        a.extend_from_slice(b);
+    }
+
+    #[inline(always)]
+    fn reduce_one(&self, a: &mut Self::Value, b: Option<T::Native>, _seq_id: u64) {
+        if let Some(x) = b {
+            a.push(x);
+        }
+    }
+
+    fn reduce_ca(&self, v: &mut Self::Value, ca: &ChunkedArray<Self::Dtype>, _seq_id: u64) {
+        for arr in ca.downcast_iter() {
+            v.extend(arr.non_null_values_iter());
+        }
+    }
+
+    fn finish(
+        &self,
+        v: Vec<Self::Value>,
+        m: Option<Bitmap>,
+        _dtype: &DataType,
+    ) -> PolarsResult<Series> {
+        assert!(m.is_none());
+        let empty = Series::new_empty(PlSmallStr::EMPTY, &self.storage_dtype);
+        let out_dtype = self.reduce(&empty)?.dtype().clone();
+
+        // Numeric quantiles (including booleans, which are stored as integers)
+        // are computed directly on the values of each group.
+        if self.storage_dtype.is_primitive_numeric() {
+            let (quantile, method) = self.quantile.unwrap_or((0.5, QuantileMethod::Linear));
+            let ca: Float64Chunked = v
+                .into_iter()
+                .map(|mut values| quantile_slice(&mut values, quantile, method))
+                .collect::<PolarsResult<_>>()?;
+            return ca.into_series().cast(&out_dtype);
+        }
+
+        // Reuse the in-memory implementation per group for the logical types so
+        // the results (and output types) are identical.
+        let values = v
+            .into_iter()
+            .map(|values| {
+                let s = ChunkedArray::<T>::from_vec(PlSmallStr::EMPTY, values).into_series();
+                let s = unsafe { s.from_physical_unchecked(&self.storage_dtype)? };
+                Ok(self.reduce(&s)?.into_value().to_physical())
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let phys_dtype = out_dtype.to_physical();
+        let out = Series::from_any_values_and_dtype(PlSmallStr::EMPTY, &values, &phys_dtype, true)?;
+        unsafe { out.from_physical_unchecked(&out_dtype) }
+    }
+
+    fn has_spillable_state(&self) -> bool {
+        true
+    }
+
+    fn values_to_state(&self, v: Vec<Self::Value>) -> PolarsResult<Vec<Series>> {
+        // The values of every group are stored as a list.
+        let mut offsets = Vec::with_capacity(v.len() + 1);
+        offsets.push(0i64);
+        let mut values = Vec::with_capacity(v.iter().map(Vec::len).sum());
+        for group in v {
+            values.extend(group);
+            offsets.push(values.len() as i64);
+        }
+
+        let values = PrimitiveArray::from_vec(values).boxed();
+        let arr_dtype = LargeListArray::default_datatype(values.dtype().clone());
+        // SAFETY: offsets are monotonically increasing.
+        let arr = LargeListArray::new(
+            arr_dtype,
+            unsafe { Offsets::new_unchecked(offsets) }.into(),
+            values,
+            None,
+        );
+        let dtype = DataType::List(Box::new(T::get_static_dtype()));
+        let ca = unsafe {
+            ListChunked::from_chunks_and_dtype(PlSmallStr::EMPTY, vec![Box::new(arr)], dtype)
+        };
+        Ok(vec![ca.into_series()])
+    }
+
+    fn values_from_state(&self, state: &[Series]) -> PolarsResult<Vec<Self::Value>> {
+        let ca = state[0].list()?;
+        polars_ensure!(
+            ca.inner_dtype() == &T::get_static_dtype() && !ca.has_nulls(),
+            ComputeError: "invalid reduction state of type {}", ca.dtype()
+        );
+        let mut out = Vec::with_capacity(ca.len());
+        for arr in ca.downcast_iter() {
+            let values = arr
+                .values()
+                .as_any()
+                .downcast_ref::<PrimitiveArray<T::Native>>()
+                .unwrap();
+            polars_ensure!(
+                values.null_count() == 0,
+                ComputeError: "invalid reduction state with null values"
+            );
+            out.extend(
+                arr.offsets()
+                    .windows(2)
+                    .map(|w| values.values()[w[0] as usize..w[1] as usize].to_vec()),
+            );
+        }
+        Ok(out)
+    }
+}
+
+/// Estimates the quantile using a [`QuantileSketch`].
+struct ApproxQuantileReducer<T> {
+    quantile: f64,
+    out_dtype: DataType,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> Clone for ApproxQuantileReducer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            quantile: self.quantile,
+            out_dtype: self.out_dtype.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: PolarsNumericType> Reducer for ApproxQuantileReducer<T> {
+    type Dtype = T;
+    type Value = QuantileSketch;
+
+    fn init(&self) -> Self::Value {
+        QuantileSketch::new()
+    }
+
+    fn combine(&self, a: &mut Self::Value, b: &Self::Value) {
+        a.combine(b)
+    }
+
+    #[inline(always)]
+    fn reduce_one(&self, a: &mut Self::Value, b: Option<T::Native>, _seq_id: u64) {
+        if let Some(x) = b {
+            a.insert(x.as_());
+        }
+    }
+
+    fn reduce_ca(&self, v: &mut Self::Value, ca: &ChunkedArray<Self::Dtype>, _seq_id: u64) {
+        for arr in ca.downcast_iter() {
+            for x in arr.non_null_values_iter() {
+                v.insert(x.as_());
+            }
+        }
+    }
+
+    fn finish(
+        &self,
+        v: Vec<Self::Value>,
+        m: Option<Bitmap>,
+        _dtype: &DataType,
+    ) -> PolarsResult<Series> {
+        assert!(m.is_none());
+        let ca: Float64Chunked = v
+            .into_iter()
+            .map(|mut sketch| sketch.quantile(self.quantile))
+            .collect_ca(PlSmallStr::EMPTY);
+        ca.into_series().cast(&self.out_dtype)
+    }
+}
@@ -124,6 +124,18 @@ where
             Series::from_chunks_and_dtype_unchecked(PlSmallStr::EMPTY, vec![arr], &out_dtype(dtype))
         })
     }
+
+    fn has_spillable_state(&self) -> bool {
+        true
+    }
+
+    fn values_to_state(&self, v: Vec<Self::Value>) -> PolarsResult<Vec<Series>> {
+        Ok(vec![natives_to_series(v)])
+    }
+
+    fn values_from_state(&self, state: &[Series]) -> PolarsResult<Vec<Self::Value>> {
+        series_to_natives(&state[0])
+    }
 }
 
 #[derive(Clone)]
@@ -162,4 +174,16 @@ impl Reducer for BoolSumReducer {
         assert!(dtype == &DataType::Boolean);
         Ok(IdxCa::from_vec(PlSmallStr::EMPTY, v).into_series())
     }
+
+    fn has_spillable_state(&self) -> bool {
+        true
+    }
+
+    fn values_to_state(&self, v: Vec<Self::Value>) -> PolarsResult<Vec<Series>> {
+        Ok(vec![natives_to_series(v)])
+    }
+
+    fn values_from_state(&self, state: &[Series]) -> PolarsResult<Vec<Self::Value>> {
+        series_to_natives(&state[0])
+    }
 }
@@ -1,10 +1,11 @@
 use std::borrow::Cow;
-use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicI64, AtomicU8, Ordering};
 use std::sync::{Mutex, OnceLock, RwLock};
 use std::time::Duration;
 
 use bitflags::bitflags;
 use polars_core::config::verbose;
+use polars_core::error::signals::CancellationToken;
 use polars_core::prelude::*;
 use polars_ops::prelude::ChunkJoinOptIds;
 use polars_utils::unique_id::UniqueId;
@@ -115,7 +116,7 @@ pub struct ExecutionState {
     pub flags: AtomicU8,
     pub ext_contexts: Arc<Vec<DataFrame>>,
     node_timer: Option<NodeTimer>,
-    stop: Arc<AtomicBool>,
+    cancel_token: CancellationToken,
 }
 
 impl ExecutionState {
@@ -132,7 +133,7 @@ impl ExecutionState {
             flags: AtomicU8::new(StateFlags::init().as_u8()),
             ext_contexts: Default::default(),
             node_timer: None,
-            stop: Arc::new(AtomicBool::new(false)),
+            cancel_token: CancellationToken::new(),
         }
     }
 
@@ -163,12 +164,16 @@ impl ExecutionState {
     // This is wrong when the U64 overflows which will never happen.
     pub fn should_stop(&self) -> PolarsResult<()> {
         try_raise_keyboard_interrupt();
-        polars_ensure!(!self.stop.load(Ordering::Relaxed), ComputeError: "query interrupted");
-        Ok(())
+        self.cancel_token.check()
     }
 
-    pub fn cancel_token(&self) -> Arc<AtomicBool> {
-        self.stop.clone()
+    pub fn cancel_token(&self) -> CancellationToken {
+        self.cancel_token.clone()
+    }
+
+    /// Run this state (and every state split from it) under `token`.
+    pub fn set_cancel_token(&mut self, token: CancellationToken) {
+        self.cancel_token = token;
     }
 
     pub fn record<T, F: FnOnce() -> T>(&self, func: F, name: Cow<'static, str>) -> T {
@@ -196,7 +201,7 @@ impl ExecutionState {
             flags: AtomicU8::new(self.flags.load(Ordering::Relaxed)),
             ext_contexts: self.ext_contexts.clone(),
             node_timer: self.node_timer.clone(),
-            stop: self.stop.clone(),
+            cancel_token: self.cancel_token.clone(),
         }
     }
 
@@ -309,7 +314,7 @@ impl Clone for ExecutionState {
             flags: AtomicU8::new(self.flags.load(Ordering::Relaxed)),
             ext_contexts: self.ext_contexts.clone(),
             node_timer: self.node_timer.clone(),
-            stop: self.stop.clone(),
+            cancel_token: self.cancel_token.clone(),
         }
     }
 }
@@ -0,0 +1,199 @@
+use std::io::Read;
+
+use arrow::io::avro::avro_schema::file::{Block, Compression};
+use arrow::io::avro::avro_schema::read::fallible_streaming_iterator::FallibleStreamingIterator;
+use arrow::io::avro::avro_schema::read::{
+    BlockStreamingIterator, CompressedBlockStreamingIterator, block_iterator,
+};
+use arrow::io::avro::avro_schema::schema::{Record, Schema as AvroSchema};
+use polars_core::error::to_compute_err;
+use polars_core::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The codec used to compress the blocks of an Avro file.
+///
+/// Unlike [`Compression`] this includes the codecs the `avro-schema` crate can't handle itself.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub enum AvroCodec {
+    Deflate,
+    Snappy,
+    Zstd,
+}
+
+impl AvroCodec {
+    /// The name of the codec in the file metadata.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Deflate => "deflate",
+            Self::Snappy => "snappy",
+            Self::Zstd => "zstandard",
+        }
+    }
+
+    fn from_name(name: &[u8]) -> PolarsResult<Option<Self>> {
+        Ok(match name {
+            b"null" => None,
+            b"deflate" => Some(Self::Deflate),
+            b"snappy" => Some(Self::Snappy),
+            b"zstandard" => Some(Self::Zstd),
+            _ => polars_bail!(
+                ComputeError: "unsupported Avro codec '{}'", String::from_utf8_lossy(name)
+            ),
+        })
+    }
+
+    /// The equivalent `avro-schema` compression, if it supports this codec.
+    pub(super) fn to_compression(self) -> Option<Compression> {
+        match self {
+            Self::Deflate => Some(Compression::Deflate),
+            Self::Snappy => Some(Compression::Snappy),
+            Self::Zstd => None,
+        }
+    }
+}
+
+impl From<Compression> for AvroCodec {
+    fn from(compression: Compression) -> Self {
+        match compression {
+            Compression::Deflate => Self::Deflate,
+            Compression::Snappy => Self::Snappy,
+        }
+    }
+}
+
+/// The metadata in the header of an Avro object container file.
+#[derive(Clone, Debug)]
+pub struct AvroFileMetadata {
+    pub record: Record,
+    pub codec: Option<AvroCodec>,
+    pub marker: [u8; 16],
+}
+
+/// Read the header of an Avro object container file.
+pub fn read_avro_metadata<R: Read>(reader: &mut R) -> PolarsResult<AvroFileMetadata> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    polars_ensure!(
+        &magic == b"Obj\x01",
+        ComputeError: "not an Avro object container file"
+    );
+
+    let mut schema = None;
+    let mut codec = None;
+    loop {
+        let mut len = read_zigzag_long(reader)?;
+        if len == 0 {
+            break;
+        }
+        // A negative count is followed by the size of the block in bytes.
+        if len < 0 {
+            len = -len;
+            read_zigzag_long(reader)?;
+        }
+        for _ in 0..len {
+            let key = read_avro_bytes(reader)?;
+            let value = read_avro_bytes(reader)?;
+            match key.as_slice() {
+                b"avro.schema" => schema = Some(value),
+                b"avro.codec" => codec = AvroCodec::from_name(&value)?,
+                _ => {},
+            }
+        }
+    }
+
+    let mut marker = [0u8; 16];
+    reader.read_exact(&mut marker)?;
+
+    let Some(schema) = schema else {
+        polars_bail!(ComputeError: "Avro file has no schema in its metadata");
+    };
+    let AvroSchema::Record(record) = serde_json::from_slice(&schema).map_err(to_compute_err)?
+    else {
+        polars_bail!(ComputeError: "the schema of an Avro file must be a record");
+    };
+
+    Ok(AvroFileMetadata {
+        record,
+        codec,
+        marker,
+    })
+}
+
+pub(super) fn read_zigzag_long<R: Read>(reader: &mut R) -> PolarsResult<i64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let mut byte = 0u8;
+        reader
+            .read_exact(std::slice::from_mut(&mut byte))
+            .map_err(|_| polars_err!(ComputeError: "unexpected end of Avro file"))?;
+        polars_ensure!(shift < 64, ComputeError: "invalid varint in Avro file");
+        value |= u64::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+}
+
+fn read_avro_bytes<R: Read>(reader: &mut R) -> PolarsResult<Vec<u8>> {
+    let len = read_zigzag_long(reader)?;
+    polars_ensure!(len >= 0, ComputeError: "invalid length in Avro file");
+    let mut bytes = vec![];
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    polars_ensure!(
+        bytes.len() == len as usize,
+        ComputeError: "unexpected end of Avro file"
+    );
+    Ok(bytes)
+}
+
+enum Blocks<R: Read> {
+    AvroSchema(BlockStreamingIterator<R>),
+    Zstd {
+        blocks: CompressedBlockStreamingIterator<R>,
+        block: Block,
+    },
+}
+
+/// Iterator over the decompressed blocks of an Avro object container file.
+pub struct AvroBlockIter<R: Read> {
+    blocks: Blocks<R>,
+}
+
+impl<R: Read> AvroBlockIter<R> {
+    /// Iterate over the blocks in `reader`, which must be positioned after the header.
+    pub fn new(reader: R, metadata: &AvroFileMetadata) -> Self {
+        let blocks = match metadata.codec {
+            Some(AvroCodec::Zstd) => Blocks::Zstd {
+                blocks: CompressedBlockStreamingIterator::new(reader, metadata.marker, vec![]),
+                block: Block::new(0, vec![]),
+            },
+            codec => Blocks::AvroSchema(block_iterator(
+                reader,
+                codec.and_then(AvroCodec::to_compression),
+                metadata.marker,
+            )),
+        };
+        Self { blocks }
+    }
+
+    pub fn next_block(&mut self) -> PolarsResult<Option<&Block>> {
+        match &mut self.blocks {
+            Blocks::AvroSchema(blocks) => blocks.next().map_err(to_compute_err),
+            Blocks::Zstd { blocks, block } => {
+                let Some(compressed) = blocks.next().map_err(to_compute_err)? else {
+                    return Ok(None);
+                };
+                block.number_of_rows = compressed.number_of_rows;
+                block.data.clear();
+                zstd::stream::copy_decode(compressed.data.as_slice(), &mut block.data)?;
+                Ok(Some(block))
+            },
+        }
+    }
+}
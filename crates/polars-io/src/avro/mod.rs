@@ -1,5 +1,7 @@
+mod codec;
 mod read;
 mod write;
 
+pub use codec::*;
 pub use read::*;
 pub use write::*;
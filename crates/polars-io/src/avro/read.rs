@@ -1,13 +1,83 @@
-use std::io::{Read, Seek};
-
-use arrow::io::avro::{self, read};
+use std::io::{Cursor, Read, Seek};
+use std::ops::Range;
+
+use arrow::datatypes::ArrowSchema;
+use arrow::io::avro::avro_schema::read::CompressedBlockStreamingIterator;
+use arrow::io::avro::avro_schema::read::fallible_streaming_iterator::FallibleStreamingIterator;
+use arrow::io::avro::avro_schema::schema::Field as AvroField;
+use arrow::io::avro::read;
 use arrow::record_batch::RecordBatch;
 use polars_core::error::to_compute_err;
 use polars_core::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+use super::{AvroBlockIter, AvroFileMetadata, read_avro_metadata, read_zigzag_long};
 use crate::prelude::*;
 use crate::shared::{ArrowReader, finish_reader};
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub struct AvroScanOptions;
+
+/// Count the rows of an Avro object container file.
+///
+/// This only walks the block headers, the blocks are not decompressed.
+pub fn count_rows_avro<R: Read>(reader: &mut R) -> PolarsResult<usize> {
+    let metadata = read_avro_metadata(reader)?;
+    let mut blocks = CompressedBlockStreamingIterator::new(reader, metadata.marker, vec![]);
+
+    let mut n_rows = 0;
+    while let Some(block) = blocks.next().map_err(to_compute_err)? {
+        n_rows += block.number_of_rows;
+    }
+    Ok(n_rows)
+}
+
+/// The location of a block in an Avro object container file.
+#[derive(Clone, Debug)]
+pub struct AvroBlockInfo {
+    /// The bytes of the block in the file, including its header and sync marker.
+    pub byte_range: Range<usize>,
+    pub num_rows: usize,
+}
+
+/// Read the metadata of the Avro object container file in `bytes` and locate
+/// its blocks.
+///
+/// This only walks the block headers, the blocks are not decompressed.
+pub fn read_avro_blocks(bytes: &[u8]) -> PolarsResult<(AvroFileMetadata, Vec<AvroBlockInfo>)> {
+    let mut cursor = Cursor::new(bytes);
+    let metadata = read_avro_metadata(&mut cursor)?;
+
+    let mut blocks = vec![];
+    while (cursor.position() as usize) < bytes.len() {
+        let start = cursor.position() as usize;
+        let num_rows = read_zigzag_long(&mut cursor)?;
+        let num_bytes = read_zigzag_long(&mut cursor)?;
+        polars_ensure!(
+            num_rows >= 0 && num_bytes >= 0,
+            ComputeError: "invalid Avro block header at byte {start}"
+        );
+
+        let data_end = (cursor.position() as usize).saturating_add(num_bytes as usize);
+        let end = data_end.saturating_add(metadata.marker.len());
+        polars_ensure!(
+            end <= bytes.len() && bytes[data_end..end] == metadata.marker,
+            ComputeError: "Avro block at byte {start} is truncated or has an invalid sync marker"
+        );
+
+        blocks.push(AvroBlockInfo {
+            byte_range: start..end,
+            num_rows: num_rows as usize,
+        });
+        cursor.set_position(end as u64);
+    }
+
+    Ok((metadata, blocks))
+}
+
 /// Read [Apache Avro] format into a [`DataFrame`]
 ///
 /// [Apache Avro]: https://avro.apache.org
@@ -44,8 +114,7 @@ impl<R: Read + Seek> AvroReader<R> {
 
     /// Get arrow schema of the avro File, this is faster than a polars schema.
     pub fn arrow_schema(&mut self) -> PolarsResult<ArrowSchema> {
-        let metadata =
-            avro::avro_schema::read::read_metadata(&mut self.reader).map_err(to_compute_err)?;
+        let metadata = read_avro_metadata(&mut self.reader)?;
         let schema = read::infer_schema(&metadata.record)?;
         Ok(schema)
     }
@@ -70,12 +139,20 @@ impl<R: Read + Seek> AvroReader<R> {
     }
 }
 
-impl<R> ArrowReader for read::Reader<R>
-where
-    R: Read + Seek,
-{
+/// Deserializes the blocks of an Avro file into record batches.
+struct AvroBatchReader<R: Read> {
+    blocks: AvroBlockIter<R>,
+    fields: ArrowSchema,
+    avro_fields: Vec<AvroField>,
+    projection: Vec<bool>,
+}
+
+impl<R: Read> ArrowReader for AvroBatchReader<R> {
     fn next_record_batch(&mut self) -> PolarsResult<Option<RecordBatch>> {
-        self.next().map_or(Ok(None), |v| v.map(Some))
+        let Some(block) = self.blocks.next_block()? else {
+            return Ok(None);
+        };
+        read::deserialize(block, &self.fields, &self.avro_fields, &self.projection).map(Some)
     }
 }
 
@@ -100,8 +177,7 @@ where
 
     fn finish(mut self) -> PolarsResult<DataFrame> {
         let rechunk = self.rechunk;
-        let metadata =
-            avro::avro_schema::read::read_metadata(&mut self.reader).map_err(to_compute_err)?;
+        let metadata = read_avro_metadata(&mut self.reader)?;
         let schema = read::infer_schema(&metadata.record)?;
 
         if let Some(columns) = &self.columns {
@@ -118,7 +194,12 @@ where
             (None, schema.clone())
         };
 
-        let avro_reader = avro::read::Reader::new(&mut self.reader, metadata, schema, projection);
+        let avro_reader = AvroBatchReader {
+            blocks: AvroBlockIter::new(&mut self.reader, &metadata),
+            projection: projection.unwrap_or_else(|| vec![true; schema.len()]),
+            fields: schema,
+            avro_fields: metadata.record.fields,
+        };
 
         finish_reader(
             avro_reader,
@@ -2,13 +2,136 @@ use std::io::Write;
 
 pub use Compression as AvroCompression;
 pub use arrow::io::avro::avro_schema::file::Compression;
+use arrow::io::avro::avro_schema::file::{Block, CompressedBlock};
+use arrow::io::avro::avro_schema::schema::{Record, Schema as AvroSchema};
+use arrow::io::avro::avro_schema::write::encode::zigzag_encode;
 use arrow::io::avro::avro_schema::{self};
 use arrow::io::avro::write;
 use polars_core::error::to_compute_err;
 use polars_core::prelude::*;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+use super::AvroCodec;
 use crate::shared::{SerWriter, schema_to_arrow_checked};
 
+/// The name of the codec in the file metadata.
+fn codec_name(codec: Option<AvroCodec>) -> &'static str {
+    codec.map_or("null", AvroCodec::name)
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub struct AvroWriterOptions {
+    /// The codec used to compress the blocks.
+    pub compression: Option<AvroCodec>,
+    /// The name of the record in the Avro schema.
+    pub name: PlSmallStr,
+}
+
+/// The sync marker written after the metadata and every block of a file.
+pub type AvroSyncMarker = [u8; 16];
+
+/// Generate a new random sync marker, every file should get its own.
+pub fn new_avro_sync_marker() -> AvroSyncMarker {
+    let mut marker = AvroSyncMarker::default();
+    rand::thread_rng().fill(&mut marker);
+    marker
+}
+
+/// Get the Avro record describing rows of `schema`.
+pub fn avro_record(schema: &Schema, name: &str) -> PolarsResult<Record> {
+    let schema = schema_to_arrow_checked(schema, CompatLevel::oldest(), "avro")?;
+    write::to_record(&schema, name.to_string())
+}
+
+/// Write the header of an Avro object container file.
+pub fn write_avro_header<W: Write + ?Sized>(
+    writer: &mut W,
+    record: &Record,
+    compression: Option<AvroCodec>,
+    marker: &AvroSyncMarker,
+) -> PolarsResult<()> {
+    let schema = serde_json::to_vec(&AvroSchema::Record(record.clone())).map_err(to_compute_err)?;
+    let codec = codec_name(compression);
+
+    let mut header = b"Obj\x01".to_vec();
+    let metadata: [(&str, &[u8]); 2] = [("avro.schema", &schema), ("avro.codec", codec.as_bytes())];
+    zigzag_encode(metadata.len() as i64, &mut header).map_err(to_compute_err)?;
+    for (key, value) in metadata {
+        zigzag_encode(key.len() as i64, &mut header).map_err(to_compute_err)?;
+        header.extend_from_slice(key.as_bytes());
+        zigzag_encode(value.len() as i64, &mut header).map_err(to_compute_err)?;
+        header.extend_from_slice(value);
+    }
+    zigzag_encode(0, &mut header).map_err(to_compute_err)?;
+    header.extend_from_slice(marker);
+
+    writer.write_all(&header)?;
+    Ok(())
+}
+
+/// Encode the rows of `df` as Avro blocks, one per chunk, and append them to `buf`.
+///
+/// The frame must have the schema `record` was created from, and `marker` must
+/// be the one written in the header of the file.
+pub fn encode_avro_blocks(
+    df: &DataFrame,
+    record: &Record,
+    compression: Option<AvroCodec>,
+    marker: &AvroSyncMarker,
+    buf: &mut Vec<u8>,
+) -> PolarsResult<()> {
+    let mut block = Block::new(0, vec![]);
+    let mut compressed_block = CompressedBlock::default();
+
+    for chunk in df.iter_chunks(CompatLevel::oldest(), true) {
+        if chunk.len() == 0 {
+            continue;
+        }
+
+        let mut serializers = chunk
+            .iter()
+            .zip(record.fields.iter())
+            .map(|(array, field)| write::new_serializer(array.as_ref(), &field.schema))
+            .collect::<Vec<_>>();
+
+        block.number_of_rows = chunk.len();
+        write::serialize(&mut serializers, &mut block);
+
+        let data = match compression {
+            None => &block.data,
+            Some(compression) => compress(&mut block, &mut compressed_block, compression)?,
+        };
+
+        zigzag_encode(block.number_of_rows as i64, buf).map_err(to_compute_err)?;
+        zigzag_encode(data.len() as i64, buf).map_err(to_compute_err)?;
+        buf.extend_from_slice(data);
+        buf.extend_from_slice(marker);
+    }
+
+    Ok(())
+}
+
+fn compress<'a>(
+    block: &mut Block,
+    compressed_block: &'a mut CompressedBlock,
+    compression: AvroCodec,
+) -> PolarsResult<&'a Vec<u8>> {
+    compressed_block.data.clear();
+    match compression.to_compression() {
+        Some(compression) => {
+            avro_schema::write::compress(block, compressed_block, Some(compression))
+                .map_err(to_compute_err)?;
+        },
+        // The `avro-schema` crate can't write zstd.
+        None => zstd::stream::copy_encode(block.data.as_slice(), &mut compressed_block.data, 0)?,
+    }
+    Ok(&compressed_block.data)
+}
+
 /// Write a [`DataFrame`] to [Apache Avro] format
 ///
 /// [Apache Avro]: https://avro.apache.org
@@ -31,7 +154,7 @@ use crate::shared::{SerWriter, schema_to_arrow_checked};
 #[must_use]
 pub struct AvroWriter<W> {
     writer: W,
-    compression: Option<AvroCompression>,
+    compression: Option<AvroCodec>,
     name: String,
 }
 
@@ -41,7 +164,14 @@ where
 {
     /// Set the compression used. Defaults to None.
     pub fn with_compression(mut self, compression: Option<AvroCompression>) -> Self {
-        self.compression = compression;
+        self.compression = compression.map(AvroCodec::from);
+        self
+    }
+
+    /// Set the codec used, this also supports the codecs [`AvroCompression`] lacks. Defaults to
+    /// None.
+    pub fn with_codec(mut self, codec: Option<AvroCodec>) -> Self {
+        self.compression = codec;
         self
     }
 
@@ -64,38 +194,13 @@ where
     }
 
     fn finish(&mut self, df: &mut DataFrame) -> PolarsResult<()> {
-        let schema = schema_to_arrow_checked(df.schema(), CompatLevel::oldest(), "avro")?;
-        let record = write::to_record(&schema, self.name.clone())?;
-
-        let mut data = vec![];
-        let mut compressed_block = avro_schema::file::CompressedBlock::default();
-        for chunk in df.iter_chunks(CompatLevel::oldest(), true) {
-            let mut serializers = chunk
-                .iter()
-                .zip(record.fields.iter())
-                .map(|(array, field)| write::new_serializer(array.as_ref(), &field.schema))
-                .collect::<Vec<_>>();
-
-            let mut block =
-                avro_schema::file::Block::new(chunk.arrays()[0].len(), std::mem::take(&mut data));
-            write::serialize(&mut serializers, &mut block);
-            let _was_compressed =
-                avro_schema::write::compress(&mut block, &mut compressed_block, self.compression)
-                    .map_err(to_compute_err)?;
-
-            avro_schema::write::write_metadata(&mut self.writer, record.clone(), self.compression)
-                .map_err(to_compute_err)?;
-
-            avro_schema::write::write_block(&mut self.writer, &compressed_block)
-                .map_err(to_compute_err)?;
-            // reuse block for next iteration.
-            data = block.data;
-            data.clear();
+        let record = avro_record(df.schema(), &self.name)?;
+        let marker = new_avro_sync_marker();
+        write_avro_header(&mut self.writer, &record, self.compression, &marker)?;
 
-            // reuse block for next iteration
-            compressed_block.data.clear();
-            compressed_block.number_of_rows = 0
-        }
+        let mut buf = vec![];
+        encode_avro_blocks(df, &record, self.compression, &marker, &mut buf)?;
+        self.writer.write_all(&buf)?;
 
         Ok(())
     }
@@ -3,7 +3,7 @@ use arrow::bitmap::Bitmap;
 use arrow::datatypes::Field;
 use polars_error::PolarsResult;
 use polars_parquet::read::{
-    BasicDecompressor, ColumnChunkMetadata, Filter, PageReader, column_iter_to_arrays,
+    BasicDecompressor, ColumnChunkMetadata, Filter, PageMetaData, PageReader, column_iter_to_arrays,
 };
 use polars_utils::mmap::{MemReader, MemSlice};
 
@@ -70,3 +70,27 @@ pub fn to_deserializer(
 
     column_iter_to_arrays(columns, types, field, filter)
 }
+
+/// Like [`to_deserializer`] for a flat column of which `chunk` only holds some of the data pages,
+/// preceded by the dictionary page if there is one. These pages hold `num_values` values.
+pub fn to_page_deserializer(
+    column_meta: &ColumnChunkMetadata,
+    chunk: MemSlice,
+    num_values: usize,
+    field: Field,
+    filter: Option<Filter>,
+) -> PolarsResult<(Box<dyn Array>, Bitmap)> {
+    chunk.prefetch();
+
+    let mut page_meta = PageMetaData::from(column_meta);
+    page_meta.num_values = num_values as i64;
+    let pages =
+        PageReader::new_with_page_meta(MemReader::new(chunk), page_meta, vec![], usize::MAX);
+
+    column_iter_to_arrays(
+        vec![BasicDecompressor::new(pages, vec![])],
+        vec![&column_meta.descriptor().descriptor.primitive_type],
+        field,
+        filter,
+    )
+}
@@ -18,6 +18,7 @@
 mod async_impl;
 mod mmap;
 mod options;
+mod page_index;
 mod predicates;
 mod read_impl;
 mod reader;
@@ -36,13 +37,17 @@ pub use options::{ParallelStrategy, ParquetOptions};
 use polars_error::{ErrString, PolarsError};
 pub use polars_parquet::arrow::read::infer_schema;
 pub use polars_parquet::read::FileMetadata;
-pub use read_impl::{create_sorting_map, try_set_sorted_flag};
+pub use read_impl::{create_sorting_map, is_sorted_ascending_by, try_set_sorted_flag};
 pub use reader::ParquetReader;
 pub use utils::materialize_empty_df;
 
 pub mod _internal {
-    pub use super::mmap::to_deserializer;
-    pub use super::predicates::collect_statistics_with_live_columns;
+    pub use super::mmap::{to_deserializer, to_page_deserializer};
+    pub use super::page_index::{ColumnPages, PageSelection, page_index_byte_ranges, select_pages};
+    pub use super::predicates::{
+        bloom_filter_hashes, bloom_filter_may_contain, collect_statistics_with_live_columns,
+        statistics_columns,
+    };
     pub use super::read_impl::{PrefilterMaskSetting, calc_prefilter_cost};
     pub use super::utils::ensure_matching_dtypes_if_found;
 }
@@ -0,0 +1,287 @@
+//! Page-level predicate pushdown using the `ColumnIndex` and `OffsetIndex` of column chunks.
+//!
+//! The column indexes of the live columns hold the statistics of every data page. The pages of
+//! all live columns are aligned by splitting the row group at every page boundary, after which the
+//! skip batch predicate is evaluated per split. The rows that remain are then mapped back to the
+//! pages of every projected column through its offset index.
+use std::ops::Range;
+
+use arrow::bitmap::{Bitmap, MutableBitmap};
+use polars_core::prelude::*;
+use polars_parquet::read::statistics::deserialize_batches;
+use polars_parquet::read::{
+    ColumnChunkMetadata, OffsetIndex, RowGroupMetadata, column_index_statistics,
+    deserialize_column_index, deserialize_offset_index,
+};
+use polars_utils::mmap::MemSlice;
+
+use super::predicates::statistics_columns;
+use crate::predicates::SkipBatchPredicate;
+
+/// The rows of a row group that remain after skipping pages through the page index, together with
+/// the pages that need to be read to decode them.
+#[derive(Debug, Clone)]
+pub struct PageSelection {
+    /// Sorted and non-adjacent ranges of the selected rows of the row group.
+    pub row_ranges: Vec<Range<usize>>,
+    /// The selected rows as a mask over all rows of the row group.
+    pub row_mask: Bitmap,
+    /// The pages to read of the projected columns that have an offset index, by the index of the
+    /// column chunk in the row group. Other columns are read in full.
+    pub column_pages: PlHashMap<usize, ColumnPages>,
+}
+
+/// The data pages of a column chunk that hold the selected rows of a row group.
+#[derive(Debug, Clone)]
+pub struct ColumnPages {
+    /// The byte ranges in the file to read, in order. This starts with the bytes in front of the
+    /// first data page, which hold the dictionary page if there is one.
+    pub byte_ranges: Vec<Range<usize>>,
+    /// Sorted and non-adjacent ranges of the rows of the row group in the selected pages.
+    pub row_ranges: Vec<Range<usize>>,
+}
+
+impl ColumnPages {
+    /// The number of rows in the selected pages.
+    pub fn num_rows(&self) -> usize {
+        self.row_ranges.iter().map(|r| r.len()).sum()
+    }
+
+    /// Restricts `row_group_mask`, a mask over all rows of the row group, to the rows in the
+    /// selected pages.
+    pub fn row_mask(&self, row_group_mask: &Bitmap) -> Bitmap {
+        let mut mask = MutableBitmap::with_capacity(self.num_rows());
+        for range in &self.row_ranges {
+            mask.extend_from_bitmap(&row_group_mask.clone().sliced(range.start, range.len()));
+        }
+        mask.freeze()
+    }
+}
+
+/// The single leaf column chunk of the flat column `name`, if it has an offset index.
+fn paged_column<'a>(
+    row_group: &'a RowGroupMetadata,
+    schema: &ArrowSchema,
+    name: &str,
+) -> Option<(usize, &'a ColumnChunkMetadata)> {
+    if schema.get(name)?.dtype().is_nested() {
+        return None;
+    }
+    let [idx] = row_group.columns_idxs_under_root_iter(name)? else {
+        return None;
+    };
+    let column = &row_group.parquet_columns()[*idx];
+    column
+        .offset_index_byte_range()
+        .is_some()
+        .then_some((*idx, column))
+}
+
+fn to_usize_range(range: Range<u64>) -> Range<usize> {
+    range.start as usize..range.end as usize
+}
+
+/// Returns the byte ranges of the page indexes needed to select the pages of `row_group`: the
+/// column indexes of the `live_columns` and the offset indexes of the `live_columns` and
+/// `projected_columns`.
+///
+/// Returns `None` if a live column is not a flat column of the file with a page index, in which
+/// case no pages can be skipped.
+pub fn page_index_byte_ranges<'a>(
+    row_group: &RowGroupMetadata,
+    schema: &ArrowSchema,
+    live_columns: &PlIndexSet<PlSmallStr>,
+    projected_columns: impl Iterator<Item = &'a PlSmallStr>,
+) -> Option<Vec<Range<usize>>> {
+    let mut ranges = Vec::with_capacity(2 * live_columns.len());
+
+    for name in live_columns.iter() {
+        let (_, column) = paged_column(row_group, schema, name)?;
+        ranges.push(to_usize_range(column.column_index_byte_range()?));
+    }
+    for name in live_columns.iter().chain(projected_columns) {
+        if let Some((_, column)) = paged_column(row_group, schema, name) {
+            ranges.push(to_usize_range(column.offset_index_byte_range().unwrap()));
+        }
+    }
+
+    ranges.sort_unstable_by_key(|r| (r.start, r.end));
+    ranges.dedup();
+    Some(ranges)
+}
+
+/// Returns the index of the page holding `row` given the first rows of the pages.
+fn page_of_row(first_rows: &[usize], row: usize) -> usize {
+    first_rows.partition_point(|&first_row| first_row <= row) - 1
+}
+
+/// The first rows of the pages of a column chunk in a row group with `num_rows` rows.
+fn page_first_rows(offset_index: &OffsetIndex, num_rows: usize) -> PolarsResult<Vec<usize>> {
+    let first_rows = offset_index
+        .page_locations
+        .iter()
+        .map(|l| l.first_row_index as usize)
+        .collect::<Vec<_>>();
+
+    polars_ensure!(
+        first_rows.first() == Some(&0)
+            && first_rows.windows(2).all(|w| w[0] < w[1])
+            && first_rows.last().is_some_and(|&r| r < num_rows.max(1)),
+        ComputeError: "parquet offset index has invalid page locations"
+    );
+    Ok(first_rows)
+}
+
+/// Selects the rows and pages of `row_group` that remain after skipping the rows of which the
+/// pages of the `live_columns` can't pass `skip_batch_predicate`. `get_bytes` returns the bytes of
+/// a range returned by [`page_index_byte_ranges`].
+///
+/// Returns `None` if no pages can be skipped.
+pub fn select_pages<'a>(
+    row_group: &RowGroupMetadata,
+    schema: &ArrowSchema,
+    live_columns: &PlIndexSet<PlSmallStr>,
+    projected_columns: impl Iterator<Item = &'a PlSmallStr>,
+    skip_batch_predicate: &dyn SkipBatchPredicate,
+    get_bytes: impl Fn(Range<usize>) -> MemSlice,
+) -> PolarsResult<Option<PageSelection>> {
+    let num_rows = row_group.num_rows();
+
+    let mut live_pages = Vec::with_capacity(live_columns.len());
+    for name in live_columns.iter() {
+        let Some((_, column)) = paged_column(row_group, schema, name) else {
+            return Ok(None);
+        };
+        let Some(column_index_range) = column.column_index_byte_range() else {
+            return Ok(None);
+        };
+
+        let column_index =
+            deserialize_column_index(&get_bytes(to_usize_range(column_index_range)))?;
+        let offset_index = deserialize_offset_index(&get_bytes(to_usize_range(
+            column.offset_index_byte_range().unwrap(),
+        )))?;
+
+        let primitive_type = &column.descriptor().descriptor.primitive_type;
+        let statistics = column_index_statistics(&column_index, primitive_type)?;
+        let first_rows = page_first_rows(&offset_index, num_rows)?;
+        polars_ensure!(
+            statistics.len() == first_rows.len(),
+            ComputeError: "parquet column index and offset index have a different number of pages"
+        );
+
+        live_pages.push((name, column, statistics, first_rows));
+    }
+
+    // Split the row group at the page boundaries of all live columns, so that every split lies
+    // within a single page of each live column.
+    let mut split_starts = live_pages
+        .iter()
+        .flat_map(|(_, _, _, first_rows)| first_rows.iter().copied())
+        .collect::<Vec<_>>();
+    split_starts.sort_unstable();
+    split_starts.dedup();
+
+    if split_starts.len() <= 1 {
+        return Ok(None);
+    }
+
+    let num_splits = split_starts.len();
+    let split_end = |i: usize| split_starts.get(i + 1).copied().unwrap_or(num_rows);
+
+    let mut columns = Vec::with_capacity(1 + 3 * live_pages.len());
+    let lengths = (0..num_splits)
+        .map(|i| (split_end(i) - split_starts[i]) as IdxSize)
+        .collect::<Vec<_>>();
+    columns.push(Column::new("len".into(), lengths));
+    for (name, column, statistics, first_rows) in &live_pages {
+        let field = schema.get(name.as_str()).unwrap();
+        let primitive_type = &column.descriptor().descriptor.primitive_type;
+        let split_statistics = split_starts
+            .iter()
+            .map(|&row| Ok(Some(statistics[page_of_row(first_rows, row)].clone())));
+
+        let stats = deserialize_batches(field, primitive_type, num_splits, split_statistics)?;
+        columns.extend(statistics_columns(name, field, stats, num_splits)?);
+    }
+    let statistics_df = DataFrame::new_with_height(num_splits, columns)?;
+
+    let skip_mask = skip_batch_predicate.evaluate_with_stat_df(&statistics_df)?;
+    if skip_mask.set_bits() == 0 {
+        return Ok(None);
+    }
+
+    let mut row_ranges: Vec<Range<usize>> = Vec::new();
+    for i in (0..num_splits).filter(|&i| !skip_mask.get_bit(i)) {
+        let range = split_starts[i]..split_end(i);
+        match row_ranges.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => row_ranges.push(range),
+        }
+    }
+
+    let mut row_mask = MutableBitmap::with_capacity(num_rows);
+    for range in &row_ranges {
+        row_mask.extend_constant(range.start - row_mask.len(), false);
+        row_mask.extend_constant(range.len(), true);
+    }
+    row_mask.extend_constant(num_rows - row_mask.len(), false);
+    let row_mask = row_mask.freeze();
+
+    let mut column_pages = PlHashMap::new();
+    for name in live_columns.iter().chain(projected_columns) {
+        let Some((idx, column)) = paged_column(row_group, schema, name) else {
+            continue;
+        };
+        if column_pages.contains_key(&idx) {
+            continue;
+        }
+
+        let offset_index = deserialize_offset_index(&get_bytes(to_usize_range(
+            column.offset_index_byte_range().unwrap(),
+        )))?;
+        let first_rows = page_first_rows(&offset_index, num_rows)?;
+        let locations = &offset_index.page_locations;
+
+        // The bytes in front of the first data page.
+        let column_start = column.byte_range().start as usize;
+        let mut byte_ranges = vec![column_start..locations[0].offset as usize];
+        let mut page_row_ranges: Vec<Range<usize>> = Vec::new();
+
+        for (page, location) in locations.iter().enumerate() {
+            let page_rows = first_rows[page]..first_rows.get(page + 1).copied().unwrap_or(num_rows);
+            let overlaps = row_ranges
+                .iter()
+                .any(|r| r.start < page_rows.end && page_rows.start < r.end);
+            if !overlaps {
+                continue;
+            }
+
+            let bytes = location.offset as usize
+                ..location.offset as usize + location.compressed_page_size as usize;
+            match byte_ranges.last_mut() {
+                Some(last) if last.end == bytes.start => last.end = bytes.end,
+                _ => byte_ranges.push(bytes),
+            }
+            match page_row_ranges.last_mut() {
+                Some(last) if last.end == page_rows.start => last.end = page_rows.end,
+                _ => page_row_ranges.push(page_rows),
+            }
+        }
+        byte_ranges.retain(|r| !r.is_empty());
+
+        column_pages.insert(
+            idx,
+            ColumnPages {
+                byte_ranges,
+                row_ranges: page_row_ranges,
+            },
+        );
+    }
+
+    Ok(Some(PageSelection {
+        row_ranges,
+        row_mask,
+        column_pages,
+    }))
+}
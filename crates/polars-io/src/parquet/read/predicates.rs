@@ -1,8 +1,10 @@
 use arrow::array::{MutablePrimitiveArray, PrimitiveArray};
 use arrow::pushable::Pushable;
 use polars_core::prelude::*;
-use polars_parquet::read::RowGroupMetadata;
+use polars_parquet::arrow::bloom_filter;
 use polars_parquet::read::statistics::{ArrowColumnStatisticsArrays, deserialize_all};
+use polars_parquet::read::{PhysicalType, RowGroupMetadata};
+use polars_utils::format_pl_smallstr;
 
 /// Collect the statistics in a row-group
 pub fn collect_statistics_with_live_columns(
@@ -96,3 +98,103 @@ pub fn collect_statistics_with_live_columns(
         })
         .collect::<PolarsResult<Vec<_>>>()
 }
+
+/// Returns the `{name}_min`, `{name}_max` and `{name}_nc` columns of a statistics DataFrame with
+/// `height` batches for the column `name` read as `field`. Without `stats`, these are all null.
+pub fn statistics_columns(
+    name: &str,
+    field: &ArrowField,
+    stats: Option<ArrowColumnStatisticsArrays>,
+    height: usize,
+) -> PolarsResult<[Column; 3]> {
+    let min_name = format_pl_smallstr!("{name}_min");
+    let max_name = format_pl_smallstr!("{name}_max");
+    let nc_name = format_pl_smallstr!("{name}_nc");
+
+    Ok(match stats {
+        None => {
+            let dtype = DataType::from_arrow_field(field);
+
+            [
+                Column::full_null(min_name, height, &dtype),
+                Column::full_null(max_name, height, &dtype),
+                Column::full_null(nc_name, height, &IDX_DTYPE),
+            ]
+        },
+        Some(stats) => {
+            let md = field.metadata.as_deref();
+
+            [
+                unsafe {
+                    Series::_try_from_arrow_unchecked_with_md(
+                        min_name,
+                        vec![stats.min_value],
+                        field.dtype(),
+                        md,
+                    )
+                }?
+                .into_column(),
+                unsafe {
+                    Series::_try_from_arrow_unchecked_with_md(
+                        max_name,
+                        vec![stats.max_value],
+                        field.dtype(),
+                        md,
+                    )
+                }?
+                .into_column(),
+                Series::from_arrow(nc_name, stats.null_count.boxed())?.into_column(),
+            ]
+        },
+    })
+}
+
+/// The hashes of `values` as they are inserted into the bloom filter of a column chunk with
+/// `physical_type`. Returns `None` if the values can't be looked up in such a filter.
+pub fn bloom_filter_hashes(values: &Series, physical_type: PhysicalType) -> Option<Vec<u64>> {
+    use bloom_filter::{hash_byte, hash_native};
+    use {AnyValue as A, PhysicalType as P};
+
+    // Values are hashed in their plain encoding, which for the integer types is the (bit pattern
+    // of the) value extended to the physical type.
+    let values = values.rechunk();
+    values
+        .iter()
+        .map(|av| {
+            Some(match (physical_type, av) {
+                (P::Int32, A::Int8(v)) => hash_native(v as i32),
+                (P::Int32, A::Int16(v)) => hash_native(v as i32),
+                (P::Int32, A::Int32(v)) => hash_native(v),
+                (P::Int32, A::UInt8(v)) => hash_native(v as i32),
+                (P::Int32, A::UInt16(v)) => hash_native(v as i32),
+                (P::Int32, A::UInt32(v)) => hash_native(v as i32),
+                #[cfg(feature = "dtype-date")]
+                (P::Int32, A::Date(v)) => hash_native(v),
+                (P::Int64, A::Int64(v)) => hash_native(v),
+                (P::Int64, A::UInt64(v)) => hash_native(v as i64),
+                #[cfg(feature = "dtype-datetime")]
+                (P::Int64, A::Datetime(v, _, _) | A::DatetimeOwned(v, _, _)) => hash_native(v),
+                (P::ByteArray, A::String(v)) => hash_byte(v),
+                (P::ByteArray, A::StringOwned(v)) => hash_byte(v.as_str()),
+                (P::ByteArray, A::Binary(v)) => hash_byte(v),
+                (P::ByteArray, A::BinaryOwned(v)) => hash_byte(v),
+                _ => return None,
+            })
+        })
+        .collect()
+}
+
+/// Whether any of the `hashes` may be in the bloom filter at the start of `bytes`. This is `true`
+/// if the filter uses an unsupported algorithm.
+pub fn bloom_filter_may_contain(bytes: &[u8], hashes: &[u64]) -> PolarsResult<bool> {
+    let mut bitset = Vec::new();
+    bloom_filter::read_from_slice(bytes, &mut bitset)?;
+
+    // A split block filter consists of whole 32 byte blocks.
+    if bitset.len() < 32 {
+        return Ok(true);
+    }
+    Ok(hashes
+        .iter()
+        .any(|&hash| bloom_filter::is_in_set(&bitset, hash)))
+}
@@ -101,6 +101,54 @@ pub fn create_sorting_map(md: &RowGroupMetadata) -> PlHashMap<usize, IsSorted> {
     sorting_map
 }
 
+/// Returns whether the rows of a file are sorted ascendingly by a top-level
+/// column as a whole.
+///
+/// This requires every row group to be sorted by the column first, and the
+/// statistics to show that the row groups follow each other in key order.
+pub fn is_sorted_ascending_by(
+    md: &FileMetadata,
+    schema: &ArrowSchema,
+    column: &PlSmallStr,
+) -> PolarsResult<bool> {
+    let row_groups = &md.row_groups;
+    for rg in row_groups {
+        let Some(&[column_idx]) = rg.columns_idxs_under_root_iter(column) else {
+            return Ok(false);
+        };
+        // Only the first sorting column is sorted by itself.
+        let sorted = rg
+            .sorting_columns()
+            .and_then(|s| s.first())
+            .is_some_and(|s| s.column_idx as usize == column_idx && !s.descending);
+        if !sorted {
+            return Ok(false);
+        }
+    }
+    if row_groups.len() <= 1 {
+        return Ok(true);
+    }
+
+    let live_columns = PlIndexSet::from_iter([column.clone()]);
+    let Some(stats) = super::predicates::collect_statistics_with_live_columns(
+        row_groups,
+        schema,
+        &live_columns,
+        None,
+    )?
+    .pop()
+    .flatten() else {
+        return Ok(false);
+    };
+    let min = Series::from_arrow(column.clone(), stats.min_value)?;
+    let max = Series::from_arrow(column.clone(), stats.max_value)?;
+    if min.has_nulls() || max.has_nulls() {
+        return Ok(false);
+    }
+    let n = row_groups.len() - 1;
+    Ok(max.slice(0, n).lt_eq(&min.slice(1, n))?.all())
+}
+
 fn column_idx_to_series(
     column_i: usize,
     // The metadata belonging to this column
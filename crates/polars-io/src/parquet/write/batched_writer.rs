@@ -7,13 +7,20 @@ use polars_core::prelude::*;
 use polars_parquet::read::{ParquetError, fallible_streaming_iterator};
 use polars_parquet::write::{
     ColumnWriteOptions, CompressedPage, Compressor, DynIter, DynStreamingIterator,
-    FallibleStreamingIterator, FileWriter, Page, ParquetType, RowGroupIterColumns,
-    SchemaDescriptor, WriteOptions, array_to_columns, schema_to_metadata_key,
+    FallibleStreamingIterator, FileWriter, Page, ParquetType, RowGroupBloomFilters,
+    RowGroupIterColumns, SchemaDescriptor, WriteOptions, array_to_bloom_filters, array_to_columns,
+    schema_to_metadata_key,
 };
 use rayon::prelude::*;
 
 use super::{KeyValueMetadata, ParquetMetadataContext};
 
+/// The encoded and compressed columns of a row group, and the bloom filters of its leaf columns.
+pub type EncodedRowGroup = (
+    RowGroupIterColumns<'static, PolarsError>,
+    RowGroupBloomFilters,
+);
+
 pub struct BatchedWriter<W: Write> {
     // A mutex so that streaming engine can get concurrent read access to
     // compress pages.
@@ -49,7 +56,7 @@ impl<W: Write> BatchedWriter<W> {
     pub fn encode_and_compress<'a>(
         &'a self,
         df: &'a DataFrame,
-    ) -> impl Iterator<Item = PolarsResult<RowGroupIterColumns<'static, PolarsError>>> + 'a {
+    ) -> impl Iterator<Item = PolarsResult<EncodedRowGroup>> + 'a {
         let rb_iter = df.iter_chunks(CompatLevel::newest(), false);
         rb_iter.filter_map(move |batch| match batch.len() {
             0 => None,
@@ -81,7 +88,8 @@ impl<W: Write> BatchedWriter<W> {
         // Lock before looping so that order is maintained under contention.
         let mut writer = self.writer.lock().unwrap();
         for group in row_group_iter {
-            writer.write(group?)?;
+            let (group, bloom_filters) = group?;
+            writer.write_with_bloom_filters(group, bloom_filters)?;
         }
         Ok(())
     }
@@ -91,14 +99,18 @@ impl<W: Write> BatchedWriter<W> {
         writer.parquet_schema()
     }
 
-    pub fn write_row_group(&mut self, rg: &[Vec<CompressedPage>]) -> PolarsResult<()> {
+    pub fn write_row_group(
+        &mut self,
+        rg: &[Vec<CompressedPage>],
+        bloom_filters: RowGroupBloomFilters,
+    ) -> PolarsResult<()> {
         let writer = self.writer.get_mut().unwrap();
         let rg = DynIter::new(rg.iter().map(|col_pages| {
             Ok(DynStreamingIterator::new(
                 fallible_streaming_iterator::convert(col_pages.iter().map(PolarsResult::Ok)),
             ))
         }));
-        writer.write(rg)?;
+        writer.write_with_bloom_filters(rg, bloom_filters)?;
         Ok(())
     }
 
@@ -106,14 +118,11 @@ impl<W: Write> BatchedWriter<W> {
         &self.writer
     }
 
-    pub fn write_row_groups(
-        &self,
-        rgs: Vec<RowGroupIterColumns<'static, PolarsError>>,
-    ) -> PolarsResult<()> {
+    pub fn write_row_groups(&self, rgs: Vec<EncodedRowGroup>) -> PolarsResult<()> {
         // Lock before looping so that order is maintained.
         let mut writer = self.writer.lock().unwrap();
-        for group in rgs {
-            writer.write(group)?;
+        for (group, bloom_filters) in rgs {
+            writer.write_with_bloom_filters(group, bloom_filters)?;
         }
         Ok(())
     }
@@ -150,7 +159,7 @@ fn prepare_rg_iter<'a>(
     column_options: &'a [ColumnWriteOptions],
     options: WriteOptions,
     parallel: bool,
-) -> impl Iterator<Item = PolarsResult<RowGroupIterColumns<'static, PolarsError>>> + 'a {
+) -> impl Iterator<Item = PolarsResult<EncodedRowGroup>> + 'a {
     let rb_iter = df.iter_chunks(CompatLevel::newest(), false);
     rb_iter.filter_map(move |batch| match batch.len() {
         0 => None,
@@ -210,7 +219,7 @@ fn create_serializer(
     column_options: &[ColumnWriteOptions],
     options: WriteOptions,
     parallel: bool,
-) -> PolarsResult<RowGroupIterColumns<'static, PolarsError>> {
+) -> PolarsResult<EncodedRowGroup> {
     let func = move |((array, type_), column_options): (
         (&ArrayRef, &ParquetType),
         &ColumnWriteOptions,
@@ -237,8 +246,9 @@ fn create_serializer(
     };
 
     let row_group = DynIter::new(columns.into_iter());
+    let bloom_filters = create_bloom_filters(&batch, column_options, parallel);
 
-    Ok(row_group)
+    Ok((row_group, bloom_filters))
 }
 
 /// This serializer encodes and compresses all eagerly in memory.
@@ -248,7 +258,7 @@ fn create_eager_serializer(
     fields: &[ParquetType],
     column_options: &[ColumnWriteOptions],
     options: WriteOptions,
-) -> PolarsResult<RowGroupIterColumns<'static, PolarsError>> {
+) -> PolarsResult<EncodedRowGroup> {
     let func = move |((array, type_), column_options): (
         (&ArrayRef, &ParquetType),
         &ColumnWriteOptions,
@@ -263,6 +273,35 @@ fn create_eager_serializer(
         .collect::<Vec<_>>();
 
     let row_group = DynIter::new(columns.into_iter());
+    let bloom_filters = create_bloom_filters(&batch, column_options, false);
+
+    Ok((row_group, bloom_filters))
+}
+
+fn create_bloom_filters(
+    batch: &RecordBatch,
+    column_options: &[ColumnWriteOptions],
+    parallel: bool,
+) -> RowGroupBloomFilters {
+    let func = |(array, column_options): (&ArrayRef, &ColumnWriteOptions)| {
+        array_to_bloom_filters(array.as_ref(), column_options)
+    };
 
-    Ok(row_group)
+    if parallel {
+        POOL.install(|| {
+            batch
+                .columns()
+                .par_iter()
+                .zip(column_options)
+                .flat_map(func)
+                .collect()
+        })
+    } else {
+        batch
+            .columns()
+            .iter()
+            .zip(column_options)
+            .flat_map(func)
+            .collect()
+    }
 }
@@ -1,7 +1,9 @@
+use std::hash::{Hash, Hasher};
+
 use polars_error::PolarsResult;
 use polars_parquet::write::{
-    BrotliLevel as BrotliLevelParquet, CompressionOptions, GzipLevel as GzipLevelParquet,
-    StatisticsOptions, ZstdLevel as ZstdLevelParquet,
+    BloomFilterWriteOptions, BrotliLevel as BrotliLevelParquet, CompressionOptions,
+    GzipLevel as GzipLevelParquet, StatisticsOptions, ZstdLevel as ZstdLevelParquet,
 };
 use polars_utils::pl_str::PlSmallStr;
 #[cfg(feature = "serde")]
@@ -57,6 +59,46 @@ pub struct ParquetFieldOverwrites {
     pub required: Option<bool>,
     pub field_id: Option<i32>,
     pub metadata: Option<Vec<MetadataKeyValue>>,
+    /// Write a bloom filter for every row group of this (leaf) field.
+    pub bloom_filter: Option<ParquetBloomFilterOptions>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "dsl-schema", derive(schemars::JsonSchema))]
+pub struct ParquetBloomFilterOptions {
+    /// Estimate of the number of distinct values in a row group. If `None`, every filter is sized
+    /// for the number of distinct values in its row group.
+    pub ndv: Option<u64>,
+    /// The false positive probability of the filters, between 0 and 1.
+    pub fpp: f64,
+}
+
+impl Default for ParquetBloomFilterOptions {
+    fn default() -> Self {
+        Self {
+            ndv: None,
+            fpp: 0.05,
+        }
+    }
+}
+
+impl Eq for ParquetBloomFilterOptions {}
+
+impl Hash for ParquetBloomFilterOptions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ndv.hash(state);
+        self.fpp.to_bits().hash(state);
+    }
+}
+
+impl From<ParquetBloomFilterOptions> for BloomFilterWriteOptions {
+    fn from(value: ParquetBloomFilterOptions) -> Self {
+        BloomFilterWriteOptions {
+            ndv: value.ndv,
+            fpp: value.fpp,
+        }
+    }
 }
 
 /// The compression strategy to use for writing Parquet files.
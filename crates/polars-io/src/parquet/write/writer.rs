@@ -27,6 +27,7 @@ impl ParquetWriteOptions {
             .with_row_group_size(self.row_group_size)
             .with_data_page_size(self.data_page_size)
             .with_key_value_metadata(self.key_value_metadata.clone())
+            .with_field_overwrites(self.field_overwrites.clone())
     }
 }
 
@@ -113,6 +114,12 @@ where
         self
     }
 
+    /// Set the per-field overwrites of the write options.
+    pub fn with_field_overwrites(mut self, field_overwrites: Vec<ParquetFieldOverwrites>) -> Self {
+        self.field_overwrites = field_overwrites;
+        self
+    }
+
     /// Set context information for the writer
     pub fn with_context_info(mut self, context_info: Option<PlHashMap<String, String>>) -> Self {
         self.context_info = context_info;
@@ -186,6 +193,7 @@ fn to_column_write_options_rec(
         // Dummy value.
         children: ChildWriteOptions::Leaf(FieldWriteOptions {
             encoding: Encoding::Plain,
+            bloom_filter: None,
         }),
     };
 
@@ -201,6 +209,7 @@ fn to_column_write_options_rec(
         | Dictionary(_) | LargeUtf8 | BinaryView | Utf8View => {
             column_options.children = ChildWriteOptions::Leaf(FieldWriteOptions {
                 encoding: encoding_map(field.dtype()),
+                bloom_filter: overwrites.and_then(|o| o.bloom_filter).map(Into::into),
             });
         },
         List | FixedSizeList | LargeList => {
@@ -385,6 +385,18 @@ pub trait SkipBatchPredicate: Send + Sync {
     fn evaluate_with_stat_df(&self, df: &DataFrame) -> PolarsResult<Bitmap>;
 }
 
+/// A conjunct of a predicate that only passes rows in which `column` is equal to one of `values`,
+/// e.g. `col(a) == 1` or `col(a).is_in([1, 2])`.
+///
+/// A batch can be skipped if none of the `values` occur in it, which can be checked with e.g. a
+/// bloom filter.
+#[derive(Debug, Clone)]
+pub struct ColumnMembershipPredicate {
+    pub column: PlSmallStr,
+    /// The values that pass, of the dtype of `column`. Never contains nulls.
+    pub values: Series,
+}
+
 #[derive(Clone)]
 pub struct ColumnPredicates {
     pub predicates: PlHashMap<
@@ -458,6 +470,9 @@ pub struct ScanIOPredicate {
 
     /// A predicate that gets given statistics and evaluates whether a batch can be skipped.
     pub column_predicates: Arc<ColumnPredicates>,
+
+    /// Conjuncts of the predicate that test a column for equality with a set of values.
+    pub membership_predicates: Arc<[ColumnMembershipPredicate]>,
 }
 impl ScanIOPredicate {
     pub fn set_external_constant_columns(&mut self, constant_columns: Vec<(PlSmallStr, Scalar)>) {
@@ -496,6 +511,13 @@ impl ScanIOPredicate {
         }
         self.column_predicates = Arc::new(column_predicates);
 
+        self.membership_predicates = self
+            .membership_predicates
+            .iter()
+            .filter(|p| !constant_columns.iter().any(|(c, _)| c == &p.column))
+            .cloned()
+            .collect();
+
         self.predicate = Arc::new(PhysicalExprWithConstCols {
             constants: constant_columns,
             child: self.predicate.clone(),
@@ -1,8 +1,8 @@
 use std::sync::Mutex;
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{Receiver, channel};
 
 use polars_core::POOL;
+use polars_core::error::signals::CancellationToken;
 
 use super::*;
 
@@ -14,6 +14,9 @@ impl LazyFrame {
         let token = state.cancel_token();
         POOL.spawn_fifo(move || {
             let result = physical_plan.execute(&mut state);
+            // Don't hand out a result if the query was cancelled while its
+            // last operation was running.
+            let result = result.and_then(|df| state.should_stop().map(|_| df));
             tx.send(result).unwrap();
         });
 
@@ -27,13 +30,13 @@ impl LazyFrame {
 #[derive(Clone)]
 pub struct InProcessQuery {
     rx: Arc<Mutex<Receiver<PolarsResult<DataFrame>>>>,
-    token: Arc<AtomicBool>,
+    token: CancellationToken,
 }
 
 impl InProcessQuery {
     /// Cancel the query at earliest convenience.
     pub fn cancel(&self) {
-        self.token.store(true, Ordering::Relaxed)
+        self.token.cancel()
     }
 
     /// Fetch the result.
@@ -54,6 +57,6 @@ impl InProcessQuery {
 
 impl Drop for InProcessQuery {
     fn drop(&mut self) {
-        self.token.store(true, Ordering::Relaxed);
+        self.token.cancel();
     }
 }
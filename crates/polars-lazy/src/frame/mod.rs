@@ -13,6 +13,8 @@ use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 pub use anonymous_scan::*;
+#[cfg(feature = "avro")]
+pub use avro::*;
 #[cfg(feature = "csv")]
 pub use csv::*;
 #[cfg(not(target_arch = "wasm32"))]
@@ -38,6 +40,8 @@ use polars_ops::frame::{JoinCoalesce, MaintainOrderJoin};
 use polars_ops::prelude::ClosedInterval;
 pub use polars_plan::frame::{AllowedOptimizations, OptFlags};
 use polars_plan::global::FETCH_ROWS;
+#[cfg(feature = "new_streaming")]
+pub use polars_stream::QueryProfile;
 use polars_utils::pl_str::PlSmallStr;
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 
@@ -80,6 +84,17 @@ pub struct LazyFrame {
     pub(crate) cached_arena: Arc<Mutex<Option<CachedArena>>>,
 }
 
+/// Options for running a query, see [`LazyFrame::collect_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct CollectOptions {
+    /// Cancels the query once triggered or past its deadline.
+    pub cancel_token: CancellationToken,
+    /// The most memory (in bytes) the nodes of a streaming query may hold on
+    /// to. Without a limit it is taken from `POLARS_STREAMING_MEMORY_LIMIT`,
+    /// if set.
+    pub streaming_memory_limit: Option<usize>,
+}
+
 impl From<DslPlan> for LazyFrame {
     fn from(plan: DslPlan) -> Self {
         Self {
@@ -744,7 +759,50 @@ impl LazyFrame {
     /// `engine`.
     ///
     /// The query is optimized prior to execution.
-    pub fn collect_with_engine(mut self, mut engine: Engine) -> PolarsResult<DataFrame> {
+    pub fn collect_with_engine(self, engine: Engine) -> PolarsResult<DataFrame> {
+        self.collect_with_cancel_token(engine, CancellationToken::new())
+    }
+
+    /// Like [`LazyFrame::collect_with_engine`], but the query can be cancelled
+    /// through `cancel_token`, or runs until the deadline of the token.
+    ///
+    /// A cancelled query returns [`PolarsError::Cancelled`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// fn example(lf: LazyFrame) -> PolarsResult<DataFrame> {
+    ///     let token = CancellationToken::with_timeout(Duration::from_secs(60));
+    ///     lf.collect_with_cancel_token(Engine::Streaming, token)
+    /// }
+    /// ```
+    pub fn collect_with_cancel_token(
+        self,
+        engine: Engine,
+        cancel_token: CancellationToken,
+    ) -> PolarsResult<DataFrame> {
+        self.collect_with_options(
+            engine,
+            CollectOptions {
+                cancel_token,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Like [`LazyFrame::collect_with_engine`], but runs the query with the
+    /// given `options`.
+    pub fn collect_with_options(
+        mut self,
+        mut engine: Engine,
+        options: CollectOptions,
+    ) -> PolarsResult<DataFrame> {
+        let cancel_token = options.cancel_token;
         let payload = if let DslPlan::Sink { payload, .. } = &self.logical_plan {
             payload.clone()
         } else {
@@ -770,7 +828,10 @@ impl LazyFrame {
 
         #[cfg(feature = "new_streaming")]
         {
-            if let Some(result) = self.try_new_streaming_if_requested() {
+            if let Some(result) = self.try_new_streaming_if_requested(
+                cancel_token.clone(),
+                options.streaming_memory_limit,
+            ) {
                 return result.map(|v| v.unwrap_single());
             }
         }
@@ -793,6 +854,8 @@ impl LazyFrame {
                     alp_plan.lp_top,
                     &mut alp_plan.lp_arena,
                     &mut alp_plan.expr_arena,
+                    cancel_token,
+                    options.streaming_memory_limit,
                 );
                 #[cfg(feature = "dtype-categorical")]
                 drop(string_cache_hold);
@@ -813,6 +876,7 @@ impl LazyFrame {
                     BUILD_STREAMING_EXECUTOR,
                 )?;
                 let mut state = ExecutionState::new();
+                state.set_cancel_token(cancel_token);
                 physical_plan.execute(&mut state)
             },
             Engine::OldStreaming => {
@@ -823,6 +887,7 @@ impl LazyFrame {
                     is_streaming,
                     ComputeError: format!("cannot run the whole query in a streaming order")
                 );
+                state.set_cancel_token(cancel_token);
                 physical_plan.execute(&mut state)
             },
         }
@@ -838,10 +903,41 @@ impl LazyFrame {
     }
 
     pub fn collect_all_with_engine(
+        plans: Vec<DslPlan>,
+        engine: Engine,
+        opt_state: OptFlags,
+    ) -> PolarsResult<Vec<DataFrame>> {
+        Self::collect_all_with_cancel_token(plans, engine, opt_state, CancellationToken::new())
+    }
+
+    /// Like [`LazyFrame::collect_all_with_engine`], but all queries are
+    /// cancelled through `cancel_token`, or once its deadline has passed.
+    pub fn collect_all_with_cancel_token(
+        plans: Vec<DslPlan>,
+        engine: Engine,
+        opt_state: OptFlags,
+        cancel_token: CancellationToken,
+    ) -> PolarsResult<Vec<DataFrame>> {
+        Self::collect_all_with_options(
+            plans,
+            engine,
+            opt_state,
+            CollectOptions {
+                cancel_token,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Like [`LazyFrame::collect_all_with_engine`], but runs the queries with
+    /// the given `options`.
+    pub fn collect_all_with_options(
         plans: Vec<DslPlan>,
         mut engine: Engine,
         opt_state: OptFlags,
+        options: CollectOptions,
     ) -> PolarsResult<Vec<DataFrame>> {
+        let cancel_token = options.cancel_token;
         if plans.is_empty() {
             return Ok(Vec::new());
         }
@@ -863,7 +959,10 @@ impl LazyFrame {
 
         #[cfg(feature = "new_streaming")]
         {
-            if let Some(result) = sink_multiple.try_new_streaming_if_requested() {
+            if let Some(result) = sink_multiple.try_new_streaming_if_requested(
+                cancel_token.clone(),
+                options.streaming_memory_limit,
+            ) {
                 return result.map(|v| v.unwrap_multiple());
             }
         }
@@ -892,6 +991,8 @@ impl LazyFrame {
                     alp_plan.lp_top,
                     &mut alp_plan.lp_arena,
                     &mut alp_plan.expr_arena,
+                    cancel_token,
+                    options.streaming_memory_limit,
                 );
                 #[cfg(feature = "dtype-categorical")]
                 drop(string_cache_hold);
@@ -919,6 +1020,7 @@ impl LazyFrame {
                 // this might then lead to a rayon SO. So we take a multitude of the threads to keep work stealing
                 // within bounds
                 let mut state = ExecutionState::new();
+                state.set_cancel_token(cancel_token);
                 if let Some(mut cache_prefiller) = multiplan.cache_prefiller {
                     cache_prefiller.execute(&mut state)?;
                 }
@@ -1001,6 +1103,31 @@ impl LazyFrame {
         self._profile_post_opt(|_, _, _, _| Ok(()))
     }
 
+    /// Profile a LazyFrame on the streaming engine.
+    ///
+    /// This will run the query and return a tuple containing the materialized
+    /// DataFrame and a [`QueryProfile`] with the runtime statistics of each
+    /// node of the physical plan.
+    #[cfg(feature = "new_streaming")]
+    pub fn profile_streaming(mut self) -> PolarsResult<(DataFrame, QueryProfile)> {
+        if !matches!(self.logical_plan, DslPlan::Sink { .. }) {
+            self.logical_plan = DslPlan::Sink {
+                input: Arc::new(self.logical_plan),
+                payload: SinkType::Memory,
+            };
+        }
+        let mut alp_plan = self.with_new_streaming(true).to_alp_optimized()?;
+
+        #[cfg(feature = "dtype-categorical")]
+        let _hold = StringCacheHolder::hold();
+        let (result, profile) = polars_stream::profile_query(
+            alp_plan.lp_top,
+            &mut alp_plan.lp_arena,
+            &mut alp_plan.expr_arena,
+        )?;
+        Ok((result.unwrap_single(), profile))
+    }
+
     /// Stream a query result into a parquet file. This is useful if the final result doesn't fit
     /// into memory. This methods will return an error if the query cannot be completely done in a
     /// streaming fashion.
@@ -1077,6 +1204,25 @@ impl LazyFrame {
         }))
     }
 
+    /// Stream a query result into an Avro file. This is useful if the final result doesn't fit
+    /// into memory. This methods will return an error if the query cannot be completely done in a
+    /// streaming fashion.
+    #[cfg(feature = "avro")]
+    pub fn sink_avro(
+        self,
+        target: SinkTarget,
+        options: AvroWriterOptions,
+        cloud_options: Option<polars_io::cloud::CloudOptions>,
+        sink_options: SinkOptions,
+    ) -> PolarsResult<Self> {
+        self.sink(SinkType::File(FileSinkType {
+            target,
+            sink_options,
+            file_type: FileType::Avro(options),
+            cloud_options,
+        }))
+    }
+
     /// Stream a query result into a parquet file in a partitioned manner. This is useful if the
     /// final result doesn't fit into memory. This methods will return an error if the query cannot
     /// be completely done in a streaming fashion.
@@ -1189,9 +1335,39 @@ impl LazyFrame {
         }))
     }
 
+    /// Stream a query result into an Avro file in a partitioned manner. This is useful if the
+    /// final result doesn't fit into memory. This methods will return an error if the query cannot
+    /// be completely done in a streaming fashion.
+    #[cfg(feature = "avro")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn sink_avro_partitioned(
+        self,
+        base_path: Arc<PathBuf>,
+        file_path_cb: Option<PartitionTargetCallback>,
+        variant: PartitionVariant,
+        options: AvroWriterOptions,
+        cloud_options: Option<polars_io::cloud::CloudOptions>,
+        sink_options: SinkOptions,
+        per_partition_sort_by: Option<Vec<SortColumn>>,
+        finish_callback: Option<SinkFinishCallback>,
+    ) -> PolarsResult<Self> {
+        self.sink(SinkType::Partition(PartitionSinkType {
+            base_path,
+            file_path_cb,
+            sink_options,
+            variant,
+            file_type: FileType::Avro(options),
+            cloud_options,
+            per_partition_sort_by,
+            finish_callback,
+        }))
+    }
+
     #[cfg(feature = "new_streaming")]
     pub fn try_new_streaming_if_requested(
         &mut self,
+        cancel_token: CancellationToken,
+        memory_limit: Option<usize>,
     ) -> Option<PolarsResult<polars_stream::QueryResult>> {
         let auto_new_streaming = std::env::var("POLARS_AUTO_NEW_STREAMING").as_deref() == Ok("1");
         let force_new_streaming = std::env::var("POLARS_FORCE_NEW_STREAMING").as_deref() == Ok("1");
@@ -1214,6 +1390,8 @@ impl LazyFrame {
                     alp_plan.lp_top,
                     &mut alp_plan.lp_arena,
                     &mut alp_plan.expr_arena,
+                    cancel_token,
+                    memory_limit,
                 )
             };
 
@@ -2675,12 +2853,13 @@ mod streaming_dispatch {
     }
 
     impl Executor for StreamingQueryExecutor {
-        fn execute(&mut self, _cache: &mut ExecutionState) -> PolarsResult<DataFrame> {
+        fn execute(&mut self, state: &mut ExecutionState) -> PolarsResult<DataFrame> {
             // Must not block rayon thread on pending new-streaming future.
             assert!(POOL.current_thread_index().is_none());
 
             let mut df = { self.executor.try_lock().unwrap().take() }
                 .expect("unhandled: execute() more than once")
+                .with_cancel_token(state.cancel_token())
                 .execute()
                 .map(|x| x.unwrap_single())?;
 
@@ -1,16 +1,20 @@
 //! Module containing implementation of the pivot operation.
 //!
-//! Polars lazy does not implement a pivot because it is impossible to know the schema without
+//! A general pivot can't be lazy because it is impossible to know the schema without
 //! materializing the whole dataset. This makes a pivot quite a terrible operation for performant
 //! workflows. An optimization can never be pushed down passed a pivot.
 //!
 //! We can do a pivot on an eager `DataFrame` as that is already materialized. The code for the
 //! pivot is here, because we want to be able to pass expressions to the pivot operation.
 //!
+//! If the `on` values are declared up front the schema is known, in that case
+//! [`LazyFrame::pivot`] plans the pivot as a group-by.
+//!
 
 use polars_core::frame::group_by::expr::PhysicalAggExpr;
 use polars_core::prelude::*;
 use polars_ops::pivot::PivotAgg;
+use polars_utils::format_pl_smallstr;
 
 use crate::physical_plan::exotic::{prepare_eval_expr, prepare_expression_for_context};
 use crate::prelude::*;
@@ -88,3 +92,238 @@ where
     });
     polars_ops::pivot::pivot_stable(df, on, index, values, sort_columns, agg_expr, separator)
 }
+
+/// What a lazy pivot does with rows whose `on` values were not declared.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UnseenOnValues {
+    /// Raise an error once such a row is encountered during execution.
+    #[default]
+    Raise,
+    /// Drop the rows, they don't contribute to any output column.
+    Ignore,
+}
+
+/// Arguments for [`LazyFrame::pivot`].
+#[derive(Clone, Debug, Default)]
+pub struct LazyPivotArgs {
+    /// The columns whose values become the new columns.
+    pub on: Vec<PlSmallStr>,
+    /// The values of the `on` columns to create output columns for, a row per
+    /// output column. This determines the output schema up front.
+    pub on_columns: DataFrame,
+    /// The columns that remain rows in the output.
+    pub index: Vec<PlSmallStr>,
+    /// The columns to aggregate, defaults to all columns not in `on` or
+    /// `index`.
+    pub values: Option<Vec<PlSmallStr>>,
+    /// The aggregations to apply to the values, expressed on [`element`]. If
+    /// there are several they must be given distinct names with `alias`.
+    pub aggregations: Vec<Expr>,
+    /// Used as separator/delimiter in generated column names.
+    pub separator: Option<PlSmallStr>,
+    pub maintain_order: bool,
+    pub unseen_on_values: UnseenOnValues,
+}
+
+/// Builds the aggregation of `value` over the rows for which `cond` holds.
+///
+/// The other rows are masked as null rather than filtered out, so that the
+/// aggregation stays an elementwise input of the group-by which the streaming
+/// engine can execute.
+fn conditional_agg(agg: &Expr, value: &PlSmallStr, cond: &Expr) -> PolarsResult<Expr> {
+    let masked = when(cond.clone())
+        .then(col(value.clone()))
+        .otherwise(lit(NULL));
+    let num_matched = when(cond.clone())
+        .then(lit(true))
+        .otherwise(lit(NULL))
+        .count();
+
+    let agg = agg.clone().try_map_expr(|e| match e {
+        Expr::Column(_) | Expr::Nth(_) => Ok(masked.clone()),
+        // Counting all rows would count the masked ones.
+        Expr::Len | Expr::Agg(AggExpr::Count(_, true)) => Ok(num_matched.clone()),
+        e @ Expr::Agg(
+            AggExpr::First(_)
+            | AggExpr::Last(_)
+            | AggExpr::NUnique(_)
+            | AggExpr::Implode(_)
+            | AggExpr::AggGroups(_),
+        ) => polars_bail!(
+            InvalidOperation: "aggregation '{}' is not supported in a lazy pivot, \
+            it depends on the rows of other `on` values", e
+        ),
+        e => Ok(e),
+    })?;
+
+    // Missing combinations of index and `on` values are null, like in the
+    // eager pivot.
+    Ok(when(num_matched.gt(lit(0))).then(agg).otherwise(lit(NULL)))
+}
+
+fn scalar_lit(column: &Column, idx: usize) -> PolarsResult<Expr> {
+    let value = column.get(idx)?.into_static();
+    Ok(Expr::Literal(
+        Scalar::new(column.dtype().clone(), value).into(),
+    ))
+}
+
+impl LazyFrame {
+    /// Pivot the frame over `on` values that are declared up front.
+    ///
+    /// Unlike the eager pivot the output schema doesn't depend on the data,
+    /// which allows planning it as a group-by on `index` with an aggregation
+    /// per output column over the rows of its `on` value. The output columns
+    /// are ordered by value column, then aggregation, then `on` value.
+    ///
+    /// See [`LazyPivotArgs`] for the arguments.
+    pub fn pivot(self, args: LazyPivotArgs) -> PolarsResult<LazyFrame> {
+        let LazyPivotArgs {
+            on,
+            on_columns,
+            index,
+            values,
+            aggregations,
+            separator,
+            maintain_order,
+            unseen_on_values,
+        } = args;
+        polars_ensure!(!index.is_empty(), ComputeError: "index cannot be zero length");
+        polars_ensure!(!on.is_empty(), ComputeError: "`on` cannot be zero length");
+        polars_ensure!(
+            !aggregations.is_empty(),
+            InvalidOperation: "a lazy pivot requires at least one aggregation"
+        );
+        let sep = separator.as_deref().unwrap_or("_");
+
+        let mut lf = self;
+        let schema = lf.collect_schema()?;
+        let values = match values {
+            Some(values) => values,
+            None => schema
+                .iter_names()
+                .filter(|name| !on.contains(name) && !index.contains(name))
+                .cloned()
+                .collect(),
+        };
+
+        // The declared values, cast to the types of the `on` columns.
+        let on_columns = on
+            .iter()
+            .map(|name| {
+                let dtype = schema.try_get(name)?;
+                on_columns.column(name)?.strict_cast(dtype)
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let height = on_columns[0].len();
+
+        let headers = if on.len() > 1 {
+            let name = format_pl_smallstr!("{{\"{}\"}}", on.join("\",\""));
+            StructChunked::from_columns(name, height, &on_columns)?
+                .into_column()
+                .cast(&DataType::String)?
+        } else {
+            on_columns[0].cast(&DataType::String)?
+        };
+        let headers = headers
+            .str()?
+            .iter()
+            .map(|h| PlSmallStr::from_str(h.unwrap_or("null")))
+            .collect::<Vec<_>>();
+        let mut seen = PlHashSet::with_capacity(height);
+        for header in &headers {
+            polars_ensure!(
+                seen.insert(header),
+                Duplicate: "the declared `on` values for column '{}' are not unique", header
+            );
+        }
+
+        let aggregations = aggregations
+            .into_iter()
+            .map(|agg| match agg {
+                Expr::Alias(agg, name) => (Arc::unwrap_or_clone(agg), Some(name)),
+                agg => (agg, None),
+            })
+            .collect::<Vec<_>>();
+        polars_ensure!(
+            aggregations.len() == 1 || aggregations.iter().all(|(_, name)| name.is_some()),
+            InvalidOperation: "several aggregations in a lazy pivot must be named with `alias`"
+        );
+
+        // The condition for every declared row, using null-aware equality so
+        // null `on` values can be declared as well.
+        let conditions = (0..height)
+            .map(|i| {
+                let mut cond = None;
+                for (name, column) in on.iter().zip(&on_columns) {
+                    let eq = col(name.clone()).eq_missing(scalar_lit(column, i)?);
+                    cond = Some(match cond {
+                        Some(cond) => cond.and(eq),
+                        None => eq,
+                    });
+                }
+                Ok(cond.unwrap())
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let declared = conditions
+            .iter()
+            .cloned()
+            .reduce(|a, b| a.or(b))
+            .unwrap_or_else(|| lit(false));
+
+        lf = match unseen_on_values {
+            UnseenOnValues::Ignore => lf.filter(declared),
+            UnseenOnValues::Raise => {
+                let on_names = on.clone();
+                let check = move |c: &mut [Column]| {
+                    let declared = c[0].bool()?;
+                    if let Some(idx) = declared.iter().position(|d| d != Some(true)) {
+                        let values = c[1..]
+                            .iter()
+                            .map(|c| c.get(idx).map(|v| v.to_string()))
+                            .collect::<PolarsResult<Vec<_>>>()?;
+                        polars_bail!(
+                            ComputeError: "pivot encountered value(s) [{}] in `on` column(s) {:?} \
+                            which were not declared, declare them or ignore unseen `on` values",
+                            values.join(", "), on_names
+                        );
+                    }
+                    Ok(Some(std::mem::take(&mut c[0])))
+                };
+                let on_exprs = on.iter().map(|name| col(name.clone())).collect::<Vec<_>>();
+                lf.filter(declared.map_many(
+                    check,
+                    &on_exprs,
+                    GetOutput::from_type(DataType::Boolean),
+                ))
+            },
+        };
+
+        let mut aggs = Vec::with_capacity(values.len() * aggregations.len() * height);
+        for value in &values {
+            for (agg, agg_name) in &aggregations {
+                for (cond, header) in conditions.iter().zip(&headers) {
+                    let mut name = String::new();
+                    if values.len() > 1 {
+                        name.push_str(value);
+                        name.push_str(sep);
+                    }
+                    if let Some(agg_name) = agg_name {
+                        name.push_str(agg_name);
+                        name.push_str(sep);
+                    }
+                    name.push_str(header);
+                    aggs.push(conditional_agg(agg, value, cond)?.alias(name));
+                }
+            }
+        }
+
+        let index = index.into_iter().map(col).collect::<Vec<_>>();
+        let gb = if maintain_order {
+            lf.group_by_stable(index)
+        } else {
+            lf.group_by(index)
+        };
+        Ok(gb.agg(aggs))
+    }
+}
@@ -417,3 +417,56 @@ fn test_streaming_full_outer_join() -> PolarsResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_streaming_cancelled() -> PolarsResult<()> {
+    let q = get_parquet_file()
+        .group_by([col("sugars_g")])
+        .agg([col("calories").sum()]);
+
+    for engine in [Engine::Streaming, Engine::InMemory] {
+        let token = CancellationToken::new();
+        token.cancel();
+        let out = q.clone().collect_with_cancel_token(engine, token);
+        assert!(matches!(out, Err(PolarsError::Cancelled(_))));
+
+        let token = CancellationToken::with_timeout(std::time::Duration::ZERO);
+        let out = q.clone().collect_with_cancel_token(engine, token);
+        assert!(matches!(out, Err(PolarsError::Cancelled(_))));
+    }
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let out = LazyFrame::collect_all_with_cancel_token(
+        vec![q.clone().logical_plan],
+        Engine::Streaming,
+        Default::default(),
+        token,
+    );
+    assert!(matches!(out, Err(PolarsError::Cancelled(_))));
+
+    let token = CancellationToken::with_timeout(std::time::Duration::from_secs(3600));
+    let out = q.collect_with_cancel_token(Engine::Streaming, token)?;
+    assert_eq!(out.width(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_streaming_memory_limit_option() -> PolarsResult<()> {
+    let q = get_parquet_file().select([col("calories"), col("sugars_g")]);
+
+    let options = CollectOptions {
+        streaming_memory_limit: Some(1),
+        ..Default::default()
+    };
+    let out = q.clone().collect_with_options(Engine::Streaming, options);
+    assert!(matches!(out, Err(PolarsError::ComputeError(msg)) if msg.contains("memory budget")));
+
+    let options = CollectOptions {
+        streaming_memory_limit: Some(1 << 30),
+        ..Default::default()
+    };
+    let out = q.collect_with_options(Engine::Streaming, options)?;
+    assert_eq!(out.width(), 2);
+    Ok(())
+}
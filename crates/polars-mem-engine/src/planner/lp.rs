@@ -8,7 +8,9 @@ use polars_utils::unique_id::UniqueId;
 use recursive::recursive;
 
 use self::expr_ir::OutputName;
-use self::predicates::{aexpr_to_column_predicates, aexpr_to_skip_batch_predicate};
+use self::predicates::{
+    aexpr_to_column_predicates, aexpr_to_membership_predicates, aexpr_to_skip_batch_predicate,
+};
 #[cfg(feature = "python")]
 use self::python_dsl::PythonScanSource;
 use super::super::executors::{self, Executor};
@@ -268,6 +270,8 @@ fn create_physical_plan_impl(
                         FileType::Csv(_) => "csv",
                         #[cfg(feature = "json")]
                         FileType::Json(_) => "json",
+                        #[cfg(feature = "avro")]
+                        FileType::Avro(_) => "avro",
                         #[allow(unreachable_patterns)]
                         _ => panic!("enable filetype feature"),
                     };
@@ -291,6 +295,7 @@ fn create_physical_plan_impl(
                                         .with_row_group_size(options.row_group_size)
                                         .with_data_page_size(options.data_page_size)
                                         .with_key_value_metadata(options.key_value_metadata.clone())
+                                        .with_field_overwrites(options.field_overwrites.clone())
                                         .finish(&mut df)?;
                                 },
                                 #[cfg(feature = "ipc")]
@@ -343,6 +348,16 @@ fn create_physical_plan_impl(
                                         .with_json_format(JsonFormat::JsonLines)
                                         .finish(&mut df)?;
                                 },
+                                #[cfg(feature = "avro")]
+                                FileType::Avro(options) => {
+                                    use polars_io::SerWriter;
+                                    use polars_io::avro::AvroWriter;
+
+                                    AvroWriter::new(BufWriter::new(writer))
+                                        .with_codec(options.compression)
+                                        .with_name(options.name.to_string())
+                                        .finish(&mut df)?;
+                                },
                                 #[allow(unreachable_patterns)]
                                 _ => panic!("enable filetype feature"),
                             }
@@ -910,8 +925,12 @@ pub fn create_scan_predicate(
     )));
 
     let mut skip_batch_predicate = None;
+    let mut membership_predicates = Arc::default();
 
     if create_skip_batch_predicate {
+        membership_predicates =
+            aexpr_to_membership_predicates(predicate.node(), expr_arena, schema).into();
+
         if let Some(node) = aexpr_to_skip_batch_predicate(predicate.node(), expr_arena, schema) {
             let expr = ExprIR::new(node, predicate.output_name_inner().clone());
 
@@ -995,5 +1014,6 @@ pub fn create_scan_predicate(
         live_columns,
         skip_batch_predicate,
         column_predicates,
+        membership_predicates,
     })
 }
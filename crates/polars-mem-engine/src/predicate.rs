@@ -10,7 +10,8 @@ use polars_error::PolarsResult;
 use polars_expr::prelude::{AggregationContext, PhysicalExpr, phys_expr_to_io_expr};
 use polars_expr::state::ExecutionState;
 use polars_io::predicates::{
-    ColumnPredicates, ScanIOPredicate, SkipBatchPredicate, SpecializedColumnPredicateExpr,
+    ColumnMembershipPredicate, ColumnPredicates, ScanIOPredicate, SkipBatchPredicate,
+    SpecializedColumnPredicateExpr,
 };
 use polars_utils::pl_str::PlSmallStr;
 use polars_utils::{IdxSize, format_pl_smallstr};
@@ -33,6 +34,10 @@ pub struct ScanPredicate {
 
     /// Partial predicates for each column for filter when loading columnar formats.
     pub column_predicates: PhysicalColumnPredicates,
+
+    /// Conjuncts of the predicate that test a column for equality with a set of values, used to
+    /// skip record batches with bloom filters.
+    pub membership_predicates: Arc<[ColumnMembershipPredicate]>,
 }
 
 impl fmt::Debug for ScanPredicate {
@@ -157,10 +162,19 @@ impl ScanPredicate {
             }) as _
         });
 
+        // The constant columns were removed from the live columns.
+        let membership_predicates = self
+            .membership_predicates
+            .iter()
+            .filter(|p| live_columns.contains(&p.column))
+            .cloned()
+            .collect();
+
         Self {
             predicate,
             live_columns: Arc::new(live_columns),
             skip_batch_predicate,
+            membership_predicates,
             column_predicates: self.column_predicates.clone(), // Q? Maybe this should cull
                                                                // predicates.
         }
@@ -198,6 +212,7 @@ impl ScanPredicate {
                     .collect(),
                 is_sumwise_complete: self.column_predicates.is_sumwise_complete,
             }),
+            membership_predicates: self.membership_predicates.clone(),
         }
     }
 }
@@ -168,7 +168,10 @@ where
         AsofStrategy::Backward => {
             join_asof_impl::<T, AsofJoinBackwardState, _>(left, right, filter, allow_eq)
         },
-        AsofStrategy::Nearest => unimplemented!(),
+        AsofStrategy::Nearest => polars_bail!(
+            InvalidOperation: "asof join with strategy 'nearest' is not supported on {} keys",
+            input_ca.dtype()
+        ),
     })
 }
 
@@ -334,7 +334,10 @@ where
         AsofStrategy::Forward => dispatch_join_by_type::<T, AsofJoinForwardState, _>(
             left_asof, right_asof, left_by, right_by, filter, allow_eq,
         ),
-        AsofStrategy::Nearest => unimplemented!(),
+        AsofStrategy::Nearest => polars_bail!(
+            InvalidOperation: "asof join with strategy 'nearest' is not supported on {} keys",
+            left_asof.dtype()
+        ),
     }
 }
 
@@ -404,6 +407,13 @@ fn dispatch_join_type(
                 ca, right_asof, left_by, right_by, strategy, tolerance, allow_eq,
             )
         },
+        #[cfg(feature = "dtype-i128")]
+        DataType::Int128 => {
+            let ca = left_asof.i128().unwrap();
+            dispatch_join_strategy_numeric(
+                ca, right_asof, left_by, right_by, strategy, tolerance, allow_eq,
+            )
+        },
         DataType::UInt64 => {
             let ca = left_asof.u64().unwrap();
             dispatch_join_strategy_numeric(
@@ -428,7 +438,8 @@ fn dispatch_join_type(
                 ca, right_asof, left_by, right_by, strategy, tolerance, allow_eq,
             )
         },
-        DataType::Boolean => {
+        // A nearest search needs a distance, so it goes through the numeric path below.
+        DataType::Boolean if strategy != AsofStrategy::Nearest => {
             let ca = left_asof.bool().unwrap();
             dispatch_join_strategy::<BooleanType>(
                 ca, right_asof, left_by, right_by, strategy, allow_eq,
@@ -613,6 +624,8 @@ impl AsofJoinBy for DataFrame {}
 
 #[cfg(test)]
 mod test {
+    use rand::prelude::*;
+
     use super::*;
 
     #[test]
@@ -852,4 +865,216 @@ mod test {
 
         Ok(())
     }
+
+    /// Finds the nearest right row for each left row by brute force, breaking
+    /// ties like the sequential search does: with `allow_eq` the later row wins
+    /// (and the last of equal keys), without it the earlier row wins.
+    fn nearest_oracle(
+        left_by: &[i64],
+        left_on: &[Option<i64>],
+        right_by: &[i64],
+        right_on: &[Option<i64>],
+        tolerance: Option<i64>,
+        allow_eq: bool,
+    ) -> Vec<Option<IdxSize>> {
+        left_by
+            .iter()
+            .zip(left_on)
+            .map(|(lg, l)| {
+                let l = (*l)?;
+                let group: Vec<(usize, i64)> = right_by
+                    .iter()
+                    .zip(right_on)
+                    .enumerate()
+                    .filter(|(_, (rg, r))| *rg == lg && r.is_some())
+                    .map(|(i, (_, r))| (i, r.unwrap()))
+                    .collect();
+                let before = |r: i64| if allow_eq { r <= l } else { r < l };
+                let backward = group.iter().rev().find(|(_, r)| before(*r));
+                let forward = group.iter().find(|(_, r)| !before(*r));
+                let (idx, r) = match (backward, forward) {
+                    (None, None) => return None,
+                    (Some(b), Some(f))
+                        if (f.1 - l) > (l - b.1) || ((f.1 - l) == (l - b.1) && !allow_eq) =>
+                    {
+                        *b
+                    },
+                    (Some(b), None) => *b,
+                    (_, Some(f)) if allow_eq => {
+                        *group.iter().rev().find(|(_, r)| *r == f.1).unwrap()
+                    },
+                    (_, Some(f)) => *f,
+                };
+                tolerance
+                    .is_none_or(|t| (r - l).abs() <= t)
+                    .then_some(idx as IdxSize)
+            })
+            .collect()
+    }
+
+    /// Generates sorted keys within each of the groups, with some nulls.
+    fn random_sorted_in_groups(rng: &mut SmallRng, n_groups: i64) -> (Vec<i64>, Vec<Option<i64>>) {
+        let n = rng.gen_range(0..50);
+        let by: Vec<i64> = (0..n).map(|_| rng.gen_range(0..n_groups)).collect();
+        let mut on: Vec<Option<i64>> = vec![None; n];
+        for g in 0..n_groups {
+            let idxs: Vec<usize> = (0..n).filter(|i| by[*i] == g).collect();
+            let mut vals: Vec<i64> = idxs.iter().map(|_| rng.gen_range(0..40)).collect();
+            vals.sort_unstable();
+            for (i, v) in idxs.into_iter().zip(vals) {
+                on[i] = (rng.gen_range(0..10) > 0).then_some(v);
+            }
+        }
+        (by, on)
+    }
+
+    #[test]
+    fn test_asof_by_nearest_oracle() -> PolarsResult<()> {
+        let mut rng = SmallRng::seed_from_u64(0xdeadbeef);
+
+        for _ in 0..200 {
+            let n_groups = rng.gen_range(1..4);
+            let (left_by, left_on) = random_sorted_in_groups(&mut rng, n_groups);
+            let (right_by, right_on) = random_sorted_in_groups(&mut rng, n_groups);
+            let allow_eq = rng.r#gen::<bool>();
+            let tolerance = rng.gen_range(-1..6);
+            let tolerance = (tolerance >= 0).then_some(tolerance);
+            let expected = nearest_oracle(
+                &left_by, &left_on, &right_by, &right_on, tolerance, allow_eq,
+            );
+
+            let left = df![
+                "t" => &left_on,
+                "g" => &left_by,
+                "gs" => left_by.iter().map(|g| format!("g{g}")).collect::<Vec<_>>(),
+            ]?;
+            let right = df![
+                "t" => &right_on,
+                "g" => &right_by,
+                "gs" => right_by.iter().map(|g| format!("g{g}")).collect::<Vec<_>>(),
+                "idx" => (0..right_by.len() as IdxSize).collect::<Vec<_>>(),
+            ]?;
+
+            for on_dtype in [DataType::Int64, DataType::Int32, DataType::Float64] {
+                let cast = |df: &DataFrame| -> PolarsResult<DataFrame> {
+                    let mut df = df.clone();
+                    df.apply("t", |c| c.cast(&on_dtype).unwrap())?;
+                    Ok(df)
+                };
+                let (left, right) = (cast(&left)?, cast(&right)?);
+                let tolerance = tolerance.map(|t| AnyValue::Int64(t).cast(&on_dtype).into_static());
+                for by in [&["g"][..], &["gs"], &["g", "gs"]] {
+                    let out = left.join_asof_by(
+                        &right,
+                        "t",
+                        "t",
+                        by.iter().copied(),
+                        by.iter().copied(),
+                        AsofStrategy::Nearest,
+                        tolerance.clone(),
+                        allow_eq,
+                        false,
+                    )?;
+                    let out: Vec<_> = out.column("idx")?.idx()?.into_iter().collect();
+                    assert_eq!(
+                        out, expected,
+                        "by {by:?} on {on_dtype:?} allow_eq {allow_eq} tolerance {tolerance:?}"
+                    );
+                }
+
+                if n_groups == 1 {
+                    // Everything is in the same group, so this must match the
+                    // ungrouped join.
+                    let out = left._join_asof(
+                        &right,
+                        left.column("t")?.as_materialized_series(),
+                        right.column("t")?.as_materialized_series(),
+                        AsofStrategy::Nearest,
+                        tolerance.clone(),
+                        None,
+                        None,
+                        true,
+                        allow_eq,
+                        false,
+                    )?;
+                    let out: Vec<_> = out.column("idx")?.idx()?.into_iter().collect();
+                    assert_eq!(
+                        out, expected,
+                        "ungrouped on {on_dtype:?} allow_eq {allow_eq} tolerance {tolerance:?}"
+                    );
+                }
+            }
+
+            // Booleans are compared as integers, and don't allow a tolerance.
+            let to_bool =
+                |on: &[Option<i64>]| on.iter().map(|v| v.map(|v| v >= 20)).collect::<Vec<_>>();
+            let (left_on, right_on) = (to_bool(&left_on), to_bool(&right_on));
+            let expected = nearest_oracle(
+                &left_by,
+                &left_on.iter().map(|v| v.map(i64::from)).collect::<Vec<_>>(),
+                &right_by,
+                &right_on
+                    .iter()
+                    .map(|v| v.map(i64::from))
+                    .collect::<Vec<_>>(),
+                None,
+                allow_eq,
+            );
+            let mut left = left;
+            let mut right = right;
+            left.with_column(Column::new("t".into(), &left_on))?;
+            right.with_column(Column::new("t".into(), &right_on))?;
+            let out = left.join_asof_by(
+                &right,
+                "t",
+                "t",
+                ["gs"],
+                ["gs"],
+                AsofStrategy::Nearest,
+                None,
+                allow_eq,
+                false,
+            )?;
+            let out: Vec<_> = out.column("idx")?.idx()?.into_iter().collect();
+            assert_eq!(out, expected, "bool allow_eq {allow_eq}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_asof_nearest_unsupported_dtype() -> PolarsResult<()> {
+        let a = df!["a" => ["a", "c"], "b" => [1, 1]]?;
+        let b = df!["a" => ["b", "d"], "b" => [1, 1]]?;
+
+        let out = a.join_asof_by(
+            &b,
+            "a",
+            "a",
+            ["b"],
+            ["b"],
+            AsofStrategy::Nearest,
+            None,
+            true,
+            false,
+        );
+        assert!(matches!(out, Err(PolarsError::InvalidOperation(_))));
+
+        let a_key = a.column("a")?.as_materialized_series();
+        let b_key = b.column("a")?.as_materialized_series();
+        let out = a._join_asof(
+            &b,
+            a_key,
+            b_key,
+            AsofStrategy::Nearest,
+            None,
+            None,
+            None,
+            true,
+            true,
+            false,
+        );
+        assert!(matches!(out, Err(PolarsError::InvalidOperation(_))));
+        Ok(())
+    }
 }
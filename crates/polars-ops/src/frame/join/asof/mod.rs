@@ -312,7 +312,8 @@ pub trait AsofJoin: IntoDf {
                 let ca = left_key.f64().unwrap();
                 join_asof_numeric(ca, &right_key, strategy, tolerance, allow_eq)
             },
-            DataType::Boolean => {
+            // A nearest search needs a distance, so it goes through the numeric path below.
+            DataType::Boolean if strategy != AsofStrategy::Nearest => {
                 let ca = left_key.bool().unwrap();
                 join_asof::<BooleanType>(ca, &right_key, strategy, allow_eq)
             },
@@ -12,7 +12,7 @@ use polars_utils::pl_str::PlSmallStr;
 
 use super::{ParquetTimeUnit, RowGroupMetadata};
 use crate::parquet::error::{ParquetError, ParquetResult};
-use crate::parquet::schema::types::PhysicalType as ParquetPhysicalType;
+use crate::parquet::schema::types::{PhysicalType as ParquetPhysicalType, PrimitiveType};
 use crate::parquet::statistics::Statistics as ParquetStatistics;
 use crate::read::{
     ColumnChunkMetadata, PrimitiveLogicalType, convert_days_ms, convert_i128, convert_i256,
@@ -299,6 +299,30 @@ pub fn deserialize_all(
     field_idx: usize,
 ) -> ParquetResult<Option<ArrowColumnStatisticsArrays>> {
     assert!(!row_groups.is_empty());
+
+    let primitive_type = &row_groups[0].parquet_columns()[field_idx]
+        .descriptor()
+        .descriptor
+        .primitive_type;
+    let statistics = row_groups
+        .iter()
+        .map(|rg| rg.parquet_columns()[field_idx].statistics().transpose());
+
+    deserialize_batches(field, primitive_type, row_groups.len(), statistics)
+}
+
+/// Deserializes the statistics of `num_batches` consecutive batches of a leaf column with
+/// `primitive_type`, e.g. the row groups of a file or the pages of a column chunk, into
+/// [`ArrowColumnStatisticsArrays`] associated from `field`'s name.
+///
+/// # Errors
+/// This function errors if the deserialization of the statistics fails (e.g. invalid utf8)
+pub fn deserialize_batches(
+    field: &Field,
+    primitive_type: &PrimitiveType,
+    num_batches: usize,
+    statistics: impl IntoIterator<Item = ParquetResult<Option<ParquetStatistics>>>,
+) -> ParquetResult<Option<ArrowColumnStatisticsArrays>> {
     use ArrowDataType as D;
     match field.dtype() {
         // @TODO: These are all a bit more complex, skip for now.
@@ -308,26 +332,19 @@ pub fn deserialize_all(
         D::Struct(..) => Ok(None),
 
         _ => {
-            let mut null_count = MutablePrimitiveArray::<IdxSize>::with_capacity(row_groups.len());
-            let mut distinct_count =
-                MutablePrimitiveArray::<IdxSize>::with_capacity(row_groups.len());
-
-            let primitive_type = &row_groups[0].parquet_columns()[field_idx]
-                .descriptor()
-                .descriptor
-                .primitive_type;
+            let mut null_count = MutablePrimitiveArray::<IdxSize>::with_capacity(num_batches);
+            let mut distinct_count = MutablePrimitiveArray::<IdxSize>::with_capacity(num_batches);
 
             let logical_type = &primitive_type.logical_type;
             let physical_type = &primitive_type.physical_type;
 
             macro_rules! rmap {
                 ($expect:ident, $map:expr, $arr:ty$(, $arg:expr)?) => {{
-                    let mut min_arr = <$arr>::with_capacity(row_groups.len()$(, $arg)?);
-                    let mut max_arr = <$arr>::with_capacity(row_groups.len()$(, $arg)?);
+                    let mut min_arr = <$arr>::with_capacity(num_batches$(, $arg)?);
+                    let mut max_arr = <$arr>::with_capacity(num_batches$(, $arg)?);
 
-                    for rg in row_groups {
-                        let column = &rg.parquet_columns()[field_idx];
-                        let s = column.statistics().transpose()?;
+                    for s in statistics {
+                        let s = s?;
 
                         let (v_min, v_max, v_null_count, v_distinct_count) = match s {
                             None => (None, None, None, None),
@@ -401,8 +418,8 @@ pub fn deserialize_all(
             use {ArrowDataType as D, ParquetPhysicalType as PPT};
             let (min_value, max_value) = match (field.dtype(), physical_type) {
                 (D::Null, _) => (
-                    NullArray::new(ArrowDataType::Null, row_groups.len()).to_boxed(),
-                    NullArray::new(ArrowDataType::Null, row_groups.len()).to_boxed(),
+                    NullArray::new(ArrowDataType::Null, num_batches).to_boxed(),
+                    NullArray::new(ArrowDataType::Null, num_batches).to_boxed(),
                 ),
 
                 (D::Boolean, _) => rmap!(
@@ -0,0 +1,119 @@
+use arrow::array::{
+    Array, BinaryArray, BinaryViewArray, DictionaryArray, DictionaryKey, PrimitiveArray, Utf8Array,
+    Utf8ViewArray,
+};
+use arrow::datatypes::ArrowDataType;
+use arrow::match_integer_type;
+use polars_compute::hyperloglogplus::HyperLogLog;
+
+use super::{BloomFilterWriteOptions, ColumnWriteOptions, RowGroupBloomFilters, to_leaves};
+use crate::parquet::bloom_filter::{hash_byte, hash_native, insert, optimal_num_bytes};
+use crate::parquet::types::NativeType as ParquetNativeType;
+
+/// Builds the bloom filters of the leaf columns of `array` for which `column_options` request one.
+///
+/// Leaves of a type that can't be hashed the way it is written get no filter, the planner rejects
+/// bloom filters for those types up front.
+pub fn array_to_bloom_filters(
+    array: &dyn Array,
+    column_options: &ColumnWriteOptions,
+) -> RowGroupBloomFilters {
+    let mut field_options = Vec::new();
+    column_options.to_leaves(&mut field_options);
+
+    if field_options.iter().all(|o| o.bloom_filter.is_none()) {
+        return vec![None; field_options.len()];
+    }
+
+    let mut leaves = Vec::new();
+    to_leaves(array, &mut leaves);
+    assert_eq!(leaves.len(), field_options.len());
+
+    leaves
+        .iter()
+        .zip(field_options)
+        .map(|(leaf, field_options)| {
+            let options = field_options.bloom_filter?;
+            let hashes = leaf_hashes(leaf.as_ref())?;
+            Some(build_bitset(&hashes, options))
+        })
+        .collect()
+}
+
+fn build_bitset(hashes: &[u64], options: BloomFilterWriteOptions) -> Vec<u8> {
+    // Without a given number of distinct values it is estimated, counting them exactly would need
+    // memory in the order of the row group.
+    let ndv = options.ndv.unwrap_or_else(|| {
+        let mut hll = HyperLogLog::new();
+        hll.extend(hashes);
+        hll.count() as u64
+    });
+
+    let mut bitset = vec![0; optimal_num_bytes(ndv, options.fpp)];
+    for &hash in hashes {
+        insert(&mut bitset, hash);
+    }
+    bitset
+}
+
+/// The hashes of the valid values of `array` in their plain encoding. The casts MUST match the
+/// ones done when writing the pages.
+fn leaf_hashes(array: &dyn Array) -> Option<Vec<u64>> {
+    use ArrowDataType as D;
+
+    Some(match array.dtype().to_logical_type() {
+        D::UInt8 => primitive_hashes(array, |v: u8| v as i32),
+        D::UInt16 => primitive_hashes(array, |v: u16| v as i32),
+        D::UInt32 => primitive_hashes(array, |v: u32| v as i32),
+        D::UInt64 => primitive_hashes(array, |v: u64| v as i64),
+        D::Int8 => primitive_hashes(array, |v: i8| v as i32),
+        D::Int16 => primitive_hashes(array, |v: i16| v as i32),
+        D::Int32 | D::Date32 | D::Time32(_) => primitive_hashes(array, |v: i32| v),
+        D::Int64 | D::Date64 | D::Time64(_) | D::Timestamp(_, _) | D::Duration(_) => {
+            primitive_hashes(array, |v: i64| v)
+        },
+        D::Float32 => primitive_hashes(array, |v: f32| v),
+        D::Float64 => primitive_hashes(array, |v: f64| v),
+        D::LargeUtf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
+            array.non_null_values_iter().map(hash_byte).collect()
+        },
+        D::LargeBinary => {
+            let array = array.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
+            array.non_null_values_iter().map(hash_byte).collect()
+        },
+        D::Utf8View => {
+            let array = array.as_any().downcast_ref::<Utf8ViewArray>().unwrap();
+            array.non_null_values_iter().map(hash_byte).collect()
+        },
+        D::BinaryView => {
+            let array = array.as_any().downcast_ref::<BinaryViewArray>().unwrap();
+            array.non_null_values_iter().map(hash_byte).collect()
+        },
+        D::Dictionary(key_type, _, _) => match_integer_type!(key_type, |$T| {
+            dictionary_hashes::<$T>(array.as_any().downcast_ref().unwrap())?
+        }),
+        _ => return None,
+    })
+}
+
+fn primitive_hashes<T: arrow::types::NativeType, P: ParquetNativeType>(
+    array: &dyn Array,
+    cast: impl Fn(T) -> P,
+) -> Vec<u64> {
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    array
+        .non_null_values_iter()
+        .map(|v| hash_native(cast(v)))
+        .collect()
+}
+
+/// Dictionaries of strings are written as byte arrays, so their values are hashed as such.
+fn dictionary_hashes<K: DictionaryKey>(array: &DictionaryArray<K>) -> Option<Vec<u64>> {
+    let values = array.values().as_any().downcast_ref::<Utf8ViewArray>()?;
+    array
+        .keys()
+        .non_null_values_iter()
+        .map(|k| Some(hash_byte(values.value(k.try_into().ok()?))))
+        .collect()
+}
@@ -14,6 +14,8 @@
 
 mod binary;
 mod binview;
+#[cfg(feature = "bloom_filter")]
+mod bloom_filter;
 mod boolean;
 mod dictionary;
 mod file;
@@ -124,8 +126,22 @@ impl ColumnWriteOptions {
 #[derive(Clone)]
 pub struct FieldWriteOptions {
     pub encoding: Encoding,
+    pub bloom_filter: Option<BloomFilterWriteOptions>,
 }
 
+/// Options of the bloom filters written for a leaf column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BloomFilterWriteOptions {
+    /// The number of distinct values to size the filters for. If `None`, the filter of every row
+    /// group is sized for the number of distinct values in that row group.
+    pub ndv: Option<u64>,
+    /// The false positive probability to size the filters for.
+    pub fpp: f64,
+}
+
+/// The bloom filter bitsets of the leaf columns of a row group, `None` for the columns without one.
+pub type RowGroupBloomFilters = Vec<Option<Vec<u8>>>;
+
 impl ColumnWriteOptions {
     pub fn default_with(children: ChildWriteOptions) -> Self {
         Self {
@@ -139,7 +155,10 @@ impl ColumnWriteOptions {
 
 impl FieldWriteOptions {
     pub fn default_with_encoding(encoding: Encoding) -> Self {
-        Self { encoding }
+        Self {
+            encoding,
+            bloom_filter: None,
+        }
     }
 
     pub fn into_default_column_write_options(self) -> ColumnWriteOptions {
@@ -159,6 +178,8 @@ pub struct StructFieldWriteOptions {
 
 use arrow::compute::aggregate::estimated_bytes_size;
 use arrow::match_integer_type;
+#[cfg(feature = "bloom_filter")]
+pub use bloom_filter::array_to_bloom_filters;
 pub use file::FileWriter;
 pub use pages::{Nested, array_to_columns, arrays_to_columns};
 use polars_error::{PolarsResult, polars_bail};
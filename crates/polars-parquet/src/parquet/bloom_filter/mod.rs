@@ -4,8 +4,8 @@ mod read;
 mod split_block;
 
 pub use hash::{hash_byte, hash_native};
-pub use read::read;
-pub use split_block::{insert, is_in_set};
+pub use read::{read, read_from_slice};
+pub use split_block::{insert, is_in_set, optimal_num_bytes};
 
 #[cfg(test)]
 mod tests {
@@ -68,4 +68,12 @@ mod tests {
         ];
         assert_eq!(bitset, expected);
     }
+
+    #[test]
+    fn optimal_size() {
+        assert_eq!(optimal_num_bytes(0, 0.05), 32);
+        assert_eq!(optimal_num_bytes(1000, 0.05), 1024);
+        assert_eq!(optimal_num_bytes(1_000_000, 0.01), 2 * 1024 * 1024);
+        assert_eq!(optimal_num_bytes(u64::MAX, 0.01), 128 * 1024 * 1024);
+    }
 }
@@ -15,7 +15,7 @@ use crate::parquet::metadata::ColumnChunkMetadata;
 /// Errors if the column contains no metadata or the filter can't be read or deserialized.
 pub fn read<R: Read + Seek>(
     column_metadata: &ColumnChunkMetadata,
-    mut reader: &mut R,
+    reader: &mut R,
     bitset: &mut Vec<u8>,
 ) -> ParquetResult<()> {
     let offset = column_metadata.metadata().bloom_filter_offset;
@@ -28,6 +28,19 @@ pub fn read<R: Read + Seek>(
     };
     reader.seek(SeekFrom::Start(offset))?;
 
+    read_from(reader, bitset)
+}
+
+/// Reads a bloom filter that starts at the beginning of `bytes` into `bitset`. `bytes` holds the
+/// range given by the `bloom_filter_offset` and `bloom_filter_length` of the column metadata.
+/// Results in an empty `bitset` if the algorithm is not supported.
+/// # Error
+/// Errors if the filter can't be deserialized.
+pub fn read_from_slice(bytes: &[u8], bitset: &mut Vec<u8>) -> ParquetResult<()> {
+    read_from(&mut std::io::Cursor::new(bytes), bitset)
+}
+
+fn read_from<R: Read>(mut reader: &mut R, bitset: &mut Vec<u8>) -> ParquetResult<()> {
     // deserialize header
     let mut prot = TCompactInputProtocol::new(&mut reader, usize::MAX); // max is ok since `BloomFilterHeader` never allocates
     let header = BloomFilterHeader::read_from_in_protocol(&mut prot)?;
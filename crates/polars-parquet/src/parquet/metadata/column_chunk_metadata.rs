@@ -172,6 +172,20 @@ impl ColumnChunkMetadata {
         column_metadata_byte_range(self.metadata())
     }
 
+    /// Returns the byte range of the serialized `ColumnIndex` of this column chunk, if any.
+    pub fn column_index_byte_range(&self) -> Option<core::ops::Range<u64>> {
+        let offset = u64::try_from(self.column_chunk.column_index_offset?).ok()?;
+        let length = u64::try_from(self.column_chunk.column_index_length?).ok()?;
+        Some(offset..offset + length)
+    }
+
+    /// Returns the byte range of the serialized `OffsetIndex` of this column chunk, if any.
+    pub fn offset_index_byte_range(&self) -> Option<core::ops::Range<u64>> {
+        let offset = u64::try_from(self.column_chunk.offset_index_offset?).ok()?;
+        let length = u64::try_from(self.column_chunk.offset_index_length?).ok()?;
+        Some(offset..offset + length)
+    }
+
     /// Method to convert from Thrift.
     pub(crate) fn try_from_thrift(
         column_descr: ColumnDescriptor,
@@ -0,0 +1,61 @@
+use polars_parquet_format::thrift::protocol::TCompactInputProtocol;
+pub use polars_parquet_format::{ColumnIndex, OffsetIndex, PageLocation};
+
+use crate::parquet::error::{ParquetError, ParquetResult};
+use crate::parquet::schema::types::PrimitiveType;
+use crate::parquet::statistics::{ParquetStatistics, Statistics};
+
+/// Deserializes the [`ColumnIndex`] of a column chunk from the bytes in its
+/// `column_index_offset` and `column_index_length` range.
+pub fn deserialize_column_index(bytes: &[u8]) -> ParquetResult<ColumnIndex> {
+    let mut prot = TCompactInputProtocol::new(bytes, bytes.len() * 2 + 1024);
+    Ok(ColumnIndex::read_from_in_protocol(&mut prot)?)
+}
+
+/// Deserializes the [`OffsetIndex`] of a column chunk from the bytes in its
+/// `offset_index_offset` and `offset_index_length` range.
+pub fn deserialize_offset_index(bytes: &[u8]) -> ParquetResult<OffsetIndex> {
+    let mut prot = TCompactInputProtocol::new(bytes, bytes.len() * 2 + 1024);
+    Ok(OffsetIndex::read_from_in_protocol(&mut prot)?)
+}
+
+/// Returns the [`Statistics`] of every data page in `column_index`.
+///
+/// Pages that only hold nulls have no min and max value.
+/// # Error
+/// Errors if the lists of the index have different lengths or the values can't be deserialized
+/// to `primitive_type`.
+pub fn column_index_statistics(
+    column_index: &ColumnIndex,
+    primitive_type: &PrimitiveType,
+) -> ParquetResult<Vec<Statistics>> {
+    let num_pages = column_index.null_pages.len();
+    if column_index.min_values.len() != num_pages
+        || column_index.max_values.len() != num_pages
+        || column_index
+            .null_counts
+            .as_ref()
+            .is_some_and(|nc| nc.len() != num_pages)
+    {
+        return Err(ParquetError::oos(
+            "The lists of a column index must have a value for every page",
+        ));
+    }
+
+    (0..num_pages)
+        .map(|i| {
+            let is_null_page = column_index.null_pages[i];
+            let statistics = ParquetStatistics {
+                null_count: column_index.null_counts.as_ref().map(|nc| nc[i]),
+                distinct_count: None,
+                max_value: (!is_null_page).then(|| column_index.max_values[i].clone()),
+                min_value: (!is_null_page).then(|| column_index.min_values[i].clone()),
+                max: None,
+                min: None,
+                is_max_value_exact: None,
+                is_min_value_exact: None,
+            };
+            Statistics::deserialize(&statistics, primitive_type.clone())
+        })
+        .collect()
+}
@@ -1,5 +1,6 @@
 mod column;
 mod compression;
+mod indexes;
 pub mod levels;
 mod metadata;
 mod page;
@@ -10,6 +11,10 @@ use std::io::{Seek, SeekFrom};
 
 pub use column::*;
 pub use compression::{BasicDecompressor, decompress};
+pub use indexes::{
+    ColumnIndex, OffsetIndex, PageLocation, column_index_statistics, deserialize_column_index,
+    deserialize_offset_index,
+};
 pub use metadata::{deserialize_metadata, read_metadata, read_metadata_with_size};
 pub use page::{PageIterator, PageMetaData, PageReader};
 #[cfg(feature = "async")]
@@ -12,7 +12,7 @@ use crate::parquet::error::ParquetResult;
 use crate::parquet::schema::types::{PhysicalType, PrimitiveType};
 pub use crate::parquet::thrift_format::Statistics as ParquetStatistics;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Statistics {
     Binary(BinaryStatistics),
     Boolean(BooleanStatistics),
@@ -1,7 +1,10 @@
 use std::io::Write;
 
-use polars_parquet_format::RowGroup;
 use polars_parquet_format::thrift::protocol::TCompactOutputProtocol;
+use polars_parquet_format::{
+    BloomFilterAlgorithm, BloomFilterCompression, BloomFilterHash, BloomFilterHeader, RowGroup,
+    SplitBlockAlgorithm, Uncompressed, XxHash,
+};
 
 use super::indexes::{write_column_index, write_offset_index};
 use super::page::PageWriteSpec;
@@ -39,6 +42,23 @@ pub(super) fn end_file<W: Write>(
     Ok(metadata_len as u64 + FOOTER_SIZE)
 }
 
+/// Writes a split block bloom filter with xxHash hashes. Returns the number of bytes written.
+fn write_bloom_filter<W: Write>(mut writer: &mut W, bitset: &[u8]) -> ParquetResult<u64> {
+    let header = BloomFilterHeader {
+        num_bytes: bitset.len().try_into()?,
+        algorithm: BloomFilterAlgorithm::BLOCK(SplitBlockAlgorithm {}),
+        hash: BloomFilterHash::XXHASH(XxHash {}),
+        compression: BloomFilterCompression::UNCOMPRESSED(Uncompressed {}),
+    };
+
+    let header_len = {
+        let mut protocol = TCompactOutputProtocol::new(&mut writer);
+        header.write_to_out_protocol(&mut protocol)? as u64
+    };
+    writer.write_all(bitset)?;
+    Ok(header_len + bitset.len() as u64)
+}
+
 fn create_column_orders(schema_desc: &SchemaDescriptor) -> Vec<polars_parquet_format::ColumnOrder> {
     // We only include ColumnOrder for leaf nodes.
     // Currently only supported ColumnOrder is TypeDefinedOrder so we set this
@@ -66,6 +86,8 @@ pub struct FileWriter<W: Write> {
     offset: u64,
     row_groups: Vec<RowGroup>,
     page_specs: Vec<Vec<Vec<PageWriteSpec>>>,
+    /// The bloom filter bitsets of the columns of every row group, written with the footer.
+    bloom_filters: Vec<Vec<Option<Vec<u8>>>>,
     /// Used to store the current state for writing the file
     state: State,
     // when the file is written, metadata becomes available
@@ -125,6 +147,7 @@ impl<W: Write> FileWriter<W> {
             offset: 0,
             row_groups: vec![],
             page_specs: vec![],
+            bloom_filters: vec![],
             state: State::Initialised,
             metadata: None,
         }
@@ -152,6 +175,22 @@ impl<W: Write> FileWriter<W> {
     ///
     /// This call is IO-bounded
     pub fn write<E>(&mut self, row_group: RowGroupIterColumns<'_, E>) -> ParquetResult<()>
+    where
+        ParquetError: From<E>,
+        E: std::error::Error,
+    {
+        self.write_with_bloom_filters(row_group, vec![])
+    }
+
+    /// Writes a row group to the file, together with the bloom filter bitsets of its columns.
+    ///
+    /// `bloom_filters` holds a bitset or `None` for every column. It may be empty if no column has
+    /// a bloom filter. The filters are written before the page indexes when the file is ended.
+    pub fn write_with_bloom_filters<E>(
+        &mut self,
+        row_group: RowGroupIterColumns<'_, E>,
+        bloom_filters: Vec<Option<Vec<u8>>>,
+    ) -> ParquetResult<()>
     where
         ParquetError: From<E>,
         E: std::error::Error,
@@ -170,6 +209,7 @@ impl<W: Write> FileWriter<W> {
         self.offset += size;
         self.row_groups.push(group);
         self.page_specs.push(specs);
+        self.bloom_filters.push(bloom_filters);
         Ok(())
     }
 
@@ -188,6 +228,28 @@ impl<W: Write> FileWriter<W> {
         // compute file stats
         let num_rows = self.row_groups.iter().map(|group| group.num_rows).sum();
 
+        // write bloom filters
+        self.row_groups
+            .iter_mut()
+            .zip(std::mem::take(&mut self.bloom_filters))
+            .try_for_each(|(group, bloom_filters)| {
+                group
+                    .columns
+                    .iter_mut()
+                    .zip(bloom_filters)
+                    .try_for_each(|(column, bitset)| {
+                        let Some(bitset) = bitset else {
+                            return Ok(());
+                        };
+                        let offset = self.offset;
+                        self.offset += write_bloom_filter(&mut self.writer, &bitset)?;
+                        let metadata = column.meta_data.as_mut().unwrap();
+                        metadata.bloom_filter_offset = Some(offset as i64);
+                        metadata.bloom_filter_length = Some((self.offset - offset) as i32);
+                        ParquetResult::Ok(())
+                    })
+            })?;
+
         if self.options.write_statistics {
             // write column indexes (require page statistics)
             self.row_groups
@@ -0,0 +1,101 @@
+//! This module extracts the conjuncts of a predicate that test a column for equality with a set of
+//! literal values, which can be checked against e.g. bloom filters.
+
+use polars_core::prelude::{AnyValue, DataType, Scalar};
+use polars_core::schema::Schema;
+use polars_io::predicates::ColumnMembershipPredicate;
+use polars_utils::arena::{Arena, Node};
+use polars_utils::pl_str::PlSmallStr;
+
+use super::get_binary_expr_col_and_lv;
+use crate::dsl::Operator;
+use crate::plans::{AExpr, MintermIter};
+
+/// Returns a [`ColumnMembershipPredicate`] for every conjunct of the predicate of the form
+/// `col(a) == lit` or `col(a).is_in(lit)`.
+pub fn aexpr_to_membership_predicates(
+    root: Node,
+    expr_arena: &Arena<AExpr>,
+    schema: &Schema,
+) -> Vec<ColumnMembershipPredicate> {
+    MintermIter::new(root, expr_arena)
+        .filter_map(|minterm| minterm_to_membership_predicate(minterm, expr_arena, schema))
+        .collect()
+}
+
+fn minterm_to_membership_predicate(
+    minterm: Node,
+    expr_arena: &Arena<AExpr>,
+    schema: &Schema,
+) -> Option<ColumnMembershipPredicate> {
+    let (column, values) = match expr_arena.get(minterm) {
+        AExpr::BinaryExpr { left, op, right } => {
+            if !matches!(op, Operator::Eq | Operator::EqValidity) {
+                return None;
+            }
+
+            let ((column, _), (lv, _)) =
+                get_binary_expr_col_and_lv(*left, *right, expr_arena, schema)?;
+            let av = lv?.to_any_value()?.into_static();
+            let dtype = schema.get(column)?;
+
+            // `col(a) == null` passes no rows, but `col(a).eq_missing(null)` passes the nulls,
+            // which aren't in bloom filters.
+            if av.is_null() && matches!(op, Operator::EqValidity) {
+                return None;
+            }
+            if !av.is_null() && &av.dtype() != dtype {
+                return None;
+            }
+
+            let value = if av.is_null() {
+                Scalar::null(dtype.clone())
+            } else {
+                Scalar::new(dtype.clone(), av)
+            };
+            (column, value.into_series(PlSmallStr::EMPTY))
+        },
+        #[cfg(feature = "is_in")]
+        AExpr::Function {
+            input,
+            function:
+                crate::dsl::FunctionExpr::Boolean(crate::dsl::BooleanFunction::IsIn { nulls_equal }),
+            ..
+        } => {
+            use super::super::evaluate::{constant_evaluate, into_column};
+            use crate::plans::LiteralValue;
+
+            let column = into_column(input[0].node(), expr_arena, schema, 0)?;
+            let lv = constant_evaluate(input[1].node(), expr_arena, schema, 0)??;
+            let values = match lv.as_ref() {
+                LiteralValue::Series(s) => s.as_ref().clone(),
+                lv => match lv.to_any_value()? {
+                    AnyValue::List(s) => s,
+                    _ => return None,
+                },
+            };
+
+            // An imploded literal holds the values in its single element.
+            let values = match values.dtype() {
+                DataType::List(_) if values.len() == 1 => values.list().ok()?.get_as_series(0)?,
+                _ => values,
+            };
+
+            // Null values match the nulls of the column, which aren't in bloom filters.
+            if *nulls_equal && values.has_nulls() {
+                return None;
+            }
+            (column, values)
+        },
+        _ => return None,
+    };
+
+    if values.dtype() != schema.get(column)? {
+        return None;
+    }
+
+    Some(ColumnMembershipPredicate {
+        column: column.clone(),
+        values: values.drop_nulls().rechunk(),
+    })
+}
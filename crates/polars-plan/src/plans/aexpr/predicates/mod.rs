@@ -1,9 +1,11 @@
 mod column_expr;
+mod membership;
 mod skip_batches;
 
 use std::borrow::Cow;
 
 pub use column_expr::*;
+pub use membership::*;
 use polars_core::schema::Schema;
 use polars_utils::arena::{Arena, Node};
 use polars_utils::pl_str::PlSmallStr;
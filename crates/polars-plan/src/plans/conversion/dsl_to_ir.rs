@@ -185,6 +185,9 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                         #[cfg(feature = "ipc")]
                         FileScan::Ipc { .. } => sources
                             .expand_paths_with_hive_update(unified_scan_args, cloud_options)?,
+                        #[cfg(feature = "avro")]
+                        FileScan::Avro { .. } => sources
+                            .expand_paths_with_hive_update(unified_scan_args, cloud_options)?,
                         #[cfg(feature = "csv")]
                         FileScan::Csv { .. } => {
                             sources.expand_paths(unified_scan_args, cloud_options)?
@@ -238,6 +241,13 @@ pub fn to_alp_impl(lp: DslPlan, ctxt: &mut DslConversionContext) -> PolarsResult
                         *metadata = Some(Arc::new(md));
                         file_info
                     },
+                    #[cfg(feature = "avro")]
+                    FileScan::Avro { .. } => scans::avro_file_info(
+                        &sources,
+                        unified_scan_args.row_index.as_ref(),
+                        cloud_options,
+                    )
+                    .map_err(|e| e.context(failed_here!(avro scan)))?,
                     #[cfg(feature = "csv")]
                     FileScan::Csv { options } => {
                         // TODO: This is a hack. We conditionally set `allow_missing_columns` to
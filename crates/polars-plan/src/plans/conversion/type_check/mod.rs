@@ -66,10 +66,32 @@ impl OptimizationRule for TypeCheckRule {
 
                     fn push_children<'a>(
                         stack: &mut Vec<Item<'a>>,
-                        children: &'a ChildFieldOverwrites,
+                        o: &'a ParquetFieldOverwrites,
                         dtype: &'a DataType,
                     ) -> PolarsResult<()> {
-                        match children {
+                        if let Some(bloom_filter) = &o.bloom_filter {
+                            // Only the types whose written values are hashed by the writer.
+                            let supported = match dtype {
+                                DataType::Int128 => false,
+                                DataType::String | DataType::Binary | DataType::BinaryOffset => {
+                                    true
+                                },
+                                dt => {
+                                    dt.is_primitive_numeric()
+                                        || dt.is_temporal()
+                                        || dt.is_categorical()
+                                        || dt.is_enum()
+                                },
+                            };
+                            if !supported {
+                                polars_bail!(InvalidOperation: "cannot write a parquet bloom filter for a column of type {dtype}");
+                            }
+                            if !(bloom_filter.fpp > 0.0 && bloom_filter.fpp < 1.0) {
+                                polars_bail!(InvalidOperation: "parquet bloom filter false positive probability must be between 0 and 1, got {}", bloom_filter.fpp);
+                            }
+                        }
+
+                        match &o.children {
                             ChildFieldOverwrites::None => {},
                             ChildFieldOverwrites::ListLike(child_overwrites) => {
                                 let Some(child_dtype) = dtype.inner_dtype() else {
@@ -104,7 +126,7 @@ impl OptimizationRule for TypeCheckRule {
                             polars_bail!(InvalidOperation: "duplicate parquet field overwrite for struct field `{name}`");
                         }
 
-                        push_children(&mut stack, &o.children, dtype)?;
+                        push_children(&mut stack, o, dtype)?;
                     }
 
                     while let Some(item) = stack.pop() {
@@ -113,7 +135,7 @@ impl OptimizationRule for TypeCheckRule {
                                 if o.name.is_some() {
                                     polars_bail!(InvalidOperation: "parquet field overwrite list child cannot have name");
                                 };
-                                push_children(&mut stack, &o.children, dt)?;
+                                push_children(&mut stack, o, dt)?;
                             },
                             Item::Struct(fields, os) => {
                                 // @NOTE: Avoid quadratic behavior through HashMap.
@@ -134,7 +156,7 @@ impl OptimizationRule for TypeCheckRule {
                                         polars_bail!(InvalidOperation: "duplicate parquet field overwrite for struct field `{name}`");
                                     }
 
-                                    push_children(&mut stack, &o.children, field.dtype())?;
+                                    push_children(&mut stack, o, field.dtype())?;
                                 }
                             },
                         }
@@ -29,7 +29,8 @@ pub fn count_rows(
         feature = "parquet",
         feature = "ipc",
         feature = "json",
-        feature = "csv"
+        feature = "csv",
+        feature = "avro"
     )))]
     {
         unreachable!()
@@ -39,7 +40,8 @@ pub fn count_rows(
         feature = "parquet",
         feature = "ipc",
         feature = "json",
-        feature = "csv"
+        feature = "csv",
+        feature = "avro"
     ))]
     {
         let count: PolarsResult<usize> = match scan_type {
@@ -56,6 +58,8 @@ pub fn count_rows(
             ),
             #[cfg(feature = "json")]
             FileScan::NDJson { options } => count_rows_ndjson(sources, cloud_options),
+            #[cfg(feature = "avro")]
+            FileScan::Avro { .. } => count_rows_avro(sources),
             #[cfg(feature = "python")]
             FileScan::PythonDataset { .. } => unreachable!(),
             FileScan::Anonymous { .. } => {
@@ -187,6 +191,21 @@ pub(super) fn count_rows_ipc(
     }
 }
 
+#[cfg(feature = "avro")]
+fn count_rows_avro(sources: &ScanSources) -> PolarsResult<usize> {
+    if sources.is_cloud_url() {
+        polars_bail!(nyi = "counting the rows of Avro files in cloud storage");
+    }
+
+    sources
+        .iter()
+        .map(|source| {
+            let memslice = source.to_memslice()?;
+            polars_io::avro::count_rows_avro(&mut std::io::Cursor::new(memslice))
+        })
+        .sum::<PolarsResult<usize>>()
+}
+
 #[cfg(all(feature = "ipc", feature = "async"))]
 async fn count_rows_cloud_ipc(
     paths: &[std::path::PathBuf],
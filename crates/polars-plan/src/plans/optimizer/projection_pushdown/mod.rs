@@ -441,6 +441,8 @@ impl ProjectionPushDown {
                     FileScan::NDJson { .. } => true,
                     #[cfg(feature = "ipc")]
                     FileScan::Ipc { .. } => true,
+                    #[cfg(feature = "avro")]
+                    FileScan::Avro { .. } => true,
                     #[cfg(feature = "csv")]
                     FileScan::Csv { .. } => true,
                     #[cfg(feature = "parquet")]
@@ -12,7 +12,7 @@ use std::path::PathBuf;
 use polars::chunked_array::object::PolarsObjectSafe;
 use polars::frame::row::Row;
 #[cfg(feature = "avro")]
-use polars::io::avro::AvroCompression;
+use polars::io::avro::AvroCodec;
 #[cfg(feature = "cloud")]
 use polars::io::cloud::CloudOptions;
 use polars::series::ops::NullBehavior;
@@ -772,15 +772,16 @@ impl<'py> FromPyObject<'py> for Wrap<InterpolationMethod> {
 }
 
 #[cfg(feature = "avro")]
-impl<'py> FromPyObject<'py> for Wrap<Option<AvroCompression>> {
+impl<'py> FromPyObject<'py> for Wrap<Option<AvroCodec>> {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
         let parsed = match &*ob.extract::<PyBackedStr>()? {
             "uncompressed" => None,
-            "snappy" => Some(AvroCompression::Snappy),
-            "deflate" => Some(AvroCompression::Deflate),
+            "snappy" => Some(AvroCodec::Snappy),
+            "deflate" => Some(AvroCodec::Deflate),
+            "zstd" => Some(AvroCodec::Zstd),
             v => {
                 return Err(PyValueError::new_err(format!(
-                    "avro `compression` must be one of {{'uncompressed', 'snappy', 'deflate'}}, got {v}",
+                    "avro `compression` must be one of {{'uncompressed', 'snappy', 'deflate', 'zstd'}}, got {v}",
                 )));
             },
         };
@@ -1030,9 +1031,10 @@ impl<'py> FromPyObject<'py> for Wrap<QuantileMethod> {
             "linear" => QuantileMethod::Linear,
             "midpoint" => QuantileMethod::Midpoint,
             "equiprobable" => QuantileMethod::Equiprobable,
+            "approximate" => QuantileMethod::Approximate,
             v => {
                 return Err(PyValueError::new_err(format!(
-                    "`interpolation` must be one of {{'lower', 'higher', 'nearest', 'linear', 'midpoint', 'equiprobable'}}, got {v}",
+                    "`interpolation` must be one of {{'lower', 'higher', 'nearest', 'linear', 'midpoint', 'equiprobable', 'approximate'}}, got {v}",
                 )));
             },
         };
@@ -1108,6 +1110,23 @@ impl<'py> FromPyObject<'py> for Wrap<UniqueKeepStrategy> {
     }
 }
 
+#[cfg(feature = "pivot")]
+impl<'py> FromPyObject<'py> for Wrap<polars_lazy::frame::pivot::UnseenOnValues> {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        use polars_lazy::frame::pivot::UnseenOnValues;
+        let parsed = match &*ob.extract::<PyBackedStr>()? {
+            "raise" => UnseenOnValues::Raise,
+            "ignore" => UnseenOnValues::Ignore,
+            v => {
+                return Err(PyValueError::new_err(format!(
+                    "`unseen_on_values` must be one of {{'raise', 'ignore'}}, got {v}",
+                )));
+            },
+        };
+        Ok(Wrap(parsed))
+    }
+}
+
 #[cfg(feature = "search_sorted")]
 impl<'py> FromPyObject<'py> for Wrap<SearchSortedSide> {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use polars::io::RowIndex;
 #[cfg(feature = "avro")]
-use polars::io::avro::AvroCompression;
+use polars::io::avro::AvroCodec;
 use polars::prelude::*;
 use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedStr;
@@ -351,14 +351,14 @@ impl PyDataFrame {
         &mut self,
         py: Python<'_>,
         py_f: PyObject,
-        compression: Wrap<Option<AvroCompression>>,
+        compression: Wrap<Option<AvroCodec>>,
         name: String,
     ) -> PyResult<()> {
         use polars::io::avro::AvroWriter;
         let mut buf = get_file_like(py_f, true)?;
         py.enter_polars(|| {
             AvroWriter::new(&mut buf)
-                .with_compression(compression.0)
+                .with_codec(compression.0)
                 .with_name(name)
                 .finish(&mut self.df)
         })
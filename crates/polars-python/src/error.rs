@@ -15,8 +15,8 @@ use crate::Wrap;
 use crate::exceptions::{
     CategoricalRemappingWarning, ColumnNotFoundError, ComputeError, DuplicateError,
     InvalidOperationError, MapWithoutReturnDtypeWarning, NoDataError, OutOfBoundsError,
-    SQLInterfaceError, SQLSyntaxError, SchemaError, SchemaFieldNotFoundError, ShapeError,
-    StringCacheMismatchError, StructFieldNotFoundError,
+    QueryCancelledError, SQLInterfaceError, SQLSyntaxError, SchemaError, SchemaFieldNotFoundError,
+    ShapeError, StringCacheMismatchError, StructFieldNotFoundError,
 };
 
 pub enum PyPolarsErr {
@@ -65,6 +65,7 @@ impl From<PyPolarsErr> for PyErr {
                 PolarsError::AssertionError(err) => {
                     pyo3::exceptions::PyAssertionError::new_err(err.to_string())
                 },
+                PolarsError::Cancelled(err) => QueryCancelledError::new_err(err.to_string()),
                 PolarsError::ColumnNotFound(name) => ColumnNotFoundError::new_err(name.to_string()),
                 PolarsError::ComputeError(err) => ComputeError::new_err(err.to_string()),
                 PolarsError::Duplicate(err) => DuplicateError::new_err(err.to_string()),
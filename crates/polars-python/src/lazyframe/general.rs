@@ -6,6 +6,8 @@ use either::Either;
 use polars::io::{HiveOptions, RowIndex};
 use polars::time::*;
 use polars_core::prelude::*;
+#[cfg(feature = "pivot")]
+use polars_lazy::frame::pivot::{LazyPivotArgs, UnseenOnValues};
 #[cfg(feature = "parquet")]
 use polars_parquet::arrow::write::StatisticsOptions;
 use polars_plan::dsl::ScanSources;
@@ -484,6 +486,81 @@ impl PyLazyFrame {
         Ok(lf.into())
     }
 
+    #[cfg(feature = "avro")]
+    #[staticmethod]
+    #[pyo3(signature = (
+        source, sources, n_rows, cache, rechunk, row_index, cloud_options,credential_provider,
+        hive_partitioning, hive_schema, try_parse_hive_dates, retries, file_cache_ttl,
+        include_file_paths
+    ))]
+    fn new_from_avro(
+        source: Option<PyObject>,
+        sources: Wrap<ScanSources>,
+        n_rows: Option<usize>,
+        cache: bool,
+        rechunk: bool,
+        row_index: Option<(String, IdxSize)>,
+        cloud_options: Option<Vec<(String, String)>>,
+        credential_provider: Option<PyObject>,
+        hive_partitioning: Option<bool>,
+        hive_schema: Option<Wrap<Schema>>,
+        try_parse_hive_dates: bool,
+        retries: usize,
+        file_cache_ttl: Option<u64>,
+        include_file_paths: Option<String>,
+    ) -> PyResult<Self> {
+        #[cfg(feature = "cloud")]
+        use cloud::credential_provider::PlCredentialProvider;
+        let row_index = row_index.map(|(name, offset)| RowIndex {
+            name: name.into(),
+            offset,
+        });
+
+        let hive_options = HiveOptions {
+            enabled: hive_partitioning,
+            hive_start_idx: 0,
+            schema: hive_schema.map(|x| Arc::new(x.0)),
+            try_parse_dates: try_parse_hive_dates,
+        };
+
+        let mut args = ScanArgsAvro {
+            n_rows,
+            cache,
+            rechunk,
+            row_index,
+            cloud_options: None,
+            hive_options,
+            include_file_paths: include_file_paths.map(|x| x.into()),
+        };
+
+        let sources = sources.0;
+        let (first_path, sources) = match source {
+            None => (sources.first_path().map(|p| p.to_path_buf()), sources),
+            Some(source) => pyobject_to_first_path_and_scan_sources(source)?,
+        };
+
+        #[cfg(feature = "cloud")]
+        if let Some(first_path) = first_path {
+            let first_path_url = first_path.to_string_lossy();
+
+            let mut cloud_options =
+                parse_cloud_options(&first_path_url, cloud_options.unwrap_or_default())?;
+            if let Some(file_cache_ttl) = file_cache_ttl {
+                cloud_options.file_cache_ttl = file_cache_ttl;
+            }
+            args.cloud_options = Some(
+                cloud_options
+                    .with_max_retries(retries)
+                    .with_credential_provider(
+                        credential_provider.map(PlCredentialProvider::from_python_builder),
+                    ),
+            );
+        }
+
+        let lf = LazyFrame::scan_avro_sources(sources, args).map_err(PyPolarsErr::from)?;
+        Ok(lf.into())
+    }
+
     #[staticmethod]
     #[pyo3(signature = (
         dataset_object
@@ -583,6 +660,12 @@ impl PyLazyFrame {
         py.enter_polars(|| self.ldf.to_dot_streaming_phys(optimized))
     }
 
+    #[cfg(feature = "new_streaming")]
+    fn to_dot_streaming_profile(&self, py: Python) -> PyResult<String> {
+        let (_, profile) = py.enter_polars(|| self.ldf.clone().profile_streaming())?;
+        Ok(profile.annotated_plan)
+    }
+
     fn optimization_toggle(
         &self,
         type_coercion: bool,
@@ -729,6 +812,12 @@ impl PyLazyFrame {
         Ok((df.into(), time_df.into()))
     }
 
+    #[cfg(feature = "new_streaming")]
+    fn profile_streaming(&self, py: Python<'_>) -> PyResult<(PyDataFrame, PyDataFrame)> {
+        let (df, profile) = py.enter_polars(|| self.ldf.clone().profile_streaming())?;
+        Ok((df.into(), profile.nodes.into()))
+    }
+
     #[pyo3(signature = (engine, lambda_post_opt=None))]
     fn collect(
         &self,
@@ -916,6 +1005,69 @@ impl PyLazyFrame {
         .map_err(Into::into)
     }
 
+    #[cfg(all(feature = "streaming", feature = "avro"))]
+    #[pyo3(signature = (
+        target, compression, name, cloud_options, credential_provider, retries, sink_options
+    ))]
+    fn sink_avro(
+        &self,
+        py: Python<'_>,
+        target: SinkTarget,
+        compression: Wrap<Option<polars::io::avro::AvroCodec>>,
+        name: String,
+        cloud_options: Option<Vec<(String, String)>>,
+        credential_provider: Option<PyObject>,
+        retries: usize,
+        sink_options: Wrap<SinkOptions>,
+    ) -> PyResult<PyLazyFrame> {
+        let options = AvroWriterOptions {
+            compression: compression.0,
+            name: name.into(),
+        };
+
+        #[cfg(feature = "cloud")]
+        let cloud_options = match target.base_path() {
+            None => None,
+            Some(base_path) => {
+                let cloud_options = parse_cloud_options(
+                    base_path.to_str().unwrap(),
+                    cloud_options.unwrap_or_default(),
+                )?;
+                Some(
+                    cloud_options
+                        .with_max_retries(retries)
+                        .with_credential_provider(
+                            credential_provider.map(polars::prelude::cloud::credential_provider::PlCredentialProvider::from_python_builder),
+                        ),
+                )
+            },
+        };
+
+        #[cfg(not(feature = "cloud"))]
+        let cloud_options = None;
+
+        py.enter_polars(|| {
+            let ldf = self.ldf.clone();
+            match target {
+                SinkTarget::File(target) => {
+                    ldf.sink_avro(target, options, cloud_options, sink_options.0)
+                },
+                SinkTarget::Partition(partition) => ldf.sink_avro_partitioned(
+                    Arc::new(partition.base_path),
+                    partition.file_path_cb.map(PartitionTargetCallback::Python),
+                    partition.variant,
+                    options,
+                    cloud_options,
+                    sink_options.0,
+                    partition.per_partition_sort_by,
+                    partition.finish_callback,
+                ),
+            }
+        })
+        .map(Into::into)
+        .map_err(Into::into)
+    }
+
     #[cfg(all(feature = "streaming", feature = "csv"))]
     #[pyo3(signature = (
         target, include_bom, include_header, separator, line_terminator, quote_char, batch_size,
@@ -1559,6 +1711,34 @@ impl PyLazyFrame {
         ldf.unpivot(args).into()
     }
 
+    #[cfg(feature = "pivot")]
+    #[pyo3(signature = (on, on_columns, index, values, aggregate_exprs, maintain_order, separator, unseen_on_values))]
+    fn pivot(
+        &self,
+        on: Vec<String>,
+        on_columns: PyDataFrame,
+        index: Vec<String>,
+        values: Option<Vec<String>>,
+        aggregate_exprs: Vec<PyExpr>,
+        maintain_order: bool,
+        separator: Option<String>,
+        unseen_on_values: Wrap<UnseenOnValues>,
+    ) -> PyResult<Self> {
+        let args = LazyPivotArgs {
+            on: on.into_iter().map(|s| s.into()).collect(),
+            on_columns: on_columns.df,
+            index: index.into_iter().map(|s| s.into()).collect(),
+            values: values.map(|v| v.into_iter().map(|s| s.into()).collect()),
+            aggregations: aggregate_exprs.to_exprs(),
+            separator: separator.map(|s| s.into()),
+            maintain_order,
+            unseen_on_values: unseen_on_values.0,
+        };
+
+        let ldf = self.ldf.clone();
+        Ok(ldf.pivot(args).map_err(PyPolarsErr::from)?.into())
+    }
+
     #[pyo3(signature = (name, offset=None))]
     fn with_row_index(&self, name: &str, offset: Option<IdxSize>) -> Self {
         let ldf = self.ldf.clone();
@@ -1654,7 +1834,7 @@ impl PyLazyFrame {
 #[cfg(feature = "parquet")]
 impl<'py> FromPyObject<'py> for Wrap<polars_io::parquet::write::ParquetFieldOverwrites> {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
-        use polars_io::parquet::write::ParquetFieldOverwrites;
+        use polars_io::parquet::write::{ParquetBloomFilterOptions, ParquetFieldOverwrites};
 
         let parsed = ob.extract::<pyo3::Bound<'_, PyDict>>()?;
 
@@ -1696,12 +1876,20 @@ impl<'py> FromPyObject<'py> for Wrap<polars_io::parquet::write::ParquetFieldOver
             .map(|v| v.extract::<bool>())
             .transpose()?;
 
+        let bloom_filter = PyDictMethods::get_item(&parsed, "bloom_filter")?
+            .map(|v| {
+                let (ndv, fpp) = v.extract::<(Option<u64>, f64)>()?;
+                PyResult::Ok(ParquetBloomFilterOptions { ndv, fpp })
+            })
+            .transpose()?;
+
         Ok(Wrap(ParquetFieldOverwrites {
             name,
             children,
             field_id,
             metadata,
             required,
+            bloom_filter,
         }))
     }
 }
@@ -39,6 +39,8 @@ fn scan_type_to_pyobject(
         },
         #[cfg(feature = "ipc")]
         FileScan::Ipc { .. } => Err(PyNotImplementedError::new_err("ipc scan")),
+        #[cfg(feature = "avro")]
+        FileScan::Avro { .. } => Err(PyNotImplementedError::new_err("avro scan")),
         #[cfg(feature = "json")]
         FileScan::NDJson { options, .. } => {
             let options = serde_json::to_string(options)
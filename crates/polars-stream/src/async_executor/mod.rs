@@ -3,14 +3,14 @@
 mod park_group;
 mod task;
 
-use std::cell::{Cell, UnsafeCell};
+use std::cell::{Cell, RefCell, UnsafeCell};
 use std::collections::HashMap;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::panic::{AssertUnwindSafe, Location};
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, LazyLock, OnceLock, Weak};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossbeam_deque::{Injector, Steal, Stealer, Worker as WorkQueue};
 use crossbeam_utils::CachePadded;
@@ -32,6 +32,9 @@ static GLOBAL_SCHEDULER: OnceLock<Executor> = OnceLock::new();
 thread_local!(
     /// Used to store which executor thread this is.
     static TLS_THREAD_ID: Cell<usize> = const { Cell::new(usize::MAX) };
+
+    /// The task group new tasks spawned on this thread are added to.
+    static TLS_TASK_GROUP: RefCell<Option<Arc<TaskGroupStats>>> = const { RefCell::new(None) };
 );
 
 static NS_SPENT_BLOCKED: LazyLock<Mutex<HashMap<&'static Location<'static>, u64>>> =
@@ -55,6 +58,57 @@ pub fn clear_task_wait_statistics() {
     NS_SPENT_BLOCKED.lock().clear()
 }
 
+/// Timing statistics shared by a group of tasks.
+///
+/// Tasks spawned inside [`with_task_group`] belong to that group, as do all
+/// tasks spawned while polling a task of the group.
+#[derive(Default)]
+pub struct TaskGroupStats {
+    ns_polled: AtomicU64,
+    first_poll: OnceLock<Instant>,
+    last_done: Mutex<Option<Instant>>,
+}
+
+impl TaskGroupStats {
+    /// Total time spent polling tasks of this group.
+    pub fn time_polled(&self) -> Duration {
+        Duration::from_nanos(self.ns_polled.load(Ordering::Relaxed))
+    }
+
+    /// When a task of this group was first polled.
+    pub fn first_poll(&self) -> Option<Instant> {
+        self.first_poll.get().copied()
+    }
+
+    /// When the last task of this group finished.
+    pub fn last_done(&self) -> Option<Instant> {
+        *self.last_done.lock()
+    }
+
+    fn record_poll(&self, start: Instant, done: bool) {
+        let now = Instant::now();
+        self.first_poll.get_or_init(|| start);
+        let ns: u64 = (now - start).as_nanos().try_into().unwrap();
+        self.ns_polled.fetch_add(ns, Ordering::Relaxed);
+        if done {
+            let mut last_done = self.last_done.lock();
+            *last_done = Some(last_done.map_or(now, |t| t.max(now)));
+        }
+    }
+}
+
+/// Runs `f`, adding all tasks it spawns to the given task group.
+pub fn with_task_group<R>(group: Option<Arc<TaskGroupStats>>, f: impl FnOnce() -> R) -> R {
+    let prev = TLS_TASK_GROUP.replace(group);
+    let ret = f();
+    TLS_TASK_GROUP.set(prev);
+    ret
+}
+
+fn current_task_group() -> Option<Arc<TaskGroupStats>> {
+    TLS_TASK_GROUP.with_borrow(|g| g.clone())
+}
+
 slotmap::new_key_type! {
     struct TaskKey;
 }
@@ -78,6 +132,7 @@ struct TaskMetadata {
     priority: TaskPriority,
     freshly_spawned: AtomicBool,
     scoped: Option<ScopedTaskMetadata>,
+    group: Option<Arc<TaskGroupStats>>,
 }
 
 impl Drop for TaskMetadata {
@@ -256,7 +311,16 @@ impl Executor {
                     }
                 }
                 worker.recruit_next();
-                task.run();
+                match task.metadata().group.clone() {
+                    None => {
+                        task.run();
+                    },
+                    Some(group) => {
+                        let start = Instant::now();
+                        let done = with_task_group(Some(group.clone()), || task.run());
+                        group.record_poll(start, done);
+                    },
+                }
             }
         }
     }
@@ -355,6 +419,7 @@ impl<'scope> TaskScope<'scope, '_> {
                             task_key,
                             completed_tasks: Arc::downgrade(&self.completed_tasks),
                         }),
+                        group: current_task_group(),
                     },
                 )
             };
@@ -410,6 +475,7 @@ where
             priority,
             freshly_spawned: AtomicBool::new(true),
             scoped: None,
+            group: current_task_group(),
         },
     );
     runnable.schedule();
@@ -5,9 +5,11 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU8, Ordering};
 use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
 
 use atomic_waker::AtomicWaker;
 use pin_project_lite::pin_project;
+use polars_error::signals::CancellationToken;
 
 /// Single-producer, single-consumer capacity-one channel.
 pub fn connector<T>() -> (Sender<T>, Receiver<T>) {
@@ -15,11 +17,26 @@ pub fn connector<T>() -> (Sender<T>, Receiver<T>) {
     (
         Sender {
             connector: connector.clone(),
+            observer: None,
+        },
+        Receiver {
+            connector,
+            observer: None,
+            cancel_token: None,
         },
-        Receiver { connector },
     )
 }
 
+/// Observes the traffic through a connector, used for profiling.
+pub trait ConnectorObserver<T>: Send + Sync {
+    /// Called for every value the observed [`Receiver`] receives.
+    fn on_recv(&self, _value: &T) {}
+
+    /// Called with the time the observed [`Sender`] spent waiting for the
+    /// receiver to make room.
+    fn on_send_blocked(&self, _duration: Duration) {}
+}
+
 /*
     For UnsafeCell safety, a sender may only set the FULL_BIT (giving exclusive
     access to value to the receiver), and a receiver may only unset the FULL_BIT
@@ -190,6 +207,7 @@ impl<T> Connector<T> {
 
 pub struct Sender<T> {
     connector: Arc<Connector<T>>,
+    observer: Option<Arc<dyn ConnectorObserver<T>>>,
 }
 
 unsafe impl<T: Send> Send for Sender<T> {}
@@ -202,6 +220,8 @@ impl<T> Drop for Sender<T> {
 
 pub struct Receiver<T> {
     connector: Arc<Connector<T>>,
+    observer: Option<Arc<dyn ConnectorObserver<T>>>,
+    cancel_token: Option<CancellationToken>,
 }
 
 unsafe impl<T: Send> Send for Receiver<T> {}
@@ -216,6 +236,8 @@ pin_project! {
     pub struct SendFuture<'a, T> {
         connector: &'a Connector<T>,
         value: Option<T>,
+        observer: Option<&'a dyn ConnectorObserver<T>>,
+        blocked_since: Option<Instant>,
     }
 }
 
@@ -229,9 +251,16 @@ impl<T: Send> Sender<T> {
         SendFuture {
             connector: &self.connector,
             value: Some(value),
+            observer: self.observer.as_deref(),
+            blocked_since: None,
         }
     }
 
+    /// Reports the time spent blocked on sends to `observer`.
+    pub fn set_observer(&mut self, observer: Option<Arc<dyn ConnectorObserver<T>>>) {
+        self.observer = observer;
+    }
+
     #[allow(unused)]
     pub fn try_send(&mut self, value: T) -> Result<(), SendError<T>> {
         unsafe { self.connector.try_send(value) }
@@ -246,13 +275,24 @@ impl<T> std::future::Future for SendFuture<'_, T> {
             self.value.is_some(),
             "re-poll after Poll::Ready in connector SendFuture"
         );
-        unsafe { self.connector.poll_send(self.project().value, cx.waker()) }
+        let this = self.project();
+        let poll = unsafe { this.connector.poll_send(this.value, cx.waker()) };
+        if let Some(observer) = this.observer {
+            match (&poll, *this.blocked_since) {
+                (Poll::Pending, None) => *this.blocked_since = Some(Instant::now()),
+                (Poll::Ready(_), Some(t)) => observer.on_send_blocked(t.elapsed()),
+                _ => {},
+            }
+        }
+        poll
     }
 }
 
 pin_project! {
     pub struct RecvFuture<'a, T> {
         connector: &'a Connector<T>,
+        observer: Option<&'a dyn ConnectorObserver<T>>,
+        cancel_token: Option<&'a CancellationToken>,
         done: bool,
     }
 }
@@ -267,13 +307,33 @@ impl<T: Send> Receiver<T> {
     pub fn recv(&mut self) -> RecvFuture<'_, T> {
         RecvFuture {
             connector: &self.connector,
+            observer: self.observer.as_deref(),
+            cancel_token: self.cancel_token.as_ref(),
             done: false,
         }
     }
 
+    /// Reports every received value to `observer`.
+    pub fn set_observer(&mut self, observer: Option<Arc<dyn ConnectorObserver<T>>>) {
+        self.observer = observer;
+    }
+
+    /// Makes the receiver behave as if the [`Sender`] was dropped once
+    /// `cancel_token` is cancelled.
+    pub fn set_cancel_token(&mut self, cancel_token: Option<CancellationToken>) {
+        self.cancel_token = cancel_token;
+    }
+
     #[allow(unused)]
     pub fn try_recv(&mut self) -> Result<T, RecvError> {
-        unsafe { self.connector.try_recv() }
+        if self.cancel_token.as_ref().is_some_and(|t| t.is_cancelled()) {
+            return Err(RecvError::Closed);
+        }
+        let ret = unsafe { self.connector.try_recv() };
+        if let (Ok(value), Some(observer)) = (&ret, &self.observer) {
+            observer.on_recv(value);
+        }
+        ret
     }
 }
 
@@ -285,6 +345,13 @@ impl<T> std::future::Future for RecvFuture<'_, T> {
             !self.done,
             "re-poll after Poll::Ready in connector SendFuture"
         );
-        unsafe { self.connector.poll_recv(cx.waker()) }
+        if self.cancel_token.is_some_and(|t| t.is_cancelled()) {
+            return Poll::Ready(Err(()));
+        }
+        let poll = unsafe { self.connector.poll_recv(cx.waker()) };
+        if let (Poll::Ready(Ok(value)), Some(observer)) = (&poll, self.observer) {
+            observer.on_recv(value);
+        }
+        poll
     }
 }
@@ -1,13 +1,22 @@
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use parking_lot::Mutex;
 use polars_core::POOL;
 use polars_core::frame::DataFrame;
-use polars_error::PolarsResult;
+use polars_error::signals::CancellationToken;
+use polars_error::{PolarsResult, polars_bail, polars_err};
 use polars_expr::state::ExecutionState;
 use polars_utils::aliases::PlHashSet;
 use slotmap::{SecondaryMap, SparseSecondaryMap};
 
 use crate::async_executor;
 use crate::graph::{Graph, GraphNode, GraphNodeKey, LogicalPipeKey, PortState};
+use crate::metrics::GraphMetrics;
 use crate::pipe::PhysicalPipe;
+use crate::utils::spill::default_spill_threshold;
 
 #[derive(Clone)]
 pub struct StreamingExecutionState {
@@ -16,6 +25,16 @@ pub struct StreamingExecutionState {
 
     // The ExecutionState passed to any non-streaming operations.
     pub in_memory_exec_state: ExecutionState,
+
+    // The memory budget shared by all nodes of the query.
+    pub memory_manager: Arc<MemoryManager>,
+
+    // Cancels the query once triggered or past its deadline.
+    pub cancel_token: CancellationToken,
+
+    // The node this state is handed to, its memory reservations are
+    // attributed to it.
+    pub node_key: Option<GraphNodeKey>,
 }
 
 impl Default for StreamingExecutionState {
@@ -23,8 +42,220 @@ impl Default for StreamingExecutionState {
         Self {
             num_pipelines: POOL.current_num_threads(),
             in_memory_exec_state: ExecutionState::default(),
+            memory_manager: Arc::new(MemoryManager::new(None)),
+            cancel_token: CancellationToken::new(),
+            node_key: None,
+        }
+    }
+}
+
+impl StreamingExecutionState {
+    /// The state handed to the node `node_key`.
+    pub fn for_node(&self, node_key: GraphNodeKey) -> Self {
+        Self {
+            node_key: Some(node_key),
+            ..self.clone()
+        }
+    }
+}
+
+/// Awaits `fut`, giving up early with an error once `token` is cancelled or
+/// its deadline has passed.
+async fn until_cancelled<F>(fut: F, token: &CancellationToken) -> PolarsResult<()>
+where
+    F: Future<Output = PolarsResult<()>>,
+{
+    let watch = async {
+        while token.check().is_ok() {
+            match token.deadline() {
+                Some(deadline) => {
+                    let deadline = tokio::time::sleep_until(deadline.into());
+                    futures::future::select(pin!(token.cancelled()), pin!(deadline)).await;
+                },
+                None => token.cancelled().await,
+            }
+        }
+        token.check()
+    };
+    futures::future::select(pin!(fut), pin!(watch))
+        .await
+        .factor_first()
+        .0
+}
+
+/// Once more than this fraction of the memory budget is in use, spill-capable
+/// nodes are asked to spill.
+const SPILL_FRACTION: f64 = 0.75;
+
+/// Accounts the memory the nodes of a single query hold on to against an
+/// optional budget.
+///
+/// Nodes register what they buffer through a [`MemoryReservation`]. If a
+/// reservation would exceed the budget the query fails, naming the node.
+/// Spill-capable nodes are asked to spill once the query holds more than a
+/// fraction of the budget, or more than the process-wide default spill
+/// threshold without one.
+pub struct MemoryManager {
+    limit: Option<usize>,
+    spill_threshold: usize,
+    used: AtomicUsize,
+    node_memory: Mutex<SecondaryMap<GraphNodeKey, Arc<NodeMemory>>>,
+}
+
+/// The memory reserved by all reservations of a single graph node.
+#[derive(Default)]
+struct NodeMemory {
+    used: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl MemoryManager {
+    pub fn new(limit: Option<usize>) -> Self {
+        let spill_threshold = match limit {
+            Some(limit) => (limit as f64 * SPILL_FRACTION) as usize,
+            None => default_spill_threshold(),
+        };
+        Self {
+            limit,
+            spill_threshold,
+            used: AtomicUsize::new(0),
+            node_memory: Mutex::new(SecondaryMap::new()),
+        }
+    }
+
+    /// Creates a manager with the given budget (in bytes), falling back to
+    /// the one configured by `POLARS_STREAMING_MEMORY_LIMIT`, if any.
+    pub fn with_limit_or_env(limit: Option<usize>) -> PolarsResult<Self> {
+        if limit.is_some() {
+            return Ok(Self::new(limit));
+        }
+        let limit = std::env::var("POLARS_STREAMING_MEMORY_LIMIT")
+            .ok()
+            .map(|v| {
+                v.parse().map_err(|_| {
+                    polars_err!(
+                        InvalidOperation:
+                        "POLARS_STREAMING_MEMORY_LIMIT must be an integer number of bytes, got '{}'",
+                        v
+                    )
+                })
+            })
+            .transpose()?;
+        Ok(Self::new(limit))
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// The number of bytes currently reserved by all nodes.
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Whether the query holds enough memory that nodes which can spill to
+    /// disk should do so.
+    pub fn should_spill(&self) -> bool {
+        self.used() > self.spill_threshold
+    }
+
+    /// Creates an empty reservation for the node with the given key and name.
+    pub fn reservation(
+        self: &Arc<Self>,
+        node_key: Option<GraphNodeKey>,
+        node_name: &'static str,
+    ) -> MemoryReservation {
+        let node = node_key.map(|key| {
+            let mut node_memory = self.node_memory.lock();
+            node_memory.entry(key).unwrap().or_default().clone()
+        });
+        MemoryReservation {
+            manager: self.clone(),
+            node_name,
+            size: AtomicUsize::new(0),
+            node,
         }
     }
+
+    /// The most memory the reservations of `node` held at once, if it made
+    /// any.
+    pub fn node_peak(&self, node: GraphNodeKey) -> Option<usize> {
+        let node_memory = self.node_memory.lock();
+        Some(node_memory.get(node)?.peak.load(Ordering::Relaxed))
+    }
+}
+
+/// The memory held by a single node, released when this is dropped.
+///
+/// The sizes are estimates, and may be updated concurrently by all tasks of
+/// the node.
+pub struct MemoryReservation {
+    manager: Arc<MemoryManager>,
+    node_name: &'static str,
+    size: AtomicUsize,
+    node: Option<Arc<NodeMemory>>,
+}
+
+impl MemoryReservation {
+    /// Reserves another `bytes`, failing if that exceeds the budget.
+    pub fn grow(&self, bytes: usize) -> PolarsResult<()> {
+        let used = self.manager.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if let Some(limit) = self.manager.limit {
+            if used > limit {
+                self.manager.used.fetch_sub(bytes, Ordering::Relaxed);
+                polars_bail!(
+                    ComputeError:
+                    "streaming query exceeded its memory budget of {limit} bytes in node '{}', \
+                    which holds {} bytes; increase the memory limit",
+                    self.node_name, self.size() + bytes
+                );
+            }
+        }
+        self.size.fetch_add(bytes, Ordering::Relaxed);
+        if let Some(node) = &self.node {
+            let used = node.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+            node.peak.fetch_max(used, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Releases `bytes` of this reservation.
+    pub fn shrink(&self, bytes: usize) {
+        let prev = self
+            .size
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |s| {
+                Some(s.saturating_sub(bytes))
+            })
+            .unwrap();
+        self.release(bytes.min(prev));
+    }
+
+    /// Releases everything held by this reservation.
+    pub fn clear(&self) {
+        let bytes = self.size.swap(0, Ordering::Relaxed);
+        self.release(bytes);
+    }
+
+    fn release(&self, bytes: usize) {
+        self.manager.used.fetch_sub(bytes, Ordering::Relaxed);
+        if let Some(node) = &self.node {
+            node.used.fetch_sub(bytes, Ordering::Relaxed);
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size.load(Ordering::Relaxed)
+    }
+
+    pub fn should_spill(&self) -> bool {
+        self.manager.should_spill()
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.clear();
+    }
 }
 
 /// Finds all runnable pipeline blockers in the graph, that is, nodes which:
@@ -123,7 +354,14 @@ fn run_subgraph(
     nodes: &PlHashSet<GraphNodeKey>,
     pipes: &[LogicalPipeKey],
     state: &StreamingExecutionState,
+    metrics: Option<&GraphMetrics>,
 ) -> PolarsResult<()> {
+    // The states handed to the nodes, these must outlive their tasks.
+    let node_states: SecondaryMap<GraphNodeKey, StreamingExecutionState> = nodes
+        .iter()
+        .map(|node_key| (*node_key, state.for_node(*node_key)))
+        .collect();
+
     // Construct physical pipes for the logical pipes we'll use.
     let mut physical_pipes = SecondaryMap::new();
     for pipe_key in pipes.iter().copied() {
@@ -175,21 +413,34 @@ fn run_subgraph(
             }
 
             // Construct the receive/send ports.
-            for input_pipe in &mut input_pipes {
-                recv_ports.push(input_pipe.as_mut().map(|p| p.recv_port()));
+            for (input, input_pipe) in node.inputs.iter().zip(&mut input_pipes) {
+                let observer = metrics.map(|m| m.pipe_observer(*input));
+                recv_ports.push(input_pipe.as_mut().map(|p| {
+                    p.recv_port()
+                        .with_observer(observer)
+                        .with_cancel_token(state.cancel_token.clone())
+                }));
             }
-            for output_pipe in &mut output_pipes {
-                send_ports.push(output_pipe.as_mut().map(|p| p.send_port()));
+            for (output, output_pipe) in node.outputs.iter().zip(&mut output_pipes) {
+                let observer = metrics.map(|m| m.pipe_observer(*output));
+                send_ports.push(
+                    output_pipe
+                        .as_mut()
+                        .map(|p| p.send_port().with_observer(observer)),
+                );
             }
 
             // Spawn a task per pipeline.
-            node.compute.spawn(
-                scope,
-                &mut recv_ports[..],
-                &mut send_ports[..],
-                state,
-                &mut join_handles,
-            );
+            let task_group = metrics.map(|m| m.node_tasks(node_key));
+            async_executor::with_task_group(task_group, || {
+                node.compute.spawn(
+                    scope,
+                    &mut recv_ports[..],
+                    &mut send_ports[..],
+                    &node_states[node_key],
+                    &mut join_handles,
+                )
+            });
 
             // Ensure the ports were consumed.
             assert!(recv_ports.iter().all(|p| p.is_none()));
@@ -242,12 +493,21 @@ fn run_subgraph(
         if std::env::var("POLARS_TRACK_WAIT_STATS").as_deref() == Ok("1") {
             async_executor::track_task_wait_statistics(true);
         }
-        let ret = polars_io::pl_async::get_runtime().block_on(async move {
-            for handle in join_handles {
-                handle.await?;
-            }
-            PolarsResult::Ok(())
-        });
+        // Receive ports stop handing out morsels once the query is cancelled,
+        // which winds down busy nodes. Tasks blocked on something else are
+        // covered by no longer waiting on them, the scope then cancels all
+        // tasks that are still in flight.
+        let ret = polars_io::pl_async::get_runtime().block_on(until_cancelled(
+            async move {
+                for handle in join_handles {
+                    handle.await?;
+                }
+                // Nodes see a cancelled receive port as their input being
+                // done, don't mistake that for a completed subgraph.
+                state.cancel_token.check()
+            },
+            &state.cancel_token,
+        ));
         if std::env::var("POLARS_TRACK_WAIT_STATS").as_deref() == Ok("1") {
             async_executor::track_task_wait_statistics(false);
         }
@@ -257,16 +517,27 @@ fn run_subgraph(
     Ok(())
 }
 
+/// Executes the graph, collecting runtime statistics into `metrics` if given.
+///
+/// Without a `memory_limit` the budget is taken from the environment.
 pub fn execute_graph(
     graph: &mut Graph,
+    mut metrics: Option<&mut GraphMetrics>,
+    cancel_token: CancellationToken,
+    memory_limit: Option<usize>,
 ) -> PolarsResult<SparseSecondaryMap<GraphNodeKey, DataFrame>> {
     // Get the number of threads from the rayon thread-pool as that respects our config.
     let num_pipelines = POOL.current_num_threads();
     async_executor::set_num_threads(num_pipelines);
 
+    let mut in_memory_exec_state = ExecutionState::default();
+    in_memory_exec_state.set_cancel_token(cancel_token.clone());
     let state = StreamingExecutionState {
         num_pipelines,
-        in_memory_exec_state: ExecutionState::default(),
+        in_memory_exec_state,
+        memory_manager: Arc::new(MemoryManager::with_limit_or_env(memory_limit)?),
+        cancel_token,
+        node_key: None,
     };
 
     // Ensure everything is properly connected.
@@ -282,10 +553,14 @@ pub fn execute_graph(
     }
 
     loop {
+        state.cancel_token.check()?;
         if polars_core::config::verbose() {
             eprintln!("polars-stream: updating graph state");
         }
         graph.update_all_states(&state)?;
+        if let Some(metrics) = metrics.as_deref_mut() {
+            metrics.sample_memory(graph);
+        }
         let (nodes, pipes) = find_runnable_subgraph(graph);
         if polars_core::config::verbose() {
             for node in &nodes {
@@ -298,12 +573,19 @@ pub fn execute_graph(
         if nodes.is_empty() {
             break;
         }
-        run_subgraph(graph, &nodes, &pipes, &state)?;
+        run_subgraph(graph, &nodes, &pipes, &state, metrics.as_deref())?;
+        if let Some(metrics) = metrics.as_deref_mut() {
+            metrics.sample_memory(graph);
+        }
         if polars_core::config::verbose() {
             eprintln!("polars-stream: done running graph phase");
         }
     }
 
+    if let Some(metrics) = metrics.as_deref_mut() {
+        metrics.record_memory_peaks(graph, &state.memory_manager);
+    }
+
     // Ensure everything is done.
     for pipe in graph.pipes.values() {
         assert!(pipe.send_state == PortState::Done && pipe.recv_state == PortState::Done);
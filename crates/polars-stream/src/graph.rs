@@ -97,8 +97,11 @@ impl Graph {
                     node.compute.name()
                 );
             }
-            node.compute
-                .update_state(&mut recv_state, &mut send_state, state)?;
+            node.compute.update_state(
+                &mut recv_state,
+                &mut send_state,
+                &state.for_node(node_key),
+            )?;
             if verbose {
                 eprintln!(
                     "updating {}, after: {recv_state:?} {send_state:?}",
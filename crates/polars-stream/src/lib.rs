@@ -4,12 +4,13 @@ mod skeleton;
 
 use std::sync::LazyLock;
 
-pub use skeleton::{run_query, visualize_physical_plan};
+pub use skeleton::{profile_query, run_query, visualize_physical_plan};
 
 mod execute;
 pub(crate) mod expression;
 mod graph;
-pub use skeleton::{QueryResult, StreamingQuery};
+pub use skeleton::{QueryProfile, QueryResult, StreamingQuery};
+mod metrics;
 mod morsel;
 mod nodes;
 mod physical_plan;
@@ -0,0 +1,195 @@
+use std::fmt::Write;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use polars_core::prelude::*;
+use slotmap::SecondaryMap;
+
+use crate::async_executor::TaskGroupStats;
+use crate::async_primitives::connector::ConnectorObserver;
+use crate::execute::MemoryManager;
+use crate::graph::{Graph, GraphNodeKey, LogicalPipeKey};
+use crate::morsel::Morsel;
+use crate::pipe::MorselObserver;
+
+/// Statistics of the morsels flowing through a pipe.
+#[derive(Default)]
+pub struct PipeMetrics {
+    morsels: AtomicU64,
+    rows: AtomicU64,
+    ns_send_blocked: AtomicU64,
+}
+
+impl ConnectorObserver<Morsel> for PipeMetrics {
+    fn on_recv(&self, morsel: &Morsel) {
+        self.morsels.fetch_add(1, Ordering::Relaxed);
+        self.rows
+            .fetch_add(morsel.df().height() as u64, Ordering::Relaxed);
+    }
+
+    fn on_send_blocked(&self, duration: Duration) {
+        let ns: u64 = duration.as_nanos().try_into().unwrap();
+        self.ns_send_blocked.fetch_add(ns, Ordering::Relaxed);
+    }
+}
+
+/// Runtime statistics of the nodes and pipes of a graph, collected while
+/// profiling a query.
+pub struct GraphMetrics {
+    start: Instant,
+    tasks: SecondaryMap<GraphNodeKey, Arc<TaskGroupStats>>,
+    peak_memory: SecondaryMap<GraphNodeKey, usize>,
+    pipes: SecondaryMap<LogicalPipeKey, Arc<PipeMetrics>>,
+}
+
+impl GraphMetrics {
+    pub fn new(graph: &Graph) -> Self {
+        Self {
+            start: Instant::now(),
+            tasks: graph.nodes.keys().map(|k| (k, Arc::default())).collect(),
+            peak_memory: SecondaryMap::new(),
+            pipes: graph.pipes.keys().map(|k| (k, Arc::default())).collect(),
+        }
+    }
+
+    /// The task group the tasks of the given node should be spawned in.
+    pub fn node_tasks(&self, node: GraphNodeKey) -> Arc<TaskGroupStats> {
+        self.tasks[node].clone()
+    }
+
+    pub fn pipe_observer(&self, pipe: LogicalPipeKey) -> MorselObserver {
+        self.pipes[pipe].clone()
+    }
+
+    /// Samples the memory usage of all nodes. Done between execution phases,
+    /// which is when pipeline blockers hold on to the most data. This covers
+    /// the nodes which don't reserve their memory from the memory manager.
+    pub fn sample_memory(&mut self, graph: &Graph) {
+        for (key, node) in graph.nodes.iter() {
+            if let Some(bytes) = node.compute.memory_usage() {
+                let peak = self.peak_memory.entry(key).unwrap().or_insert(0);
+                *peak = (*peak).max(bytes);
+            }
+        }
+    }
+
+    /// Takes the high-water marks of the memory the nodes reserved from the
+    /// memory manager into account, which includes peaks within a phase.
+    pub fn record_memory_peaks(&mut self, graph: &Graph, memory_manager: &MemoryManager) {
+        for key in graph.nodes.keys() {
+            if let Some(bytes) = memory_manager.node_peak(key) {
+                let peak = self.peak_memory.entry(key).unwrap().or_insert(0);
+                *peak = (*peak).max(bytes);
+            }
+        }
+    }
+
+    pub fn node_profile(&self, graph: &Graph, key: GraphNodeKey) -> NodeProfile {
+        let node = &graph.nodes[key];
+        let tasks = &self.tasks[key];
+        let since_start = |t: Instant| t.saturating_duration_since(self.start);
+
+        let mut profile = NodeProfile {
+            name: node.compute.name().to_string(),
+            start: tasks.first_poll().map(since_start),
+            end: tasks.last_done().map(since_start),
+            busy: tasks.time_polled(),
+            blocked: Duration::ZERO,
+            rows_in: 0,
+            morsels_in: 0,
+            rows_out: 0,
+            morsels_out: 0,
+            peak_memory: self.peak_memory.get(key).copied(),
+        };
+        for input in &node.inputs {
+            let pipe = &self.pipes[*input];
+            profile.rows_in += pipe.rows.load(Ordering::Relaxed);
+            profile.morsels_in += pipe.morsels.load(Ordering::Relaxed);
+        }
+        for output in &node.outputs {
+            let pipe = &self.pipes[*output];
+            profile.rows_out += pipe.rows.load(Ordering::Relaxed);
+            profile.morsels_out += pipe.morsels.load(Ordering::Relaxed);
+            profile.blocked += Duration::from_nanos(pipe.ns_send_blocked.load(Ordering::Relaxed));
+        }
+        profile
+    }
+}
+
+/// The runtime statistics of a single node.
+pub struct NodeProfile {
+    pub name: String,
+    /// When the node was first polled, relative to the start of the query.
+    pub start: Option<Duration>,
+    /// When the last task of the node finished, relative to the start of the
+    /// query.
+    pub end: Option<Duration>,
+    /// Total time spent running the tasks of the node, over all threads.
+    pub busy: Duration,
+    /// Total time the node waited for its consumers to accept morsels.
+    pub blocked: Duration,
+    pub rows_in: u64,
+    pub morsels_in: u64,
+    pub rows_out: u64,
+    pub morsels_out: u64,
+    pub peak_memory: Option<usize>,
+}
+
+impl NodeProfile {
+    /// Formats the statistics to be appended to the node label in a plan.
+    pub fn annotation(&self) -> String {
+        let mut out = String::new();
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            write!(out, "\nwall: {:?}", end.saturating_sub(start)).unwrap();
+        }
+        write!(
+            out,
+            "\nbusy: {:?}\nblocked: {:?}\nrows: {} -> {}\nmorsels: {} -> {}",
+            self.busy, self.blocked, self.rows_in, self.rows_out, self.morsels_in, self.morsels_out
+        )
+        .unwrap();
+        if let Some(bytes) = self.peak_memory {
+            write!(out, "\npeak memory: {bytes} bytes").unwrap();
+        }
+        out
+    }
+}
+
+/// Collects the profiles into a [`DataFrame`] with one row per node, ordered by
+/// start time. The times are in microseconds.
+pub fn profiles_to_df(profiles: &[(u64, NodeProfile)]) -> PolarsResult<DataFrame> {
+    let micros = |d: Duration| d.as_micros() as u64;
+    let u64_column = |name: &'static str, f: &dyn Fn(&NodeProfile) -> Option<u64>| {
+        let ca: UInt64Chunked = profiles.iter().map(|(_, p)| f(p)).collect();
+        ca.with_name(PlSmallStr::from_static(name)).into_column()
+    };
+
+    let columns = vec![
+        Column::new(
+            PlSmallStr::from_static("node"),
+            profiles
+                .iter()
+                .map(|(_, p)| p.name.as_str())
+                .collect::<Vec<_>>(),
+        ),
+        Column::new(
+            PlSmallStr::from_static("id"),
+            profiles.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+        ),
+        u64_column("start", &|p| p.start.map(micros)),
+        u64_column("end", &|p| p.end.map(micros)),
+        u64_column("busy", &|p| Some(micros(p.busy))),
+        u64_column("blocked", &|p| Some(micros(p.blocked))),
+        u64_column("rows_in", &|p| Some(p.rows_in)),
+        u64_column("morsels_in", &|p| Some(p.morsels_in)),
+        u64_column("rows_out", &|p| Some(p.rows_out)),
+        u64_column("morsels_out", &|p| Some(p.morsels_out)),
+        u64_column("peak_memory", &|p| p.peak_memory.map(|b| b as u64)),
+    ];
+    let df = DataFrame::new(columns)?;
+    df.sort(
+        ["start", "id"],
+        SortMultipleOptions::default().with_nulls_last(true),
+    )
+}
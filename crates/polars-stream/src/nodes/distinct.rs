@@ -0,0 +1,419 @@
+use std::sync::Arc;
+
+use polars_core::POOL;
+use polars_core::prelude::PlRandomState;
+use polars_core::schema::Schema;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_expr::groups::{Grouper, new_hash_grouper};
+use polars_expr::hash_keys::HashKeys;
+use polars_utils::IdxSize;
+use polars_utils::cardinality_sketch::CardinalitySketch;
+use polars_utils::hashing::HashPartitioner;
+use polars_utils::itertools::Itertools;
+use polars_utils::pl_str::PlSmallStr;
+use rayon::prelude::*;
+
+use super::compute_node_prelude::*;
+use crate::async_primitives::connector::Receiver;
+use crate::morsel::get_ideal_morsel_size;
+use crate::nodes::in_memory_source::InMemorySourceNode;
+
+/// The position of a row in the input stream. Rows are ordered by the sequence
+/// id of their morsel, ties between morsels with the same sequence id are
+/// broken by the pipeline and the order in which it received them.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct RowPosition {
+    seq: MorselSeq,
+    local: u32,
+    morsel: u32,
+    row: IdxSize,
+}
+
+/// The last row seen for each key by a single pipeline.
+struct LocalDistinctSinkState {
+    grouper: Box<dyn Grouper>,
+
+    // For each group the position of its last row and the index of that row
+    // in the kept rows.
+    last: Vec<Option<(RowPosition, IdxSize)>>,
+
+    // The kept rows, including those that got superseded by a later row of
+    // the same key. These are compacted once they outnumber the groups.
+    kept: Vec<DataFrame>,
+    num_kept: usize,
+
+    num_morsels: u32,
+    buffered_bytes: usize,
+}
+
+impl LocalDistinctSinkState {
+    fn new(grouper: Box<dyn Grouper>) -> Self {
+        Self {
+            grouper,
+            last: Vec::new(),
+            kept: Vec::new(),
+            num_kept: 0,
+            num_morsels: 0,
+            buffered_bytes: 0,
+        }
+    }
+
+    /// Keeps the rows of the given morsel that are the last occurrence of
+    /// their key seen so far.
+    fn insert_morsel(
+        &mut self,
+        seq: MorselSeq,
+        local: u32,
+        df: &DataFrame,
+        group_idxs: &[IdxSize],
+    ) {
+        let morsel = self.num_morsels;
+        self.num_morsels += 1;
+        self.last.resize(self.grouper.num_groups() as usize, None);
+
+        // Later rows win, so we go backwards to only keep the last row of
+        // each key within this morsel.
+        let mut take = Vec::new();
+        for (row, &g) in group_idxs.iter().enumerate().rev() {
+            let pos = RowPosition {
+                seq,
+                local,
+                morsel,
+                row: row as IdxSize,
+            };
+            let last = &mut self.last[g as usize];
+            if last.is_none_or(|(last_pos, _)| pos > last_pos) {
+                *last = Some((pos, (self.num_kept + take.len()) as IdxSize));
+                take.push(row as IdxSize);
+            }
+        }
+
+        if !take.is_empty() {
+            let rows = unsafe { df.take_slice_unchecked(&take) };
+            self.buffered_bytes += rows.estimated_size();
+            self.num_kept += rows.height();
+            self.kept.push(rows);
+        }
+
+        let num_groups = self.last.len();
+        if self.num_kept >= 2 * num_groups && self.num_kept >= get_ideal_morsel_size() {
+            self.compact();
+        }
+    }
+
+    /// Drops the superseded rows, after which row i holds the last row of
+    /// group i.
+    fn compact(&mut self) {
+        let mut kept = accumulate_dataframes_vertical_unchecked(core::mem::take(&mut self.kept));
+        kept.rechunk_mut(); // For gathers.
+        let idxs = self
+            .last
+            .iter_mut()
+            .enumerate()
+            .map(|(g, last)| {
+                let (_pos, idx) = last.as_mut().unwrap();
+                core::mem::replace(idx, g as IdxSize)
+            })
+            .collect_vec();
+        let rows = unsafe { kept.take_slice_unchecked(&idxs) };
+        self.buffered_bytes = rows.estimated_size();
+        self.num_kept = rows.height();
+        self.kept = vec![rows];
+    }
+
+    /// Returns the last row of each group seen by this pipeline together with
+    /// its position, in group order.
+    fn into_rows(mut self, schema: &Schema) -> (DataFrame, Vec<RowPosition>) {
+        if self.last.is_empty() {
+            return (DataFrame::empty_with_schema(schema), Vec::new());
+        }
+        self.compact();
+        let positions = self.last.iter().map(|l| l.unwrap().0).collect();
+        (self.kept.pop().unwrap(), positions)
+    }
+}
+
+/// The last rows of a single pipeline, partitioned on their keys.
+struct LocalRows {
+    df: DataFrame,
+    positions: Vec<RowPosition>,
+    keys: HashKeys,
+    idxs_per_p: Vec<Vec<IdxSize>>,
+    sketch_per_p: Vec<CardinalitySketch>,
+}
+
+struct DistinctSinkState {
+    locals: Vec<LocalDistinctSinkState>,
+    partitioner: HashPartitioner,
+}
+
+impl DistinctSinkState {
+    async fn sink(
+        mut recv: Receiver<Morsel>,
+        local_idx: u32,
+        local: &mut LocalDistinctSinkState,
+        params: &DistinctParams,
+    ) -> PolarsResult<()> {
+        let mut all_idxs = Vec::new();
+        let mut group_idxs = Vec::new();
+        while let Ok(morsel) = recv.recv().await {
+            let (df, seq, _src_token, _wait_token) = morsel.into_inner();
+            if df.height() == 0 {
+                continue;
+            }
+
+            let keys = df.select(params.key_cols.iter().cloned())?;
+            let hash_keys = HashKeys::from_df(&keys, params.random_state, true, false);
+            all_idxs.clear();
+            all_idxs.extend(0..df.height() as IdxSize);
+            group_idxs.clear();
+            unsafe {
+                local
+                    .grouper
+                    .insert_keys_subset(&hash_keys, &all_idxs, Some(&mut group_idxs));
+            }
+            local.insert_morsel(seq, local_idx, &df, &group_idxs);
+        }
+        Ok(())
+    }
+
+    /// Combines the last rows seen by each pipeline, keeping the last row of
+    /// each key in the order in which they were received.
+    fn finalize(self, params: &DistinctParams, output_schema: &Schema) -> DataFrame {
+        let num_partitions = self.partitioner.num_partitions();
+        let partitioner = &self.partitioner;
+        POOL.install(|| {
+            let locals = self
+                .locals
+                .into_par_iter()
+                .map(|l| {
+                    let (df, positions) = l.into_rows(output_schema);
+                    let keys = df.select(params.key_cols.iter().cloned()).unwrap();
+                    let hash_keys = HashKeys::from_df(&keys, params.random_state, true, false);
+                    let mut idxs_per_p = vec![Vec::new(); num_partitions];
+                    let mut sketch_per_p = vec![CardinalitySketch::new(); num_partitions];
+                    hash_keys.gen_idxs_per_partition(
+                        partitioner,
+                        &mut idxs_per_p,
+                        &mut sketch_per_p,
+                        true,
+                    );
+                    LocalRows {
+                        df,
+                        positions,
+                        keys: hash_keys,
+                        idxs_per_p,
+                        sketch_per_p,
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            // The offset of the rows of each local in the combined rows.
+            let mut offset = 0;
+            let offsets = locals
+                .iter()
+                .map(|l| {
+                    let o = offset;
+                    offset += l.df.height();
+                    o as IdxSize
+                })
+                .collect_vec();
+
+            // Per partition find which local saw the last row of each key.
+            let mut selected = (0..num_partitions)
+                .into_par_iter()
+                .flat_map_iter(|p| {
+                    let mut sketch = CardinalitySketch::new();
+                    for l in &locals {
+                        sketch.combine(&l.sketch_per_p[p]);
+                    }
+                    let mut grouper = params.grouper.new_empty();
+                    grouper.reserve(sketch.estimate() * 5 / 4);
+
+                    let mut last: Vec<(RowPosition, IdxSize)> = Vec::new();
+                    let mut group_idxs = Vec::new();
+                    for (l, offset) in locals.iter().zip(&offsets) {
+                        let idxs = &l.idxs_per_p[p];
+                        group_idxs.clear();
+                        unsafe {
+                            grouper.insert_keys_subset(&l.keys, idxs, Some(&mut group_idxs));
+                        }
+                        for (&row, &g) in idxs.iter().zip(&group_idxs) {
+                            let pos = l.positions[row as usize];
+                            if let Some(best) = last.get_mut(g as usize) {
+                                if pos > best.0 {
+                                    *best = (pos, offset + row);
+                                }
+                            } else {
+                                debug_assert!(g as usize == last.len());
+                                last.push((pos, offset + row));
+                            }
+                        }
+                    }
+                    last
+                })
+                .collect::<Vec<_>>();
+            selected.par_sort_unstable();
+
+            let dfs = locals.into_iter().map(|l| l.df).collect_vec();
+            if selected.is_empty() {
+                return DataFrame::empty_with_schema(output_schema);
+            }
+            let mut rows = accumulate_dataframes_vertical_unchecked(dfs);
+            rows.rechunk_mut(); // For gathers.
+            let idxs = selected.into_iter().map(|(_pos, idx)| idx).collect_vec();
+            unsafe { rows.take_slice_unchecked(&idxs) }
+        })
+    }
+}
+
+struct DistinctParams {
+    key_cols: Vec<PlSmallStr>,
+    grouper: Box<dyn Grouper>,
+    random_state: PlRandomState,
+}
+
+enum DistinctState {
+    Sink(DistinctSinkState),
+    Source(InMemorySourceNode),
+    Done,
+}
+
+/// Keeps the last row of each distinct key, preserving the order of the input.
+///
+/// Every pipeline only holds on to the last row it has seen of each key, the
+/// pipelines are combined in parallel over hash partitions of the keys once
+/// the input is done.
+pub struct DistinctNode {
+    state: DistinctState,
+    params: DistinctParams,
+    output_schema: Arc<Schema>,
+}
+
+impl DistinctNode {
+    pub fn new(
+        input_schema: Arc<Schema>,
+        key_cols: Vec<PlSmallStr>,
+        num_pipelines: usize,
+    ) -> PolarsResult<Self> {
+        let key_schema = Arc::new(input_schema.try_project(key_cols.iter())?);
+        let grouper = new_hash_grouper(key_schema);
+        let num_partitions = num_pipelines;
+        let locals = (0..num_pipelines)
+            .map(|_| LocalDistinctSinkState::new(grouper.new_empty()))
+            .collect();
+        Ok(Self {
+            state: DistinctState::Sink(DistinctSinkState {
+                locals,
+                partitioner: HashPartitioner::new(num_partitions, 0),
+            }),
+            params: DistinctParams {
+                key_cols,
+                grouper,
+                random_state: PlRandomState::default(),
+            },
+            output_schema: input_schema,
+        })
+    }
+}
+
+impl ComputeNode for DistinctNode {
+    fn name(&self) -> &str {
+        "distinct"
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 1 && send.len() == 1);
+
+        // State transitions.
+        match &mut self.state {
+            // If the output doesn't want any more data, transition to being done.
+            _ if send[0] == PortState::Done => {
+                self.state = DistinctState::Done;
+            },
+            // Input is done, transition to being a source.
+            DistinctState::Sink(_) if matches!(recv[0], PortState::Done) => {
+                let DistinctState::Sink(sink) =
+                    core::mem::replace(&mut self.state, DistinctState::Done)
+                else {
+                    unreachable!()
+                };
+                let df = sink.finalize(&self.params, &self.output_schema);
+                let source = InMemorySourceNode::new(Arc::new(df), MorselSeq::new(0));
+                self.state = DistinctState::Source(source);
+            },
+            // Defer to source node implementation.
+            DistinctState::Source(src) => {
+                src.update_state(&mut [], send, state)?;
+                if send[0] == PortState::Done {
+                    self.state = DistinctState::Done;
+                }
+            },
+            // Nothing to change.
+            DistinctState::Done | DistinctState::Sink(_) => {},
+        }
+
+        // Communicate our state.
+        match &self.state {
+            DistinctState::Sink { .. } => {
+                send[0] = PortState::Blocked;
+                recv[0] = PortState::Ready;
+            },
+            DistinctState::Source(..) => {
+                recv[0] = PortState::Done;
+                send[0] = PortState::Ready;
+            },
+            DistinctState::Done => {
+                recv[0] = PortState::Done;
+                send[0] = PortState::Done;
+            },
+        }
+        Ok(())
+    }
+
+    fn is_memory_intensive_pipeline_blocker(&self) -> bool {
+        matches!(self.state, DistinctState::Sink { .. })
+    }
+
+    fn memory_usage(&self) -> Option<usize> {
+        match &self.state {
+            DistinctState::Sink(sink) => Some(sink.locals.iter().map(|l| l.buffered_bytes).sum()),
+            DistinctState::Source(source) => source.memory_usage(),
+            DistinctState::Done => Some(0),
+        }
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(send_ports.len() == 1 && recv_ports.len() == 1);
+        match &mut self.state {
+            DistinctState::Sink(sink) => {
+                assert!(send_ports[0].is_none());
+                let receivers = recv_ports[0].take().unwrap().parallel();
+                for (local_idx, (local, recv)) in sink.locals.iter_mut().zip(receivers).enumerate()
+                {
+                    join_handles.push(scope.spawn_task(
+                        TaskPriority::High,
+                        DistinctSinkState::sink(recv, local_idx as u32, local, &self.params),
+                    ));
+                }
+            },
+            DistinctState::Source(source) => {
+                assert!(recv_ports[0].is_none());
+                source.spawn(scope, &mut [], send_ports, state, join_handles);
+            },
+            DistinctState::Done => unreachable!(),
+        }
+    }
+}
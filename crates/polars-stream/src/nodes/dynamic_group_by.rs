@@ -0,0 +1,657 @@
+use std::sync::Arc;
+
+use arrow::legacy::time_zone::Tz;
+use polars_core::prelude::row_encode::encode_rows_vertical_par_unordered;
+use polars_core::prelude::{
+    ChunkSort, Column, DataType, Field, Int64Chunked, IntoColumn, PlIndexMap, PlRandomState,
+    SortOptions, TimeUnit, TimeZone,
+};
+use polars_core::schema::Schema;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_error::{polars_bail, polars_ensure};
+use polars_expr::groups::Grouper;
+use polars_expr::hash_keys::HashKeys;
+use polars_expr::reduce::GroupedReduction;
+use polars_time::prelude::{
+    Bounds, ClosedWindow, Duration, DynamicGroupOptions, Label, RollingGroupOptions, Window,
+    ensure_duration_matches_dtype,
+};
+use polars_utils::IdxSize;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::compute_node_prelude::*;
+use crate::async_primitives::connector::Sender;
+use crate::expression::StreamExpr;
+use crate::morsel::SourceToken;
+
+const LOWER_BOUND_NAME: PlSmallStr = PlSmallStr::from_static("_lower_boundary");
+const UPPER_BOUND_NAME: PlSmallStr = PlSmallStr::from_static("_upper_boundary");
+
+/// The windows a [`DynamicGroupByNode`] aggregates over.
+pub enum WindowKind {
+    /// Windows of a fixed period which start at a fixed interval, as in
+    /// `group_by_dynamic`.
+    Dynamic(DynamicGroupOptions),
+    /// A window for every row relative to its index value, as in `rolling`.
+    Rolling(RollingGroupOptions),
+}
+
+impl WindowKind {
+    pub fn index_column(&self) -> &PlSmallStr {
+        match self {
+            Self::Dynamic(options) => &options.index_column,
+            Self::Rolling(options) => &options.index_column,
+        }
+    }
+
+    fn closed_window(&self) -> ClosedWindow {
+        match self {
+            Self::Dynamic(options) => options.closed_window,
+            Self::Rolling(options) => options.closed_window,
+        }
+    }
+
+    fn operation(&self) -> &'static str {
+        match self {
+            Self::Dynamic(_) => "group_by_dynamic",
+            Self::Rolling(_) => "rolling",
+        }
+    }
+
+    /// The time unit the index values are converted to.
+    fn time_unit(index_dtype: &DataType) -> TimeUnit {
+        match index_dtype {
+            DataType::Datetime(tu, _) => *tu,
+            DataType::Date => TimeUnit::Milliseconds,
+            _ => TimeUnit::Nanoseconds,
+        }
+    }
+
+    /// Returns the fields of the columns this node outputs between the keys
+    /// and the aggregations: the window boundaries (if requested) and the
+    /// index column.
+    pub fn output_fields(&self, index_dtype: &DataType) -> Vec<Field> {
+        let index_field = Field::new(self.index_column().clone(), index_dtype.clone());
+        match self {
+            Self::Dynamic(options) if options.include_boundaries => {
+                let bound_dtype = match index_dtype {
+                    DataType::Datetime(_, _) => index_dtype.clone(),
+                    DataType::Date => DataType::Datetime(TimeUnit::Milliseconds, None),
+                    _ => index_dtype.clone(),
+                };
+                vec![
+                    Field::new(LOWER_BOUND_NAME, bound_dtype.clone()),
+                    Field::new(UPPER_BOUND_NAME, bound_dtype),
+                    index_field,
+                ]
+            },
+            _ => vec![index_field],
+        }
+    }
+}
+
+/// The state of a single group, or of the whole input if there are no keys.
+#[derive(Default)]
+struct GroupState {
+    /// The values of the keys of this group, as a single row.
+    key: DataFrame,
+    /// The rows which can still be part of a window that hasn't been emitted.
+    buffer: Vec<DataFrame>,
+    /// The index values of the buffered rows.
+    times: Vec<i64>,
+    /// The first window that hasn't been emitted, for `group_by_dynamic`.
+    next_window: Option<Bounds>,
+    /// The number of buffered rows whose window has been emitted, for `rolling`.
+    rows_done: usize,
+    /// The start and end of the last emitted window in the buffered rows, for
+    /// `rolling`.
+    window_start: usize,
+    window_end: usize,
+}
+
+impl GroupState {
+    fn estimated_size(&self) -> usize {
+        let buffered: usize = self.buffer.iter().map(|df| df.estimated_size()).sum();
+        buffered + self.times.len() * size_of::<i64>()
+    }
+}
+
+struct WindowParams {
+    kind: WindowKind,
+    key_selectors: Vec<StreamExpr>,
+    agg_cols: Vec<PlSmallStr>,
+    output_schema: Arc<Schema>,
+    index_dtype: DataType,
+    tu: TimeUnit,
+    time_zone: Option<TimeZone>,
+    tz: Option<Tz>,
+    random_state: PlRandomState,
+}
+
+impl WindowParams {
+    fn add(&self, duration: &Duration, t: i64) -> PolarsResult<i64> {
+        let tz = self.tz.as_ref();
+        match self.tu {
+            TimeUnit::Nanoseconds => duration.add_ns(t, tz),
+            TimeUnit::Microseconds => duration.add_us(t, tz),
+            TimeUnit::Milliseconds => duration.add_ms(t, tz),
+        }
+    }
+
+    /// Converts the index column to timestamps in `self.tu`.
+    fn timestamps(&self, index: &Column) -> PolarsResult<Vec<i64>> {
+        polars_ensure!(
+            index.null_count() == 0,
+            ComputeError: "null values in `{}` not supported, fill nulls.", self.kind.operation()
+        );
+        let ts = match &self.index_dtype {
+            DataType::Date => index.cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?,
+            _ => index.clone(),
+        };
+        let ts = ts.cast(&DataType::Int64)?;
+        Ok(ts.i64()?.into_no_null_iter().collect())
+    }
+
+    /// The window of a row with the given index value in a rolling group-by.
+    fn rolling_bounds(&self, options: &RollingGroupOptions, t: i64) -> PolarsResult<Bounds> {
+        let lower = self.add(&options.offset, t)?;
+        // If the window ends at t itself we don't compute `t + offset + period`
+        // as that doesn't round-trip for calendar durations.
+        let upper = if options.offset.negative()
+            && !options.offset.is_zero()
+            && options.offset.duration_ns() == options.period.duration_ns()
+        {
+            t
+        } else {
+            self.add(&options.period, lower)?
+        };
+        Ok(Bounds::new(lower, upper))
+    }
+
+    /// Adds rows of the group, which must continue its sorted index.
+    fn append(
+        &self,
+        group: &mut GroupState,
+        df: DataFrame,
+        times: Vec<i64>,
+        has_keys: bool,
+    ) -> PolarsResult<()> {
+        let prev = group.times.last().copied().unwrap_or(i64::MIN);
+        let sorted = times.first().is_none_or(|t| prev <= *t) && times.is_sorted();
+        if !sorted {
+            if has_keys {
+                polars_bail!(ComputeError: "input data is not sorted");
+            }
+            polars_bail!(
+                InvalidOperation: "argument in operation '{}' is not sorted, please sort the 'expr/series/column' first",
+                self.kind.operation()
+            );
+        }
+
+        if let (WindowKind::Dynamic(options), None) = (&self.kind, group.next_window) {
+            let window = Window::new(options.every, options.period, options.offset);
+            group.next_window = Some(window.get_first_bounds(
+                times[0],
+                options.closed_window,
+                self.tu,
+                self.tz.as_ref(),
+                options.start_by,
+            )?);
+        }
+        group.times.extend(times);
+        group.buffer.push(df);
+        Ok(())
+    }
+
+    /// Aggregates the buffered rows in the given groups of (offset, len).
+    fn aggregate(
+        &self,
+        df: &DataFrame,
+        groups: &[[IdxSize; 2]],
+        reductions: &mut [Box<dyn GroupedReduction>],
+    ) -> PolarsResult<Vec<Column>> {
+        let mut subset = Vec::new();
+        let mut group_idxs = Vec::new();
+        for (g, [offset, len]) in groups.iter().enumerate() {
+            subset.extend(*offset..*offset + *len);
+            group_idxs.extend(std::iter::repeat_n(g as IdxSize, *len as usize));
+        }
+
+        let agg_names = self
+            .output_schema
+            .iter_names()
+            .skip(self.output_schema.len() - reductions.len());
+        let mut out = Vec::with_capacity(reductions.len());
+        for ((reduction, col), name) in reductions.iter_mut().zip(&self.agg_cols).zip(agg_names) {
+            reduction.resize(groups.len() as IdxSize);
+            // All rows of a window are in a single update, in order.
+            unsafe { reduction.update_groups_subset(df.column(col)?, &subset, &group_idxs, 0)? };
+            out.push(reduction.finalize()?.with_name(name.clone()).into_column());
+        }
+        Ok(out)
+    }
+
+    /// Converts timestamps in `self.tu` back to the type of the index column.
+    fn index_column(&self, values: Vec<i64>) -> PolarsResult<Column> {
+        let name = self.kind.index_column().clone();
+        let dt = Int64Chunked::from_vec(name, values)
+            .into_datetime(self.tu, None)
+            .into_column();
+        if self.index_dtype.is_integer() {
+            dt.cast(&DataType::Int64)?.cast(&self.index_dtype)
+        } else {
+            dt.cast(&self.index_dtype)
+        }
+    }
+
+    fn bound_column(&self, name: PlSmallStr, values: Vec<i64>) -> PolarsResult<Column> {
+        let dt = Int64Chunked::from_vec(name, values)
+            .into_datetime(self.tu, self.time_zone.clone())
+            .into_column();
+        if self.index_dtype.is_integer() {
+            dt.cast(&DataType::Int64)?.cast(&self.index_dtype)
+        } else {
+            Ok(dt)
+        }
+    }
+
+    /// Emits the windows of the group that are complete, or all of them if
+    /// the input is finished, and drops the rows no other window needs.
+    fn flush(
+        &self,
+        group: &mut GroupState,
+        reductions: &mut [Box<dyn GroupedReduction>],
+        finished: bool,
+    ) -> PolarsResult<Option<DataFrame>> {
+        let Some(&watermark) = group.times.last() else {
+            return Ok(None);
+        };
+        let closed = self.kind.closed_window();
+        let times = group.times.as_slice();
+
+        // Find the windows to emit as (offset, len) into the buffered rows,
+        // together with the index and window columns.
+        let mut groups = Vec::new();
+        let mut window_columns = Vec::new();
+        let first_needed;
+        let rows_done;
+        let mut window_start = 0;
+        let mut window_end = 0;
+        match &self.kind {
+            WindowKind::Dynamic(options) => {
+                let window = Window::new(options.every, options.period, options.offset);
+                let mut bounds = group.next_window.unwrap();
+                let mut lower = Vec::new();
+                let mut upper = Vec::new();
+                let mut labels = Vec::new();
+                let mut start = 0;
+                loop {
+                    // Later rows can't be in a window once the watermark is
+                    // past it. At the end there are no more windows once they
+                    // start after the last row.
+                    let complete = if finished {
+                        bounds.start() <= watermark
+                    } else {
+                        bounds.is_future(watermark, closed)
+                    };
+                    if !complete {
+                        break;
+                    }
+
+                    start +=
+                        times[start..].partition_point(|t| !bounds.is_member_entry(*t, closed));
+                    let len = times[start..].partition_point(|t| bounds.is_member_exit(*t, closed));
+                    if len > 0 {
+                        groups.push([start as IdxSize, len as IdxSize]);
+                        lower.push(bounds.start());
+                        upper.push(bounds.stop());
+                        labels.push(match options.label {
+                            Label::Left => bounds.start(),
+                            Label::Right => bounds.stop(),
+                            Label::DataPoint => times[start],
+                        });
+                    }
+                    bounds = window.get_next_bounds(bounds, self.tu, self.tz.as_ref())?;
+                }
+                group.next_window = Some(bounds);
+                first_needed = times.partition_point(|t| !bounds.is_member_entry(*t, closed));
+                rows_done = 0;
+
+                if !groups.is_empty() {
+                    if options.include_boundaries {
+                        window_columns.push(self.bound_column(LOWER_BOUND_NAME, lower)?);
+                        window_columns.push(self.bound_column(UPPER_BOUND_NAME, upper)?);
+                    }
+                    window_columns.push(self.index_column(labels)?);
+                }
+            },
+            WindowKind::Rolling(options) => {
+                let mut row = group.rows_done;
+                let mut start = group.window_start;
+                let mut end = group.window_end;
+                while row < times.len() {
+                    let bounds = self.rolling_bounds(options, times[row])?;
+                    if !finished && !bounds.is_future(watermark, closed) {
+                        break;
+                    }
+                    start +=
+                        times[start..].partition_point(|t| !bounds.is_member_entry(*t, closed));
+                    // Like in the in-memory engine the start and end never move
+                    // backwards, which matters if calendar durations clamp the
+                    // window.
+                    end = end.max(start);
+                    end += times[end..].partition_point(|t| bounds.is_member_exit(*t, closed));
+                    groups.push([start as IdxSize, (end - start) as IdxSize]);
+                    row += 1;
+                }
+                window_start = start;
+                window_end = end;
+
+                // The rows before the window of the next row (or of the last
+                // row) are no longer needed.
+                let next = self.rolling_bounds(options, times[row.min(times.len() - 1)])?;
+                let next_start =
+                    start + times[start..].partition_point(|t| !next.is_member_entry(*t, closed));
+                first_needed = next_start.min(row);
+                rows_done = row;
+            },
+        }
+
+        if groups.is_empty() && first_needed == 0 {
+            return Ok(None);
+        }
+
+        let mut df = accumulate_dataframes_vertical_unchecked(group.buffer.drain(..));
+        df.as_single_chunk();
+        let out = if groups.is_empty() {
+            None
+        } else {
+            if let WindowKind::Rolling(_) = &self.kind {
+                let index = df.column(self.kind.index_column())?;
+                let emitted = rows_done - group.rows_done;
+                window_columns.push(index.slice(group.rows_done as i64, emitted));
+            }
+
+            let height = groups.len();
+            let mut columns = group.key.new_from_index(0, height).take_columns();
+            columns.extend(window_columns);
+            columns.extend(self.aggregate(&df, &groups, reductions)?);
+            Some(DataFrame::new_with_height(height, columns)?)
+        };
+
+        group.buffer.push(df.slice(first_needed as i64, usize::MAX));
+        group.times.drain(..first_needed);
+        group.rows_done = rows_done.saturating_sub(first_needed);
+        group.window_start = window_start.saturating_sub(first_needed);
+        group.window_end = window_end.saturating_sub(first_needed);
+        Ok(out)
+    }
+}
+
+/// Group-by over temporal windows of an index column which is sorted, per
+/// group if there are keys.
+///
+/// The input is processed in order and a window is aggregated as soon as the
+/// index passes its end, so only the rows of windows that are still open have
+/// to be kept around. Without keys the windows are emitted right away. With
+/// keys the in-memory engine orders its output by key, so the aggregated
+/// windows are buffered and emitted in that order once the input is finished.
+pub struct DynamicGroupByNode {
+    params: WindowParams,
+    groups: Vec<GroupState>,
+    /// Assigns the rows to groups, `None` if there are no keys.
+    grouper: Option<Box<dyn Grouper>>,
+    /// The aggregated windows so far if there are keys.
+    keyed_output: Vec<DataFrame>,
+    reductions: Vec<Box<dyn GroupedReduction>>,
+    seq: MorselSeq,
+    flushed: bool,
+}
+
+impl DynamicGroupByNode {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input_schema: &Schema,
+        kind: WindowKind,
+        key_selectors: Vec<StreamExpr>,
+        grouper: Option<Box<dyn Grouper>>,
+        agg_cols: Vec<PlSmallStr>,
+        reductions: Vec<Box<dyn GroupedReduction>>,
+        output_schema: Arc<Schema>,
+    ) -> PolarsResult<Self> {
+        let index_dtype = input_schema.try_get(kind.index_column())?.clone();
+        match &kind {
+            WindowKind::Dynamic(options) => {
+                polars_ensure!(
+                    matches!(
+                        index_dtype,
+                        DataType::Date | DataType::Datetime(_, _) | DataType::Int32 | DataType::Int64
+                    ),
+                    ComputeError:
+                    "expected any of the following dtypes: {{ Date, Datetime, Int32, Int64 }}, got {}",
+                    index_dtype
+                );
+                ensure_duration_matches_dtype(options.every, &index_dtype, "every")?;
+                ensure_duration_matches_dtype(options.offset, &index_dtype, "offset")?;
+                ensure_duration_matches_dtype(options.period, &index_dtype, "period")?;
+                polars_ensure!(!options.every.negative(), ComputeError: "'every' argument must be positive");
+            },
+            WindowKind::Rolling(options) => {
+                polars_ensure!(
+                    !options.period.is_zero() && !options.period.negative(),
+                    ComputeError: "rolling window period should be strictly positive",
+                );
+                polars_ensure!(
+                    matches!(
+                        index_dtype,
+                        DataType::Date
+                            | DataType::Datetime(_, _)
+                            | DataType::Int32
+                            | DataType::Int64
+                            | DataType::UInt32
+                            | DataType::UInt64
+                    ),
+                    ComputeError:
+                    "expected any of the following dtypes: {{ Date, Datetime, Int32, Int64, UInt32, UInt64 }}, got {}",
+                    index_dtype
+                );
+                ensure_duration_matches_dtype(options.period, &index_dtype, "period")?;
+                ensure_duration_matches_dtype(options.offset, &index_dtype, "offset")?;
+            },
+        }
+
+        let time_zone = match &index_dtype {
+            DataType::Datetime(_, tz) => tz.clone(),
+            _ => None,
+        };
+        let tz = match &time_zone {
+            #[cfg(feature = "timezones")]
+            Some(tz) => tz.parse::<Tz>().ok(),
+            _ => None,
+        };
+
+        Ok(Self {
+            params: WindowParams {
+                tu: WindowKind::time_unit(&index_dtype),
+                kind,
+                key_selectors,
+                agg_cols,
+                output_schema,
+                index_dtype,
+                time_zone,
+                tz,
+                random_state: PlRandomState::default(),
+            },
+            groups: Vec::new(),
+            grouper,
+            keyed_output: Vec::new(),
+            reductions,
+            seq: MorselSeq::default(),
+            flushed: false,
+        })
+    }
+
+    /// Processes a morsel of the input, returning the windows it completed.
+    async fn process(
+        &mut self,
+        df: DataFrame,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<Vec<DataFrame>> {
+        let params = &self.params;
+        if df.height() == 0 {
+            return Ok(Vec::new());
+        }
+        let times = params.timestamps(df.column(params.kind.index_column())?)?;
+
+        let mut out = Vec::new();
+        let Some(grouper) = &mut self.grouper else {
+            if self.groups.is_empty() {
+                self.groups.push(GroupState::default());
+            }
+            let group = &mut self.groups[0];
+            params.append(group, df, times, false)?;
+            out.extend(params.flush(group, &mut self.reductions, false)?);
+            return Ok(out);
+        };
+
+        let mut key_columns = Vec::with_capacity(params.key_selectors.len());
+        for selector in &params.key_selectors {
+            let s = selector.evaluate(&df, &state.in_memory_exec_state).await?;
+            key_columns.push(s.into_column());
+        }
+        let keys = DataFrame::new_with_broadcast_len(key_columns, df.height())?;
+        let hash_keys = HashKeys::from_df(&keys, params.random_state, true, false);
+        let all_rows = (0..df.height() as IdxSize).collect::<Vec<_>>();
+        let mut group_idxs = Vec::with_capacity(df.height());
+        unsafe { grouper.insert_keys_subset(&hash_keys, &all_rows, Some(&mut group_idxs)) };
+
+        // New groups get the next index, in order of their first row.
+        let mut rows_per_group: PlIndexMap<IdxSize, Vec<IdxSize>> = PlIndexMap::default();
+        for (row, g) in group_idxs.iter().enumerate() {
+            rows_per_group.entry(*g).or_default().push(row as IdxSize);
+        }
+        for (g, rows) in rows_per_group {
+            if g as usize == self.groups.len() {
+                self.groups.push(GroupState {
+                    key: keys.slice(rows[0] as i64, 1),
+                    ..Default::default()
+                });
+            }
+            let group = &mut self.groups[g as usize];
+            let group_df = unsafe { df.take_slice_unchecked(&rows) };
+            let group_times = rows.iter().map(|r| times[*r as usize]).collect();
+            params.append(group, group_df, group_times, true)?;
+            self.keyed_output
+                .extend(params.flush(group, &mut self.reductions, false)?);
+        }
+        Ok(out)
+    }
+
+    /// Emits all windows that are left after the input is finished.
+    fn finish(&mut self) -> PolarsResult<Vec<DataFrame>> {
+        let mut out = core::mem::take(&mut self.keyed_output);
+        for group in &mut self.groups {
+            out.extend(self.params.flush(group, &mut self.reductions, true)?);
+        }
+        self.groups.clear();
+
+        if self.grouper.is_none() || out.is_empty() {
+            return Ok(out);
+        }
+
+        // Order by key like the in-memory engine, the windows of a key are
+        // already ordered by time.
+        let df = accumulate_dataframes_vertical_unchecked(out);
+        let keys = &df.get_columns()[..self.params.key_selectors.len()];
+        let encoded = encode_rows_vertical_par_unordered(keys)?
+            .rechunk()
+            .into_owned();
+        let idx = encoded.arg_sort(SortOptions {
+            maintain_order: true,
+            ..Default::default()
+        });
+        Ok(vec![unsafe { df.take_unchecked(&idx) }])
+    }
+
+    async fn send(
+        &mut self,
+        send: &mut Sender<Morsel>,
+        dfs: Vec<DataFrame>,
+        source_token: &SourceToken,
+    ) -> bool {
+        if dfs.is_empty() {
+            return true;
+        }
+        let df = accumulate_dataframes_vertical_unchecked(dfs);
+        let morsel = Morsel::new(df, self.seq, source_token.clone());
+        self.seq = self.seq.successor();
+        send.send(morsel).await.is_ok()
+    }
+}
+
+impl ComputeNode for DynamicGroupByNode {
+    fn name(&self) -> &str {
+        match self.params.kind {
+            WindowKind::Dynamic(_) => "group-by-dynamic",
+            WindowKind::Rolling(_) => "rolling-group-by",
+        }
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        _state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 1 && send.len() == 1);
+
+        if send[0] == PortState::Done || (recv[0] == PortState::Done && self.flushed) {
+            self.groups.clear();
+            recv[0] = PortState::Done;
+            send[0] = PortState::Done;
+        } else if recv[0] == PortState::Done {
+            // We still have to emit the windows that are left.
+            send[0] = PortState::Ready;
+        } else {
+            recv.swap_with_slice(send);
+        }
+        Ok(())
+    }
+
+    fn memory_usage(&self) -> Option<usize> {
+        let buffered: usize = self.groups.iter().map(|g| g.estimated_size()).sum();
+        let output: usize = self.keyed_output.iter().map(|df| df.estimated_size()).sum();
+        Some(buffered + output)
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(recv_ports.len() == 1 && send_ports.len() == 1);
+        let recv = recv_ports[0].take().map(|p| p.serial());
+        let mut send = send_ports[0].take().unwrap().serial();
+
+        join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+            let Some(mut recv) = recv else {
+                let out = self.finish()?;
+                self.send(&mut send, out, &SourceToken::new()).await;
+                self.flushed = true;
+                return Ok(());
+            };
+
+            while let Ok(morsel) = recv.recv().await {
+                let (df, _seq, source_token, _wait_token) = morsel.into_inner();
+                let out = self.process(df, state).await?;
+                if !self.send(&mut send, out, &source_token).await {
+                    break;
+                }
+            }
+            Ok(())
+        }));
+    }
+}
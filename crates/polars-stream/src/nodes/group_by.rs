@@ -1,33 +1,105 @@
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use polars_core::POOL;
-use polars_core::prelude::{IntoColumn, PlHashSet, PlRandomState};
+use polars_core::prelude::{Column, IntoColumn, PlHashSet, PlRandomState, Series};
 use polars_core::schema::Schema;
 use polars_core::utils::accumulate_dataframes_vertical_unchecked;
 use polars_expr::groups::Grouper;
 use polars_expr::hash_keys::HashKeys;
 use polars_expr::hot_groups::{HotGrouper, new_hash_hot_grouper};
 use polars_expr::reduce::GroupedReduction;
-use polars_utils::IdxSize;
 use polars_utils::cardinality_sketch::CardinalitySketch;
 use polars_utils::hashing::HashPartitioner;
 use polars_utils::itertools::Itertools;
 use polars_utils::pl_str::PlSmallStr;
 use polars_utils::sparse_init_vec::SparseInitVec;
+use polars_utils::{IdxSize, format_pl_smallstr};
 use rayon::prelude::*;
 
 use super::compute_node_prelude::*;
 use crate::async_executor;
-use crate::async_primitives::connector::Receiver;
+use crate::async_primitives::connector::{Receiver, Sender};
+use crate::async_primitives::wait_group::WaitGroup;
+use crate::execute::MemoryReservation;
 use crate::expression::StreamExpr;
-use crate::morsel::get_ideal_morsel_size;
+use crate::morsel::{SourceToken, get_ideal_morsel_size};
 use crate::nodes::in_memory_source::InMemorySourceNode;
+use crate::utils::spill::{LazySpillDir, MaybeSpilled, SpillDir, can_spill, get_spill_threshold};
 
 #[cfg(debug_assertions)]
 const DEFAULT_HOT_TABLE_SIZE: usize = 4;
 #[cfg(not(debug_assertions))]
 const DEFAULT_HOT_TABLE_SIZE: usize = 4096;
 
+const SPILL_SEQ_NAME: PlSmallStr = PlSmallStr::from_static("__POLARS_GB_SEQ");
+
+/// Decides when the group by starts spilling and where to.
+struct GroupBySpillState {
+    threshold: usize,
+    // An estimate of the memory used by the cold morsels and pre-aggregates.
+    buffered_bytes: AtomicUsize,
+    active: AtomicBool,
+    dir: LazySpillDir,
+    // What we buffer in memory, as accounted against the memory budget.
+    memory: Option<MemoryReservation>,
+    // If all reductions support it we spill the keys and reduction states of
+    // the pre-aggregated groups instead of the raw rows, these are the number
+    // of state columns of each reduction.
+    state_widths: Option<Vec<usize>>,
+}
+
+/// Turns the keys back into columns, in the same order as the keys.
+fn hash_keys_to_df(
+    keys: &HashKeys,
+    grouper_template: &dyn Grouper,
+    key_schema: &Schema,
+) -> DataFrame {
+    let mut grouper = grouper_template.new_empty();
+    let subset = (0..keys.len() as IdxSize).collect_vec();
+    let mut group_idxs = Vec::with_capacity(keys.len());
+    unsafe {
+        grouper.insert_keys_subset(keys, &subset, Some(&mut group_idxs));
+        grouper
+            .get_keys_in_group_order(key_schema)
+            .take_slice_unchecked_impl(&group_idxs, false)
+    }
+}
+
+/// Renames the keys to not clash with the other columns we spill.
+fn spill_key_columns(keys: DataFrame) -> Vec<Column> {
+    keys.take_columns()
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| c.with_name(format_pl_smallstr!("__POLARS_GB_KEY_{i}")))
+        .collect_vec()
+}
+
+/// Creates the frame we write to disk when spilling rows: the keys, the
+/// payload and the sequence id of each row.
+fn spill_frame(keys: DataFrame, payload: DataFrame, seq: u64) -> DataFrame {
+    let height = payload.height();
+    let mut columns = spill_key_columns(keys);
+    columns.extend(payload.take_columns());
+    columns.push(Column::new_scalar(SPILL_SEQ_NAME, seq.into(), height));
+    unsafe { DataFrame::new_no_checks(height, columns) }
+}
+
+/// Creates the frame we write to disk when spilling pre-aggregated groups:
+/// the keys followed by the state columns of each reduction.
+fn state_frame(keys: DataFrame, states: Vec<Vec<Series>>) -> DataFrame {
+    let height = keys.height();
+    let mut columns = spill_key_columns(keys);
+    for (i, state) in states.into_iter().enumerate() {
+        for (j, s) in state.into_iter().enumerate() {
+            let name = format_pl_smallstr!("__POLARS_GB_STATE_{i}_{j}");
+            columns.push(s.with_name(name).into_column());
+        }
+    }
+    unsafe { DataFrame::new_no_checks(height, columns) }
+}
+
 struct LocalGroupBySinkState {
     hot_grouper: Box<dyn HotGrouper>,
     hot_grouped_reductions: Vec<Box<dyn GroupedReduction>>,
@@ -47,6 +119,22 @@ struct LocalGroupBySinkState {
     pre_aggs: Vec<(HashKeys, Vec<Box<dyn GroupedReduction>>)>,
     pre_agg_idxs_values_per_p: Vec<Vec<IdxSize>>,
     pre_agg_idxs_offsets_per_p: Vec<usize>,
+
+    // Estimated bytes added to the cold morsels and pre-aggregates since we
+    // last reported to the shared spill state.
+    buffered_bytes: usize,
+    // Estimated bytes of the cold morsels and pre-aggregates since we last
+    // spilled them.
+    unspilled_bytes: usize,
+    // Estimated bytes of the key and reduction state for a single group.
+    row_bytes: usize,
+
+    // Once spilling we bypass the hot table and write each row to its
+    // partition, buffering them per partition to avoid tiny files.
+    spilling: bool,
+    spill_buffer_per_p: Vec<Vec<DataFrame>>,
+    spill_buffer_rows_per_p: Vec<usize>,
+    spilled_per_p: Vec<Vec<MaybeSpilled>>,
 }
 
 impl LocalGroupBySinkState {
@@ -70,11 +158,21 @@ impl LocalGroupBySinkState {
             pre_aggs: Vec::new(),
             pre_agg_idxs_values_per_p: vec![Vec::new(); num_partitions],
             pre_agg_idxs_offsets_per_p: vec![0; num_partitions],
+
+            buffered_bytes: 0,
+            unspilled_bytes: 0,
+            row_bytes: 0,
+
+            spilling: false,
+            spill_buffer_per_p: vec![Vec::new(); num_partitions],
+            spill_buffer_rows_per_p: vec![0; num_partitions],
+            spilled_per_p: (0..num_partitions).map(|_| Vec::new()).collect(),
         }
     }
 
     fn flush_evictions(&mut self, partitioner: &HashPartitioner) {
         let hash_keys = self.hot_grouper.take_evicted_keys();
+        self.buffered_bytes += hash_keys.len() * self.row_bytes;
         let reductions = self
             .hot_grouped_reductions
             .iter_mut()
@@ -99,9 +197,165 @@ impl LocalGroupBySinkState {
             .extend(self.pre_agg_idxs_values_per_p.iter().map(|vp| vp.len()));
         self.pre_aggs.push((hash_keys, reductions));
     }
+
+    /// Moves the groups in the hot table into the pre-aggregates.
+    fn flush_hot_table(
+        &mut self,
+        partitioner: &HashPartitioner,
+        grouped_reductions_template: &[Box<dyn GroupedReduction>],
+    ) {
+        if self.hot_grouper.num_evictions() > 0 {
+            self.flush_evictions(partitioner);
+        }
+        let hot_keys = self.hot_grouper.keys();
+        let hot_reductions = core::mem::replace(
+            &mut self.hot_grouped_reductions,
+            grouped_reductions_template
+                .iter()
+                .map(|gr| gr.new_empty())
+                .collect(),
+        );
+        self.add_pre_agg(hot_keys, hot_reductions, partitioner);
+        self.hot_grouper = self.hot_grouper.new_empty(DEFAULT_HOT_TABLE_SIZE);
+    }
+
+    /// The offsets into cold_morsels[i] for partition p.
+    fn cold_morsel_idxs(&self, i: usize, p: usize) -> &[IdxSize] {
+        let num_partitions = self.morsel_idxs_values_per_p.len();
+        let start = self.morsel_idxs_offsets_per_p[i * num_partitions + p];
+        let stop = self.morsel_idxs_offsets_per_p[(i + 1) * num_partitions + p];
+        &self.morsel_idxs_values_per_p[p][start..stop]
+    }
+
+    /// The offsets into pre_aggs[i] for partition p.
+    fn pre_agg_idxs(&self, i: usize, p: usize) -> &[IdxSize] {
+        let num_partitions = self.pre_agg_idxs_values_per_p.len();
+        let start = self.pre_agg_idxs_offsets_per_p[i * num_partitions + p];
+        let stop = self.pre_agg_idxs_offsets_per_p[(i + 1) * num_partitions + p];
+        &self.pre_agg_idxs_values_per_p[p][start..stop]
+    }
+
+    fn clear_cold_morsels(&mut self) {
+        let num_partitions = self.spilled_per_p.len();
+        self.cold_morsels = Vec::new();
+        for idxs in &mut self.morsel_idxs_values_per_p {
+            *idxs = Vec::new();
+        }
+        self.morsel_idxs_offsets_per_p = vec![0; num_partitions];
+    }
+
+    /// Combines the cold morsels and pre-aggregates per partition and writes
+    /// the keys and reduction states of the resulting groups to disk.
+    fn spill_states(
+        &mut self,
+        partitioner: &HashPartitioner,
+        templates: &GroupByTemplates<'_>,
+        spill_dir: &SpillDir,
+    ) -> PolarsResult<()> {
+        self.flush_hot_table(partitioner, templates.grouped_reductions);
+
+        let num_partitions = self.spilled_per_p.len();
+        for p in 0..num_partitions {
+            let mut partition = GroupByPartition::new(templates);
+            for (i, morsel) in self.cold_morsels.iter().enumerate() {
+                unsafe {
+                    partition.insert_cold_morsel(
+                        morsel,
+                        self.cold_morsel_idxs(i, p),
+                        templates.grouped_reduction_cols,
+                    )?;
+                }
+            }
+            for (i, (keys, pre_aggs)) in self.pre_aggs.iter().enumerate() {
+                unsafe {
+                    partition.insert_pre_agg(keys, pre_aggs, self.pre_agg_idxs(i, p))?;
+                }
+            }
+            if partition.grouper.num_groups() == 0 {
+                continue;
+            }
+
+            let keys = partition
+                .grouper
+                .get_keys_in_group_order(templates.key_schema);
+            let states = partition
+                .grouped_reductions
+                .iter_mut()
+                .map(|r| r.take_state())
+                .try_collect_vec()?;
+            let mut df = state_frame(keys, states);
+            self.spilled_per_p[p].push(MaybeSpilled::Spilled(spill_dir.spill(&mut df)?));
+        }
+
+        self.clear_cold_morsels();
+        self.pre_aggs = Vec::new();
+        for idxs in &mut self.pre_agg_idxs_values_per_p {
+            *idxs = Vec::new();
+        }
+        self.pre_agg_idxs_offsets_per_p = vec![0; num_partitions];
+        Ok(())
+    }
+
+    /// Switches this local state to spilling rows. The hot table is moved into
+    /// the pre-aggregates and the cold morsels are written to disk.
+    fn start_spilling(
+        &mut self,
+        partitioner: &HashPartitioner,
+        templates: &GroupByTemplates<'_>,
+        spill_dir: &SpillDir,
+    ) -> PolarsResult<()> {
+        self.spilling = true;
+        self.flush_hot_table(partitioner, templates.grouped_reductions);
+
+        let num_partitions = self.spilled_per_p.len();
+        let cold_morsels = core::mem::take(&mut self.cold_morsels);
+        for (i, (seq, keys, df)) in cold_morsels.iter().enumerate() {
+            let keys = hash_keys_to_df(keys, templates.grouper, templates.key_schema);
+            let df = spill_frame(keys, df.clone(), *seq);
+            for p in 0..num_partitions {
+                let idxs = self.cold_morsel_idxs(i, p);
+                if !idxs.is_empty() {
+                    let p_df = unsafe { df.take_slice_unchecked_impl(idxs, false) };
+                    self.buffer_for_spill(p, p_df);
+                }
+            }
+        }
+        drop(cold_morsels);
+        self.clear_cold_morsels();
+
+        for p in 0..num_partitions {
+            self.flush_spill_buffer(p, spill_dir)?;
+        }
+        Ok(())
+    }
+
+    fn buffer_for_spill(&mut self, p: usize, df: DataFrame) {
+        self.spill_buffer_rows_per_p[p] += df.height();
+        self.spill_buffer_per_p[p].push(df);
+    }
+
+    fn flush_spill_buffer(&mut self, p: usize, spill_dir: &SpillDir) -> PolarsResult<()> {
+        if self.spill_buffer_per_p[p].is_empty() {
+            return Ok(());
+        }
+        let dfs = core::mem::take(&mut self.spill_buffer_per_p[p]);
+        let mut df = accumulate_dataframes_vertical_unchecked(dfs);
+        self.spilled_per_p[p].push(MaybeSpilled::Spilled(spill_dir.spill(&mut df)?));
+        self.spill_buffer_rows_per_p[p] = 0;
+        Ok(())
+    }
+}
+
+/// The templates and schemas used by everything that builds groups.
+struct GroupByTemplates<'a> {
+    key_schema: &'a Schema,
+    grouper: &'a dyn Grouper,
+    grouped_reductions: &'a [Box<dyn GroupedReduction>],
+    grouped_reduction_cols: &'a [PlSmallStr],
 }
 
 struct GroupBySinkState {
+    key_schema: Arc<Schema>,
     key_selectors: Vec<StreamExpr>,
     grouper: Box<dyn Grouper>,
     uniq_grouped_reduction_cols: Vec<PlSmallStr>,
@@ -110,6 +364,10 @@ struct GroupBySinkState {
     locals: Vec<LocalGroupBySinkState>,
     random_state: PlRandomState,
     partitioner: HashPartitioner,
+    spill: GroupBySpillState,
+    // Order-sensitive reductions can't have the rows of a group split between
+    // the hot table and the cold morsels, so we treat all rows as cold.
+    skip_hot_table: bool,
 }
 
 impl GroupBySinkState {
@@ -120,16 +378,29 @@ impl GroupBySinkState {
         state: &'s StreamingExecutionState,
         join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
     ) {
+        self.spill
+            .memory
+            .get_or_insert_with(|| state.memory_manager.reservation(state.node_key, "group-by"));
+        let num_locals = self.locals.len();
         for (mut recv, local) in receivers.into_iter().zip(&mut self.locals) {
             let key_selectors = &self.key_selectors;
             let uniq_grouped_reduction_cols = &self.uniq_grouped_reduction_cols;
             let grouped_reduction_cols = &self.grouped_reduction_cols;
             let random_state = &self.random_state;
             let partitioner = self.partitioner.clone();
+            let templates = GroupByTemplates {
+                key_schema: &self.key_schema,
+                grouper: &*self.grouper,
+                grouped_reductions: &self.grouped_reductions,
+                grouped_reduction_cols: &self.grouped_reduction_cols,
+            };
+            let spill = &self.spill;
+            let skip_hot_table = self.skip_hot_table;
             join_handles.push(scope.spawn_task(TaskPriority::High, async move {
                 let mut hot_idxs = Vec::new();
                 let mut hot_group_idxs = Vec::new();
                 let mut cold_idxs = Vec::new();
+                let mut spill_idxs_per_p = vec![Vec::new(); partitioner.num_partitions()];
                 while let Ok(morsel) = recv.recv().await {
                     // Compute hot group indices from key.
                     let seq = morsel.seq().to_u64();
@@ -141,16 +412,63 @@ impl GroupBySinkState {
                     }
                     let keys = DataFrame::new_with_broadcast_len(key_columns, df.height())?;
                     let hash_keys = HashKeys::from_df(&keys, *random_state, true, false);
+                    local.row_bytes = keys.estimated_size() / keys.height().max(1)
+                        + grouped_reduction_cols.len() * size_of::<u64>();
+
+                    if spill.state_widths.is_none()
+                        && !local.spilling
+                        && spill.active.load(Ordering::Relaxed)
+                    {
+                        local.start_spilling(&partitioner, &templates, &*spill.dir.get()?)?;
+
+                        // The cold morsels are on disk now, what remains in
+                        // memory is bounded by the size of the hot table.
+                        let spilled_bytes = core::mem::take(&mut local.unspilled_bytes);
+                        spill
+                            .buffered_bytes
+                            .fetch_sub(spilled_bytes, Ordering::Relaxed);
+                        spill.memory.as_ref().unwrap().shrink(spilled_bytes);
+                    }
+
+                    // When spilling rows we write every row to its partition.
+                    if local.spilling {
+                        df = df._select_impl(uniq_grouped_reduction_cols).unwrap();
+                        let df = spill_frame(keys, df, seq);
+                        for idxs in &mut spill_idxs_per_p {
+                            idxs.clear();
+                        }
+                        hash_keys.gen_idxs_per_partition(
+                            &partitioner,
+                            &mut spill_idxs_per_p,
+                            &mut local.sketch_per_p,
+                            true,
+                        );
+                        for (p, idxs) in spill_idxs_per_p.iter().enumerate() {
+                            if idxs.is_empty() {
+                                continue;
+                            }
+                            let p_df = unsafe { df.take_slice_unchecked_impl(idxs, false) };
+                            local.buffer_for_spill(p, p_df);
+                            if local.spill_buffer_rows_per_p[p] >= get_ideal_morsel_size() {
+                                local.flush_spill_buffer(p, &*spill.dir.get()?)?;
+                            }
+                        }
+                        continue;
+                    }
 
                     hot_idxs.clear();
                     hot_group_idxs.clear();
                     cold_idxs.clear();
-                    local.hot_grouper.insert_keys(
-                        &hash_keys,
-                        &mut hot_idxs,
-                        &mut hot_group_idxs,
-                        &mut cold_idxs,
-                    );
+                    if skip_hot_table {
+                        cold_idxs.extend(0..df.height() as IdxSize);
+                    } else {
+                        local.hot_grouper.insert_keys(
+                            &hash_keys,
+                            &mut hot_idxs,
+                            &mut hot_group_idxs,
+                            &mut cold_idxs,
+                        );
+                    }
 
                     // Drop columns not used for reductions (key-only columns).
                     if uniq_grouped_reduction_cols.len() < grouped_reduction_cols.len() {
@@ -191,6 +509,8 @@ impl GroupBySinkState {
                             local
                                 .morsel_idxs_offsets_per_p
                                 .extend(local.morsel_idxs_values_per_p.iter().map(|vp| vp.len()));
+                            local.buffered_bytes +=
+                                cold_df.estimated_size() + cold_idxs.len() * local.row_bytes;
                             local.cold_morsels.push((seq, cold_keys, cold_df));
                         }
                     }
@@ -199,6 +519,42 @@ impl GroupBySinkState {
                     if local.hot_grouper.num_evictions() >= get_ideal_morsel_size() {
                         local.flush_evictions(&partitioner);
                     }
+
+                    // Start spilling if we're over budget, either our own or
+                    // that of the whole query.
+                    let new_bytes = core::mem::take(&mut local.buffered_bytes);
+                    local.unspilled_bytes += new_bytes;
+                    let total =
+                        spill.buffered_bytes.fetch_add(new_bytes, Ordering::Relaxed) + new_bytes;
+                    let memory = spill.memory.as_ref().unwrap();
+                    let grown = memory.grow(new_bytes);
+                    let spill_now = if spill.threshold != usize::MAX {
+                        total > spill.threshold || memory.should_spill() || grown.is_err()
+                    } else {
+                        grown?;
+                        false
+                    };
+                    if spill_now {
+                        spill.active.store(true, Ordering::Relaxed);
+                    }
+
+                    // When spilling reduction states each local spills its own
+                    // share of the budget, or a morsel's worth of groups if
+                    // the query as a whole is short on memory.
+                    if spill.state_widths.is_some()
+                        && spill.active.load(Ordering::Relaxed)
+                        && (local.unspilled_bytes >= spill.threshold / num_locals
+                            || (memory.should_spill()
+                                && local.unspilled_bytes
+                                    >= get_ideal_morsel_size() * local.row_bytes))
+                    {
+                        local.spill_states(&partitioner, &templates, &*spill.dir.get()?)?;
+                        let spilled_bytes = core::mem::take(&mut local.unspilled_bytes);
+                        spill
+                            .buffered_bytes
+                            .fetch_sub(spilled_bytes, Ordering::Relaxed);
+                        memory.shrink(spilled_bytes);
+                    }
                 }
                 Ok(())
             }));
@@ -212,14 +568,7 @@ impl GroupBySinkState {
                 .as_mut_slice()
                 .into_par_iter()
                 .with_max_len(1)
-                .for_each(|l| {
-                    if l.hot_grouper.num_evictions() > 0 {
-                        l.flush_evictions(&self.partitioner);
-                    }
-                    let hot_keys = l.hot_grouper.keys();
-                    let hot_reductions = core::mem::take(&mut l.hot_grouped_reductions);
-                    l.add_pre_agg(hot_keys, hot_reductions, &self.partitioner);
-                });
+                .for_each(|l| l.flush_hot_table(&self.partitioner, &self.grouped_reductions));
         });
 
         // To reduce maximum memory usage we want to drop the morsels
@@ -245,9 +594,13 @@ impl GroupBySinkState {
         let output_per_partition: SparseInitVec<GroupByPartition> =
             SparseInitVec::with_capacity(num_partitions);
         let locals = &self.locals;
-        let grouper_template = &self.grouper;
-        let grouped_reductions_template = &self.grouped_reductions;
-        let grouped_reduction_cols = &self.grouped_reduction_cols;
+        let templates = GroupByTemplates {
+            key_schema: &self.key_schema,
+            grouper: &*self.grouper,
+            grouped_reductions: &self.grouped_reductions,
+            grouped_reduction_cols: &self.grouped_reduction_cols,
+        };
+        let templates = &templates;
 
         async_executor::task_scope(|s| {
             // Wrap in outer Arc to move to each thread, performing the
@@ -275,19 +628,11 @@ impl GroupBySinkState {
 
                     // Allocate grouper and reductions.
                     let est_num_groups = sketch.estimate() * 5 / 4;
-                    let mut p_grouper = grouper_template.new_empty();
-                    let mut p_reductions = grouped_reductions_template
-                        .iter()
-                        .map(|gr| gr.new_empty())
-                        .collect_vec();
-                    p_grouper.reserve(est_num_groups);
-                    for r in &mut p_reductions {
-                        r.reserve(est_num_groups);
-                    }
+                    let mut partition = GroupByPartition::new(templates);
+                    partition.reserve(est_num_groups);
 
                     // Insert morsels.
                     let mut skip_drop_attempt = false;
-                    let mut group_idxs = Vec::new();
                     for (l, l_morsels) in locals.iter().zip(morsels_per_local) {
                         // Try to help with dropping.
                         if !skip_drop_attempt {
@@ -295,31 +640,12 @@ impl GroupBySinkState {
                         }
 
                         for (i, morsel) in l_morsels.iter().enumerate() {
-                            let (seq_id, keys, cols) = morsel;
                             unsafe {
-                                let p_morsel_idxs_start =
-                                    l.morsel_idxs_offsets_per_p[i * num_partitions + p];
-                                let p_morsel_idxs_stop =
-                                    l.morsel_idxs_offsets_per_p[(i + 1) * num_partitions + p];
-                                let p_morsel_idxs = &l.morsel_idxs_values_per_p[p]
-                                    [p_morsel_idxs_start..p_morsel_idxs_stop];
-
-                                group_idxs.clear();
-                                p_grouper.insert_keys_subset(
-                                    keys,
-                                    p_morsel_idxs,
-                                    Some(&mut group_idxs),
-                                );
-                                for (c, r) in grouped_reduction_cols.iter().zip(&mut p_reductions) {
-                                    let values = cols.column(c.as_str()).unwrap();
-                                    r.resize(p_grouper.num_groups());
-                                    r.update_groups_subset(
-                                        values,
-                                        p_morsel_idxs,
-                                        &group_idxs,
-                                        *seq_id,
-                                    )?;
-                                }
+                                partition.insert_cold_morsel(
+                                    morsel,
+                                    l.cold_morsel_idxs(i, p),
+                                    templates.grouped_reduction_cols,
+                                )?;
                             }
                         }
 
@@ -341,26 +667,9 @@ impl GroupBySinkState {
                             drop(drop_q_recv.try_recv());
                         }
 
-                        for (i, key_pre_aggs) in l_pre_aggs.iter().enumerate() {
-                            let (keys, pre_aggs) = key_pre_aggs;
+                        for (i, (keys, pre_aggs)) in l_pre_aggs.iter().enumerate() {
                             unsafe {
-                                let p_pre_agg_idxs_start =
-                                    l.pre_agg_idxs_offsets_per_p[i * num_partitions + p];
-                                let p_pre_agg_idxs_stop =
-                                    l.pre_agg_idxs_offsets_per_p[(i + 1) * num_partitions + p];
-                                let p_pre_agg_idxs = &l.pre_agg_idxs_values_per_p[p]
-                                    [p_pre_agg_idxs_start..p_pre_agg_idxs_stop];
-
-                                group_idxs.clear();
-                                p_grouper.insert_keys_subset(
-                                    keys,
-                                    p_pre_agg_idxs,
-                                    Some(&mut group_idxs),
-                                );
-                                for (pre_agg, r) in pre_aggs.iter().zip(&mut p_reductions) {
-                                    r.resize(p_grouper.num_groups());
-                                    r.combine_subset(&**pre_agg, p_pre_agg_idxs, &group_idxs)?;
-                                }
+                                partition.insert_pre_agg(keys, pre_aggs, l.pre_agg_idxs(i, p))?;
                             }
                         }
 
@@ -381,16 +690,7 @@ impl GroupBySinkState {
                         drop(to_drop);
                     }
 
-                    output_per_partition
-                        .try_set(
-                            p,
-                            GroupByPartition {
-                                grouper: p_grouper,
-                                grouped_reductions: p_reductions,
-                            },
-                        )
-                        .ok()
-                        .unwrap();
+                    output_per_partition.try_set(p, partition).ok().unwrap();
 
                     PolarsResult::Ok(())
                 }));
@@ -423,14 +723,337 @@ impl GroupBySinkState {
 
         Ok(output_per_partition.try_assume_init().ok().unwrap())
     }
+
+    /// Turns the sink into the source of our output.
+    ///
+    /// If nothing got spilled all partitions are combined in parallel in
+    /// memory, otherwise each partition gets combined once it's its turn to be
+    /// sent, so only one partition of groups is in memory at a time.
+    fn into_source(mut self, output_schema: &Arc<Schema>) -> PolarsResult<GroupByState> {
+        let spilled = self
+            .locals
+            .iter()
+            .any(|l| l.spilling || l.spilled_per_p.iter().any(|s| !s.is_empty()));
+        if !spilled {
+            let partitions = self.combine_locals()?;
+            let dfs = POOL.install(|| {
+                partitions
+                    .into_par_iter()
+                    .map(|p| p.into_df(&self.key_schema, output_schema))
+                    .collect::<Result<Vec<_>, _>>()
+            })?;
+
+            // The buffered data is gone, the output is owned by the source.
+            if let Some(memory) = &self.spill.memory {
+                memory.clear();
+            }
+            let df = accumulate_dataframes_vertical_unchecked(dfs);
+            let source = InMemorySourceNode::new(Arc::new(df), MorselSeq::new(0));
+            return Ok(GroupByState::Source(source));
+        }
+
+        // Collect what was spilled per partition, including the rows that are
+        // still buffered.
+        let num_partitions = self.locals[0].sketch_per_p.len();
+        let mut spilled_per_p = (0..num_partitions).map(|_| Vec::new()).collect_vec();
+        for l in &mut self.locals {
+            l.flush_hot_table(&self.partitioner, &self.grouped_reductions);
+            for (p, p_spilled) in spilled_per_p.iter_mut().enumerate() {
+                p_spilled.append(&mut l.spilled_per_p[p]);
+                let buffer = core::mem::take(&mut l.spill_buffer_per_p[p]);
+                p_spilled.extend(buffer.into_iter().map(MaybeSpilled::InMemory));
+            }
+        }
+
+        Ok(GroupByState::SpilledSource(SpilledGroupBySource {
+            spilled_per_p,
+            next_partition: 0,
+            pending: VecDeque::new(),
+            seq: MorselSeq::new(0),
+            spill_dir: self.spill.dir.into_inner(),
+            memory: self.spill.memory,
+            locals: self.locals,
+            key_schema: self.key_schema,
+            output_schema: output_schema.clone(),
+            grouper: self.grouper,
+            grouped_reductions: self.grouped_reductions,
+            grouped_reduction_cols: self.grouped_reduction_cols,
+            random_state: self.random_state,
+            state_widths: self.spill.state_widths,
+        }))
+    }
 }
 
+/// Sends the output of a group by that spilled, one partition at a time. The
+/// spilled data of a partition is loaded and combined with what was still
+/// buffered in memory right before its groups are sent.
+struct SpilledGroupBySource {
+    spilled_per_p: Vec<Vec<MaybeSpilled>>,
+    next_partition: usize,
+    /// Groups that still have to be sent.
+    pending: VecDeque<DataFrame>,
+    seq: MorselSeq,
+    // Keeps the spilled files alive until we're done.
+    spill_dir: Option<Arc<SpillDir>>,
+    // Accounts for the data of the locals that is still in memory.
+    memory: Option<MemoryReservation>,
+
+    locals: Vec<LocalGroupBySinkState>,
+    key_schema: Arc<Schema>,
+    output_schema: Arc<Schema>,
+    grouper: Box<dyn Grouper>,
+    grouped_reductions: Vec<Box<dyn GroupedReduction>>,
+    grouped_reduction_cols: Vec<PlSmallStr>,
+    random_state: PlRandomState,
+    state_widths: Option<Vec<usize>>,
+}
+
+impl SpilledGroupBySource {
+    fn is_exhausted(&self) -> bool {
+        self.pending.is_empty() && self.next_partition == self.spilled_per_p.len()
+    }
+
+    fn combine_partition(&mut self, p: usize) -> PolarsResult<DataFrame> {
+        let templates = GroupByTemplates {
+            key_schema: &self.key_schema,
+            grouper: &*self.grouper,
+            grouped_reductions: &self.grouped_reductions,
+            grouped_reduction_cols: &self.grouped_reduction_cols,
+        };
+        let mut partition = GroupByPartition::new(&templates);
+        for l in &self.locals {
+            for (i, morsel) in l.cold_morsels.iter().enumerate() {
+                unsafe {
+                    partition.insert_cold_morsel(
+                        morsel,
+                        l.cold_morsel_idxs(i, p),
+                        &self.grouped_reduction_cols,
+                    )?;
+                }
+            }
+            for (i, (keys, pre_aggs)) in l.pre_aggs.iter().enumerate() {
+                unsafe {
+                    partition.insert_pre_agg(keys, pre_aggs, l.pre_agg_idxs(i, p))?;
+                }
+            }
+        }
+
+        // Load the spilled files one by one so we only hold the groups.
+        let num_keys = self.key_schema.len();
+        for spilled in core::mem::take(&mut self.spilled_per_p[p]) {
+            let df = spilled.load()?;
+            match &self.state_widths {
+                Some(state_widths) => partition.insert_spilled_states(
+                    df,
+                    num_keys,
+                    state_widths,
+                    self.random_state,
+                )?,
+                None => partition.insert_spilled_rows(
+                    df,
+                    num_keys,
+                    &self.grouped_reduction_cols,
+                    self.random_state,
+                )?,
+            }
+        }
+
+        partition.into_df(&self.key_schema, &self.output_schema)
+    }
+
+    /// Fills the pending queue with the groups of the next partition(s) that
+    /// have any. Returns false if there is nothing left to send.
+    fn fill_pending(&mut self) -> PolarsResult<bool> {
+        while self.pending.is_empty() && self.next_partition < self.spilled_per_p.len() {
+            let df = self.combine_partition(self.next_partition)?;
+            self.next_partition += 1;
+            if self.next_partition == self.spilled_per_p.len() {
+                // Free what was buffered in memory and the spill directory.
+                self.locals = Vec::new();
+                self.spill_dir = None;
+                if let Some(memory) = &self.memory {
+                    memory.clear();
+                }
+            }
+
+            let morsel_size = get_ideal_morsel_size();
+            for offset in (0..df.height()).step_by(morsel_size) {
+                self.pending.push_back(df.slice(offset as i64, morsel_size));
+            }
+        }
+        Ok(!self.pending.is_empty())
+    }
+
+    async fn send_morsels(&mut self, mut send: Sender<Morsel>) -> PolarsResult<()> {
+        let source_token = SourceToken::new();
+        let wait_group = WaitGroup::default();
+        while self.fill_pending()? {
+            let df = self.pending.pop_front().unwrap();
+            let mut morsel = Morsel::new(df, self.seq, source_token.clone());
+            self.seq = self.seq.successor();
+            morsel.set_consume_token(wait_group.token());
+            if send.send(morsel).await.is_err() {
+                break;
+            }
+
+            wait_group.wait().await;
+            if source_token.stop_requested() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The groups of a single partition, built up from the data of all locals.
 struct GroupByPartition {
     grouper: Box<dyn Grouper>,
     grouped_reductions: Vec<Box<dyn GroupedReduction>>,
+    group_idxs: Vec<IdxSize>,
 }
 
 impl GroupByPartition {
+    fn new(templates: &GroupByTemplates<'_>) -> Self {
+        Self {
+            grouper: templates.grouper.new_empty(),
+            grouped_reductions: templates
+                .grouped_reductions
+                .iter()
+                .map(|gr| gr.new_empty())
+                .collect(),
+            group_idxs: Vec::new(),
+        }
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.grouper.reserve(additional);
+        for r in &mut self.grouped_reductions {
+            r.reserve(additional);
+        }
+    }
+
+    /// Inserts the rows of a cold morsel at the given offsets.
+    ///
+    /// # Safety
+    /// The offsets are in-bounds for the morsel.
+    unsafe fn insert_cold_morsel(
+        &mut self,
+        morsel: &(u64, HashKeys, DataFrame),
+        idxs: &[IdxSize],
+        grouped_reduction_cols: &[PlSmallStr],
+    ) -> PolarsResult<()> {
+        let (seq_id, keys, cols) = morsel;
+        self.group_idxs.clear();
+        unsafe {
+            self.grouper
+                .insert_keys_subset(keys, idxs, Some(&mut self.group_idxs));
+            for (c, r) in grouped_reduction_cols
+                .iter()
+                .zip(&mut self.grouped_reductions)
+            {
+                let values = cols.column(c.as_str()).unwrap();
+                r.resize(self.grouper.num_groups());
+                r.update_groups_subset(values, idxs, &self.group_idxs, *seq_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Combines the pre-aggregated groups at the given offsets.
+    ///
+    /// # Safety
+    /// The offsets are in-bounds for the pre-aggregates.
+    unsafe fn insert_pre_agg(
+        &mut self,
+        keys: &HashKeys,
+        pre_aggs: &[Box<dyn GroupedReduction>],
+        idxs: &[IdxSize],
+    ) -> PolarsResult<()> {
+        self.group_idxs.clear();
+        unsafe {
+            self.grouper
+                .insert_keys_subset(keys, idxs, Some(&mut self.group_idxs));
+            for (pre_agg, r) in pre_aggs.iter().zip(&mut self.grouped_reductions) {
+                r.resize(self.grouper.num_groups());
+                r.combine_subset(&**pre_agg, idxs, &self.group_idxs)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Inserts rows spilled by [`spill_frame`].
+    fn insert_spilled_rows(
+        &mut self,
+        mut df: DataFrame,
+        num_keys: usize,
+        grouped_reduction_cols: &[PlSmallStr],
+        random_state: PlRandomState,
+    ) -> PolarsResult<()> {
+        df.rechunk_mut();
+        let keys = df.select_by_range(0..num_keys)?;
+        let keys = HashKeys::from_df(&keys, random_state, true, false);
+        let seqs = df.column(&SPILL_SEQ_NAME)?.as_materialized_series();
+        let seqs = seqs.u64()?.rechunk();
+        let seqs = seqs.cont_slice()?;
+
+        // Update the reductions per run of rows with the same sequence id.
+        let mut start = 0;
+        while start < seqs.len() {
+            let seq_id = seqs[start];
+            let len = seqs[start..].iter().take_while(|s| **s == seq_id).count();
+            let subset = (start as IdxSize..(start + len) as IdxSize).collect_vec();
+            unsafe {
+                self.group_idxs.clear();
+                self.grouper
+                    .insert_keys_subset(&keys, &subset, Some(&mut self.group_idxs));
+                for (c, r) in grouped_reduction_cols
+                    .iter()
+                    .zip(&mut self.grouped_reductions)
+                {
+                    let values = df.column(c.as_str()).unwrap();
+                    r.resize(self.grouper.num_groups());
+                    r.update_groups_subset(values, &subset, &self.group_idxs, seq_id)?;
+                }
+            }
+            start += len;
+        }
+        Ok(())
+    }
+
+    /// Combines the groups spilled by [`state_frame`].
+    fn insert_spilled_states(
+        &mut self,
+        mut df: DataFrame,
+        num_keys: usize,
+        state_widths: &[usize],
+        random_state: PlRandomState,
+    ) -> PolarsResult<()> {
+        df.rechunk_mut();
+        let keys = df.select_by_range(0..num_keys)?;
+        let keys = HashKeys::from_df(&keys, random_state, true, false);
+        let subset = (0..df.height() as IdxSize).collect_vec();
+        self.group_idxs.clear();
+        unsafe {
+            self.grouper
+                .insert_keys_subset(&keys, &subset, Some(&mut self.group_idxs));
+        }
+
+        let mut state_columns = df.get_columns()[num_keys..].iter();
+        for (r, width) in self.grouped_reductions.iter_mut().zip(state_widths) {
+            let state = state_columns
+                .by_ref()
+                .take(*width)
+                .map(|c| c.as_materialized_series().clone())
+                .collect_vec();
+            let other = r.new_from_state(&state)?;
+            r.resize(self.grouper.num_groups());
+            unsafe {
+                r.combine_subset(&*other, &subset, &self.group_idxs)?;
+            }
+        }
+        Ok(())
+    }
+
     fn into_df(self, key_schema: &Schema, output_schema: &Schema) -> PolarsResult<DataFrame> {
         let mut out = self.grouper.get_keys_in_group_order(key_schema);
         let out_names = output_schema.iter_names().skip(out.width());
@@ -446,18 +1069,19 @@ impl GroupByPartition {
 enum GroupByState {
     Sink(GroupBySinkState),
     Source(InMemorySourceNode),
+    SpilledSource(SpilledGroupBySource),
     Done,
 }
 
 pub struct GroupByNode {
     state: GroupByState,
-    key_schema: Arc<Schema>,
     output_schema: Arc<Schema>,
 }
 
 impl GroupByNode {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
+        input_schema: &Schema,
         key_schema: Arc<Schema>,
         key_selectors: Vec<StreamExpr>,
         grouper: Box<dyn Grouper>,
@@ -489,8 +1113,32 @@ impl GroupByNode {
             })
             .collect();
         let partitioner = HashPartitioner::new(num_partitions, 0);
+        let threshold = if can_spill(&key_schema) && can_spill(input_schema) {
+            get_spill_threshold()
+        } else {
+            usize::MAX
+        };
+        let skip_hot_table = grouped_reductions.iter().any(|r| r.is_order_sensitive());
+        let state_widths = grouped_reductions
+            .iter()
+            .map(|r| {
+                if !r.has_spillable_state() {
+                    return None;
+                }
+                Some(r.new_empty().take_state().ok()?.len())
+            })
+            .collect();
+        let spill = GroupBySpillState {
+            threshold,
+            buffered_bytes: AtomicUsize::new(0),
+            active: AtomicBool::new(false),
+            dir: LazySpillDir::new("group_by"),
+            memory: None,
+            state_widths,
+        };
         Self {
             state: GroupByState::Sink(GroupBySinkState {
+                key_schema,
                 key_selectors,
                 grouped_reductions,
                 grouper,
@@ -499,8 +1147,9 @@ impl GroupByNode {
                 grouped_reduction_cols,
                 locals,
                 partitioner,
+                spill,
+                skip_hot_table,
             }),
-            key_schema,
             output_schema,
         }
     }
@@ -527,22 +1176,12 @@ impl ComputeNode for GroupByNode {
             },
             // Input is done, transition to being a source.
             GroupByState::Sink(_) if matches!(recv[0], PortState::Done) => {
-                let GroupByState::Sink(mut sink) =
+                let GroupByState::Sink(sink) =
                     core::mem::replace(&mut self.state, GroupByState::Done)
                 else {
                     unreachable!()
                 };
-                let partitions = sink.combine_locals()?;
-                let dfs = POOL.install(|| {
-                    partitions
-                        .into_par_iter()
-                        .map(|p| p.into_df(&self.key_schema, &self.output_schema))
-                        .collect::<Result<Vec<_>, _>>()
-                })?;
-
-                let df = accumulate_dataframes_vertical_unchecked(dfs);
-                let source = InMemorySourceNode::new(Arc::new(df), MorselSeq::new(0));
-                self.state = GroupByState::Source(source);
+                self.state = sink.into_source(&self.output_schema)?;
             },
             // Defer to source node implementation.
             GroupByState::Source(src) => {
@@ -551,6 +1190,11 @@ impl ComputeNode for GroupByNode {
                     self.state = GroupByState::Done;
                 }
             },
+            GroupByState::SpilledSource(src) => {
+                if src.is_exhausted() {
+                    self.state = GroupByState::Done;
+                }
+            },
             // Nothing to change.
             GroupByState::Done | GroupByState::Sink(_) => {},
         }
@@ -561,7 +1205,7 @@ impl ComputeNode for GroupByNode {
                 send[0] = PortState::Blocked;
                 recv[0] = PortState::Ready;
             },
-            GroupByState::Source(..) => {
+            GroupByState::Source(..) | GroupByState::SpilledSource(..) => {
                 recv[0] = PortState::Done;
                 send[0] = PortState::Ready;
             },
@@ -596,6 +1240,11 @@ impl ComputeNode for GroupByNode {
                 assert!(recv_ports[0].is_none());
                 source.spawn(scope, &mut [], send_ports, state, join_handles);
             },
+            GroupByState::SpilledSource(source) => {
+                assert!(recv_ports[0].is_none());
+                let send = send_ports[0].take().unwrap().serial();
+                join_handles.push(scope.spawn_task(TaskPriority::High, source.send_morsels(send)));
+            },
             GroupByState::Done => unreachable!(),
         }
     }
@@ -19,7 +19,7 @@ pub enum InMemoryMapNode {
 impl InMemoryMapNode {
     pub fn new(input_schema: Arc<Schema>, map: Arc<dyn DataFrameUdf>) -> Self {
         Self::Sink {
-            sink_node: InMemorySinkNode::new(input_schema),
+            sink_node: InMemorySinkNode::new_for_node(input_schema, "in-memory-map"),
             map,
         }
     }
@@ -76,6 +76,14 @@ impl ComputeNode for InMemoryMapNode {
         matches!(self, Self::Sink { .. })
     }
 
+    fn memory_usage(&self) -> Option<usize> {
+        match self {
+            Self::Sink { sink_node, .. } => sink_node.memory_usage(),
+            Self::Source(source_node) => source_node.memory_usage(),
+            Self::Done => Some(0),
+        }
+    }
+
     fn spawn<'env, 's>(
         &'env mut self,
         scope: &'s TaskScope<'s, 'env>,
@@ -5,18 +5,30 @@ use polars_core::schema::Schema;
 use polars_core::utils::accumulate_dataframes_vertical_unchecked;
 
 use super::compute_node_prelude::*;
+use crate::execute::MemoryReservation;
 use crate::utils::in_memory_linearize::linearize;
 
 pub struct InMemorySinkNode {
     morsels_per_pipe: Mutex<Vec<Vec<Morsel>>>,
     schema: Arc<Schema>,
+    // The name under which the buffered morsels count against the memory budget.
+    node_name: &'static str,
+    memory: Option<MemoryReservation>,
 }
 
 impl InMemorySinkNode {
     pub fn new(schema: Arc<Schema>) -> Self {
+        Self::new_for_node(schema, "in-memory-sink")
+    }
+
+    /// Creates a sink that is part of another node, whose name is reported if
+    /// the buffered morsels exceed the memory budget.
+    pub fn new_for_node(schema: Arc<Schema>, node_name: &'static str) -> Self {
         Self {
             morsels_per_pipe: Mutex::default(),
             schema,
+            node_name,
+            memory: None,
         }
     }
 }
@@ -47,23 +59,34 @@ impl ComputeNode for InMemorySinkNode {
         true
     }
 
+    fn memory_usage(&self) -> Option<usize> {
+        let morsels_per_pipe = self.morsels_per_pipe.lock();
+        let morsels = morsels_per_pipe.iter().flatten();
+        Some(morsels.map(|m| m.df().estimated_size()).sum())
+    }
+
     fn spawn<'env, 's>(
         &'env mut self,
         scope: &'s TaskScope<'s, 'env>,
         recv_ports: &mut [Option<RecvPort<'_>>],
         send_ports: &mut [Option<SendPort<'_>>],
-        _state: &'s StreamingExecutionState,
+        state: &'s StreamingExecutionState,
         join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
     ) {
         assert!(recv_ports.len() == 1 && send_ports.is_empty());
         let receivers = recv_ports[0].take().unwrap().parallel();
 
+        let node_name = self.node_name;
+        self.memory
+            .get_or_insert_with(|| state.memory_manager.reservation(state.node_key, node_name));
         for mut recv in receivers {
             let slf = &*self;
+            let memory = slf.memory.as_ref().unwrap();
             join_handles.push(scope.spawn_task(TaskPriority::High, async move {
                 let mut morsels = Vec::new();
                 while let Ok(mut morsel) = recv.recv().await {
                     morsel.take_consume_token();
+                    memory.grow(morsel.df().estimated_size())?;
                     morsels.push(morsel);
                 }
 
@@ -75,6 +98,8 @@ impl ComputeNode for InMemorySinkNode {
 
     fn get_output(&mut self) -> PolarsResult<Option<DataFrame>> {
         let morsels_per_pipe = core::mem::take(&mut *self.morsels_per_pipe.get_mut());
+        // The output is no longer owned by this node.
+        self.memory = None;
         let dataframes = linearize(morsels_per_pipe);
         if dataframes.is_empty() {
             Ok(Some(DataFrame::empty_with_schema(&self.schema)))
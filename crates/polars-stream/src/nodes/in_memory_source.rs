@@ -64,6 +64,10 @@ impl ComputeNode for InMemorySourceNode {
         Ok(())
     }
 
+    fn memory_usage(&self) -> Option<usize> {
+        Some(self.source.as_ref().map_or(0, |df| df.estimated_size()))
+    }
+
     fn spawn<'env, 's>(
         &'env mut self,
         scope: &'s TaskScope<'s, 'env>,
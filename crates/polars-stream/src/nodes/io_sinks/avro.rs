@@ -0,0 +1,139 @@
+use std::cmp::Reverse;
+use std::sync::Arc;
+
+use arrow::io::avro::avro_schema::schema::Record;
+use polars_core::schema::SchemaRef;
+use polars_error::PolarsResult;
+use polars_io::avro::{
+    AvroWriterOptions, avro_record, encode_avro_blocks, new_avro_sync_marker, write_avro_header,
+};
+use polars_io::cloud::CloudOptions;
+use polars_plan::dsl::{SinkOptions, SinkTarget};
+use polars_utils::priority::Priority;
+
+use super::{SinkInputPort, SinkNode};
+use crate::async_executor::spawn;
+use crate::async_primitives::connector::Receiver;
+use crate::execute::StreamingExecutionState;
+use crate::nodes::io_sinks::parallelize_receive_task;
+use crate::nodes::io_sinks::phase::PhaseOutcome;
+use crate::nodes::{JoinHandle, TaskPriority};
+
+pub struct AvroSinkNode {
+    target: SinkTarget,
+    record: Arc<Record>,
+    sink_options: SinkOptions,
+    write_options: AvroWriterOptions,
+    cloud_options: Option<CloudOptions>,
+}
+impl AvroSinkNode {
+    pub fn new(
+        target: SinkTarget,
+        schema: SchemaRef,
+        sink_options: SinkOptions,
+        write_options: AvroWriterOptions,
+        cloud_options: Option<CloudOptions>,
+    ) -> PolarsResult<Self> {
+        // Resolve the Avro schema up front so unsupported types error before
+        // anything is written.
+        let record = Arc::new(avro_record(&schema, &write_options.name)?);
+        Ok(Self {
+            target,
+            record,
+            sink_options,
+            write_options,
+            cloud_options,
+        })
+    }
+}
+
+impl SinkNode for AvroSinkNode {
+    fn name(&self) -> &str {
+        "avro-sink"
+    }
+
+    fn is_sink_input_parallel(&self) -> bool {
+        true
+    }
+
+    fn spawn_sink(
+        &mut self,
+        recv_port_rx: Receiver<(PhaseOutcome, SinkInputPort)>,
+        state: &StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        let (pass_rxs, mut io_rx) = parallelize_receive_task(
+            join_handles,
+            recv_port_rx,
+            state.num_pipelines,
+            self.sink_options.maintain_order,
+        );
+
+        // Every file gets its own sync marker, shared by the header and all blocks.
+        let marker = new_avro_sync_marker();
+
+        // Encode task.
+        //
+        // Task encodes the morsels into complete, compressed Avro blocks.
+        join_handles.extend(pass_rxs.into_iter().map(|mut pass_rx| {
+            let record = self.record.clone();
+            let compression = self.write_options.compression;
+
+            spawn(TaskPriority::High, async move {
+                while let Ok((mut rx, mut lin_tx)) = pass_rx.recv().await {
+                    while let Ok(morsel) = rx.recv().await {
+                        let (df, seq, _, consume_token) = morsel.into_inner();
+
+                        let mut buffer = Vec::new();
+                        encode_avro_blocks(&df, &record, compression, &marker, &mut buffer)?;
+
+                        if lin_tx.insert(Priority(Reverse(seq), buffer)).await.is_err() {
+                            return Ok(());
+                        }
+                        drop(consume_token); // Keep the consume_token until here to increase the
+                        // backpressure.
+                    }
+                }
+
+                PolarsResult::Ok(())
+            })
+        }));
+
+        // IO task.
+        //
+        // Task that will actually do write to the target file.
+        let target = self.target.clone();
+        let sink_options = self.sink_options.clone();
+        let record = self.record.clone();
+        let compression = self.write_options.compression;
+        let cloud_options = self.cloud_options.clone();
+        let io_task = polars_io::pl_async::get_runtime().spawn(async move {
+            use tokio::io::AsyncWriteExt;
+
+            let mut file = target
+                .open_into_writeable_async(&sink_options, cloud_options.as_ref())
+                .await?;
+
+            // Write the header
+            write_avro_header(&mut *file, &record, compression, &marker)?;
+
+            let mut file = file.try_into_async_writeable()?;
+
+            while let Ok(mut lin_rx) = io_rx.recv().await {
+                while let Some(Priority(_, buffer)) = lin_rx.get().await {
+                    file.write_all(&buffer).await?;
+                }
+            }
+
+            file.sync_on_close(sink_options.sync_on_close).await?;
+            file.close().await?;
+
+            PolarsResult::Ok(())
+        });
+        join_handles.push(spawn(TaskPriority::Low, async move {
+            io_task
+                .await
+                .unwrap_or_else(|e| Err(std::io::Error::from(e).into()))
+        }));
+    }
+}
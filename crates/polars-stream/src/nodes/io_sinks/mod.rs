@@ -22,6 +22,8 @@ mod metrics;
 mod phase;
 use phase::PhaseOutcome;
 
+#[cfg(feature = "avro")]
+pub mod avro;
 #[cfg(feature = "csv")]
 pub mod csv;
 #[cfg(feature = "ipc")]
@@ -126,6 +126,7 @@ impl FileReader for BatchFnReader {
             pre_slice: None,
             predicate: None,
             cast_columns_policy: _,
+            runtime_filters: _,
             num_pipelines: _,
             callbacks:
                 FileReaderCallbacks {
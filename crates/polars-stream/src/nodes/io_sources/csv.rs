@@ -140,6 +140,7 @@ impl FileReader for CsvFileReader {
             pre_slice,
             predicate: None,
             cast_columns_policy: _,
+            runtime_filters: _,
             num_pipelines,
             callbacks:
                 FileReaderCallbacks {
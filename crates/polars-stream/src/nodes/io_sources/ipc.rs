@@ -201,6 +201,7 @@ impl FileReader for IpcFileReader {
             pre_slice: pre_slice_arg,
             predicate: None,
             cast_columns_policy: _,
+            runtime_filters: _,
             num_pipelines,
             callbacks:
                 FileReaderCallbacks {
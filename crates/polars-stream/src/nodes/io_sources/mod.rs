@@ -1,5 +1,7 @@
 pub mod multi_file_reader;
 
+#[cfg(feature = "avro")]
+pub mod avro;
 pub mod batch;
 #[cfg(feature = "csv")]
 pub mod csv;
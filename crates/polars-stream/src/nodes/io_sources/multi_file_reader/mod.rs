@@ -6,12 +6,13 @@ pub mod reader_interface;
 pub mod reader_pipelines;
 
 use std::sync::atomic::AtomicUsize;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use bridge::BridgeState;
 use initialization::MultiScanTaskInitializer;
 use polars_core::schema::SchemaRef;
 use polars_error::PolarsResult;
+use polars_error::signals::CancellationToken;
 use polars_io::cloud::CloudOptions;
 use polars_io::predicates::ScanIOPredicate;
 use polars_io::{RowIndex, pl_async};
@@ -30,6 +31,7 @@ use crate::execute::StreamingExecutionState;
 use crate::graph::PortState;
 use crate::morsel::Morsel;
 use crate::nodes::ComputeNode;
+use crate::nodes::joins::runtime_filter::RuntimeJoinFilter;
 
 // Some parts are called MultiFileReader for now to avoid conflict with existing MultiScan.
 
@@ -54,12 +56,16 @@ pub struct MultiFileReaderConfig {
     pub missing_columns_policy: MissingColumnsPolicy,
     pub extra_columns_policy: ExtraColumnsPolicy,
     pub cast_columns_policy: CastColumnsPolicy,
+    /// Filters published by a join consuming this scan, see [`RuntimeJoinFilter`].
+    pub runtime_filters: Vec<Arc<RuntimeJoinFilter>>,
 
     pub num_pipelines: AtomicUsize,
     /// Number of readers to initialize concurrently. e.g. Parquet will want to fetch metadata in this
     /// step.
     pub n_readers_pre_init: AtomicUsize,
     pub max_concurrent_scans: AtomicUsize,
+    /// Token of the query, readers stop starting new files once it is cancelled.
+    pub cancel_token: OnceLock<CancellationToken>,
 
     pub verbose: bool,
 }
@@ -75,6 +81,10 @@ impl MultiFileReaderConfig {
             .load(std::sync::atomic::Ordering::Relaxed)
     }
 
+    fn cancel_token(&self) -> CancellationToken {
+        self.cancel_token.get().cloned().unwrap_or_default()
+    }
+
     fn max_concurrent_scans(&self) -> usize {
         self.max_concurrent_scans
             .load(std::sync::atomic::Ordering::Relaxed)
@@ -173,12 +183,13 @@ impl ComputeNode for MultiFileReader {
 
         let phase_morsel_tx = send_ports[0].take().unwrap().serial();
         let num_pipelines = state.num_pipelines;
+        let cancel_token = state.cancel_token.clone();
         let verbose = self.verbose;
 
         join_handles.push(scope.spawn_task(TaskPriority::Low, async move {
             use MultiScanState::*;
 
-            self.state.initialize(num_pipelines);
+            self.state.initialize(num_pipelines, cancel_token);
             self.state.refresh(verbose).await?;
 
             match &mut self.state {
@@ -274,7 +285,7 @@ impl MultiScanState {
     }
 
     /// Initialize state if not yet initialized.
-    fn initialize(&mut self, num_pipelines: usize) {
+    fn initialize(&mut self, num_pipelines: usize, cancel_token: CancellationToken) {
         use MultiScanState::*;
 
         let slf = std::mem::replace(self, Finished);
@@ -298,6 +309,8 @@ impl MultiScanState {
             std::sync::atomic::Ordering::Relaxed,
         );
 
+        _ = config.cancel_token.set(cancel_token);
+
         let (join_handle, send_phase_tx_to_bridge, bridge_state) =
             MultiScanTaskInitializer::new(config).spawn_background_tasks();
 
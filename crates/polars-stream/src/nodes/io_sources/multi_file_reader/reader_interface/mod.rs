@@ -4,6 +4,8 @@ pub mod builder;
 pub mod capabilities;
 pub mod output;
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use output::FileReaderOutputRecv;
 use polars_core::schema::SchemaRef;
@@ -16,6 +18,7 @@ use polars_utils::slice_enum::Slice;
 
 use crate::async_executor::JoinHandle;
 use crate::async_primitives::connector;
+use crate::nodes::joins::runtime_filter::RuntimeJoinFilter;
 
 /// Interface to read a single file
 #[async_trait]
@@ -119,6 +122,12 @@ pub struct BeginReadArgs {
     /// This can be ignored by the reader, as the policy is also applied in post.
     pub cast_columns_policy: CastColumnsPolicy,
 
+    /// Filters on the keys of a join consuming the output. Rows that don't pass a published
+    /// filter can't have a match, so the reader may skip them, although it doesn't have to.
+    ///
+    /// These are only passed if the reader isn't given a row index or slice.
+    pub runtime_filters: Vec<Arc<RuntimeJoinFilter>>,
+
     pub num_pipelines: usize,
     pub callbacks: FileReaderCallbacks,
     // TODO
@@ -136,6 +145,7 @@ impl Default for BeginReadArgs {
             predicate: None,
             // TODO: Use less restrictive default
             cast_columns_policy: CastColumnsPolicy::ERROR_ON_MISMATCH,
+            runtime_filters: Vec::new(),
             num_pipelines: 1,
             callbacks: FileReaderCallbacks::default(),
         }
@@ -8,6 +8,7 @@ use polars_core::prelude::{AnyValue, DataType};
 use polars_core::scalar::Scalar;
 use polars_core::schema::SchemaRef;
 use polars_error::PolarsResult;
+use polars_error::signals::CancellationToken;
 use polars_io::predicates::ScanIOPredicate;
 use polars_plan::dsl::{CastColumnsPolicy, ExtraColumnsPolicy, MissingColumnsPolicy, ScanSource};
 use polars_plan::plans::hive::HivePartitionsDf;
@@ -32,6 +33,7 @@ use crate::nodes::io_sources::multi_file_reader::reader_interface::capabilities:
 use crate::nodes::io_sources::multi_file_reader::reader_interface::{
     BeginReadArgs, FileReader, FileReaderCallbacks,
 };
+use crate::nodes::joins::runtime_filter::RuntimeJoinFilter;
 
 impl MultiScanTaskInitializer {
     /// Generic reader pipeline that should work for all file types and configurations
@@ -254,6 +256,8 @@ impl MultiScanTaskInitializer {
                     verbose,
                 },
                 num_pipelines,
+                runtime_filters: self.config.runtime_filters.clone(),
+                cancel_token: self.config.cancel_token(),
                 verbose,
             }
             .run(),
@@ -295,6 +299,8 @@ struct ReaderStarter {
     extra_ops: ExtraOperations,
     constant_args: StartReaderArgsConstant,
     num_pipelines: usize,
+    runtime_filters: Vec<Arc<RuntimeJoinFilter>>,
+    cancel_token: CancellationToken,
     verbose: bool,
 }
 
@@ -310,6 +316,8 @@ impl ReaderStarter {
             extra_ops,
             constant_args,
             num_pipelines,
+            runtime_filters,
+            cancel_token,
             verbose,
         } = self;
 
@@ -328,6 +336,8 @@ impl ReaderStarter {
             // Note: This loop should only do basic bookkeeping (e.g. slice position) and reader initialization.
             // It should avoid doing compute as much as possible - those should instead be deferred to spawned tasks.
 
+            cancel_token.check()?;
+
             let pre_slice_this_file = extra_ops.pre_slice.clone().map(|x| match x {
                 Slice::Positive { .. } => x.offsetted(current_row_position as usize),
                 Slice::Negative { .. } => x,
@@ -473,12 +483,21 @@ impl ReaderStarter {
                 None
             };
 
+            // Skipping rows would shift the row positions of a slice or row index applied in post.
+            let runtime_filters =
+                if extra_ops_post.row_index.is_none() && extra_ops_post.pre_slice.is_none() {
+                    runtime_filters.clone()
+                } else {
+                    Vec::new()
+                };
+
             let begin_read_args = BeginReadArgs {
                 projected_schema: constant_args.projected_file_schema.clone(),
                 row_index,
                 pre_slice,
                 predicate,
                 cast_columns_policy: extra_ops_post.cast_columns_policy.clone(),
+                runtime_filters,
                 num_pipelines,
                 callbacks,
             };
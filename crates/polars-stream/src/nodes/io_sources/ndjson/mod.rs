@@ -74,6 +74,7 @@ impl FileReader for NDJsonFileReader {
 
             predicate: None,
             cast_columns_policy: _,
+            runtime_filters: _,
         } = args
         else {
             panic!("unsupported args: {:?}", &args)
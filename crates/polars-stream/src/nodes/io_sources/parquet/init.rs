@@ -4,18 +4,22 @@ use std::sync::Arc;
 
 use arrow::datatypes::ArrowDataType;
 use polars_core::frame::DataFrame;
-use polars_core::prelude::{Column, DataType, IDX_DTYPE, IntoColumn};
-use polars_core::series::Series;
-use polars_core::utils::arrow::bitmap::Bitmap;
-use polars_core::utils::arrow::datatypes::ArrowSchemaRef;
+use polars_core::prelude::{Column, PlIndexSet};
+use polars_core::utils::arrow::bitmap::{Bitmap, MutableBitmap};
+use polars_core::utils::arrow::datatypes::{ArrowSchema, ArrowSchemaRef};
 use polars_error::{PolarsResult, polars_ensure};
 use polars_io::RowIndex;
 use polars_io::predicates::ScanIOPredicate;
-use polars_io::prelude::_internal::{PrefilterMaskSetting, collect_statistics_with_live_columns};
+use polars_io::prelude::_internal::{
+    PrefilterMaskSetting, bloom_filter_hashes, bloom_filter_may_contain,
+    collect_statistics_with_live_columns, statistics_columns,
+};
 use polars_io::prelude::{FileMetadata, ParallelStrategy};
-use polars_utils::{IdxSize, format_pl_smallstr};
+use polars_io::utils::byte_source::{ByteSource, DynByteSource};
+use polars_utils::IdxSize;
+use polars_utils::pl_str::PlSmallStr;
 
-use super::row_group_data_fetch::RowGroupDataFetcher;
+use super::row_group_data_fetch::{RowGroupDataFetcher, get_row_group_byte_ranges_for_projection};
 use super::row_group_decode::RowGroupDecoder;
 use super::{AsyncTaskData, ParquetReadImpl};
 use crate::async_executor;
@@ -24,6 +28,50 @@ use crate::nodes::io_sources::multi_file_reader::reader_interface::output::FileR
 use crate::nodes::{MorselSeq, TaskPriority};
 use crate::utils::task_handles_ext::{self, AbortOnDropHandle};
 
+/// Builds a DataFrame with a row per row group in `row_group_slice`, holding the `len` of the row
+/// group and the `{c}_min`, `{c}_max` and `{c}_nc` statistics of the live columns.
+pub(super) fn row_group_statistics_df(
+    row_group_slice: Range<usize>,
+    metadata: &FileMetadata,
+    reader_schema: &ArrowSchema,
+    live_columns: &PlIndexSet<PlSmallStr>,
+    row_index: Option<&RowIndex>,
+) -> PolarsResult<DataFrame> {
+    let num_row_groups = row_group_slice.len();
+
+    let stats = collect_statistics_with_live_columns(
+        &metadata.row_groups[row_group_slice.clone()],
+        reader_schema,
+        live_columns,
+        row_index.map(|ri| (&ri.name, ri.offset)),
+    )?;
+
+    let mut columns = Vec::with_capacity(1 + live_columns.len() * 3);
+
+    let lengths: Vec<IdxSize> = metadata.row_groups[row_group_slice]
+        .iter()
+        .map(|rg| rg.num_rows() as IdxSize)
+        .collect();
+    columns.push(Column::new("len".into(), lengths));
+    for (c, stat) in live_columns.iter().zip(stats) {
+        let field = reader_schema.get(c).map(Cow::Borrowed).unwrap_or_else(|| {
+            let row_index = row_index.cloned().unwrap();
+            assert_eq!(c, &row_index.name);
+
+            Cow::Owned(arrow::datatypes::Field {
+                name: row_index.name,
+                dtype: ArrowDataType::IDX_DTYPE,
+                is_nullable: false,
+                metadata: None,
+            })
+        });
+
+        columns.extend(statistics_columns(c, field.as_ref(), stat, num_row_groups)?);
+    }
+
+    DataFrame::new_with_height(num_row_groups, columns)
+}
+
 async fn calculate_row_group_pred_pushdown_skip_mask(
     row_group_slice: Range<usize>,
     use_statistics: bool,
@@ -58,85 +106,162 @@ async fn calculate_row_group_pred_pushdown_skip_mask(
             }
         }
 
-        let stats = collect_statistics_with_live_columns(
-            &metadata.row_groups[row_group_slice.clone()],
-            reader_schema.as_ref(),
+        let statistics_df = row_group_statistics_df(
+            row_group_slice,
+            &metadata,
+            &reader_schema,
             &live_columns,
-            row_index.as_ref().map(|ri| (&ri.name, ri.offset)),
+            row_index.as_ref(),
         )?;
+        sbp.evaluate_with_stat_df(&statistics_df)
+    })
+    .await?;
 
-        let mut columns = Vec::with_capacity(1 + live_columns.len() * 3);
+    if verbose {
+        eprintln!(
+            "[ParquetFileReader]: Predicate pushdown: \
+                                reading {} / {} row groups",
+            skip_row_group_mask.unset_bits(),
+            num_row_groups,
+        );
+    }
 
-        let lengths: Vec<IdxSize> = metadata.row_groups[row_group_slice.clone()]
+    Ok(Some(skip_row_group_mask))
+}
+
+/// Extends `skip_row_group_mask` with the row groups in which none of the values of a membership
+/// predicate occur according to the bloom filters of the file.
+///
+/// For remote sources a filter is only fetched if the row group is large enough to amortize the
+/// extra request, i.e. its projected bytes are at least `min_row_group_bytes` and 8 times the size
+/// of the filter.
+#[allow(clippy::too_many_arguments)]
+async fn calculate_row_group_bloom_filter_skip_mask(
+    row_group_slice: Range<usize>,
+    use_statistics: bool,
+    predicate: Option<&ScanIOPredicate>,
+    metadata: &FileMetadata,
+    byte_source: &DynByteSource,
+    projection: Option<&ArrowSchema>,
+    min_row_group_bytes: usize,
+    skip_row_group_mask: Option<Bitmap>,
+    verbose: bool,
+) -> PolarsResult<Option<Bitmap>> {
+    if !use_statistics {
+        return Ok(skip_row_group_mask);
+    }
+
+    let Some(predicate) = predicate else {
+        return Ok(skip_row_group_mask);
+    };
+    if predicate.membership_predicates.is_empty() {
+        return Ok(skip_row_group_mask);
+    }
+
+    let file_size = match byte_source {
+        DynByteSource::MemSlice(mem_slice) => Some(mem_slice.0.len()),
+        DynByteSource::Cloud(_) => None,
+    };
+
+    // (row group, hashes, bloom filter byte range) for every filter to check.
+    let mut lookups: Vec<(usize, usize, Range<usize>)> = vec![];
+    let mut hashes: Vec<Vec<u64>> = vec![];
+
+    for membership_predicate in predicate.membership_predicates.iter() {
+        let mut hashes_idx = None;
+
+        for (i, rg) in metadata.row_groups[row_group_slice.clone()]
             .iter()
-            .map(|rg| rg.num_rows() as IdxSize)
-            .collect();
-        columns.push(Column::new("len".into(), lengths));
-        for (c, stat) in live_columns.iter().zip(stats) {
-            let field = reader_schema.get(c).map(Cow::Borrowed).unwrap_or_else(|| {
-                let row_index = row_index.clone().unwrap();
-                assert_eq!(c, &row_index.name);
-
-                Cow::Owned(arrow::datatypes::Field {
-                    name: row_index.name,
-                    dtype: ArrowDataType::IDX_DTYPE,
-                    is_nullable: false,
-                    metadata: None,
-                })
-            });
-
-            let min_name = format_pl_smallstr!("{c}_min");
-            let max_name = format_pl_smallstr!("{c}_max");
-            let nc_name = format_pl_smallstr!("{c}_nc");
-
-            let (min, max, nc) = match stat {
-                None => {
-                    let dtype = DataType::from_arrow_field(field.as_ref());
+            .enumerate()
+        {
+            if skip_row_group_mask.as_ref().is_some_and(|m| m.get_bit(i)) {
+                continue;
+            }
 
-                    (
-                        Column::full_null(min_name, num_row_groups, &dtype),
-                        Column::full_null(max_name, num_row_groups, &dtype),
-                        Column::full_null(nc_name, num_row_groups, &IDX_DTYPE),
-                    )
-                },
-                Some(stat) => {
-                    let md = field.metadata.as_deref();
-
-                    (
-                        unsafe {
-                            Series::_try_from_arrow_unchecked_with_md(
-                                min_name,
-                                vec![stat.min_value],
-                                field.dtype(),
-                                md,
-                            )
-                        }?
-                        .into_column(),
-                        unsafe {
-                            Series::_try_from_arrow_unchecked_with_md(
-                                max_name,
-                                vec![stat.max_value],
-                                field.dtype(),
-                                md,
-                            )
-                        }?
-                        .into_column(),
-                        Series::from_arrow(nc_name, stat.null_count.boxed())?.into_column(),
-                    )
+            // Nested columns have no single filter for the values.
+            let Some(mut columns) = rg.columns_under_root_iter(&membership_predicate.column) else {
+                continue;
+            };
+            let (Some(column), None) = (columns.next(), columns.next()) else {
+                continue;
+            };
+
+            let column_md = column.metadata();
+            let Some(offset) = column_md.bloom_filter_offset else {
+                continue;
+            };
+            let offset = offset as usize;
+            let range = match (column_md.bloom_filter_length, file_size) {
+                (Some(length), _) => offset..offset + length as usize,
+                // The filter header holds its size, so it suffices to read until the end.
+                (None, Some(file_size)) => offset..file_size,
+                (None, None) => continue,
+            };
+
+            if file_size.is_none() {
+                let row_group_bytes: usize = match projection {
+                    Some(projection) => {
+                        get_row_group_byte_ranges_for_projection(rg, &mut projection.iter_names())
+                            .map(|range| range.len())
+                            .sum()
+                    },
+                    None => rg.compressed_size(),
+                };
+
+                if row_group_bytes < min_row_group_bytes.max(8 * range.len()) {
+                    continue;
+                }
+            }
+
+            let idx = match hashes_idx {
+                Some(idx) => idx,
+                None => {
+                    // The physical type is the same for all row groups.
+                    let Some(h) =
+                        bloom_filter_hashes(&membership_predicate.values, column.physical_type())
+                    else {
+                        break;
+                    };
+                    hashes.push(h);
+                    *hashes_idx.insert(hashes.len() - 1)
                 },
             };
 
-            columns.extend([min, max, nc]);
+            lookups.push((i, idx, range));
         }
+    }
 
-        let statistics_df = DataFrame::new_with_height(num_row_groups, columns)?;
-        sbp.evaluate_with_stat_df(&statistics_df)
-    })
-    .await?;
+    if lookups.is_empty() {
+        return Ok(skip_row_group_mask);
+    }
+
+    let mut ranges = lookups
+        .iter()
+        .map(|(_, _, range)| range.clone())
+        .collect::<Vec<_>>();
+    ranges.sort_unstable_by_key(|range| (range.start, range.end));
+    ranges.dedup();
+    let bytes_map = byte_source.get_ranges(&mut ranges).await?;
+
+    let num_row_groups = row_group_slice.len();
+    let mut mask = match skip_row_group_mask {
+        Some(mask) => mask.make_mut(),
+        None => MutableBitmap::from_len_zeroed(num_row_groups),
+    };
+    for (i, idx, range) in lookups {
+        if mask.get(i) {
+            continue;
+        }
+
+        if !bloom_filter_may_contain(bytes_map.get(&range.start).unwrap(), &hashes[idx])? {
+            mask.set(i, true);
+        }
+    }
+    let skip_row_group_mask: Bitmap = mask.freeze();
 
     if verbose {
         eprintln!(
-            "[ParquetFileReader]: Predicate pushdown: \
+            "[ParquetFileReader]: Bloom filter pushdown: \
                                 reading {} / {} row groups",
             skip_row_group_mask.unset_bits(),
             num_row_groups,
@@ -192,6 +317,9 @@ impl ParquetReadImpl {
             tokio::sync::mpsc::channel(row_group_prefetch_size);
 
         let row_index = self.row_index.clone();
+        let runtime_filters = self.runtime_filters.clone();
+        let bloom_filter_min_row_group_bytes = self.config.bloom_filter_min_row_group_bytes;
+        let page_index_min_row_group_bytes = self.config.page_index_min_row_group_bytes;
 
         let prefetch_task = AbortOnDropHandle(io_runtime.spawn(async move {
             polars_ensure!(
@@ -259,6 +387,19 @@ impl ParquetReadImpl {
             )
             .await?;
 
+            let row_group_mask = calculate_row_group_bloom_filter_skip_mask(
+                row_group_slice.clone(),
+                use_statistics,
+                predicate.as_ref(),
+                &metadata,
+                &byte_source,
+                projection.as_deref(),
+                bloom_filter_min_row_group_bytes,
+                row_group_mask,
+                verbose,
+            )
+            .await?;
+
             let mut row_group_data_fetcher = RowGroupDataFetcher {
                 projection,
                 predicate,
@@ -269,6 +410,11 @@ impl ParquetReadImpl {
                 row_group_slice,
                 row_group_mask,
                 row_offset,
+                runtime_filters,
+                reader_schema,
+                use_statistics,
+                page_index_min_row_group_bytes,
+                verbose,
             };
 
             while let Some(prefetch) = row_group_data_fetcher.next().await {
@@ -418,6 +564,7 @@ impl ParquetReadImpl {
             predicate_arrow_field_indices,
             non_predicate_arrow_field_indices,
             min_values_per_thread,
+            runtime_filters: self.runtime_filters.clone(),
         }
     }
 }
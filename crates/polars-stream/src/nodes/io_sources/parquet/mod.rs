@@ -25,6 +25,7 @@ use super::multi_file_reader::reader_interface::{
 };
 use crate::async_executor::{self};
 use crate::nodes::compute_node_prelude::*;
+use crate::nodes::joins::runtime_filter::RuntimeJoinFilter;
 use crate::nodes::{TaskPriority, io_sources};
 use crate::utils::task_handles_ext;
 
@@ -135,6 +136,7 @@ impl FileReader for ParquetFileReader {
             pre_slice: pre_slice_arg,
             mut predicate,
             cast_columns_policy,
+            runtime_filters,
             num_pipelines,
             callbacks:
                 FileReaderCallbacks {
@@ -197,6 +199,16 @@ impl FileReader for ParquetFileReader {
             .map(|x| x.parse::<usize>().expect("integer").max(1))
             .unwrap_or(16_777_216);
 
+        let bloom_filter_min_row_group_bytes =
+            std::env::var("POLARS_PARQUET_BLOOM_FILTER_MIN_ROW_GROUP_BYTES")
+                .map(|x| x.parse::<usize>().expect("integer"))
+                .unwrap_or(4 * 1024 * 1024);
+
+        let page_index_min_row_group_bytes =
+            std::env::var("POLARS_PARQUET_PAGE_INDEX_MIN_ROW_GROUP_BYTES")
+                .map(|x| x.parse::<usize>().expect("integer"))
+                .unwrap_or(4 * 1024 * 1024);
+
         let projected_arrow_schema: ArrowSchemaRef = Arc::new(
             projected_schema
                 .iter_names()
@@ -244,6 +256,14 @@ impl FileReader for ParquetFileReader {
             )?;
         }
 
+        // The key columns may have a different type in this file, in which case the filter values
+        // can't be compared against it.
+        let file_schema_pl = self._file_schema();
+        let runtime_filters = runtime_filters
+            .into_iter()
+            .filter(|filter| filter.applies_to(&file_schema_pl))
+            .collect();
+
         let (output_recv, handle) = ParquetReadImpl {
             predicate,
             // TODO: Refactor to avoid full clone
@@ -258,12 +278,15 @@ impl FileReader for ParquetFileReader {
                 num_pipelines,
                 row_group_prefetch_size,
                 min_values_per_thread,
+                bloom_filter_min_row_group_bytes,
+                page_index_min_row_group_bytes,
             },
             verbose,
             schema: file_schema.clone(),
             projected_arrow_schema,
             memory_prefetch_func,
             row_index,
+            runtime_filters,
         }
         .run();
 
@@ -335,6 +358,7 @@ struct ParquetReadImpl {
     projected_arrow_schema: Arc<ArrowSchema>,
     memory_prefetch_func: fn(&[u8]) -> (),
     row_index: Option<RowIndex>,
+    runtime_filters: Vec<Arc<RuntimeJoinFilter>>,
 }
 
 #[derive(Debug)]
@@ -345,6 +369,10 @@ struct Config {
     /// Minimum number of values for a parallel spawned task to process to amortize
     /// parallelism overhead.
     min_values_per_thread: usize,
+    /// Minimum number of bytes to read from a remote row group to also fetch its bloom filters.
+    bloom_filter_min_row_group_bytes: usize,
+    /// Minimum number of bytes to read from a remote row group to also fetch its page indexes.
+    page_index_min_row_group_bytes: usize,
 }
 
 impl ParquetReadImpl {
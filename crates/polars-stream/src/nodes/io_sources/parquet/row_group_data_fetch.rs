@@ -1,18 +1,21 @@
 use std::ops::Range;
 use std::sync::Arc;
 
-use arrow::datatypes::ArrowSchemaRef;
-use polars_core::prelude::PlHashMap;
+use arrow::datatypes::{ArrowSchema, ArrowSchemaRef};
+use polars_core::prelude::{BooleanChunked, ChunkFull, PlHashMap};
 use polars_core::series::IsSorted;
 use polars_core::utils::arrow::bitmap::Bitmap;
 use polars_error::PolarsResult;
 use polars_io::predicates::ScanIOPredicate;
+use polars_io::prelude::_internal::{PageSelection, page_index_byte_ranges, select_pages};
 use polars_io::prelude::{FileMetadata, create_sorting_map};
 use polars_io::utils::byte_source::{ByteSource, DynByteSource};
 use polars_parquet::read::RowGroupMetadata;
 use polars_utils::mmap::MemSlice;
 use polars_utils::pl_str::PlSmallStr;
 
+use super::init::row_group_statistics_df;
+use crate::nodes::joins::runtime_filter::RuntimeJoinFilter;
 use crate::utils::task_handles_ext;
 
 /// Represents byte-data that can be transformed into a DataFrame after some computation.
@@ -22,11 +25,13 @@ pub(super) struct RowGroupData {
     pub(super) slice: Option<(usize, usize)>,
     pub(super) row_group_metadata: RowGroupMetadata,
     pub(super) sorting_map: PlHashMap<usize, IsSorted>,
+    /// The rows and pages that remain after page index pushdown. Only the selected pages of the
+    /// paged columns were fetched.
+    pub(super) page_selection: Option<PageSelection>,
 }
 
 pub(super) struct RowGroupDataFetcher {
     pub(super) projection: Option<ArrowSchemaRef>,
-    #[allow(unused)] // TODO: Fix!
     pub(super) predicate: Option<ScanIOPredicate>,
     pub(super) slice_range: Option<Range<usize>>,
     pub(super) memory_prefetch_func: fn(&[u8]) -> (),
@@ -37,12 +42,25 @@ pub(super) struct RowGroupDataFetcher {
     pub(super) row_group_mask: Option<Bitmap>,
 
     pub(super) row_offset: usize,
+
+    /// Runtime join filters that haven't been published yet. Once they are, the statistics of the
+    /// remaining row groups are checked against them.
+    pub(super) runtime_filters: Vec<Arc<RuntimeJoinFilter>>,
+    pub(super) reader_schema: ArrowSchemaRef,
+    pub(super) use_statistics: bool,
+    /// Minimum number of bytes to read from a remote row group to also fetch its page indexes.
+    pub(super) page_index_min_row_group_bytes: usize,
+    pub(super) verbose: bool,
 }
 
 impl RowGroupDataFetcher {
     pub(super) async fn next(
         &mut self,
     ) -> Option<PolarsResult<task_handles_ext::AbortOnDropHandle<PolarsResult<RowGroupData>>>> {
+        if let Err(e) = self.apply_published_runtime_filters() {
+            return Some(Err(e));
+        }
+
         while !self.row_group_slice.is_empty() {
             let idx = self.row_group_slice.start;
             self.row_group_slice.start += 1;
@@ -82,8 +100,44 @@ impl RowGroupDataFetcher {
             let memory_prefetch_func = self.memory_prefetch_func;
             let io_runtime = polars_io::pl_async::get_runtime();
 
+            // Pages can only be skipped if all rows of the row group are read.
+            let page_index_predicate = self.predicate.clone().filter(|p| {
+                self.use_statistics && slice.is_none() && p.skip_batch_predicate.is_some()
+            });
+            let reader_schema = self.reader_schema.clone();
+            let page_index_min_row_group_bytes = self.page_index_min_row_group_bytes;
+            let verbose = self.verbose;
+
             let handle = io_runtime.spawn(async move {
                 let row_group_metadata = &metadata.row_groups[idx];
+
+                let page_selection = match page_index_predicate {
+                    Some(predicate) => {
+                        select_row_group_pages(
+                            row_group_metadata,
+                            &reader_schema,
+                            projection.as_deref(),
+                            &predicate,
+                            &current_byte_source,
+                            page_index_min_row_group_bytes,
+                        )
+                        .await?
+                    },
+                    None => None,
+                };
+
+                if verbose {
+                    if let Some(page_selection) = &page_selection {
+                        eprintln!(
+                            "[ParquetFileReader]: Page index pushdown: \
+                                reading {} / {} rows of row group {}",
+                            page_selection.row_mask.set_bits(),
+                            row_group_metadata.num_rows(),
+                            idx,
+                        );
+                    }
+                }
+
                 let fetched_bytes =
                     if let DynByteSource::MemSlice(mem_slice) = current_byte_source.as_ref() {
                         // Skip byte range calculation for `no_prefetch`.
@@ -115,6 +169,20 @@ impl RowGroupDataFetcher {
                             offset: 0,
                             mem_slice,
                         }
+                    } else if let Some(page_selection) = page_selection.as_ref() {
+                        let mut ranges = get_row_group_byte_ranges_for_page_selection(
+                            row_group_metadata,
+                            projection.as_deref(),
+                            page_selection,
+                        );
+
+                        let n_ranges = ranges.len();
+
+                        let bytes_map = current_byte_source.get_ranges(&mut ranges).await?;
+
+                        assert_eq!(bytes_map.len(), n_ranges);
+
+                        FetchedBytes::BytesMap(bytes_map)
                     } else if let Some(columns) = projection.as_ref() {
                         let mut ranges = get_row_group_byte_ranges_for_projection(
                             row_group_metadata,
@@ -156,6 +224,7 @@ impl RowGroupDataFetcher {
                     // @TODO: Remove clone
                     row_group_metadata: row_group_metadata.clone(),
                     sorting_map,
+                    page_selection,
                 })
             });
 
@@ -165,6 +234,54 @@ impl RowGroupDataFetcher {
 
         None
     }
+
+    /// Adds the remaining row groups that can't pass a runtime join filter which has been
+    /// published since the last call to the skip mask.
+    fn apply_published_runtime_filters(&mut self) -> PolarsResult<()> {
+        if !self.runtime_filters.iter().any(|f| f.is_published()) {
+            return Ok(());
+        }
+        let (published, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.runtime_filters)
+            .into_iter()
+            .partition(|f| f.is_published());
+        self.runtime_filters = pending;
+
+        if !self.use_statistics || self.row_group_slice.is_empty() {
+            return Ok(());
+        }
+
+        let num_row_groups = self.row_group_slice.len();
+        let mut skip = BooleanChunked::full(PlSmallStr::EMPTY, false, num_row_groups);
+        for filter in published {
+            let live_columns = filter.key_schema().iter_names_cloned().collect();
+            let statistics_df = row_group_statistics_df(
+                self.row_group_slice.clone(),
+                &self.metadata,
+                &self.reader_schema,
+                &live_columns,
+                None,
+            )?;
+            if let Some(filter_skip) = filter.skip_batch_mask(&statistics_df)? {
+                skip = &skip | &filter_skip;
+            }
+        }
+        let skip = skip.rechunk().downcast_as_array().values().clone();
+
+        if self.verbose {
+            eprintln!(
+                "[ParquetFileReader]: Runtime join filter: \
+                skipping {} / {} remaining row groups",
+                skip.set_bits(),
+                num_row_groups,
+            );
+        }
+
+        self.row_group_mask = Some(match self.row_group_mask.take() {
+            Some(mask) => &mask | &skip,
+            None => skip,
+        });
+        Ok(())
+    }
 }
 
 pub(super) enum FetchedBytes {
@@ -189,7 +306,7 @@ impl FetchedBytes {
     }
 }
 
-fn get_row_group_byte_ranges_for_projection<'a>(
+pub(super) fn get_row_group_byte_ranges_for_projection<'a>(
     row_group_metadata: &'a RowGroupMetadata,
     columns: &'a mut dyn Iterator<Item = &PlSmallStr>,
 ) -> impl Iterator<Item = std::ops::Range<usize>> + 'a {
@@ -206,3 +323,94 @@ fn get_row_group_byte_ranges_for_projection<'a>(
             })
     })
 }
+
+/// Like [`get_row_group_byte_ranges_for_projection`], but only returns the byte ranges of the
+/// selected pages for the columns in `page_selection`.
+fn get_row_group_byte_ranges_for_page_selection(
+    row_group_metadata: &RowGroupMetadata,
+    projection: Option<&ArrowSchema>,
+    page_selection: &PageSelection,
+) -> Vec<std::ops::Range<usize>> {
+    let column_idxs: Vec<usize> = match projection {
+        Some(columns) => columns
+            .iter_names()
+            .flat_map(|name| {
+                row_group_metadata
+                    .columns_idxs_under_root_iter(name)
+                    .into_iter()
+                    .flatten()
+                    .copied()
+            })
+            .collect(),
+        None => (0..row_group_metadata.n_columns()).collect(),
+    };
+
+    column_idxs
+        .into_iter()
+        .flat_map(|idx| match page_selection.column_pages.get(&idx) {
+            Some(pages) => pages.byte_ranges.clone(),
+            None => {
+                let byte_range = row_group_metadata.parquet_columns()[idx].byte_range();
+                vec![byte_range.start as usize..byte_range.end as usize]
+            },
+        })
+        .collect()
+}
+
+/// Selects the rows and pages of a row group that may pass `predicate` according to the page
+/// indexes of its columns.
+///
+/// For remote sources the page indexes are only fetched if the row group is large enough to
+/// amortize the extra request, i.e. its projected bytes are at least `min_row_group_bytes`.
+async fn select_row_group_pages(
+    row_group_metadata: &RowGroupMetadata,
+    reader_schema: &ArrowSchema,
+    projection: Option<&ArrowSchema>,
+    predicate: &ScanIOPredicate,
+    byte_source: &DynByteSource,
+    min_row_group_bytes: usize,
+) -> PolarsResult<Option<PageSelection>> {
+    let Some(sbp) = predicate.skip_batch_predicate.as_deref() else {
+        return Ok(None);
+    };
+    let projected_columns = projection.unwrap_or(reader_schema);
+
+    let Some(mut ranges) = page_index_byte_ranges(
+        row_group_metadata,
+        reader_schema,
+        &predicate.live_columns,
+        projected_columns.iter_names(),
+    ) else {
+        return Ok(None);
+    };
+
+    let fetched_bytes = match byte_source {
+        DynByteSource::MemSlice(mem_slice) => FetchedBytes::MemSlice {
+            mem_slice: mem_slice.0.clone(),
+            offset: 0,
+        },
+        DynByteSource::Cloud(_) => {
+            let row_group_bytes: usize = get_row_group_byte_ranges_for_projection(
+                row_group_metadata,
+                &mut projected_columns.iter_names(),
+            )
+            .map(|range| range.len())
+            .sum();
+
+            if row_group_bytes < min_row_group_bytes {
+                return Ok(None);
+            }
+
+            FetchedBytes::BytesMap(byte_source.get_ranges(&mut ranges).await?)
+        },
+    };
+
+    select_pages(
+        row_group_metadata,
+        reader_schema,
+        &predicate.live_columns,
+        projected_columns.iter_names(),
+        sbp,
+        |range| fetched_bytes.get_range(range),
+    )
+}
@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use polars_core::frame::DataFrame;
 use polars_core::prelude::{
-    ArrowField, ArrowSchema, BooleanChunked, ChunkFilter, Column, DataType, IntoColumn,
+    ArrayRef, ArrowField, ArrowSchema, BooleanChunked, ChunkFilter, Column, DataType, IntoColumn,
 };
 use polars_core::series::Series;
 use polars_core::utils::arrow::bitmap::{Bitmap, MutableBitmap};
@@ -13,14 +13,18 @@ use polars_io::predicates::{
     ColumnPredicateExpr, ColumnPredicates, ScanIOPredicate, SpecializedColumnPredicateExpr,
 };
 pub use polars_io::prelude::_internal::PrefilterMaskSetting;
-use polars_io::prelude::_internal::calc_prefilter_cost;
+use polars_io::prelude::_internal::{ColumnPages, PageSelection, calc_prefilter_cost};
 use polars_io::prelude::try_set_sorted_flag;
-use polars_parquet::read::{Filter, ParquetType, PredicateFilter, PrimitiveLogicalType};
+use polars_parquet::read::{
+    ColumnChunkMetadata, Filter, ParquetType, PredicateFilter, PrimitiveLogicalType,
+};
 use polars_utils::IdxSize;
+use polars_utils::mmap::MemSlice;
 use polars_utils::pl_str::PlSmallStr;
 
 use super::row_group_data_fetch::RowGroupData;
 use crate::async_primitives::opt_spawned_future::parallelize_first_to_local;
+use crate::nodes::joins::runtime_filter::RuntimeJoinFilter;
 
 /// Turns row group data into DataFrames.
 pub(super) struct RowGroupDecoder {
@@ -34,6 +38,8 @@ pub(super) struct RowGroupDecoder {
     /// Indices into `projected_arrow_schema. This must be sorted.
     pub(super) non_predicate_arrow_field_indices: Arc<Vec<usize>>,
     pub(super) min_values_per_thread: usize,
+    /// Applied to the decoded rows once published.
+    pub(super) runtime_filters: Vec<Arc<RuntimeJoinFilter>>,
 }
 
 impl RowGroupDecoder {
@@ -47,14 +53,21 @@ impl RowGroupDecoder {
             slice.0 == 0 && slice.1 >= row_group_data.row_group_metadata.num_rows()
         });
 
-        if self.use_prefiltered.is_some()
+        let mut df = if self.use_prefiltered.is_some()
             && row_group_data.slice.is_none()
             && !self.predicate_arrow_field_indices.is_empty()
         {
-            self.row_group_data_to_df_prefiltered(row_group_data).await
+            self.row_group_data_to_df_prefiltered(row_group_data)
+                .await?
         } else {
-            self.row_group_data_to_df_impl(row_group_data).await
+            self.row_group_data_to_df_impl(row_group_data).await?
+        };
+
+        for filter in &self.runtime_filters {
+            df = filter.filter(df)?;
         }
+
+        Ok(df)
     }
 
     async fn row_group_data_to_df_impl(
@@ -74,19 +87,27 @@ impl RowGroupDecoder {
 
         assert!(slice_range.end <= row_group_data.row_group_metadata.num_rows());
 
+        let (filter, projection_height) = match &row_group_data.page_selection {
+            Some(page_selection) => (
+                Filter::Mask(page_selection.row_mask.clone()),
+                page_selection.row_mask.set_bits(),
+            ),
+            None => (Filter::Range(slice_range.clone()), slice_range.len()),
+        };
+
         if let Some(s) = self.materialize_row_index(row_group_data.as_ref(), slice_range.clone())? {
-            out_columns.push(s);
+            out_columns.push(match &filter {
+                Filter::Mask(mask) => s.filter(&BooleanChunked::from_bitmap(
+                    PlSmallStr::EMPTY,
+                    mask.clone(),
+                ))?,
+                _ => s,
+            });
         }
 
         let mut decoded_cols = Vec::with_capacity(row_group_data.row_group_metadata.n_columns());
-        self.decode_projected_columns(
-            &mut decoded_cols,
-            &row_group_data,
-            Some(polars_parquet::read::Filter::Range(slice_range.clone())),
-        )
-        .await?;
-
-        let projection_height = slice_range.len();
+        self.decode_projected_columns(&mut decoded_cols, &row_group_data, Some(filter))
+            .await?;
 
         out_columns.extend(decoded_cols);
 
@@ -242,26 +263,38 @@ fn decode_column(
         ));
     };
 
-    let columns_to_deserialize = iter
-        .map(|col_md| {
-            let byte_range = col_md.byte_range();
-
-            (
-                col_md,
-                row_group_data
-                    .fetched_bytes
-                    .get_range(byte_range.start as usize..byte_range.end as usize),
-            )
-        })
-        .collect::<Vec<_>>();
-
     let skip_num_rows_check = matches!(filter, Some(Filter::Predicate(_)));
 
-    let (array, pred_true_mask) = polars_io::prelude::_internal::to_deserializer(
-        columns_to_deserialize,
-        arrow_field.clone(),
-        filter,
-    )?;
+    let (array, pred_true_mask) = if let Some((col_md, pages, page_selection)) =
+        selected_column_pages(arrow_field, row_group_data)
+    {
+        deserialize_selected_pages(
+            arrow_field,
+            col_md,
+            pages,
+            row_group_data,
+            &page_selection.row_mask,
+        )?
+    } else {
+        let columns_to_deserialize = iter
+            .map(|col_md| {
+                let byte_range = col_md.byte_range();
+
+                (
+                    col_md,
+                    row_group_data
+                        .fetched_bytes
+                        .get_range(byte_range.start as usize..byte_range.end as usize),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        polars_io::prelude::_internal::to_deserializer(
+            columns_to_deserialize,
+            arrow_field.clone(),
+            filter,
+        )?
+    };
 
     if !skip_num_rows_check {
         assert_eq!(array.len(), expected_num_rows);
@@ -283,6 +316,68 @@ fn decode_column(
     Ok((series.into_column(), pred_true_mask))
 }
 
+/// Deserializes the rows set in `row_group_mask` from a column of which only the selected `pages`
+/// were fetched. Those hold a superset of the rows set in the mask.
+fn deserialize_selected_pages(
+    arrow_field: &ArrowField,
+    col_md: &ColumnChunkMetadata,
+    pages: &ColumnPages,
+    row_group_data: &RowGroupData,
+    row_group_mask: &Bitmap,
+) -> PolarsResult<(ArrayRef, Bitmap)> {
+    let chunk = match pages.byte_ranges.as_slice() {
+        [byte_range] => row_group_data.fetched_bytes.get_range(byte_range.clone()),
+        byte_ranges => {
+            let mut buf = Vec::with_capacity(byte_ranges.iter().map(|r| r.len()).sum());
+            for byte_range in byte_ranges {
+                buf.extend_from_slice(&row_group_data.fetched_bytes.get_range(byte_range.clone()));
+            }
+            MemSlice::from_vec(buf)
+        },
+    };
+
+    polars_io::prelude::_internal::to_page_deserializer(
+        col_md,
+        chunk,
+        pages.num_rows(),
+        arrow_field.clone(),
+        Some(Filter::Mask(pages.row_mask(row_group_mask))),
+    )
+}
+
+/// Returns the column chunk of `arrow_field` and its pages if only the pages holding the selected
+/// rows of the row group were fetched.
+fn selected_column_pages<'a>(
+    arrow_field: &ArrowField,
+    row_group_data: &'a RowGroupData,
+) -> Option<(&'a ColumnChunkMetadata, &'a ColumnPages, &'a PageSelection)> {
+    let page_selection = row_group_data.page_selection.as_ref()?;
+    let [idx] = row_group_data
+        .row_group_metadata
+        .columns_idxs_under_root_iter(&arrow_field.name)?
+    else {
+        return None;
+    };
+    let pages = page_selection.column_pages.get(idx)?;
+
+    Some((
+        &row_group_data.row_group_metadata.parquet_columns()[*idx],
+        pages,
+        page_selection,
+    ))
+}
+
+/// Expands `mask`, a mask over the rows set in `page_mask`, to a mask over all rows of the row
+/// group.
+fn expand_mask(page_mask: &Bitmap, mask: &Bitmap) -> Bitmap {
+    assert_eq!(page_mask.set_bits(), mask.len());
+    let mut selected = mask.iter();
+    page_mask
+        .iter()
+        .map(|is_selected| is_selected && selected.next().unwrap())
+        .collect()
+}
+
 /// # Safety
 /// All series in `cols` have the same length.
 async unsafe fn filter_cols(
@@ -358,9 +453,10 @@ fn decode_column_in_filter(
     use_column_predicates: bool,
     column_predicates: &ColumnPredicates,
     row_group_data: &RowGroupData,
+    page_mask: Option<&Bitmap>,
     projection_height: usize,
 ) -> PolarsResult<(Column, Bitmap)> {
-    let mut filter = None;
+    let mut filter = page_mask.map(|m| Filter::Mask(m.clone()));
     let mut constant = None;
     if use_column_predicates {
         if let Some((column_predicate, specialized)) =
@@ -405,6 +501,16 @@ impl RowGroupDecoder {
         let row_group_data = Arc::new(row_group_data);
         let projection_height = row_group_data.row_group_metadata.num_rows();
 
+        // With a page selection the predicate columns are only decoded for the selected rows, and
+        // the predicate mask is expanded back to all rows of the row group afterwards.
+        let page_mask = row_group_data
+            .page_selection
+            .as_ref()
+            .map(|s| s.row_mask.clone());
+        let live_height = page_mask
+            .as_ref()
+            .map_or(projection_height, |m| m.set_bits());
+
         let mut live_columns = Vec::with_capacity(
             self.row_index.is_some() as usize
                 + self.predicate_arrow_field_indices.len()
@@ -418,13 +524,17 @@ impl RowGroupDecoder {
             row_group_data.as_ref(),
             0..row_group_data.row_group_metadata.num_rows(),
         )? {
-            live_columns.push(s);
+            live_columns.push(match &page_mask {
+                Some(m) => s.filter(&BooleanChunked::from_bitmap(PlSmallStr::EMPTY, m.clone()))?,
+                None => s,
+            });
         }
 
         let scan_predicate = self.predicate.as_ref().unwrap();
 
         let use_column_predicates = scan_predicate.column_predicates.is_sumwise_complete
             && self.row_index.is_none()
+            && page_mask.is_none()
             && !row_group_data
                 .row_group_metadata
                 .parquet_columns()
@@ -450,6 +560,7 @@ impl RowGroupDecoder {
             let predicate_arrow_field_indices = self.predicate_arrow_field_indices.clone();
             let projected_arrow_schema = self.projected_arrow_schema.clone();
             let row_group_data = row_group_data.clone();
+            let page_mask = page_mask.clone();
 
             parallelize_first_to_local(
                 (0..self.predicate_arrow_field_indices.len())
@@ -459,6 +570,7 @@ impl RowGroupDecoder {
                         let predicate_arrow_field_indices = predicate_arrow_field_indices.clone();
                         let projected_arrow_schema = projected_arrow_schema.clone();
                         let column_predicates = scan_predicate.column_predicates.clone();
+                        let page_mask = page_mask.clone();
 
                         async move {
                             (offset
@@ -475,7 +587,8 @@ impl RowGroupDecoder {
                                         use_column_predicates,
                                         column_predicates.as_ref(),
                                         row_group_data.as_ref(),
-                                        projection_height,
+                                        page_mask.as_ref(),
+                                        live_height,
                                     )
                                 })
                                 .collect::<PolarsResult<Vec<_>>>()
@@ -521,9 +634,7 @@ impl RowGroupDecoder {
                 (DataFrame::new(live_columns).unwrap(), mask)
             }
         } else {
-            let mut live_df = unsafe {
-                DataFrame::new_no_checks(row_group_data.row_group_metadata.num_rows(), live_columns)
-            };
+            let mut live_df = unsafe { DataFrame::new_no_checks(live_height, live_columns) };
 
             let mask = scan_predicate.predicate.evaluate_io(&live_df)?;
             let mask = mask.bool().unwrap();
@@ -564,10 +675,14 @@ impl RowGroupDecoder {
 
         mask.rechunk_mut();
         let mask_bitmap = mask.downcast_as_array();
-        let mask_bitmap = match mask_bitmap.validity() {
+        let mut mask_bitmap = match mask_bitmap.validity() {
             None => mask_bitmap.values().clone(),
             Some(v) => mask_bitmap.values() & v,
         };
+        if let Some(page_mask) = &page_mask {
+            mask_bitmap = expand_mask(page_mask, &mask_bitmap);
+            mask = BooleanChunked::from_bitmap(PlSmallStr::EMPTY, mask_bitmap.clone());
+        }
 
         assert_eq!(mask_bitmap.len(), projection_height);
 
@@ -655,29 +770,34 @@ fn decode_column_prefiltered(
         ));
     };
 
-    let columns_to_deserialize = iter
-        .map(|col_md| {
-            let byte_range = col_md.byte_range();
-
-            (
-                col_md,
-                row_group_data
-                    .fetched_bytes
-                    .get_range(byte_range.start as usize..byte_range.end as usize),
-            )
-        })
-        .collect::<Vec<_>>();
-
     let prefilter = !arrow_field.dtype.is_nested();
 
-    let deserialize_filter =
-        prefilter.then(|| polars_parquet::read::Filter::Mask(mask_bitmap.clone()));
+    let (array, _) =
+        if let Some((col_md, pages, _)) = selected_column_pages(arrow_field, row_group_data) {
+            deserialize_selected_pages(arrow_field, col_md, pages, row_group_data, mask_bitmap)?
+        } else {
+            let columns_to_deserialize = iter
+                .map(|col_md| {
+                    let byte_range = col_md.byte_range();
+
+                    (
+                        col_md,
+                        row_group_data
+                            .fetched_bytes
+                            .get_range(byte_range.start as usize..byte_range.end as usize),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let deserialize_filter =
+                prefilter.then(|| polars_parquet::read::Filter::Mask(mask_bitmap.clone()));
 
-    let (array, _) = polars_io::prelude::_internal::to_deserializer(
-        columns_to_deserialize,
-        arrow_field.clone(),
-        deserialize_filter,
-    )?;
+            polars_io::prelude::_internal::to_deserializer(
+                columns_to_deserialize,
+                arrow_field.clone(),
+                deserialize_filter,
+            )?
+        };
 
     let mut series = Series::try_from((arrow_field, array))?;
 
@@ -0,0 +1,673 @@
+use std::sync::Arc;
+
+use polars_core::prelude::*;
+use polars_core::schema::Schema;
+use polars_expr::groups::{Grouper, new_hash_grouper};
+use polars_expr::hash_keys::HashKeys;
+use polars_ops::frame::{AsOfOptions, AsofJoin, AsofJoinBy, AsofStrategy, JoinArgs, JoinType};
+use polars_utils::abs_diff::AbsDiff;
+use polars_utils::itertools::Itertools;
+use polars_utils::pl_str::PlSmallStr;
+use polars_utils::unique_column_name;
+
+use crate::async_primitives::connector::Receiver;
+use crate::morsel::{SourceToken, get_ideal_morsel_size};
+use crate::nodes::compute_node_prelude::*;
+
+/// Dispatches on the physical dtypes of the as-of keys we support.
+macro_rules! with_match_key_type {(
+    $key_type:expr, | $_:tt $T:ident | $($body:tt)*
+) => ({
+    macro_rules! __with_ty__ {( $_ $T:ident ) => ( $($body)* )}
+    match $key_type {
+        DataType::Int32 => __with_ty__! { Int32Type },
+        DataType::Int64 => __with_ty__! { Int64Type },
+        DataType::UInt32 => __with_ty__! { UInt32Type },
+        DataType::UInt64 => __with_ty__! { UInt64Type },
+        DataType::Float32 => __with_ty__! { Float32Type },
+        DataType::Float64 => __with_ty__! { Float64Type },
+        dt => unreachable!("unsupported asof key dtype {dt:?}"),
+    }
+})}
+
+/// The as-of key bounds of each `by` group seen so far. Without `by` groups
+/// all rows belong to group 0.
+trait KeyBounds: Send + Sync {
+    /// Makes room for the given number of groups.
+    fn resize(&mut self, num_groups: usize);
+
+    /// Checks that the non-null left keys are sorted within their groups and
+    /// not smaller than the maxima of their groups, updating these.
+    fn update_left(&mut self, key: &Series, groups: &[IdxSize]) -> bool;
+
+    /// Checks that the non-null right keys are sorted within their groups and
+    /// not smaller than the maxima of their groups, updating these.
+    fn update_right(&mut self, key: &Series, groups: &[IdxSize]) -> bool;
+
+    /// Returns the number of leading left rows for which a right row with a
+    /// larger key was seen in their group.
+    fn candidate_len(&self, left_key: &Series, groups: &[IdxSize]) -> usize;
+
+    /// Returns the number of leading candidate left rows whose match (the
+    /// right key it was matched with) can not change anymore when more right
+    /// rows arrive.
+    fn final_len(&self, left_key: &Series, groups: &[IdxSize], matched: &Series) -> usize;
+
+    /// Returns which right rows of the window can still be matched by a
+    /// current or future left row, or None if all of them can.
+    fn window_mask(
+        &self,
+        left_key: &Series,
+        left_groups: &[IdxSize],
+        right_key: &Series,
+        right_groups: &[IdxSize],
+    ) -> Option<Vec<bool>>;
+}
+
+struct NumericKeyBounds<T: PolarsNumericType>
+where
+    T::Native: AbsDiff,
+{
+    left_max: Vec<Option<T::Native>>,
+    right_max: Vec<Option<T::Native>>,
+    strategy: AsofStrategy,
+    tolerance: Option<<T::Native as AbsDiff>::Abs>,
+}
+
+fn new_key_bounds(
+    key_dtype: &DataType,
+    strategy: AsofStrategy,
+    tolerance: Option<&AnyValue<'static>>,
+) -> PolarsResult<Box<dyn KeyBounds>> {
+    with_match_key_type!(key_dtype, |$T| {
+        let tolerance = match tolerance {
+            Some(t) => {
+                let t: <$T as PolarsNumericType>::Native = t.try_extract()?;
+                Some(t.abs_diff(Default::default()))
+            },
+            None => None,
+        };
+        let bounds: Box<dyn KeyBounds> = Box::new(NumericKeyBounds::<$T> {
+            left_max: Vec::new(),
+            right_max: Vec::new(),
+            strategy,
+            tolerance,
+        });
+        Ok(bounds)
+    })
+}
+
+/// Checks that the non-null values are sorted within their groups and not
+/// smaller than the previous maximum of their group, updating it.
+fn update_max_if_sorted<T>(
+    key: &ChunkedArray<T>,
+    groups: &[IdxSize],
+    max: &mut [Option<T::Native>],
+) -> bool
+where
+    T: PolarsNumericType,
+{
+    for (v, &g) in key.iter().zip(groups) {
+        let Some(v) = v else {
+            continue;
+        };
+        let max = &mut max[g as usize];
+        if max.is_some_and(|m| v < m) {
+            return false;
+        }
+        *max = Some(v);
+    }
+    true
+}
+
+impl<T: PolarsNumericType> KeyBounds for NumericKeyBounds<T>
+where
+    T::Native: AbsDiff,
+{
+    fn resize(&mut self, num_groups: usize) {
+        self.left_max.resize(num_groups, None);
+        self.right_max.resize(num_groups, None);
+    }
+
+    fn update_left(&mut self, key: &Series, groups: &[IdxSize]) -> bool {
+        update_max_if_sorted::<T>(key.as_ref().as_ref(), groups, &mut self.left_max)
+    }
+
+    fn update_right(&mut self, key: &Series, groups: &[IdxSize]) -> bool {
+        update_max_if_sorted::<T>(key.as_ref().as_ref(), groups, &mut self.right_max)
+    }
+
+    fn candidate_len(&self, left_key: &Series, groups: &[IdxSize]) -> usize {
+        let left_key: &ChunkedArray<T> = left_key.as_ref().as_ref();
+        left_key
+            .iter()
+            .zip(groups)
+            .take_while(|(l, g)| match l {
+                None => true,
+                Some(l) => self.right_max[**g as usize].is_some_and(|r| *l < r),
+            })
+            .count()
+    }
+
+    fn final_len(&self, left_key: &Series, groups: &[IdxSize], matched: &Series) -> usize {
+        let left_key: &ChunkedArray<T> = left_key.as_ref().as_ref();
+        let matched: &ChunkedArray<T> = matched.as_ref().as_ref();
+        left_key
+            .iter()
+            .zip(matched.iter())
+            .zip(groups)
+            .take_while(|((l, matched), g)| {
+                let Some(l) = *l else {
+                    return true;
+                };
+                // Any right row of this group we haven't seen yet has a key
+                // of at least right_max, which is larger than l.
+                let Some(right_max) = self.right_max[**g as usize] else {
+                    return false;
+                };
+                if self.tolerance.is_some_and(|t| right_max.abs_diff(l) > t) {
+                    return true;
+                }
+                match (self.strategy, matched) {
+                    (AsofStrategy::Backward, _) => true,
+                    // Later right rows can't precede a match.
+                    (AsofStrategy::Forward, m) => m.is_some(),
+                    (AsofStrategy::Nearest, Some(m)) if *m > l => {
+                        // Later rows with an equal key would take precedence.
+                        right_max > *m
+                    },
+                    (AsofStrategy::Nearest, Some(m)) => right_max.abs_diff(l) > l.abs_diff(*m),
+                    (AsofStrategy::Nearest, None) => false,
+                }
+            })
+            .count()
+    }
+
+    fn window_mask(
+        &self,
+        left_key: &Series,
+        left_groups: &[IdxSize],
+        right_key: &Series,
+        right_groups: &[IdxSize],
+    ) -> Option<Vec<bool>> {
+        // All left rows of a group still to be joined have a key of at least
+        // the first pending key of that group, or the largest key of that
+        // group if none are pending. Groups without any left rows so far have
+        // no bound.
+        let left_key: &ChunkedArray<T> = left_key.as_ref().as_ref();
+        let mut lower_bounds = self.left_max.clone();
+        let mut has_pending = vec![false; lower_bounds.len()];
+        for (l, &g) in left_key.iter().zip(left_groups) {
+            if let Some(l) = l {
+                if !core::mem::replace(&mut has_pending[g as usize], true) {
+                    lower_bounds[g as usize] = Some(l);
+                }
+            }
+        }
+
+        // Of the right rows below the bound of their group only the last one
+        // can still be matched.
+        let right_key: &ChunkedArray<T> = right_key.as_ref().as_ref();
+        let mut seen_below = vec![false; lower_bounds.len()];
+        let mut mask = vec![true; right_key.len()];
+        let mut any_pruned = false;
+        for ((r, &g), keep) in right_key
+            .iter()
+            .zip(right_groups)
+            .zip(mask.iter_mut())
+            .rev()
+        {
+            // The window contains no nulls.
+            let r = r.unwrap();
+            if lower_bounds[g as usize].is_some_and(|b| r < b)
+                && core::mem::replace(&mut seen_below[g as usize], true)
+            {
+                *keep = false;
+                any_pruned = true;
+            }
+        }
+        any_pruned.then_some(mask)
+    }
+}
+
+/// Assigns the rows of both inputs to their `by` group.
+struct ByGrouper {
+    grouper: Box<dyn Grouper>,
+    random_state: PlRandomState,
+}
+
+/// Performs an as-of join of two inputs which are sorted by their as-of key
+/// within each `by` group.
+///
+/// Left rows are buffered until the right input has progressed far enough in
+/// their group that no later right row can change their match, after which
+/// they are joined against a window of right rows using the in-memory as-of
+/// kernels. Right rows which can no longer be matched by any (future) left row
+/// of their group are pruned from the window.
+///
+/// Memory is bounded by the right rows that are still in range as long as all
+/// `by` groups keep appearing in both inputs. A left row of a group which the
+/// right input has not reached yet holds back the rows after it, and the right
+/// rows of a group without left rows can't be pruned.
+pub struct AsOfJoinNode {
+    left_on: PlSmallStr,
+    right_on: PlSmallStr,
+    left_by: Vec<PlSmallStr>,
+    right_by: Vec<PlSmallStr>,
+    strategy: AsofStrategy,
+    tolerance: Option<AnyValue<'static>>,
+    allow_eq: bool,
+    suffix: Option<PlSmallStr>,
+    coalesce: bool,
+
+    /// Name of the copy of the right key we add to the window to find out
+    /// which right row each left row matched.
+    matched_key: PlSmallStr,
+    bounds: Box<dyn KeyBounds>,
+    by_grouper: Option<ByGrouper>,
+
+    /// Left rows which have not been joined yet, and their groups.
+    left_pending: DataFrame,
+    left_groups: Vec<IdxSize>,
+    /// The right rows which might still be matched by a left row, and their
+    /// groups.
+    right_window: DataFrame,
+    right_groups: Vec<IdxSize>,
+    /// Size of the window after it was last pruned.
+    pruned_height: usize,
+    right_done: bool,
+    seq: MorselSeq,
+}
+
+impl AsOfJoinNode {
+    pub fn new(
+        left_input_schema: Arc<Schema>,
+        right_input_schema: Arc<Schema>,
+        left_on: PlSmallStr,
+        right_on: PlSmallStr,
+        args: JoinArgs,
+    ) -> PolarsResult<Self> {
+        let coalesce = args.should_coalesce();
+        let JoinType::AsOf(options) = args.how else {
+            unreachable!()
+        };
+        let AsOfOptions {
+            strategy,
+            tolerance,
+            tolerance_str: _,
+            left_by,
+            right_by,
+            allow_eq,
+            check_sortedness: _,
+        } = options;
+
+        let key_dtype = left_input_schema.try_get(&left_on)?.to_physical();
+        let right_key_dtype = right_input_schema.try_get(&right_on)?;
+        polars_ensure!(
+            key_dtype == right_key_dtype.to_physical(),
+            ComputeError: "mismatching key dtypes in asof-join: `{}` and `{}`",
+            left_input_schema.get(&left_on).unwrap(), right_key_dtype
+        );
+
+        let matched_key = unique_column_name();
+        let mut window_schema = right_input_schema.as_ref().clone();
+        window_schema.insert(matched_key.clone(), right_key_dtype.clone());
+
+        let left_by = left_by.unwrap_or_default();
+        let right_by = right_by.unwrap_or_default();
+        let mut bounds = new_key_bounds(&key_dtype, strategy, tolerance.as_ref())?;
+        let by_grouper = if left_by.is_empty() {
+            bounds.resize(1);
+            None
+        } else {
+            let by_schema = left_by
+                .iter()
+                .map(|name| {
+                    let dtype = left_input_schema.try_get(name)?.to_physical();
+                    Ok(Field::new(name.clone(), dtype))
+                })
+                .collect::<PolarsResult<Schema>>()?;
+            Some(ByGrouper {
+                grouper: new_hash_grouper(Arc::new(by_schema)),
+                random_state: PlRandomState::default(),
+            })
+        };
+
+        Ok(Self {
+            left_on,
+            right_on,
+            left_by,
+            right_by,
+            strategy,
+            tolerance,
+            allow_eq,
+            suffix: args.suffix,
+            coalesce,
+            matched_key,
+            bounds,
+            by_grouper,
+            left_pending: DataFrame::empty_with_schema(&left_input_schema),
+            left_groups: Vec::new(),
+            right_window: DataFrame::empty_with_schema(&window_schema),
+            right_groups: Vec::new(),
+            pruned_height: 0,
+            right_done: false,
+            seq: MorselSeq::default(),
+        })
+    }
+
+    /// Returns the `by` group of each row of the left or right input.
+    fn group_idxs(&mut self, df: &DataFrame, is_left: bool) -> PolarsResult<Vec<IdxSize>> {
+        let by = if is_left {
+            &self.left_by
+        } else {
+            &self.right_by
+        };
+        let Some(by_grouper) = &mut self.by_grouper else {
+            return Ok(vec![0; df.height()]);
+        };
+        let keys = df
+            .select_columns(by.iter().cloned())?
+            .into_iter()
+            .map(|c| c.to_physical_repr())
+            .collect();
+        let keys = unsafe { DataFrame::new_no_checks(df.height(), keys) };
+        let hash_keys = HashKeys::from_df(&keys, by_grouper.random_state, true, false);
+        let subset = (0..df.height() as IdxSize).collect_vec();
+        let mut groups = Vec::with_capacity(df.height());
+        unsafe {
+            by_grouper
+                .grouper
+                .insert_keys_subset(&hash_keys, &subset, Some(&mut groups));
+        }
+        self.bounds.resize(by_grouper.grouper.num_groups() as usize);
+        Ok(groups)
+    }
+
+    fn add_left(&mut self, df: DataFrame) -> PolarsResult<()> {
+        let groups = self.group_idxs(&df, true)?;
+        let key = df.column(&self.left_on)?.to_physical_repr();
+        let sorted = self
+            .bounds
+            .update_left(key.as_materialized_series(), &groups);
+        polars_ensure!(
+            sorted,
+            InvalidOperation: "the streaming asof join requires the left input to be sorted by \
+            its 'on' key{}; sort it first or use the in-memory engine",
+            if self.left_by.is_empty() { "" } else { " within each 'by' group" }
+        );
+        self.left_pending.vstack_mut_owned_unchecked(df);
+        self.left_groups.extend(groups);
+        Ok(())
+    }
+
+    fn add_right(&mut self, df: DataFrame) -> PolarsResult<()> {
+        // Right rows with a null key never match anything.
+        let key = df.column(&self.right_on)?;
+        let mut df = if key.has_nulls() {
+            df.filter(&key.is_not_null())?
+        } else {
+            df
+        };
+        let groups = self.group_idxs(&df, false)?;
+        let key = df.column(&self.right_on)?.clone();
+        let physical_key = key.to_physical_repr();
+        let sorted = self
+            .bounds
+            .update_right(physical_key.as_materialized_series(), &groups);
+        polars_ensure!(
+            sorted,
+            InvalidOperation: "the streaming asof join requires the right input to be sorted by \
+            its 'on' key{}; sort it first or use the in-memory engine",
+            if self.right_by.is_empty() { "" } else { " within each 'by' group" }
+        );
+        unsafe { df.with_column_unchecked(key.with_name(self.matched_key.clone())) };
+        self.right_window.vstack_mut_owned_unchecked(df);
+        self.right_groups.extend(groups);
+        Ok(())
+    }
+
+    /// Joins and returns all pending left rows whose match can no longer be
+    /// changed by right rows we haven't seen yet.
+    fn join_final(&mut self) -> PolarsResult<Option<DataFrame>> {
+        if self.left_pending.height() == 0 {
+            return Ok(None);
+        }
+
+        let left_key = self.left_pending.column(&self.left_on)?.to_physical_repr();
+        let left_key = left_key.as_materialized_series();
+        let num_candidates = if self.right_done {
+            self.left_pending.height()
+        } else {
+            self.bounds.candidate_len(left_key, &self.left_groups)
+        };
+        if num_candidates == 0 {
+            return Ok(None);
+        }
+
+        let candidates = self.left_pending.slice(0, num_candidates);
+        let mut joined = self.join(&candidates)?;
+
+        let num_final = if self.right_done {
+            num_candidates
+        } else {
+            let left_key = candidates.column(&self.left_on)?.to_physical_repr();
+            let matched = joined.column(&self.matched_key)?.to_physical_repr();
+            self.bounds.final_len(
+                left_key.as_materialized_series(),
+                &self.left_groups[..num_candidates],
+                matched.as_materialized_series(),
+            )
+        };
+        if num_final == 0 {
+            return Ok(None);
+        }
+
+        joined = joined.slice(0, num_final);
+        joined.drop_in_place(&self.matched_key)?;
+        self.left_pending = self.left_pending.slice(num_final as i64, usize::MAX);
+        self.left_groups.drain(..num_final);
+        self.prune_window()?;
+        Ok(Some(joined))
+    }
+
+    fn join(&self, left: &DataFrame) -> PolarsResult<DataFrame> {
+        let left_key = left.column(&self.left_on)?.as_materialized_series();
+        let right_key = self
+            .right_window
+            .column(&self.right_on)?
+            .as_materialized_series();
+        if self.left_by.is_empty() {
+            left._join_asof(
+                &self.right_window,
+                left_key,
+                right_key,
+                self.strategy,
+                self.tolerance.clone(),
+                self.suffix.clone(),
+                None,
+                self.coalesce,
+                self.allow_eq,
+                false,
+            )
+        } else {
+            left._join_asof_by(
+                &self.right_window,
+                left_key,
+                right_key,
+                self.left_by.clone(),
+                self.right_by.clone(),
+                self.strategy,
+                self.tolerance.clone(),
+                self.suffix.clone(),
+                None,
+                self.coalesce,
+                self.allow_eq,
+                false,
+            )
+        }
+    }
+
+    /// Removes the right rows which no current or future left row can match.
+    fn prune_window(&mut self) -> PolarsResult<()> {
+        if self.right_window.height() < (2 * self.pruned_height).max(get_ideal_morsel_size()) {
+            return Ok(());
+        }
+
+        let left_key = self.left_pending.column(&self.left_on)?.to_physical_repr();
+        let right_key = self.right_window.column(&self.right_on)?.to_physical_repr();
+        let mask = self.bounds.window_mask(
+            left_key.as_materialized_series(),
+            &self.left_groups,
+            right_key.as_materialized_series(),
+            &self.right_groups,
+        );
+        if let Some(mask) = mask {
+            let mut keep = mask.iter();
+            self.right_groups.retain(|_| *keep.next().unwrap());
+            let mask = BooleanChunked::from_slice(PlSmallStr::EMPTY, &mask);
+            self.right_window = self.right_window.filter(&mask)?;
+        }
+        self.pruned_height = self.right_window.height();
+        Ok(())
+    }
+}
+
+impl ComputeNode for AsOfJoinNode {
+    fn name(&self) -> &str {
+        "asof-join"
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        _state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 2 && send.len() == 1);
+
+        if recv[1] == PortState::Done {
+            self.right_done = true;
+        }
+
+        let left_done = recv[0] == PortState::Done && self.left_pending.height() == 0;
+        if send[0] == PortState::Done || left_done {
+            self.left_pending = self.left_pending.clear();
+            self.left_groups = Vec::new();
+            self.right_window = self.right_window.clear();
+            self.right_groups = Vec::new();
+            recv[0] = PortState::Done;
+            recv[1] = PortState::Done;
+            send[0] = PortState::Done;
+            return Ok(());
+        }
+
+        // We need more left rows if we have none pending, otherwise we need
+        // more right rows to be able to join them.
+        let send_blocked = send[0] == PortState::Blocked;
+        let left_blocked = recv[0] == PortState::Blocked && self.left_pending.height() == 0;
+        let right_blocked = recv[1] == PortState::Blocked && self.left_pending.height() > 0;
+        send[0] = if left_blocked || right_blocked {
+            PortState::Blocked
+        } else {
+            PortState::Ready
+        };
+        if recv[0] != PortState::Done {
+            recv[0] = if send_blocked || right_blocked {
+                PortState::Blocked
+            } else {
+                PortState::Ready
+            };
+        }
+        if recv[1] != PortState::Done {
+            recv[1] = if send_blocked || left_blocked {
+                PortState::Blocked
+            } else {
+                PortState::Ready
+            };
+        }
+        Ok(())
+    }
+
+    fn memory_usage(&self) -> Option<usize> {
+        Some(self.left_pending.estimated_size() + self.right_window.estimated_size())
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        _state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(recv_ports.len() == 2 && send_ports.len() == 1);
+        let mut left = recv_ports[0].take().map(|p| p.serial());
+        let mut right = recv_ports[1].take().map(|p| p.serial());
+        let mut send = send_ports[0].take().unwrap().serial();
+
+        let slf = &mut *self;
+        join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+            /// Stops the source of a port and adds all remaining morsels.
+            async fn buffer_rest(
+                port: &mut Receiver<Morsel>,
+                mut add: impl FnMut(DataFrame) -> PolarsResult<()>,
+            ) -> PolarsResult<()> {
+                while let Ok(morsel) = port.recv().await {
+                    morsel.source_token().stop();
+                    add(morsel.into_df())?;
+                }
+                Ok(())
+            }
+
+            let source_token = SourceToken::new();
+            loop {
+                if let Some(df) = slf.join_final()? {
+                    let morsel = Morsel::new(df, slf.seq, source_token.clone());
+                    slf.seq = slf.seq.successor();
+                    if send.send(morsel).await.is_err() {
+                        return Ok(());
+                    }
+                }
+
+                // Read from the left if we have nothing to join, otherwise we
+                // need more right rows to join what we have. Once the right
+                // input is done every left row can be joined right away, so
+                // we stream them instead of buffering the rest of the left.
+                let need_left = slf.left_pending.height() == 0 || slf.right_done;
+                let port = if need_left {
+                    left.as_mut()
+                } else {
+                    right.as_mut()
+                };
+                let morsel = match port {
+                    Some(port) if !source_token.stop_requested() => port.recv().await.ok(),
+                    _ => None,
+                };
+                let Some(morsel) = morsel else {
+                    if let Some(port) = &mut left {
+                        buffer_rest(port, |df| slf.add_left(df)).await?;
+                    }
+                    if let Some(port) = &mut right {
+                        buffer_rest(port, |df| slf.add_right(df)).await?;
+                    }
+                    break;
+                };
+
+                let df = morsel.into_df();
+                if need_left {
+                    slf.add_left(df)?;
+                } else {
+                    slf.add_right(df)?;
+                }
+            }
+
+            if let Some(df) = slf.join_final()? {
+                let morsel = Morsel::new(df, slf.seq, source_token);
+                slf.seq = slf.seq.successor();
+                _ = send.send(morsel).await;
+            }
+            Ok(())
+        }));
+    }
+}
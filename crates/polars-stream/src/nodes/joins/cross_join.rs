@@ -35,7 +35,7 @@ impl CrossJoinNode {
         } else {
             &right_input_schema
         };
-        let sink_node = InMemorySinkNode::new(build_input_schema.clone());
+        let sink_node = InMemorySinkNode::new_for_node(build_input_schema.clone(), "cross-join");
         let right_rename = right_input_schema
             .iter_names()
             .map(|rname| {
@@ -1,12 +1,14 @@
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 
 use arrow::array::builder::ShareStrategy;
+use crossbeam_queue::ArrayQueue;
 use polars_core::frame::builder::DataFrameBuilder;
 use polars_core::prelude::*;
 use polars_core::schema::{Schema, SchemaExt};
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
 use polars_core::{POOL, config};
 use polars_expr::hash_keys::HashKeys;
 use polars_expr::idx_table::{IdxTable, new_idx_table};
@@ -22,14 +24,26 @@ use polars_utils::sparse_init_vec::SparseInitVec;
 use polars_utils::{IdxSize, format_pl_smallstr};
 use rayon::prelude::*;
 
-use super::{BufferedStream, JOIN_SAMPLE_LIMIT, LOPSIDED_SAMPLE_FACTOR};
+use super::runtime_filter::{RuntimeFilterBuilder, RuntimeFilterHashBudget, RuntimeJoinFilter};
+use super::{BufferedStream, JOIN_SAMPLE_LIMIT, SampleState};
 use crate::async_executor;
-use crate::async_primitives::connector::{Receiver, Sender};
+use crate::async_primitives::connector::{Receiver, Sender, connector};
 use crate::async_primitives::wait_group::WaitGroup;
+use crate::execute::MemoryReservation;
 use crate::expression::StreamExpr;
 use crate::morsel::{SourceToken, get_ideal_morsel_size};
 use crate::nodes::compute_node_prelude::*;
 use crate::nodes::in_memory_source::InMemorySourceNode;
+use crate::utils::spill::{LazySpillDir, MaybeSpilled, can_spill, get_spill_threshold};
+
+/// The number of partitions both sides of the join are split into once the
+/// build side no longer fits in memory.
+const NUM_SPILL_PARTITIONS: usize = 32;
+
+/// How often the rows of a spill partition may be partitioned again because
+/// its build side doesn't fit in memory. Beyond this the keys are likely too
+/// skewed to split, and the partition is joined in memory regardless.
+const MAX_SPILL_DEPTH: u64 = 3;
 
 struct EquiJoinParams {
     left_is_build: Option<bool>,
@@ -46,6 +60,8 @@ struct EquiJoinParams {
     right_payload_schema: Arc<Schema>,
     args: JoinArgs,
     random_state: PlRandomState,
+    left_runtime_filter: Option<Arc<RuntimeJoinFilter>>,
+    right_runtime_filter: Option<Arc<RuntimeJoinFilter>>,
 }
 
 impl EquiJoinParams {
@@ -66,11 +82,29 @@ impl EquiJoinParams {
             self.args.how == JoinType::Left || self.args.how == JoinType::Full
         }
     }
+
+    /// The number of key columns on either side.
+    fn num_keys(&self) -> usize {
+        self.left_key_selectors.len()
+    }
+
+    /// The runtime filter on the keys of the probe side, if dropping the probe
+    /// rows without a match doesn't change the result.
+    fn probe_runtime_filter(&self) -> Option<&Arc<RuntimeJoinFilter>> {
+        if self.emit_unmatched_probe() {
+            return None;
+        }
+        if self.left_is_build.unwrap() {
+            self.right_runtime_filter.as_ref()
+        } else {
+            self.left_runtime_filter.as_ref()
+        }
+    }
 }
 
 /// A payload selector contains for each column whether that column should be
 /// included in the payload, and if yes with what name.
-fn compute_payload_selector(
+pub(super) fn compute_payload_selector(
     this: &Schema,
     other: &Schema,
     this_key_schema: &Schema,
@@ -112,14 +146,18 @@ fn compute_payload_selector(
 }
 
 /// Fixes names and does coalescing of columns post-join.
-fn postprocess_join(df: DataFrame, params: &EquiJoinParams) -> DataFrame {
-    if params.args.how == JoinType::Full && params.args.should_coalesce() {
+pub(super) fn postprocess_join(
+    df: DataFrame,
+    args: &JoinArgs,
+    left_key_schema: &Schema,
+) -> DataFrame {
+    if args.how == JoinType::Full && args.should_coalesce() {
         // TODO: don't do string-based column lookups for each dataframe, pre-compute coalesce indices.
         let mut coalesce_idx = 0;
         df.get_columns()
             .iter()
             .filter_map(|c| {
-                if params.left_key_schema.contains(c.name()) {
+                if left_key_schema.contains(c.name()) {
                     let other = df
                         .column(&format_pl_smallstr!(
                             "__POLARS_COALESCE_KEYCOL{coalesce_idx}"
@@ -141,7 +179,7 @@ fn postprocess_join(df: DataFrame, params: &EquiJoinParams) -> DataFrame {
     }
 }
 
-fn select_schema(schema: &Schema, selector: &[Option<PlSmallStr>]) -> Schema {
+pub(super) fn select_schema(schema: &Schema, selector: &[Option<PlSmallStr>]) -> Schema {
     schema
         .iter_fields()
         .zip(selector)
@@ -149,17 +187,25 @@ fn select_schema(schema: &Schema, selector: &[Option<PlSmallStr>]) -> Schema {
         .collect()
 }
 
-async fn select_keys(
+async fn select_key_columns(
     df: &DataFrame,
     key_selectors: &[StreamExpr],
-    params: &EquiJoinParams,
     state: &ExecutionState,
-) -> PolarsResult<HashKeys> {
+) -> PolarsResult<DataFrame> {
     let mut key_columns = Vec::new();
     for selector in key_selectors {
         key_columns.push(selector.evaluate(df, state).await?.into_column());
     }
-    let keys = DataFrame::new_with_broadcast_len(key_columns, df.height())?;
+    DataFrame::new_with_broadcast_len(key_columns, df.height())
+}
+
+async fn select_keys(
+    df: &DataFrame,
+    key_selectors: &[StreamExpr],
+    params: &EquiJoinParams,
+    state: &ExecutionState,
+) -> PolarsResult<HashKeys> {
+    let keys = select_key_columns(df, key_selectors, state).await?;
     Ok(HashKeys::from_df(
         &keys,
         params.random_state,
@@ -168,7 +214,7 @@ async fn select_keys(
     ))
 }
 
-fn select_payload(df: DataFrame, selector: &[Option<PlSmallStr>]) -> DataFrame {
+pub(super) fn select_payload(df: DataFrame, selector: &[Option<PlSmallStr>]) -> DataFrame {
     // Maintain height of zero-width dataframes.
     if df.width() == 0 {
         return df;
@@ -181,6 +227,41 @@ fn select_payload(df: DataFrame, selector: &[Option<PlSmallStr>]) -> DataFrame {
         .collect()
 }
 
+/// Rows that might be spilled are kept as their payload followed by their key
+/// columns, which is all that's needed to join them later without evaluating
+/// the key selectors again.
+fn to_spill_form(payload: DataFrame, keys: DataFrame) -> DataFrame {
+    let height = keys.height();
+    let mut columns = payload.take_columns();
+    columns.extend(
+        keys.take_columns()
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| c.with_name(format_pl_smallstr!("__POLARS_SPILL_KEYCOL{i}"))),
+    );
+    unsafe { DataFrame::new_no_checks(height, columns) }
+}
+
+/// Splits rows in spill form into their payload and their key columns.
+fn from_spill_form(df: DataFrame, num_keys: usize) -> (DataFrame, DataFrame) {
+    let height = df.height();
+    let mut payload = df.take_columns();
+    let keys = payload.split_off(payload.len() - num_keys);
+    unsafe {
+        (
+            DataFrame::new_no_checks(height, payload),
+            DataFrame::new_no_checks(height, keys),
+        )
+    }
+}
+
+/// The hashed keys of rows in spill form.
+fn spill_form_hash_keys(df: &DataFrame, params: &EquiJoinParams) -> HashKeys {
+    let keys = df.get_columns()[df.width() - params.num_keys()..].to_vec();
+    let keys = unsafe { DataFrame::new_no_checks(df.height(), keys) };
+    HashKeys::from_df(&keys, params.random_state, params.args.nulls_equal, false)
+}
+
 fn estimate_cardinality(
     morsels: &[Morsel],
     key_selectors: &[StreamExpr],
@@ -230,57 +311,19 @@ fn estimate_cardinality(
     })
 }
 
-#[derive(Default)]
-struct SampleState {
-    left: Vec<Morsel>,
-    left_len: usize,
-    right: Vec<Morsel>,
-    right_len: usize,
-}
-
 impl SampleState {
-    async fn sink(
-        mut recv: Receiver<Morsel>,
-        morsels: &mut Vec<Morsel>,
-        len: &mut usize,
-        this_final_len: Arc<AtomicUsize>,
-        other_final_len: Arc<AtomicUsize>,
-    ) -> PolarsResult<()> {
-        while let Ok(mut morsel) = recv.recv().await {
-            *len += morsel.df().height();
-            if *len >= *JOIN_SAMPLE_LIMIT
-                || *len
-                    >= other_final_len
-                        .load(Ordering::Relaxed)
-                        .saturating_mul(LOPSIDED_SAMPLE_FACTOR)
-            {
-                morsel.source_token().stop();
-            }
-
-            drop(morsel.take_consume_token());
-            morsels.push(morsel);
-        }
-        this_final_len.store(*len, Ordering::Relaxed);
-        Ok(())
-    }
-
     fn try_transition_to_build(
         &mut self,
         recv: &[PortState],
         params: &mut EquiJoinParams,
+        spill: &JoinSpillState,
         state: &StreamingExecutionState,
     ) -> PolarsResult<Option<BuildState>> {
-        let left_saturated = self.left_len >= *JOIN_SAMPLE_LIMIT;
-        let right_saturated = self.right_len >= *JOIN_SAMPLE_LIMIT;
-        let left_done = recv[0] == PortState::Done || left_saturated;
-        let right_done = recv[1] == PortState::Done || right_saturated;
-        #[expect(clippy::nonminimal_bool)]
-        let stop_sampling = (left_done && right_done)
-            || (left_done && self.right_len >= LOPSIDED_SAMPLE_FACTOR * self.left_len)
-            || (right_done && self.left_len >= LOPSIDED_SAMPLE_FACTOR * self.right_len);
-        if !stop_sampling {
+        if !self.is_finished(recv) {
             return Ok(None);
         }
+        let left_saturated = self.left_len >= *JOIN_SAMPLE_LIMIT;
+        let right_saturated = self.right_len >= *JOIN_SAMPLE_LIMIT;
 
         if config::verbose() {
             eprintln!(
@@ -344,7 +387,6 @@ impl SampleState {
             core::mem::swap(&mut sampled_build_morsels, &mut sampled_probe_morsels);
         }
 
-        let partitioner = HashPartitioner::new(state.num_pipelines, 0);
         let mut build_state = BuildState::new(
             state.num_pipelines,
             state.num_pipelines,
@@ -352,42 +394,367 @@ impl SampleState {
         );
 
         // Simulate the sample build morsels flowing into the build side.
-        if !sampled_build_morsels.is_empty() {
-            crate::async_executor::task_scope(|scope| {
-                let mut join_handles = Vec::new();
-                let receivers = sampled_build_morsels
-                    .reinsert(state.num_pipelines, None, scope, &mut join_handles)
-                    .unwrap();
+        build_state.sink_buffered(sampled_build_morsels, params, Some(spill), state)?;
 
-                for (local_builder, recv) in build_state.local_builders.iter_mut().zip(receivers) {
-                    join_handles.push(scope.spawn_task(
-                        TaskPriority::High,
-                        BuildState::partition_and_sink(
-                            recv,
-                            local_builder,
-                            partitioner.clone(),
-                            params,
-                            state,
-                        ),
-                    ));
+        Ok(Some(build_state))
+    }
+}
+
+/// Tracks how much of the build side is buffered in memory. Once that crosses
+/// the spill threshold, or the query gets close to its memory budget, the join
+/// turns into a grace hash join: both inputs are radix-partitioned on their key
+/// hashes into spill files, after which the partitions are joined one at a time.
+struct JoinSpillState {
+    threshold: usize,
+    buffered_bytes: AtomicUsize,
+    active: AtomicBool,
+    dir: LazySpillDir,
+    // The build side held in memory, as accounted against the memory budget.
+    memory: Option<MemoryReservation>,
+}
+
+impl JoinSpillState {
+    fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            buffered_bytes: AtomicUsize::new(0),
+            active: AtomicBool::new(false),
+            dir: LazySpillDir::new("equi-join"),
+            memory: None,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    fn activate(&self) {
+        self.active.store(true, Ordering::Relaxed);
+    }
+
+    fn can_spill(&self) -> bool {
+        self.threshold != usize::MAX
+    }
+
+    fn track_buffered(&self, bytes: usize) {
+        let total = self.buffered_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        let under_pressure = self.memory.as_ref().is_some_and(|m| m.should_spill());
+        if total > self.threshold || under_pressure {
+            self.activate();
+        }
+    }
+}
+
+fn spill_partitioner(depth: u64) -> HashPartitioner {
+    // Use a different seed than the in-memory partitioner so the rows of a
+    // single spill partition still spread over all in-memory partitions, and a
+    // different seed per depth so a partition that is split again spreads over
+    // all its sub-partitions.
+    HashPartitioner::new(NUM_SPILL_PARTITIONS, 1 + depth)
+}
+
+/// Buffers input rows per spill partition and writes them to disk in batches.
+struct PartitionedSpiller {
+    partitioner: HashPartitioner,
+    buffers_per_p: Vec<Vec<DataFrame>>,
+    buffered_rows: usize,
+    spilled_per_p: Vec<Vec<MaybeSpilled>>,
+    idxs_per_p: Vec<Vec<IdxSize>>,
+}
+
+impl PartitionedSpiller {
+    fn new(depth: u64) -> Self {
+        Self {
+            partitioner: spill_partitioner(depth),
+            buffers_per_p: (0..NUM_SPILL_PARTITIONS).map(|_| Vec::new()).collect(),
+            buffered_rows: 0,
+            spilled_per_p: (0..NUM_SPILL_PARTITIONS).map(|_| Vec::new()).collect(),
+            idxs_per_p: vec![Vec::new(); NUM_SPILL_PARTITIONS],
+        }
+    }
+
+    /// Adds the rows of df to their partitions. Rows with null keys that can
+    /// never match are dropped unless partition_nulls is set.
+    fn push(
+        &mut self,
+        df: &DataFrame,
+        hash_keys: &HashKeys,
+        partition_nulls: bool,
+        dir: &LazySpillDir,
+    ) -> PolarsResult<()> {
+        for idxs in self.idxs_per_p.iter_mut() {
+            idxs.clear();
+        }
+        hash_keys.gen_idxs_per_partition(
+            &self.partitioner,
+            &mut self.idxs_per_p,
+            &mut [],
+            partition_nulls,
+        );
+
+        for (buffer, idxs) in self.buffers_per_p.iter_mut().zip(&self.idxs_per_p) {
+            if !idxs.is_empty() {
+                buffer.push(unsafe { df.take_slice_unchecked_impl(idxs, false) });
+                self.buffered_rows += idxs.len();
+            }
+        }
+
+        if self.buffered_rows >= get_ideal_morsel_size() {
+            self.flush(dir)?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, dir: &LazySpillDir) -> PolarsResult<()> {
+        if self.buffered_rows == 0 {
+            return Ok(());
+        }
+
+        let dir = dir.get()?;
+        for (buffer, spilled) in self.buffers_per_p.iter_mut().zip(&mut self.spilled_per_p) {
+            if !buffer.is_empty() {
+                let mut df = accumulate_dataframes_vertical_unchecked(buffer.drain(..));
+                spilled.push(MaybeSpilled::Spilled(dir.spill(&mut df)?));
+            }
+        }
+        self.buffered_rows = 0;
+        Ok(())
+    }
+
+    /// Returns the chunks of each partition, the rows that were still buffered
+    /// are kept in memory.
+    fn into_partitions(self) -> impl Iterator<Item = Vec<MaybeSpilled>> {
+        self.spilled_per_p
+            .into_iter()
+            .zip(self.buffers_per_p)
+            .map(|(mut spilled, buffer)| {
+                if !buffer.is_empty() {
+                    let df = accumulate_dataframes_vertical_unchecked(buffer);
+                    spilled.push(MaybeSpilled::InMemory(df));
                 }
+                spilled
+            })
+    }
+}
+
+/// The spilled rows of both inputs that belong to a single spill partition.
+#[derive(Default)]
+struct SpilledPartition {
+    build: Vec<MaybeSpilled>,
+    probe: Vec<MaybeSpilled>,
+    // How often these rows were partitioned.
+    depth: u64,
+}
+
+enum LoadedPartition {
+    InMemory {
+        build: Vec<DataFrame>,
+        probe: Vec<MaybeSpilled>,
+    },
+    Split(Vec<SpilledPartition>),
+}
 
-                polars_io::pl_async::get_runtime().block_on(async move {
-                    for handle in join_handles {
-                        handle.await?;
+impl SpilledPartition {
+    /// Loads the build side of this partition into memory. If it exceeds the
+    /// spill threshold or the memory budget both sides are partitioned again
+    /// instead, the probe side is never loaded as a whole.
+    fn load(
+        self,
+        params: &EquiJoinParams,
+        spill: &JoinSpillState,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<LoadedPartition> {
+        let mut build = Vec::with_capacity(self.build.len());
+        let mut build_bytes = 0;
+        let mut build_rows = 0;
+        let mut chunks = self.build.into_iter();
+        while let Some(chunk) = chunks.next() {
+            let df = chunk.load()?;
+            let df_bytes = df.estimated_size();
+            build_bytes += df_bytes;
+            build_rows += df.height();
+            let fits = build_bytes <= spill.threshold
+                && spill
+                    .memory
+                    .as_ref()
+                    .is_none_or(|m| m.grow(df_bytes).is_ok());
+            build.push(df);
+
+            // Splitting a partition smaller than a single morsel doesn't gain
+            // anything.
+            if !fits && self.depth < MAX_SPILL_DEPTH && build_rows >= get_ideal_morsel_size() {
+                if let Some(memory) = &spill.memory {
+                    memory.clear();
+                }
+                let build = build
+                    .into_iter()
+                    .map(MaybeSpilled::InMemory)
+                    .chain(chunks)
+                    .collect();
+                let depth = self.depth + 1;
+                let build_per_p = repartition(
+                    build,
+                    params.emit_unmatched_build(),
+                    depth,
+                    params,
+                    spill,
+                    state,
+                )?;
+                let probe_per_p = repartition(
+                    self.probe,
+                    params.emit_unmatched_probe(),
+                    depth,
+                    params,
+                    spill,
+                    state,
+                )?;
+                let partitions = build_per_p
+                    .into_iter()
+                    .zip(probe_per_p)
+                    .map(|(build, probe)| SpilledPartition {
+                        build,
+                        probe,
+                        depth,
+                    })
+                    .collect();
+                return Ok(LoadedPartition::Split(partitions));
+            }
+        }
+
+        Ok(LoadedPartition::InMemory {
+            build,
+            probe: self.probe,
+        })
+    }
+}
+
+/// Partitions the given chunks, which are in spill form, into spill partitions
+/// at the given depth. A task per pipeline loads one chunk at a time and
+/// writes its rows out again.
+fn repartition(
+    chunks: Vec<MaybeSpilled>,
+    partition_nulls: bool,
+    depth: u64,
+    params: &EquiJoinParams,
+    spill: &JoinSpillState,
+    state: &StreamingExecutionState,
+) -> PolarsResult<Vec<Vec<MaybeSpilled>>> {
+    let queue = ArrayQueue::new(chunks.len().max(1));
+    for chunk in chunks {
+        let _ = queue.push(chunk);
+    }
+
+    let mut spillers = (0..state.num_pipelines)
+        .map(|_| PartitionedSpiller::new(depth))
+        .collect_vec();
+    crate::async_executor::task_scope(|scope| {
+        let queue = &queue;
+        let join_handles = spillers
+            .iter_mut()
+            .map(|spiller| {
+                scope.spawn_task(TaskPriority::High, async move {
+                    while let Some(chunk) = queue.pop() {
+                        let df = chunk.load()?;
+                        let hash_keys = spill_form_hash_keys(&df, params);
+                        spiller.push(&df, &hash_keys, partition_nulls, &spill.dir)?;
                     }
-                    PolarsResult::Ok(())
+                    spiller.flush(&spill.dir)
                 })
-            })?;
+            })
+            .collect_vec();
+
+        polars_io::pl_async::get_runtime().block_on(async move {
+            for handle in join_handles {
+                handle.await?;
+            }
+            PolarsResult::Ok(())
+        })
+    })?;
+
+    let mut partitions = (0..NUM_SPILL_PARTITIONS).map(|_| Vec::new()).collect_vec();
+    for spiller in spillers {
+        for (partition, chunks) in partitions.iter_mut().zip(spiller.into_partitions()) {
+            partition.extend(chunks);
         }
+    }
+    Ok(partitions)
+}
 
-        Ok(Some(build_state))
+/// The probe side of a spilled partition, which is loaded one chunk at a time
+/// while probing.
+struct SpilledProbeStream {
+    chunks: ArrayQueue<MaybeSpilled>,
+    seq: MorselSeq,
+}
+
+impl SpilledProbeStream {
+    fn new(chunks: Vec<MaybeSpilled>, seq: MorselSeq) -> Self {
+        let queue = ArrayQueue::new(chunks.len().max(1));
+        for chunk in chunks {
+            let _ = queue.push(chunk);
+        }
+        Self { chunks: queue, seq }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Spawns a task per pipeline that loads chunks and sends them as morsels,
+    /// a chunk is only loaded once the previous morsel of that pipeline is
+    /// consumed.
+    #[allow(clippy::needless_lifetimes)]
+    fn spawn_loaders<'s, 'env>(
+        &'s self,
+        num_pipelines: usize,
+        scope: &'s TaskScope<'s, 'env>,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) -> Vec<Receiver<Morsel>> {
+        let source_token = SourceToken::new();
+        (0..num_pipelines)
+            .map(|_| {
+                let (mut send, recv) = connector();
+                let source_token = source_token.clone();
+                join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+                    let wait_group = WaitGroup::default();
+                    while !source_token.stop_requested() {
+                        let Some(chunk) = self.chunks.pop() else {
+                            break;
+                        };
+                        let df = chunk.load()?;
+                        if df.height() == 0 {
+                            continue;
+                        }
+
+                        let mut morsel = Morsel::new(df, self.seq, source_token.clone());
+                        morsel.set_consume_token(wait_group.token());
+                        if send.send(morsel).await.is_err() {
+                            break;
+                        }
+                        wait_group.wait().await;
+                    }
+                    Ok(())
+                }));
+                recv
+            })
+            .collect()
+    }
+}
+
+impl Default for SpilledProbeStream {
+    fn default() -> Self {
+        Self {
+            chunks: ArrayQueue::new(1),
+            seq: MorselSeq::default(),
+        }
     }
 }
 
 #[derive(Default)]
 struct LocalBuilder {
     // The complete list of morsels and their computed hashes seen by this builder.
+    // While the join might still spill these are in spill form, the key
+    // columns are dropped once the build side is complete.
     morsels: Vec<(MorselSeq, DataFrame, HashKeys)>,
 
     // A cardinality sketch per partition for the keys seen by this builder.
@@ -399,11 +766,45 @@ struct LocalBuilder {
     // let stop = morsel_idxs_offsets[(i + 1) * num_partitions + p];
     morsel_idxs_values_per_p: Vec<Vec<IdxSize>>,
     morsel_idxs_offsets_per_p: Vec<usize>,
+
+    // Once spilling started all morsels go here instead.
+    spiller: Option<PartitionedSpiller>,
+
+    // The bytes of the morsels this builder reserved from the memory budget.
+    reserved_bytes: usize,
+
+    // The keys seen by this builder, for the runtime filter on the probe side.
+    runtime_filter: Option<RuntimeFilterBuilder>,
+}
+
+impl LocalBuilder {
+    /// Moves everything this builder has seen so far into the spill partitions.
+    fn start_spilling(
+        &mut self,
+        partition_nulls: bool,
+        dir: &LazySpillDir,
+    ) -> PolarsResult<&mut PartitionedSpiller> {
+        let mut spiller = PartitionedSpiller::new(0);
+        for (_seq, df, hash_keys) in self.morsels.drain(..) {
+            spiller.push(&df, &hash_keys, partition_nulls, dir)?;
+        }
+
+        let num_partitions = self.sketch_per_p.len();
+        self.sketch_per_p.fill(CardinalitySketch::default());
+        for idxs in self.morsel_idxs_values_per_p.iter_mut() {
+            idxs.clear();
+        }
+        self.morsel_idxs_offsets_per_p.clear();
+        self.morsel_idxs_offsets_per_p.resize(num_partitions, 0);
+        Ok(self.spiller.insert(spiller))
+    }
 }
 
 struct BuildState {
     local_builders: Vec<LocalBuilder>,
     sampled_probe_morsels: BufferedStream,
+    // Shared by the runtime filter builders of all local builders.
+    runtime_filter_budget: Option<RuntimeFilterHashBudget>,
 }
 
 impl BuildState {
@@ -418,19 +819,27 @@ impl BuildState {
                 sketch_per_p: vec![CardinalitySketch::default(); num_partitions],
                 morsel_idxs_values_per_p: vec![Vec::new(); num_partitions],
                 morsel_idxs_offsets_per_p: vec![0; num_partitions],
+                spiller: None,
+                reserved_bytes: 0,
+                runtime_filter: None,
             })
             .collect();
         Self {
             local_builders,
             sampled_probe_morsels,
+            runtime_filter_budget: None,
         }
     }
 
+    /// Sinks the morsels from `recv` into `local`. Without `spill` the morsels
+    /// are the build side of a spilled partition, which are in spill form.
     async fn partition_and_sink(
         mut recv: Receiver<Morsel>,
         local: &mut LocalBuilder,
+        runtime_filter_budget: &RuntimeFilterHashBudget,
         partitioner: HashPartitioner,
         params: &EquiJoinParams,
+        spill: Option<&JoinSpillState>,
         state: &StreamingExecutionState,
     ) -> PolarsResult<()> {
         let track_unmatchable = params.emit_unmatched_build();
@@ -442,19 +851,55 @@ impl BuildState {
             payload_selector = &params.right_payload_select;
             key_selectors = &params.right_key_selectors;
         };
+        let spilled_input = spill.is_none();
+        let memory = spill.and_then(|s| s.memory.as_ref());
+        let spill = spill.filter(|s| s.can_spill());
+        if params
+            .probe_runtime_filter()
+            .is_some_and(|f| !f.is_published())
+        {
+            local
+                .runtime_filter
+                .get_or_insert_with(|| RuntimeFilterBuilder::new(key_selectors.len()));
+        }
 
         while let Ok(morsel) = recv.recv().await {
             // Compute hashed keys and payload. We must rechunk the payload for
             // later gathers.
-            let hash_keys = select_keys(
-                morsel.df(),
-                key_selectors,
-                params,
-                &state.in_memory_exec_state,
-            )
-            .await?;
-            let mut payload = select_payload(morsel.df().clone(), payload_selector);
-            payload.rechunk_mut();
+            let seq = morsel.seq();
+            let (mut df, keys) = if spilled_input {
+                from_spill_form(morsel.into_df(), key_selectors.len())
+            } else {
+                let keys =
+                    select_key_columns(morsel.df(), key_selectors, &state.in_memory_exec_state)
+                        .await?;
+                (select_payload(morsel.into_df(), payload_selector), keys)
+            };
+            if let Some(runtime_filter) = &mut local.runtime_filter {
+                runtime_filter.insert(keys.get_columns(), runtime_filter_budget)?;
+            }
+            let hash_keys =
+                HashKeys::from_df(&keys, params.random_state, params.args.nulls_equal, false);
+            if spill.is_some() {
+                df = to_spill_form(df, keys);
+            }
+
+            if let Some(spill) = spill.filter(|s| s.is_active()) {
+                let spiller = match &mut local.spiller {
+                    Some(spiller) => spiller,
+                    None => {
+                        if let Some(memory) = memory {
+                            memory.shrink(core::mem::take(&mut local.reserved_bytes));
+                        }
+                        local.start_spilling(track_unmatchable, &spill.dir)?
+                    },
+                };
+                spiller.push(&df, &hash_keys, track_unmatchable, &spill.dir)?;
+                continue;
+            }
+
+            df.rechunk_mut();
+            let df_bytes = df.estimated_size();
 
             hash_keys.gen_idxs_per_partition(
                 &partitioner,
@@ -466,11 +911,157 @@ impl BuildState {
             local
                 .morsel_idxs_offsets_per_p
                 .extend(local.morsel_idxs_values_per_p.iter().map(|vp| vp.len()));
-            local.morsels.push((morsel.seq(), payload, hash_keys));
+            local.morsels.push((seq, df, hash_keys));
+
+            if let Some(spill) = spill {
+                spill.track_buffered(df_bytes);
+            }
+
+            // If we can spill we do so instead of exceeding the memory budget,
+            // there is no need to reserve morsels that are about to be spilled.
+            if let Some(memory) = memory {
+                if let Some(spill) = spill {
+                    if !spill.is_active() {
+                        if memory.grow(df_bytes).is_ok() {
+                            local.reserved_bytes += df_bytes;
+                        } else {
+                            spill.activate();
+                        }
+                    }
+                } else {
+                    memory.grow(df_bytes)?;
+                    local.reserved_bytes += df_bytes;
+                }
+            }
         }
         Ok(())
     }
 
+    /// Sinks morsels that are already in memory into the build side.
+    fn sink_buffered(
+        &mut self,
+        morsels: BufferedStream,
+        params: &EquiJoinParams,
+        spill: Option<&JoinSpillState>,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        if morsels.is_empty() {
+            return Ok(());
+        }
+
+        let partitioner = HashPartitioner::new(state.num_pipelines, 0);
+        let runtime_filter_budget = &*self.runtime_filter_budget.get_or_insert_with(|| {
+            RuntimeFilterHashBudget::new(
+                state
+                    .memory_manager
+                    .reservation(state.node_key, "equi-join"),
+            )
+        });
+        crate::async_executor::task_scope(|scope| {
+            let mut join_handles = Vec::new();
+            let receivers = morsels
+                .reinsert(state.num_pipelines, None, scope, &mut join_handles)
+                .unwrap();
+
+            for (local_builder, recv) in self.local_builders.iter_mut().zip(receivers) {
+                join_handles.push(scope.spawn_task(
+                    TaskPriority::High,
+                    BuildState::partition_and_sink(
+                        recv,
+                        local_builder,
+                        runtime_filter_budget,
+                        partitioner.clone(),
+                        params,
+                        spill,
+                        state,
+                    ),
+                ));
+            }
+
+            polars_io::pl_async::get_runtime().block_on(async move {
+                for handle in join_handles {
+                    handle.await?;
+                }
+                PolarsResult::Ok(())
+            })
+        })
+    }
+
+    /// Reduces the buffered morsels to their payload, needed if they were kept
+    /// in spill form because the join might have spilled.
+    fn drop_spill_keys(&mut self, params: &EquiJoinParams) {
+        for local in self.local_builders.iter_mut() {
+            for (_seq, df, _hash_keys) in local.morsels.iter_mut() {
+                *df = from_spill_form(core::mem::take(df), params.num_keys()).0;
+            }
+        }
+    }
+
+    /// Publishes the runtime filter on the probe side from the keys seen by
+    /// the local builders.
+    fn publish_runtime_filter(&mut self, params: &EquiJoinParams) -> PolarsResult<()> {
+        let Some(filter) = params.probe_runtime_filter() else {
+            return Ok(());
+        };
+        let builders = self
+            .local_builders
+            .iter_mut()
+            .filter_map(|l| l.runtime_filter.take())
+            .collect_vec();
+        if !filter.is_published() && !builders.is_empty() {
+            let budget = self.runtime_filter_budget.as_ref().unwrap();
+            filter.publish(builders, budget)?;
+        }
+        Ok(())
+    }
+
+    /// Moves the build side to disk entirely and spills the sampled probe
+    /// morsels, after which the rest of the probe side is spilled as well.
+    fn finalize_spilled(
+        &mut self,
+        params: &EquiJoinParams,
+        spill: &JoinSpillState,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<SpillProbeState> {
+        let track_unmatchable = params.emit_unmatched_build();
+        POOL.install(|| {
+            self.local_builders
+                .par_iter_mut()
+                .filter(|l| l.spiller.is_none())
+                .try_for_each(|l| l.start_spilling(track_unmatchable, &spill.dir).map(|_| ()))
+        })?;
+        for local in self.local_builders.iter_mut() {
+            local.reserved_bytes = 0;
+        }
+        if let Some(memory) = &spill.memory {
+            memory.clear();
+        }
+
+        let mut partitions = (0..NUM_SPILL_PARTITIONS)
+            .map(|_| SpilledPartition::default())
+            .collect_vec();
+        for local in self.local_builders.iter_mut() {
+            let spiller = local.spiller.take().unwrap();
+            for (partition, chunks) in partitions.iter_mut().zip(spiller.into_partitions()) {
+                partition.build.extend(chunks);
+            }
+        }
+
+        let mut probe_state = SpillProbeState {
+            partitions,
+            spillers: (0..state.num_pipelines)
+                .map(|_| PartitionedSpiller::new(0))
+                .collect(),
+        };
+        probe_state.sink_buffered(
+            core::mem::take(&mut self.sampled_probe_morsels),
+            params,
+            spill,
+            state,
+        )?;
+        Ok(probe_state)
+    }
+
     fn finalize_ordered(&mut self, params: &EquiJoinParams, table: &dyn IdxTable) -> ProbeState {
         let track_unmatchable = params.emit_unmatched_build();
         let payload_schema = if params.left_is_build.unwrap() {
@@ -565,6 +1156,7 @@ impl BuildState {
             table_per_partition: probe_tables.try_assume_init().ok().unwrap(),
             max_seq_sent: MorselSeq::default(),
             sampled_probe_morsels: core::mem::take(&mut self.sampled_probe_morsels),
+            spilled_probe: SpilledProbeStream::default(),
             unordered_morsel_seq: AtomicU64::new(0),
         }
     }
@@ -699,11 +1291,90 @@ impl BuildState {
             table_per_partition: probe_tables.try_assume_init().ok().unwrap(),
             max_seq_sent: MorselSeq::default(),
             sampled_probe_morsels: core::mem::take(&mut self.sampled_probe_morsels),
+            spilled_probe: SpilledProbeStream::default(),
             unordered_morsel_seq: AtomicU64::new(0),
         }
     }
 }
 
+/// The probe side of a join whose build side got spilled, the probe morsels
+/// are partitioned and spilled in the same way.
+struct SpillProbeState {
+    partitions: Vec<SpilledPartition>,
+    spillers: Vec<PartitionedSpiller>,
+}
+
+impl SpillProbeState {
+    async fn partition_and_spill(
+        mut recv: Receiver<Morsel>,
+        spiller: &mut PartitionedSpiller,
+        params: &EquiJoinParams,
+        spill: &JoinSpillState,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        let partition_nulls = params.emit_unmatched_probe();
+        let (key_selectors, payload_selector) = if params.left_is_build.unwrap() {
+            (&params.right_key_selectors, &params.right_payload_select)
+        } else {
+            (&params.left_key_selectors, &params.left_payload_select)
+        };
+
+        while let Ok(morsel) = recv.recv().await {
+            let keys =
+                select_key_columns(morsel.df(), key_selectors, &state.in_memory_exec_state).await?;
+            let hash_keys =
+                HashKeys::from_df(&keys, params.random_state, params.args.nulls_equal, false);
+            let df = to_spill_form(select_payload(morsel.into_df(), payload_selector), keys);
+            spiller.push(&df, &hash_keys, partition_nulls, &spill.dir)?;
+        }
+        Ok(())
+    }
+
+    /// Spills morsels that are already in memory.
+    fn sink_buffered(
+        &mut self,
+        morsels: BufferedStream,
+        params: &EquiJoinParams,
+        spill: &JoinSpillState,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        if morsels.is_empty() {
+            return Ok(());
+        }
+
+        crate::async_executor::task_scope(|scope| {
+            let mut join_handles = Vec::new();
+            let receivers = morsels
+                .reinsert(state.num_pipelines, None, scope, &mut join_handles)
+                .unwrap();
+
+            for (spiller, recv) in self.spillers.iter_mut().zip(receivers) {
+                join_handles.push(scope.spawn_task(
+                    TaskPriority::High,
+                    SpillProbeState::partition_and_spill(recv, spiller, params, spill, state),
+                ));
+            }
+
+            polars_io::pl_async::get_runtime().block_on(async move {
+                for handle in join_handles {
+                    handle.await?;
+                }
+                PolarsResult::Ok(())
+            })
+        })
+    }
+
+    fn finalize(&mut self) -> Vec<SpilledPartition> {
+        let mut partitions = core::mem::take(&mut self.partitions);
+        for spiller in self.spillers.drain(..) {
+            for (partition, chunks) in partitions.iter_mut().zip(spiller.into_partitions()) {
+                partition.probe.extend(chunks);
+            }
+        }
+        partitions
+    }
+}
+
 struct ProbeTable {
     hash_table: Box<dyn IdxTable>,
     payload: DataFrame,
@@ -714,13 +1385,22 @@ struct ProbeState {
     table_per_partition: Vec<ProbeTable>,
     max_seq_sent: MorselSeq,
     sampled_probe_morsels: BufferedStream,
+    // The probe side of the spilled partition being joined, if any.
+    spilled_probe: SpilledProbeStream,
 
     // For unordered joins we relabel output morsels to speed up the linearizer.
     unordered_morsel_seq: AtomicU64,
 }
 
 impl ProbeState {
-    /// Returns the max morsel sequence sent.
+    /// Whether all probe morsels that were buffered or spilled are consumed.
+    fn buffered_probe_consumed(&self) -> bool {
+        self.sampled_probe_morsels.is_empty() && self.spilled_probe.is_empty()
+    }
+
+    /// Returns the max morsel sequence sent. If `spilled` the morsels are the
+    /// probe side of a spilled partition, which are in spill form.
+    #[allow(clippy::too_many_arguments)]
     async fn partition_and_probe(
         mut recv: Receiver<Morsel>,
         mut send: Sender<Morsel>,
@@ -728,6 +1408,7 @@ impl ProbeState {
         unordered_morsel_seq: &AtomicU64,
         partitioner: HashPartitioner,
         params: &EquiJoinParams,
+        spilled: bool,
         state: &StreamingExecutionState,
     ) -> PolarsResult<MorselSeq> {
         // TODO: shuffle after partitioning and keep probe tables thread-local.
@@ -771,9 +1452,16 @@ impl ProbeState {
                 continue;
             }
 
-            let hash_keys =
-                select_keys(&df, key_selectors, params, &state.in_memory_exec_state).await?;
-            let mut payload = select_payload(df, payload_selector);
+            let (hash_keys, mut payload) = if spilled {
+                let (payload, keys) = from_spill_form(df, key_selectors.len());
+                let hash_keys =
+                    HashKeys::from_df(&keys, params.random_state, params.args.nulls_equal, false);
+                (hash_keys, payload)
+            } else {
+                let hash_keys =
+                    select_keys(&df, key_selectors, params, &state.in_memory_exec_state).await?;
+                (hash_keys, select_payload(df, payload_selector))
+            };
             let mut payload_rechunked = false; // We don't eagerly rechunk because there might be no matches.
             let mut total_matches = 0;
 
@@ -795,7 +1483,8 @@ impl ProbeState {
                             probe_df.hstack_mut_unchecked(build_df.get_columns());
                             probe_df
                         };
-                        let out_df = postprocess_join(out_df, params);
+                        let out_df =
+                            postprocess_join(out_df, &params.args, &params.left_key_schema);
                         let out_seq = if params.preserve_order_probe {
                             in_seq
                         } else {
@@ -1041,7 +1730,7 @@ impl ProbeState {
                 probe_df.hstack_mut_unchecked(build_df.get_columns());
                 probe_df
             };
-            postprocess_join(out_df, params)
+            postprocess_join(out_df, &params.args, &params.left_key_schema)
         }
     }
 }
@@ -1107,7 +1796,7 @@ impl EmitUnmatchedState {
                         probe_df
                     }
                 };
-                let out_df = postprocess_join(out_df, params);
+                let out_df = postprocess_join(out_df, &params.args, &params.left_key_schema);
 
                 // Send and wait until consume token is consumed.
                 let mut morsel = Morsel::new(out_df, self.morsel_seq, source_token.clone());
@@ -1134,6 +1823,7 @@ impl EmitUnmatchedState {
 enum EquiJoinState {
     Sample(SampleState),
     Build(BuildState),
+    SpillProbe(SpillProbeState),
     Probe(ProbeState),
     EmitUnmatchedBuild(EmitUnmatchedState),
     EmitUnmatchedBuildInOrder(InMemorySourceNode),
@@ -1144,6 +1834,9 @@ pub struct EquiJoinNode {
     state: EquiJoinState,
     params: EquiJoinParams,
     table: Box<dyn IdxTable>,
+    spill: JoinSpillState,
+    // The partitions of a spilled join that still have to be joined.
+    spilled_partitions: Vec<SpilledPartition>,
 }
 
 impl EquiJoinNode {
@@ -1157,6 +1850,8 @@ impl EquiJoinNode {
         left_key_selectors: Vec<StreamExpr>,
         right_key_selectors: Vec<StreamExpr>,
         args: JoinArgs,
+        left_runtime_filter: Option<Arc<RuntimeJoinFilter>>,
+        right_runtime_filter: Option<Arc<RuntimeJoinFilter>>,
         num_pipelines: usize,
     ) -> PolarsResult<Self> {
         let left_is_build = match args.maintain_order {
@@ -1202,6 +1897,16 @@ impl EquiJoinNode {
             EquiJoinState::Sample(SampleState::default())
         };
 
+        // We can't preserve the order when joining partition by partition.
+        let spill_threshold = if !preserve_order_probe
+            && can_spill(&left_input_schema)
+            && can_spill(&right_input_schema)
+        {
+            get_spill_threshold()
+        } else {
+            usize::MAX
+        };
+
         let left_payload_schema = Arc::new(select_schema(&left_input_schema, &left_payload_select));
         let right_payload_schema =
             Arc::new(select_schema(&right_input_schema, &right_payload_select));
@@ -1221,10 +1926,79 @@ impl EquiJoinNode {
                 right_payload_schema,
                 args,
                 random_state: PlRandomState::default(),
+                left_runtime_filter,
+                right_runtime_filter,
             },
             table: new_idx_table(unique_key_schema),
+            spill: JoinSpillState::new(spill_threshold),
+            spilled_partitions: Vec::new(),
         })
     }
+
+    /// Sets up the join of the next spilled partition that can produce output,
+    /// or returns the Done state if there is none left.
+    fn next_spilled_partition(
+        &mut self,
+        seq: MorselSeq,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<EquiJoinState> {
+        let params = &self.params;
+        while let Some(partition) = self.spilled_partitions.pop() {
+            let no_build = partition.build.is_empty();
+            let no_probe = partition.probe.is_empty();
+            if (no_build && (no_probe || !params.emit_unmatched_probe()))
+                || (no_probe && !params.emit_unmatched_build())
+            {
+                continue;
+            }
+
+            // The tables of the previous partition are no longer needed.
+            if let Some(memory) = &self.spill.memory {
+                memory.clear();
+            }
+            let (build, probe) = match partition.load(params, &self.spill, state)? {
+                LoadedPartition::InMemory { build, probe } => (build, probe),
+                LoadedPartition::Split(partitions) => {
+                    self.spilled_partitions.extend(partitions);
+                    continue;
+                },
+            };
+
+            let source_token = SourceToken::new();
+            let build_morsels = build
+                .into_iter()
+                .filter(|df| df.height() > 0)
+                .map(|df| Morsel::new(df, MorselSeq::default(), source_token.clone()))
+                .collect_vec();
+            let mut build_state = BuildState::new(
+                state.num_pipelines,
+                state.num_pipelines,
+                BufferedStream::default(),
+            );
+            build_state.sink_buffered(
+                BufferedStream::new(build_morsels, MorselSeq::default()),
+                params,
+                None,
+                state,
+            )?;
+
+            let mut probe_state = build_state.finalize_unordered(params, &*self.table);
+            probe_state.max_seq_sent = seq;
+            probe_state.unordered_morsel_seq = AtomicU64::new(seq.to_u64());
+            if probe.is_empty() {
+                return Ok(EquiJoinState::EmitUnmatchedBuild(EmitUnmatchedState {
+                    partitions: core::mem::take(&mut probe_state.table_per_partition),
+                    active_partition_idx: 0,
+                    offset_in_active_p: 0,
+                    morsel_seq: seq,
+                }));
+            }
+            probe_state.spilled_probe = SpilledProbeStream::new(probe, seq);
+            return Ok(EquiJoinState::Probe(probe_state));
+        }
+
+        Ok(EquiJoinState::Done)
+    }
 }
 
 impl ComputeNode for EquiJoinNode {
@@ -1240,6 +2014,12 @@ impl ComputeNode for EquiJoinNode {
     ) -> PolarsResult<()> {
         assert!(recv.len() == 2 && send.len() == 1);
 
+        self.spill.memory.get_or_insert_with(|| {
+            state
+                .memory_manager
+                .reservation(state.node_key, "equi-join")
+        });
+
         // If the output doesn't want any more data, transition to being done.
         if send[0] == PortState::Done {
             self.state = EquiJoinState::Done;
@@ -1248,7 +2028,7 @@ impl ComputeNode for EquiJoinNode {
         // If we are sampling and both sides are done/filled, transition to building.
         if let EquiJoinState::Sample(sample_state) = &mut self.state {
             if let Some(build_state) =
-                sample_state.try_transition_to_build(recv, &mut self.params, state)?
+                sample_state.try_transition_to_build(recv, &mut self.params, &self.spill, state)?
             {
                 self.state = EquiJoinState::Build(build_state);
             }
@@ -1264,20 +2044,38 @@ impl ComputeNode for EquiJoinNode {
         // If we are building and the build input is done, transition to probing.
         if let EquiJoinState::Build(build_state) = &mut self.state {
             if recv[build_idx] == PortState::Done {
-                let probe_state = if self.params.preserve_order_build {
-                    build_state.finalize_ordered(&self.params, &*self.table)
+                build_state.publish_runtime_filter(&self.params)?;
+                if self.spill.is_active() {
+                    let probe_state =
+                        build_state.finalize_spilled(&self.params, &self.spill, state)?;
+                    self.state = EquiJoinState::SpillProbe(probe_state);
                 } else {
-                    build_state.finalize_unordered(&self.params, &*self.table)
-                };
-                self.state = EquiJoinState::Probe(probe_state);
+                    if self.spill.can_spill() {
+                        build_state.drop_spill_keys(&self.params);
+                    }
+                    let probe_state = if self.params.preserve_order_build {
+                        build_state.finalize_ordered(&self.params, &*self.table)
+                    } else {
+                        build_state.finalize_unordered(&self.params, &*self.table)
+                    };
+                    self.state = EquiJoinState::Probe(probe_state);
+                }
+            }
+        }
+
+        // If we spilled and the probe input is done, start joining the spilled
+        // partitions one by one.
+        if let EquiJoinState::SpillProbe(probe_state) = &mut self.state {
+            if recv[probe_idx] == PortState::Done {
+                self.spilled_partitions = probe_state.finalize();
+                self.state = self.next_spilled_partition(MorselSeq::default(), state)?;
             }
         }
 
         // If we are probing and the probe input is done, emit unmatched if
-        // necessary, otherwise we're done.
+        // necessary, otherwise we're done with this (spilled) partition.
         if let EquiJoinState::Probe(probe_state) = &mut self.state {
-            let samples_consumed = probe_state.sampled_probe_morsels.is_empty();
-            if samples_consumed && recv[probe_idx] == PortState::Done {
+            if probe_state.buffered_probe_consumed() && recv[probe_idx] == PortState::Done {
                 if self.params.emit_unmatched_build() {
                     if self.params.preserve_order_build {
                         let unmatched = probe_state.ordered_unmatched(&self.params);
@@ -1295,7 +2093,8 @@ impl ComputeNode for EquiJoinNode {
                         });
                     }
                 } else {
-                    self.state = EquiJoinState::Done;
+                    let seq = probe_state.max_seq_sent.successor();
+                    self.state = self.next_spilled_partition(seq, state)?;
                 }
             }
         }
@@ -1303,27 +2102,15 @@ impl ComputeNode for EquiJoinNode {
         // Finally, check if we are done emitting unmatched keys.
         if let EquiJoinState::EmitUnmatchedBuild(emit_state) = &mut self.state {
             if emit_state.active_partition_idx >= emit_state.partitions.len() {
-                self.state = EquiJoinState::Done;
+                let seq = emit_state.morsel_seq;
+                self.state = self.next_spilled_partition(seq, state)?;
             }
         }
 
         match &mut self.state {
             EquiJoinState::Sample(sample_state) => {
                 send[0] = PortState::Blocked;
-                if recv[0] != PortState::Done {
-                    recv[0] = if sample_state.left_len < *JOIN_SAMPLE_LIMIT {
-                        PortState::Ready
-                    } else {
-                        PortState::Blocked
-                    };
-                }
-                if recv[1] != PortState::Done {
-                    recv[1] = if sample_state.right_len < *JOIN_SAMPLE_LIMIT {
-                        PortState::Ready
-                    } else {
-                        PortState::Blocked
-                    };
-                }
+                sample_state.update_recv_states(recv);
             },
             EquiJoinState::Build(_) => {
                 send[0] = PortState::Blocked;
@@ -1334,12 +2121,18 @@ impl ComputeNode for EquiJoinNode {
                     recv[probe_idx] = PortState::Blocked;
                 }
             },
+            EquiJoinState::SpillProbe(_) => {
+                send[0] = PortState::Blocked;
+                recv[build_idx] = PortState::Done;
+                if recv[probe_idx] != PortState::Done {
+                    recv[probe_idx] = PortState::Ready;
+                }
+            },
             EquiJoinState::Probe(probe_state) => {
                 if recv[probe_idx] != PortState::Done {
                     core::mem::swap(&mut send[0], &mut recv[probe_idx]);
                 } else {
-                    let samples_consumed = probe_state.sampled_probe_morsels.is_empty();
-                    send[0] = if samples_consumed {
+                    send[0] = if probe_state.buffered_probe_consumed() {
                         PortState::Done
                     } else {
                         PortState::Ready
@@ -1364,6 +2157,9 @@ impl ComputeNode for EquiJoinNode {
                 send[0] = PortState::Done;
                 recv[0] = PortState::Done;
                 recv[1] = PortState::Done;
+                if let Some(memory) = &self.spill.memory {
+                    memory.clear();
+                }
             },
         }
         Ok(())
@@ -1372,7 +2168,9 @@ impl ComputeNode for EquiJoinNode {
     fn is_memory_intensive_pipeline_blocker(&self) -> bool {
         matches!(
             self.state,
-            EquiJoinState::Sample { .. } | EquiJoinState::Build { .. }
+            EquiJoinState::Sample { .. }
+                | EquiJoinState::Build { .. }
+                | EquiJoinState::SpillProbe { .. }
         )
     }
 
@@ -1397,41 +2195,7 @@ impl ComputeNode for EquiJoinNode {
         match &mut self.state {
             EquiJoinState::Sample(sample_state) => {
                 assert!(send_ports[0].is_none());
-                let left_final_len = Arc::new(AtomicUsize::new(if recv_ports[0].is_none() {
-                    sample_state.left_len
-                } else {
-                    usize::MAX
-                }));
-                let right_final_len = Arc::new(AtomicUsize::new(if recv_ports[1].is_none() {
-                    sample_state.right_len
-                } else {
-                    usize::MAX
-                }));
-
-                if let Some(left_recv) = recv_ports[0].take() {
-                    join_handles.push(scope.spawn_task(
-                        TaskPriority::High,
-                        SampleState::sink(
-                            left_recv.serial(),
-                            &mut sample_state.left,
-                            &mut sample_state.left_len,
-                            left_final_len.clone(),
-                            right_final_len.clone(),
-                        ),
-                    ));
-                }
-                if let Some(right_recv) = recv_ports[1].take() {
-                    join_handles.push(scope.spawn_task(
-                        TaskPriority::High,
-                        SampleState::sink(
-                            right_recv.serial(),
-                            &mut sample_state.right,
-                            &mut sample_state.right_len,
-                            right_final_len,
-                            left_final_len,
-                        ),
-                    ));
-                }
+                sample_state.spawn(scope, recv_ports, join_handles);
             },
             EquiJoinState::Build(build_state) => {
                 assert!(send_ports[0].is_none());
@@ -1439,14 +2203,42 @@ impl ComputeNode for EquiJoinNode {
                 let receivers = recv_ports[build_idx].take().unwrap().parallel();
 
                 let partitioner = HashPartitioner::new(state.num_pipelines, 0);
+                let runtime_filter_budget =
+                    &*build_state.runtime_filter_budget.get_or_insert_with(|| {
+                        RuntimeFilterHashBudget::new(
+                            state
+                                .memory_manager
+                                .reservation(state.node_key, "equi-join"),
+                        )
+                    });
                 for (local_builder, recv) in build_state.local_builders.iter_mut().zip(receivers) {
                     join_handles.push(scope.spawn_task(
                         TaskPriority::High,
                         BuildState::partition_and_sink(
                             recv,
                             local_builder,
+                            runtime_filter_budget,
                             partitioner.clone(),
                             &self.params,
+                            Some(&self.spill),
+                            state,
+                        ),
+                    ));
+                }
+            },
+            EquiJoinState::SpillProbe(probe_state) => {
+                assert!(send_ports[0].is_none());
+                assert!(recv_ports[build_idx].is_none());
+                let receivers = recv_ports[probe_idx].take().unwrap().parallel();
+
+                for (spiller, recv) in probe_state.spillers.iter_mut().zip(receivers) {
+                    join_handles.push(scope.spawn_task(
+                        TaskPriority::High,
+                        SpillProbeState::partition_and_spill(
+                            recv,
+                            spiller,
+                            &self.params,
+                            &self.spill,
                             state,
                         ),
                     ));
@@ -1455,15 +2247,25 @@ impl ComputeNode for EquiJoinNode {
             EquiJoinState::Probe(probe_state) => {
                 assert!(recv_ports[build_idx].is_none());
                 let senders = send_ports[0].take().unwrap().parallel();
-                let receivers = probe_state
-                    .sampled_probe_morsels
-                    .reinsert(
+                let spilled = !probe_state.spilled_probe.is_empty();
+                let receivers = if !spilled {
+                    probe_state
+                        .sampled_probe_morsels
+                        .reinsert(
+                            state.num_pipelines,
+                            recv_ports[probe_idx].take(),
+                            scope,
+                            join_handles,
+                        )
+                        .unwrap()
+                } else {
+                    assert!(recv_ports[probe_idx].is_none());
+                    probe_state.spilled_probe.spawn_loaders(
                         state.num_pipelines,
-                        recv_ports[probe_idx].take(),
                         scope,
                         join_handles,
                     )
-                    .unwrap();
+                };
 
                 let partitioner = HashPartitioner::new(state.num_pipelines, 0);
                 let probe_tasks = receivers
@@ -1479,6 +2281,7 @@ impl ComputeNode for EquiJoinNode {
                                 &probe_state.unordered_morsel_seq,
                                 partitioner.clone(),
                                 &self.params,
+                                spilled,
                                 state,
                             ),
                         )
@@ -28,8 +28,8 @@ impl InMemoryJoinNode {
     ) -> Self {
         Self {
             state: InMemoryJoinState::Sink {
-                left: InMemorySinkNode::new(left_input_schema),
-                right: InMemorySinkNode::new(right_input_schema),
+                left: InMemorySinkNode::new_for_node(left_input_schema, "in-memory-join"),
+                right: InMemorySinkNode::new_for_node(right_input_schema, "in-memory-join"),
             },
             joiner,
         }
@@ -0,0 +1,615 @@
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use polars_core::prelude::*;
+use polars_core::schema::Schema;
+use polars_ops::frame::{JoinArgs, JoinType, MaintainOrderJoin};
+use polars_utils::pl_str::PlSmallStr;
+use polars_utils::total_ord::{TotalEq, TotalOrd};
+
+use super::equi_join::{compute_payload_selector, postprocess_join, select_payload, select_schema};
+use crate::async_primitives::connector::Receiver;
+use crate::morsel::SourceToken;
+use crate::nodes::compute_node_prelude::*;
+
+/// Dispatches on the physical dtypes of the join keys we support.
+macro_rules! with_match_key_type {(
+    $key_type:expr, | $_:tt $T:ident | $($body:tt)*
+) => ({
+    macro_rules! __with_ty__ {( $_ $T:ident ) => ( $($body)* )}
+    match $key_type {
+        DataType::Int32 => __with_ty__! { Int32Type },
+        DataType::Int64 => __with_ty__! { Int64Type },
+        DataType::UInt32 => __with_ty__! { UInt32Type },
+        DataType::UInt64 => __with_ty__! { UInt64Type },
+        DataType::Float32 => __with_ty__! { Float32Type },
+        DataType::Float64 => __with_ty__! { Float64Type },
+        DataType::String => __with_ty__! { StringType },
+        DataType::Binary => __with_ty__! { BinaryType },
+        dt => unreachable!("unsupported merge join key dtype {dt:?}"),
+    }
+})}
+
+/// Returns whether the merge join supports keys of this dtype.
+pub fn is_supported_key_dtype(dtype: &DataType) -> bool {
+    !dtype.is_categorical()
+        && !dtype.is_enum()
+        && matches!(
+            dtype.to_physical(),
+            DataType::Int32
+                | DataType::Int64
+                | DataType::UInt32
+                | DataType::UInt64
+                | DataType::Float32
+                | DataType::Float64
+                | DataType::String
+                | DataType::Binary
+        )
+}
+
+/// Returns whether the merge join can produce the row order requested by
+/// `maintain_order` for this join type.
+pub fn can_maintain_order(how: &JoinType, maintain_order: MaintainOrderJoin) -> bool {
+    match maintain_order {
+        MaintainOrderJoin::None => true,
+        MaintainOrderJoin::Left | MaintainOrderJoin::LeftRight => {
+            matches!(how, JoinType::Inner | JoinType::Left) || how.is_semi() || how.is_anti()
+        },
+        MaintainOrderJoin::Right | MaintainOrderJoin::RightLeft => {
+            matches!(how, JoinType::Inner | JoinType::Right)
+        },
+    }
+}
+
+#[derive(Default)]
+struct MergeSide {
+    /// Rows with a non-null key which have not been joined yet.
+    buffer: DataFrame,
+    /// Rows with a null key waiting to be emitted as unmatched rows, which we
+    /// hold back until the rows preceding them are emitted.
+    nulls: DataFrame,
+    /// The last non-null key seen, as a physical series of length one.
+    last_key: Option<Series>,
+    done: bool,
+}
+
+/// Performs an equi-join of two inputs which are both sorted ascendingly by
+/// their (single) join key.
+///
+/// Both inputs are consumed in key order. All rows with a key smaller than the
+/// last key seen on either side can no longer gain matches, so they are joined
+/// by merging and emitted, leaving only the last run of equal keys buffered on
+/// the side that is ahead. Rows with a null key never match.
+///
+/// The output is ordered by the join key, and the sortedness of the inputs is
+/// verified while streaming.
+pub struct MergeJoinNode {
+    left_on: PlSmallStr,
+    right_on: PlSmallStr,
+    /// Physical dtype of the join keys.
+    key_dtype: DataType,
+    args: JoinArgs,
+    /// Emit the matches within a run of equal keys in right-input order.
+    right_major: bool,
+
+    left_key_schema: Schema,
+    left_payload_select: Vec<Option<PlSmallStr>>,
+    right_payload_select: Vec<Option<PlSmallStr>>,
+    left_payload_schema: Schema,
+    right_payload_schema: Schema,
+
+    left: MergeSide,
+    right: MergeSide,
+    /// Joined rows ready to be sent.
+    ready: Vec<DataFrame>,
+    seq: MorselSeq,
+}
+
+impl MergeJoinNode {
+    pub fn new(
+        left_input_schema: Arc<Schema>,
+        right_input_schema: Arc<Schema>,
+        left_on: PlSmallStr,
+        right_on: PlSmallStr,
+        args: JoinArgs,
+    ) -> PolarsResult<Self> {
+        let key_dtype = left_input_schema.try_get(&left_on)?.to_physical();
+        let right_key_dtype = right_input_schema.try_get(&right_on)?;
+        polars_ensure!(
+            key_dtype == right_key_dtype.to_physical(),
+            ComputeError: "mismatching key dtypes in merge-join: `{}` and `{}`",
+            left_input_schema.get(&left_on).unwrap(), right_key_dtype
+        );
+
+        let left_key_schema = Schema::from_iter([left_input_schema.get_field(&left_on).unwrap()]);
+        let right_key_schema =
+            Schema::from_iter([right_input_schema.get_field(&right_on).unwrap()]);
+        let left_payload_select = compute_payload_selector(
+            &left_input_schema,
+            &right_input_schema,
+            &left_key_schema,
+            true,
+            &args,
+        )?;
+        let right_payload_select = compute_payload_selector(
+            &right_input_schema,
+            &left_input_schema,
+            &right_key_schema,
+            false,
+            &args,
+        )?;
+        let left_payload_schema = select_schema(&left_input_schema, &left_payload_select);
+        let right_payload_schema = select_schema(&right_input_schema, &right_payload_select);
+
+        let side = |schema: &Schema| MergeSide {
+            buffer: DataFrame::empty_with_schema(schema),
+            nulls: DataFrame::empty_with_schema(schema),
+            ..Default::default()
+        };
+        Ok(Self {
+            left_on,
+            right_on,
+            key_dtype,
+            right_major: matches!(
+                args.maintain_order,
+                MaintainOrderJoin::Right | MaintainOrderJoin::RightLeft
+            ),
+            args,
+            left_key_schema,
+            left_payload_select,
+            right_payload_select,
+            left_payload_schema,
+            right_payload_schema,
+            left: side(&left_input_schema),
+            right: side(&right_input_schema),
+            ready: Vec::new(),
+            seq: MorselSeq::default(),
+        })
+    }
+
+    fn emits_unmatched(&self, is_left: bool) -> bool {
+        if is_left {
+            matches!(self.args.how, JoinType::Left | JoinType::Full) || self.args.how.is_anti()
+        } else {
+            matches!(self.args.how, JoinType::Right | JoinType::Full)
+        }
+    }
+
+    /// Returns whether we should read from the left input next, which is the
+    /// case if it is behind the right input.
+    fn need_left(&self) -> bool {
+        match (self.left.done, self.right.done) {
+            (false, true) => true,
+            (true, _) => false,
+            (false, false) => match (&self.left.last_key, &self.right.last_key) {
+                (None, _) => true,
+                (_, None) => false,
+                (Some(l), Some(r)) => with_match_key_type!(&self.key_dtype, |$T| {
+                    let l: &ChunkedArray<$T> = l.as_ref().as_ref();
+                    let r: &ChunkedArray<$T> = r.as_ref().as_ref();
+                    l.get(0).unwrap().tot_le(&r.get(0).unwrap())
+                }),
+            },
+        }
+    }
+
+    /// Returns whether this side can't contribute any more output rows.
+    fn is_exhausted(&self, is_left: bool) -> bool {
+        let side = if is_left { &self.left } else { &self.right };
+        side.done && side.buffer.height() == 0 && side.nulls.height() == 0
+    }
+
+    fn add(&mut self, df: DataFrame, is_left: bool) -> PolarsResult<()> {
+        let on = if is_left {
+            self.left_on.clone()
+        } else {
+            self.right_on.clone()
+        };
+        let key = df.column(&on)?;
+        let df = if key.has_nulls() {
+            let is_null = key.is_null();
+            if self.emits_unmatched(is_left) {
+                let nulls = df.filter(&is_null)?;
+                let side = if is_left {
+                    &mut self.left
+                } else {
+                    &mut self.right
+                };
+                side.nulls.vstack_mut_owned_unchecked(nulls);
+                // Leading null rows are in order if nothing of this side is
+                // waiting to be joined, trailing ones have to wait until the
+                // rows before them are joined.
+                if side.buffer.height() == 0 && key.get(0)?.is_null() {
+                    self.flush_nulls(is_left);
+                }
+            }
+            df.filter(&!&is_null)?
+        } else {
+            df
+        };
+        if df.height() == 0 {
+            return Ok(());
+        }
+
+        let side = if is_left {
+            &mut self.left
+        } else {
+            &mut self.right
+        };
+        let key = df.column(&on)?.to_physical_repr();
+        let key = key.as_materialized_series();
+        let sorted = with_match_key_type!(&self.key_dtype, |$T| {
+            let last = side.last_key.as_ref().map(|s| s.as_ref().as_ref());
+            is_sorted_after::<$T>(key.as_ref().as_ref(), last)
+        });
+        polars_ensure!(
+            sorted,
+            InvalidOperation: "the streaming merge join requires the {} input to be sorted by its \
+            join key; sort it first or use the in-memory engine",
+            if is_left { "left" } else { "right" }
+        );
+        side.last_key = Some(key.tail(Some(1)));
+        side.buffer.vstack_mut_owned_unchecked(df);
+        Ok(())
+    }
+
+    /// Joins all buffered rows whose key is smaller than any key we might
+    /// still receive.
+    fn join_ready(&mut self) -> PolarsResult<()> {
+        let mut bounds = Vec::new();
+        for side in [&self.left, &self.right] {
+            if !side.done {
+                let Some(last_key) = &side.last_key else {
+                    return Ok(());
+                };
+                bounds.push(last_key);
+            }
+        }
+
+        let left_key = self.left.buffer.column(&self.left_on)?.to_physical_repr();
+        let left_key = left_key.as_materialized_series();
+        let right_key = self.right.buffer.column(&self.right_on)?.to_physical_repr();
+        let right_key = right_key.as_materialized_series();
+        let (left_len, right_len) = with_match_key_type!(&self.key_dtype, |$T| {
+            let left_ca: &ChunkedArray<$T> = left_key.as_ref().as_ref();
+            let right_ca: &ChunkedArray<$T> = right_key.as_ref().as_ref();
+            let mut lens = (left_ca.len(), right_ca.len());
+            for bound in bounds {
+                let bound: &ChunkedArray<$T> = bound.as_ref().as_ref();
+                let bound = bound.get(0).unwrap();
+                lens.0 = lens.0.min(left_ca.iter().take_while(|k| k.unwrap().tot_lt(&bound)).count());
+                lens.1 = lens.1.min(right_ca.iter().take_while(|k| k.unwrap().tot_lt(&bound)).count());
+            }
+            lens
+        });
+        if left_len == 0 && right_len == 0 {
+            return Ok(());
+        }
+
+        let (left, left_rest) = self.left.buffer.split_at(left_len as i64);
+        let (right, right_rest) = self.right.buffer.split_at(right_len as i64);
+        let left_key = left_key.slice(0, left_len);
+        let right_key = right_key.slice(0, right_len);
+        let (left_ids, right_ids) = with_match_key_type!(&self.key_dtype, |$T| {
+            merge_join_ids::<$T>(
+                left_key.as_ref().as_ref(),
+                right_key.as_ref().as_ref(),
+                &self.args.how,
+                self.emits_unmatched(true),
+                self.emits_unmatched(false),
+                self.right_major,
+            )
+        });
+        self.left.buffer = left_rest;
+        self.right.buffer = right_rest;
+
+        let out = if self.args.how.is_semi() || self.args.how.is_anti() {
+            let ids = left_ids.into_iter().map(Option::unwrap).collect();
+            unsafe { left.take_unchecked(&IdxCa::from_vec(PlSmallStr::EMPTY, ids)) }
+        } else {
+            let left = take_payload(
+                left,
+                left_ids,
+                &self.left_payload_select,
+                &self.left_payload_schema,
+            )?;
+            let right = take_payload(
+                right,
+                right_ids,
+                &self.right_payload_select,
+                &self.right_payload_schema,
+            )?;
+            self.combine(left, right)
+        };
+        if out.height() > 0 {
+            self.ready.push(out);
+        }
+
+        for is_left in [true, false] {
+            let side = if is_left { &self.left } else { &self.right };
+            if side.buffer.height() == 0 {
+                self.flush_nulls(is_left);
+            }
+        }
+        Ok(())
+    }
+
+    /// Emits the buffered null-key rows of a side as unmatched rows.
+    fn flush_nulls(&mut self, is_left: bool) {
+        let side = if is_left {
+            &mut self.left
+        } else {
+            &mut self.right
+        };
+        if side.nulls.height() == 0 {
+            return;
+        }
+        let empty = side.nulls.clear();
+        let nulls = std::mem::replace(&mut side.nulls, empty);
+        let out = if self.args.how.is_anti() {
+            nulls
+        } else if is_left {
+            let nulls = select_payload(nulls, &self.left_payload_select);
+            let other = DataFrame::full_null(&self.right_payload_schema, nulls.height());
+            self.combine(nulls, other)
+        } else {
+            let nulls = select_payload(nulls, &self.right_payload_select);
+            let other = DataFrame::full_null(&self.left_payload_schema, nulls.height());
+            self.combine(other, nulls)
+        };
+        self.ready.push(out);
+    }
+
+    /// Combines the (payload-selected) left and right rows into output rows.
+    fn combine(&self, mut left: DataFrame, right: DataFrame) -> DataFrame {
+        unsafe { left.hstack_mut_unchecked(right.get_columns()) };
+        postprocess_join(left, &self.args, &self.left_key_schema)
+    }
+}
+
+impl ComputeNode for MergeJoinNode {
+    fn name(&self) -> &str {
+        "merge-join"
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        _state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 2 && send.len() == 1);
+
+        if recv[0] == PortState::Done {
+            self.left.done = true;
+        }
+        if recv[1] == PortState::Done {
+            self.right.done = true;
+        }
+
+        let left_exhausted = self.is_exhausted(true);
+        let right_exhausted = self.is_exhausted(false);
+        if send[0] == PortState::Done
+            || (left_exhausted && (right_exhausted || !self.emits_unmatched(false)))
+            || (right_exhausted && !self.emits_unmatched(true))
+        {
+            self.left = MergeSide::default();
+            self.right = MergeSide::default();
+            recv[0] = PortState::Done;
+            recv[1] = PortState::Done;
+            send[0] = PortState::Done;
+            return Ok(());
+        }
+
+        // We need more rows from the side which is behind.
+        let need_left = self.need_left();
+        let send_blocked = send[0] == PortState::Blocked;
+        let left_blocked = recv[0] == PortState::Blocked && need_left;
+        let right_blocked = recv[1] == PortState::Blocked && !need_left;
+        send[0] = if left_blocked || right_blocked {
+            PortState::Blocked
+        } else {
+            PortState::Ready
+        };
+        if recv[0] != PortState::Done {
+            recv[0] = if send_blocked || right_blocked {
+                PortState::Blocked
+            } else {
+                PortState::Ready
+            };
+        }
+        if recv[1] != PortState::Done {
+            recv[1] = if send_blocked || left_blocked {
+                PortState::Blocked
+            } else {
+                PortState::Ready
+            };
+        }
+        Ok(())
+    }
+
+    fn memory_usage(&self) -> Option<usize> {
+        let sides = [&self.left, &self.right];
+        let buffered = sides.iter().flat_map(|s| [&s.buffer, &s.nulls]);
+        Some(
+            buffered
+                .chain(&self.ready)
+                .map(|df| df.estimated_size())
+                .sum(),
+        )
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        _state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(recv_ports.len() == 2 && send_ports.len() == 1);
+        let mut left = recv_ports[0].take().map(|p| p.serial());
+        let mut right = recv_ports[1].take().map(|p| p.serial());
+        let mut send = send_ports[0].take().unwrap().serial();
+
+        let slf = &mut *self;
+        join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+            /// Stops the source of a port and adds all remaining morsels.
+            async fn buffer_rest(
+                port: &mut Receiver<Morsel>,
+                mut add: impl FnMut(DataFrame) -> PolarsResult<()>,
+            ) -> PolarsResult<()> {
+                while let Ok(morsel) = port.recv().await {
+                    morsel.source_token().stop();
+                    add(morsel.into_df())?;
+                }
+                Ok(())
+            }
+
+            let source_token = SourceToken::new();
+            loop {
+                slf.join_ready()?;
+                for df in std::mem::take(&mut slf.ready) {
+                    let morsel = Morsel::new(df, slf.seq, source_token.clone());
+                    slf.seq = slf.seq.successor();
+                    if send.send(morsel).await.is_err() {
+                        return Ok(());
+                    }
+                }
+
+                let need_left = slf.need_left();
+                let port = if need_left {
+                    left.as_mut()
+                } else {
+                    right.as_mut()
+                };
+                let morsel = match port {
+                    Some(port) if !source_token.stop_requested() => port.recv().await.ok(),
+                    _ => None,
+                };
+                let Some(morsel) = morsel else {
+                    if let Some(port) = &mut left {
+                        buffer_rest(port, |df| slf.add(df, true)).await?;
+                    }
+                    if let Some(port) = &mut right {
+                        buffer_rest(port, |df| slf.add(df, false)).await?;
+                    }
+                    break;
+                };
+                slf.add(morsel.into_df(), need_left)?;
+            }
+
+            slf.join_ready()?;
+            for df in std::mem::take(&mut slf.ready) {
+                let morsel = Morsel::new(df, slf.seq, source_token.clone());
+                slf.seq = slf.seq.successor();
+                if send.send(morsel).await.is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        }));
+    }
+}
+
+/// Gathers the payload of rows by index, where a `None` index gives a row of
+/// nulls.
+fn take_payload(
+    df: DataFrame,
+    ids: Vec<Option<IdxSize>>,
+    selector: &[Option<PlSmallStr>],
+    payload_schema: &Schema,
+) -> PolarsResult<DataFrame> {
+    if df.height() == 0 {
+        return Ok(DataFrame::full_null(payload_schema, ids.len()));
+    }
+    let ids = IdxCa::from_iter_options(PlSmallStr::EMPTY, ids.into_iter());
+    select_payload(df, selector).take(&ids)
+}
+
+/// Checks that the keys, which contain no nulls, are sorted and not smaller
+/// than the last key seen.
+fn is_sorted_after<T>(key: &ChunkedArray<T>, last: Option<&ChunkedArray<T>>) -> bool
+where
+    T: PolarsDataType,
+    for<'a> T::Physical<'a>: TotalOrd,
+{
+    let mut prev = last.map(|l| l.get(0).unwrap());
+    for k in key.iter() {
+        let k = k.unwrap();
+        if prev.as_ref().is_some_and(|p| k.tot_lt(p)) {
+            return false;
+        }
+        prev = Some(k);
+    }
+    true
+}
+
+/// Merges two sorted sequences of keys without nulls, returning the indices of
+/// the rows making up each output row. A `None` index denotes the missing side
+/// of an unmatched row. For semi- and anti-joins only the left indices are
+/// returned.
+fn merge_join_ids<T>(
+    left: &ChunkedArray<T>,
+    right: &ChunkedArray<T>,
+    how: &JoinType,
+    emit_unmatched_left: bool,
+    emit_unmatched_right: bool,
+    right_major: bool,
+) -> (Vec<Option<IdxSize>>, Vec<Option<IdxSize>>)
+where
+    T: PolarsDataType,
+    for<'a> T::Physical<'a>: TotalOrd,
+{
+    let left: Vec<_> = left.iter().map(Option::unwrap).collect();
+    let right: Vec<_> = right.iter().map(Option::unwrap).collect();
+    let left_only = how.is_semi() || how.is_anti();
+
+    let mut left_ids = Vec::new();
+    let mut right_ids = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() || j < right.len() {
+        let ord = match (left.get(i), right.get(j)) {
+            (Some(l), Some(r)) => l.tot_cmp(r),
+            (Some(_), None) => Ordering::Less,
+            (None, _) => Ordering::Greater,
+        };
+        match ord {
+            Ordering::Less => {
+                if emit_unmatched_left {
+                    left_ids.push(Some(i as IdxSize));
+                    right_ids.push(None);
+                }
+                i += 1;
+            },
+            Ordering::Greater => {
+                if emit_unmatched_right {
+                    left_ids.push(None);
+                    right_ids.push(Some(j as IdxSize));
+                }
+                j += 1;
+            },
+            Ordering::Equal => {
+                let i_end = i + left[i..].iter().take_while(|l| left[i].tot_eq(l)).count();
+                let j_end = j + right[j..].iter().take_while(|r| right[j].tot_eq(r)).count();
+                if left_only {
+                    if how.is_semi() {
+                        left_ids.extend((i..i_end).map(|i| Some(i as IdxSize)));
+                    }
+                } else if right_major {
+                    for r in j..j_end {
+                        left_ids.extend((i..i_end).map(|l| Some(l as IdxSize)));
+                        right_ids.extend((i..i_end).map(|_| Some(r as IdxSize)));
+                    }
+                } else {
+                    for l in i..i_end {
+                        left_ids.extend((j..j_end).map(|_| Some(l as IdxSize)));
+                        right_ids.extend((j..j_end).map(|r| Some(r as IdxSize)));
+                    }
+                }
+                (i, j) = (i_end, j_end);
+            },
+        }
+    }
+    (left_ids, right_ids)
+}
@@ -1,4 +1,5 @@
-use std::sync::LazyLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock};
 
 use crossbeam_queue::ArrayQueue;
 use polars_core::POOL;
@@ -9,12 +10,19 @@ use rayon::prelude::*;
 use crate::async_executor::{JoinHandle, TaskPriority, TaskScope};
 use crate::async_primitives::connector::{Receiver, connector};
 use crate::async_primitives::wait_group::WaitGroup;
+use crate::graph::PortState;
 use crate::morsel::{Morsel, MorselSeq, SourceToken};
 use crate::pipe::RecvPort;
 
+#[cfg(feature = "asof_join")]
+pub mod asof;
 pub mod cross_join;
 pub mod equi_join;
 pub mod in_memory;
+pub mod merge_join;
+#[cfg(feature = "iejoin")]
+pub mod range_join;
+pub mod runtime_filter;
 #[cfg(feature = "semi_anti_join")]
 pub mod semi_anti_join;
 
@@ -28,6 +36,109 @@ static JOIN_SAMPLE_LIMIT: LazyLock<usize> = LazyLock::new(|| {
 // smaller side as the build side without checking cardinalities.
 const LOPSIDED_SAMPLE_FACTOR: usize = 10;
 
+/// Buffers the first morsels of both inputs of a join, to decide which side
+/// to build on.
+#[derive(Default)]
+struct SampleState {
+    left: Vec<Morsel>,
+    left_len: usize,
+    right: Vec<Morsel>,
+    right_len: usize,
+}
+
+impl SampleState {
+    async fn sink(
+        mut recv: Receiver<Morsel>,
+        morsels: &mut Vec<Morsel>,
+        len: &mut usize,
+        this_final_len: Arc<AtomicUsize>,
+        other_final_len: Arc<AtomicUsize>,
+    ) -> PolarsResult<()> {
+        while let Ok(mut morsel) = recv.recv().await {
+            *len += morsel.df().height();
+            if *len >= *JOIN_SAMPLE_LIMIT
+                || *len
+                    >= other_final_len
+                        .load(Ordering::Relaxed)
+                        .saturating_mul(LOPSIDED_SAMPLE_FACTOR)
+            {
+                morsel.source_token().stop();
+            }
+
+            drop(morsel.take_consume_token());
+            morsels.push(morsel);
+        }
+        this_final_len.store(*len, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Whether we have sampled enough to choose a build side.
+    fn is_finished(&self, recv: &[PortState]) -> bool {
+        let left_done = recv[0] == PortState::Done || self.left_len >= *JOIN_SAMPLE_LIMIT;
+        let right_done = recv[1] == PortState::Done || self.right_len >= *JOIN_SAMPLE_LIMIT;
+        #[expect(clippy::nonminimal_bool)]
+        let finished = (left_done && right_done)
+            || (left_done && self.right_len >= LOPSIDED_SAMPLE_FACTOR * self.left_len)
+            || (right_done && self.left_len >= LOPSIDED_SAMPLE_FACTOR * self.right_len);
+        finished
+    }
+
+    fn update_recv_states(&self, recv: &mut [PortState]) {
+        for (port, len) in recv.iter_mut().zip([self.left_len, self.right_len]) {
+            if *port != PortState::Done {
+                *port = if len < *JOIN_SAMPLE_LIMIT {
+                    PortState::Ready
+                } else {
+                    PortState::Blocked
+                };
+            }
+        }
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        let left_final_len = Arc::new(AtomicUsize::new(if recv_ports[0].is_none() {
+            self.left_len
+        } else {
+            usize::MAX
+        }));
+        let right_final_len = Arc::new(AtomicUsize::new(if recv_ports[1].is_none() {
+            self.right_len
+        } else {
+            usize::MAX
+        }));
+
+        if let Some(left_recv) = recv_ports[0].take() {
+            join_handles.push(scope.spawn_task(
+                TaskPriority::High,
+                SampleState::sink(
+                    left_recv.serial(),
+                    &mut self.left,
+                    &mut self.left_len,
+                    left_final_len.clone(),
+                    right_final_len.clone(),
+                ),
+            ));
+        }
+        if let Some(right_recv) = recv_ports[1].take() {
+            join_handles.push(scope.spawn_task(
+                TaskPriority::High,
+                SampleState::sink(
+                    right_recv.serial(),
+                    &mut self.right,
+                    &mut self.right_len,
+                    right_final_len,
+                    left_final_len,
+                ),
+            ));
+        }
+    }
+}
+
 // TODO: improve, generalize this, and move it away from here.
 struct BufferedStream {
     morsels: ArrayQueue<Morsel>,
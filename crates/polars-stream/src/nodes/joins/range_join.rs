@@ -0,0 +1,697 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow::array::BinaryArray;
+use arrow::bitmap::Bitmap;
+use polars_core::chunked_array::ops::row_encode::_get_rows_encoded_arr;
+use polars_core::config;
+use polars_core::prelude::*;
+use polars_core::schema::Schema;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_io::pl_async::get_runtime;
+use polars_ops::frame::{IEJoinOptions, InequalityOperator, JoinArgs, MaintainOrderJoin};
+use polars_utils::format_pl_smallstr;
+use polars_utils::itertools::Itertools;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::{BufferedStream, SampleState};
+use crate::async_primitives::connector::{Receiver, Sender};
+use crate::execute::MemoryReservation;
+use crate::expression::StreamExpr;
+use crate::morsel::{SourceToken, get_ideal_morsel_size};
+use crate::nodes::compute_node_prelude::*;
+use crate::nodes::in_memory_sink::InMemorySinkNode;
+
+/// The number of consecutive index entries that share bounds on the second key.
+const BLOCK_SIZE: usize = 1024;
+
+/// Flips an operator so that `a op b` holds iff `b flip(op) a` does.
+fn flip(op: InequalityOperator) -> InequalityOperator {
+    match op {
+        InequalityOperator::Lt => InequalityOperator::Gt,
+        InequalityOperator::LtEq => InequalityOperator::GtEq,
+        InequalityOperator::Gt => InequalityOperator::Lt,
+        InequalityOperator::GtEq => InequalityOperator::LtEq,
+    }
+}
+
+fn compare(lhs: &[u8], rhs: &[u8], op: InequalityOperator) -> bool {
+    match op {
+        InequalityOperator::Lt => lhs < rhs,
+        InequalityOperator::LtEq => lhs <= rhs,
+        InequalityOperator::Gt => lhs > rhs,
+        InequalityOperator::GtEq => lhs >= rhs,
+    }
+}
+
+fn partition_point(keys: &BinaryArray<i64>, pred: impl Fn(&[u8]) -> bool) -> usize {
+    let (mut lo, mut hi) = (0, keys.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(unsafe { keys.value_unchecked(mid) }) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Row-encodes a single key column such that the byte order matches the
+/// ascending order of the (non-null) values.
+fn encode_key(key: &Column) -> PolarsResult<BinaryArray<i64>> {
+    _get_rows_encoded_arr(std::slice::from_ref(key), &[false], &[false])
+}
+
+/// The rows for which none of the keys are null, or `None` if that is all rows.
+fn valid_keys_mask(keys: &[Column]) -> Option<BooleanChunked> {
+    keys.iter()
+        .filter(|k| k.has_nulls())
+        .map(|k| k.is_not_null())
+        .reduce(|a, b| &a & &b)
+}
+
+async fn select_keys(
+    df: &DataFrame,
+    key_selectors: &[StreamExpr],
+    key_dtypes: &[DataType],
+    state: &ExecutionState,
+) -> PolarsResult<Vec<Column>> {
+    let mut key_columns = Vec::with_capacity(key_selectors.len());
+    for selector in key_selectors {
+        key_columns.push(selector.evaluate(df, state).await?.into_column());
+    }
+    let keys = DataFrame::new_with_broadcast_len(key_columns, df.height())?;
+    keys.take_columns()
+        .into_iter()
+        .zip(key_dtypes)
+        .map(|(key, dtype)| key.cast(dtype))
+        .collect()
+}
+
+/// The build side of the join, sorted on its first key.
+struct RangeIndex {
+    /// The build rows with non-null keys, in ascending order of the first key.
+    order: Vec<IdxSize>,
+    /// The row-encoded keys of the rows in `order`.
+    keys: Vec<BinaryArray<i64>>,
+    /// The positions of the smallest and largest second key within each block
+    /// of `BLOCK_SIZE` entries.
+    block_bounds: Vec<(usize, usize)>,
+    /// The join predicates, written as `build_key op probe_key`.
+    operators: Vec<InequalityOperator>,
+}
+
+impl RangeIndex {
+    fn new(keys: &[Column], operators: Vec<InequalityOperator>) -> PolarsResult<Self> {
+        let height = keys[0].len() as IdxSize;
+        let mut rows = IdxCa::from_vec(PlSmallStr::EMPTY, (0..height).collect());
+        if let Some(mask) = valid_keys_mask(keys) {
+            rows = rows.filter(&mask)?;
+        }
+        let first_key = keys[0].take(&rows)?;
+        let sorted = first_key.arg_sort(SortOptions::default().with_maintain_order(true));
+        let rows = unsafe { rows.take_unchecked(&sorted) };
+
+        let keys = keys
+            .iter()
+            .map(|k| encode_key(&k.take(&rows)?))
+            .try_collect_vec()?;
+        let order = rows.rechunk().into_no_null_iter().collect_vec();
+
+        let block_bounds = match keys.get(1) {
+            None => Vec::new(),
+            Some(second_key) => (0..second_key.len())
+                .step_by(BLOCK_SIZE)
+                .map(|start| {
+                    let end = (start + BLOCK_SIZE).min(second_key.len());
+                    let (mut min, mut max) = (start, start);
+                    for i in start + 1..end {
+                        if second_key.value(i) < second_key.value(min) {
+                            min = i;
+                        }
+                        if second_key.value(i) > second_key.value(max) {
+                            max = i;
+                        }
+                    }
+                    (min, max)
+                })
+                .collect(),
+        };
+
+        Ok(Self {
+            order,
+            keys,
+            block_bounds,
+            operators,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// The entries whose first key satisfies the first predicate.
+    fn first_key_range(&self, probe_key: &[u8]) -> Range<usize> {
+        let keys = &self.keys[0];
+        match self.operators[0] {
+            InequalityOperator::Lt => 0..partition_point(keys, |k| k < probe_key),
+            InequalityOperator::LtEq => 0..partition_point(keys, |k| k <= probe_key),
+            InequalityOperator::Gt => partition_point(keys, |k| k <= probe_key)..keys.len(),
+            InequalityOperator::GtEq => partition_point(keys, |k| k < probe_key)..keys.len(),
+        }
+    }
+
+    /// Calls `f` with every build row matching the given probe keys.
+    fn for_each_match(
+        &self,
+        probe_key: &[u8],
+        second_probe_key: Option<&[u8]>,
+        mut f: impl FnMut(IdxSize),
+    ) {
+        let range = self.first_key_range(probe_key);
+        let (Some(second_key), Some(second_probe_key)) = (self.keys.get(1), second_probe_key)
+        else {
+            self.order[range].iter().copied().for_each(f);
+            return;
+        };
+
+        let op = self.operators[1];
+        let mut start = range.start;
+        while start < range.end {
+            let block = start / BLOCK_SIZE;
+            let end = ((block + 1) * BLOCK_SIZE).min(range.end);
+
+            // Skip the block if even its most extreme key can't match.
+            let (min, max) = self.block_bounds[block];
+            let extreme = match op {
+                InequalityOperator::Lt | InequalityOperator::LtEq => min,
+                InequalityOperator::Gt | InequalityOperator::GtEq => max,
+            };
+            if compare(second_key.value(extreme), second_probe_key, op) {
+                for i in start..end {
+                    let key = unsafe { second_key.value_unchecked(i) };
+                    if compare(key, second_probe_key, op) {
+                        f(self.order[i]);
+                    }
+                }
+            }
+            start = end;
+        }
+    }
+}
+
+struct RangeJoinParams {
+    left_is_build: Option<bool>,
+    left_key_selectors: Vec<StreamExpr>,
+    right_key_selectors: Vec<StreamExpr>,
+    key_dtypes: Vec<DataType>,
+    /// The join predicates, written as `left_key op right_key`.
+    operators: Vec<InequalityOperator>,
+    right_rename: Vec<Option<PlSmallStr>>,
+}
+
+impl RangeJoinParams {
+    fn build_idx(&self) -> usize {
+        if self.left_is_build == Some(true) {
+            0
+        } else {
+            1
+        }
+    }
+
+    fn build_key_selectors(&self) -> &[StreamExpr] {
+        if self.left_is_build == Some(true) {
+            &self.left_key_selectors
+        } else {
+            &self.right_key_selectors
+        }
+    }
+
+    fn probe_key_selectors(&self) -> &[StreamExpr] {
+        if self.left_is_build == Some(true) {
+            &self.right_key_selectors
+        } else {
+            &self.left_key_selectors
+        }
+    }
+
+    /// The join predicates, written as `build_key op probe_key`.
+    fn build_operators(&self) -> Vec<InequalityOperator> {
+        if self.left_is_build == Some(true) {
+            self.operators.clone()
+        } else {
+            self.operators.iter().copied().map(flip).collect()
+        }
+    }
+
+    /// Combines the gathered build and probe rows into an output frame.
+    fn combine(&self, build_df: DataFrame, probe_df: DataFrame) -> DataFrame {
+        let (mut left_df, mut right_df) = if self.left_is_build == Some(true) {
+            (build_df, probe_df)
+        } else {
+            (probe_df, build_df)
+        };
+
+        unsafe {
+            for (col, opt_rename) in right_df
+                .get_columns_mut()
+                .iter_mut()
+                .zip(&self.right_rename)
+            {
+                if let Some(rename) = opt_rename {
+                    col.rename(rename.clone());
+                }
+            }
+            left_df.hstack_mut_unchecked(right_df.get_columns());
+        }
+        left_df
+    }
+}
+
+struct BuildState {
+    sampled_build_morsels: Vec<DataFrame>,
+    sink: InMemorySinkNode,
+    sampled_probe_morsels: BufferedStream,
+}
+
+impl BuildState {
+    fn finalize(
+        &mut self,
+        params: &RangeJoinParams,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<Option<ProbeState>> {
+        let mut dfs = core::mem::take(&mut self.sampled_build_morsels);
+        dfs.extend(self.sink.get_output()?);
+        let build_df = accumulate_dataframes_vertical_unchecked(dfs);
+
+        let keys = get_runtime().block_on(select_keys(
+            &build_df,
+            params.build_key_selectors(),
+            &params.key_dtypes,
+            &state.in_memory_exec_state,
+        ))?;
+        let index = RangeIndex::new(&keys, params.build_operators())?;
+        if config::verbose() {
+            eprintln!(
+                "range-join build side has {} rows, of which {} have valid keys",
+                build_df.height(),
+                index.order.len()
+            );
+        }
+        if index.is_empty() {
+            return Ok(None);
+        }
+
+        let memory = state
+            .memory_manager
+            .reservation(state.node_key, "range-join");
+        memory.grow(build_df.estimated_size())?;
+        Ok(Some(ProbeState {
+            build_df,
+            index,
+            sampled_probe_morsels: core::mem::take(&mut self.sampled_probe_morsels),
+            memory,
+        }))
+    }
+}
+
+struct ProbeState {
+    build_df: DataFrame,
+    index: RangeIndex,
+    sampled_probe_morsels: BufferedStream,
+    memory: MemoryReservation,
+}
+
+impl ProbeState {
+    async fn probe(
+        mut recv: Receiver<Morsel>,
+        mut send: Sender<Morsel>,
+        build_df: &DataFrame,
+        index: &RangeIndex,
+        params: &RangeJoinParams,
+        state: &ExecutionState,
+    ) -> PolarsResult<()> {
+        let ideal_morsel_size = get_ideal_morsel_size();
+        let mut probe_rows: Vec<IdxSize> = Vec::new();
+        let mut build_rows: Vec<IdxSize> = Vec::new();
+
+        // Gathers the matches found so far into a morsel.
+        let flush = |probe_df: &DataFrame,
+                     probe_rows: &mut Vec<IdxSize>,
+                     build_rows: &mut Vec<IdxSize>,
+                     seq: MorselSeq,
+                     source_token: &SourceToken| {
+            let probe_idx = IdxCa::from_vec(PlSmallStr::EMPTY, core::mem::take(probe_rows));
+            let build_idx = IdxCa::from_vec(PlSmallStr::EMPTY, core::mem::take(build_rows));
+            let (build_out, probe_out) = unsafe {
+                (
+                    build_df.take_unchecked(&build_idx),
+                    probe_df.take_unchecked(&probe_idx),
+                )
+            };
+            Morsel::new(
+                params.combine(build_out, probe_out),
+                seq,
+                source_token.clone(),
+            )
+        };
+
+        while let Ok(morsel) = recv.recv().await {
+            let (probe_df, seq, source_token, consume_token) = morsel.into_inner();
+            let keys = select_keys(
+                &probe_df,
+                params.probe_key_selectors(),
+                &params.key_dtypes,
+                state,
+            )
+            .await?;
+            let valid: Option<Bitmap> = valid_keys_mask(&keys)
+                .map(|mask| mask.rechunk().downcast_as_array().values().clone());
+            let keys = keys.iter().map(encode_key).try_collect_vec()?;
+
+            for i in 0..probe_df.height() {
+                if valid.as_ref().is_some_and(|v| !v.get_bit(i)) {
+                    continue;
+                }
+                let first = unsafe { keys[0].value_unchecked(i) };
+                let second = keys.get(1).map(|k| unsafe { k.value_unchecked(i) });
+                index.for_each_match(first, second, |build_row| {
+                    probe_rows.push(i as IdxSize);
+                    build_rows.push(build_row);
+                });
+
+                if probe_rows.len() >= ideal_morsel_size {
+                    let out = flush(
+                        &probe_df,
+                        &mut probe_rows,
+                        &mut build_rows,
+                        seq,
+                        &source_token,
+                    );
+                    if send.send(out).await.is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+
+            if !probe_rows.is_empty() {
+                let out = flush(
+                    &probe_df,
+                    &mut probe_rows,
+                    &mut build_rows,
+                    seq,
+                    &source_token,
+                );
+                if send.send(out).await.is_err() {
+                    return Ok(());
+                }
+            }
+            drop(consume_token);
+        }
+
+        Ok(())
+    }
+}
+
+enum RangeJoinState {
+    Sample(SampleState),
+    Build(BuildState),
+    Probe(ProbeState),
+    Done,
+}
+
+/// Inner join on one or two inequality predicates `left_on[i] op_i right_on[i]`.
+///
+/// The smaller input is collected and sorted on its first key. The other input
+/// is streamed through it: the rows matching the first predicate of a probe row
+/// form a contiguous range of the sorted build side, which is scanned for the
+/// rows matching the second predicate.
+pub struct RangeJoinNode {
+    params: RangeJoinParams,
+    left_input_schema: Arc<Schema>,
+    right_input_schema: Arc<Schema>,
+    state: RangeJoinState,
+}
+
+impl RangeJoinNode {
+    pub fn new(
+        left_input_schema: Arc<Schema>,
+        right_input_schema: Arc<Schema>,
+        left_key_selectors: Vec<StreamExpr>,
+        right_key_selectors: Vec<StreamExpr>,
+        key_dtypes: Vec<DataType>,
+        options: IEJoinOptions,
+        args: JoinArgs,
+    ) -> Self {
+        let left_is_build = match args.maintain_order {
+            MaintainOrderJoin::None => None,
+            MaintainOrderJoin::Left | MaintainOrderJoin::LeftRight => Some(false),
+            MaintainOrderJoin::Right | MaintainOrderJoin::RightLeft => Some(true),
+        };
+        let right_rename = right_input_schema
+            .iter_names()
+            .map(|rname| {
+                if left_input_schema.contains(rname) {
+                    Some(format_pl_smallstr!("{}{}", rname, args.suffix()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let operators = [Some(options.operator1), options.operator2]
+            .into_iter()
+            .flatten()
+            .take(left_key_selectors.len())
+            .collect();
+
+        let mut slf = Self {
+            params: RangeJoinParams {
+                left_is_build,
+                left_key_selectors,
+                right_key_selectors,
+                key_dtypes,
+                operators,
+                right_rename,
+            },
+            left_input_schema,
+            right_input_schema,
+            state: RangeJoinState::Sample(SampleState::default()),
+        };
+        if left_is_build.is_some() {
+            slf.state = RangeJoinState::Build(slf.new_build_state(Vec::new(), Vec::new()));
+        }
+        slf
+    }
+
+    fn new_build_state(
+        &self,
+        sampled_build_morsels: Vec<Morsel>,
+        sampled_probe_morsels: Vec<Morsel>,
+    ) -> BuildState {
+        let build_schema = if self.params.left_is_build == Some(true) {
+            &self.left_input_schema
+        } else {
+            &self.right_input_schema
+        };
+        BuildState {
+            sampled_build_morsels: sampled_build_morsels
+                .into_iter()
+                .map(|m| m.into_df())
+                .collect(),
+            sink: InMemorySinkNode::new_for_node(build_schema.clone(), "range-join"),
+            sampled_probe_morsels: BufferedStream::new(sampled_probe_morsels, MorselSeq::default()),
+        }
+    }
+
+    fn try_transition_to_build(&mut self, recv: &[PortState]) {
+        let RangeJoinState::Sample(sample_state) = &mut self.state else {
+            return;
+        };
+        if !sample_state.is_finished(recv) {
+            return;
+        }
+
+        let left_saturated = recv[0] != PortState::Done;
+        let right_saturated = recv[1] != PortState::Done;
+        let left_is_build = match (left_saturated, right_saturated) {
+            (false, false) | (true, true) => sample_state.left_len < sample_state.right_len,
+            (false, true) => true,
+            (true, false) => false,
+        };
+        if config::verbose() {
+            eprintln!(
+                "range-join build side chosen: {}, sample lengths are: {} vs. {}",
+                if left_is_build { "left" } else { "right" },
+                sample_state.left_len,
+                sample_state.right_len
+            );
+        }
+
+        let mut sampled_build_morsels = core::mem::take(&mut sample_state.left);
+        let mut sampled_probe_morsels = core::mem::take(&mut sample_state.right);
+        if !left_is_build {
+            core::mem::swap(&mut sampled_build_morsels, &mut sampled_probe_morsels);
+        }
+        self.params.left_is_build = Some(left_is_build);
+        self.state = RangeJoinState::Build(
+            self.new_build_state(sampled_build_morsels, sampled_probe_morsels),
+        );
+    }
+}
+
+impl ComputeNode for RangeJoinNode {
+    fn name(&self) -> &str {
+        "range-join"
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 2 && send.len() == 1);
+
+        // If the output doesn't want any more data, transition to being done.
+        if send[0] == PortState::Done {
+            self.state = RangeJoinState::Done;
+        }
+
+        // If we are sampling and both sides are done/filled, transition to building.
+        self.try_transition_to_build(recv);
+
+        let build_idx = self.params.build_idx();
+        let probe_idx = 1 - build_idx;
+
+        // If we are building and the build input is done, transition to probing.
+        if let RangeJoinState::Build(build_state) = &mut self.state {
+            if recv[build_idx] == PortState::Done {
+                self.state = match build_state.finalize(&self.params, state)? {
+                    Some(probe_state) => RangeJoinState::Probe(probe_state),
+                    None => RangeJoinState::Done,
+                };
+            }
+        }
+
+        // If we are probing and the probe input is done, we're done.
+        if let RangeJoinState::Probe(probe_state) = &self.state {
+            let samples_consumed = probe_state.sampled_probe_morsels.is_empty();
+            if samples_consumed && recv[probe_idx] == PortState::Done {
+                self.state = RangeJoinState::Done;
+            }
+        }
+
+        match &mut self.state {
+            RangeJoinState::Sample(sample_state) => {
+                send[0] = PortState::Blocked;
+                sample_state.update_recv_states(recv);
+            },
+            RangeJoinState::Build(_) => {
+                send[0] = PortState::Blocked;
+                if recv[build_idx] != PortState::Done {
+                    recv[build_idx] = PortState::Ready;
+                }
+                if recv[probe_idx] != PortState::Done {
+                    recv[probe_idx] = PortState::Blocked;
+                }
+            },
+            RangeJoinState::Probe(probe_state) => {
+                if recv[probe_idx] != PortState::Done {
+                    core::mem::swap(&mut send[0], &mut recv[probe_idx]);
+                } else {
+                    let samples_consumed = probe_state.sampled_probe_morsels.is_empty();
+                    send[0] = if samples_consumed {
+                        PortState::Done
+                    } else {
+                        PortState::Ready
+                    };
+                }
+                recv[build_idx] = PortState::Done;
+            },
+            RangeJoinState::Done => {
+                send[0] = PortState::Done;
+                recv[0] = PortState::Done;
+                recv[1] = PortState::Done;
+            },
+        }
+        Ok(())
+    }
+
+    fn is_memory_intensive_pipeline_blocker(&self) -> bool {
+        matches!(
+            self.state,
+            RangeJoinState::Sample { .. } | RangeJoinState::Build { .. }
+        )
+    }
+
+    fn memory_usage(&self) -> Option<usize> {
+        match &self.state {
+            RangeJoinState::Sample(_) => None,
+            RangeJoinState::Build(build_state) => build_state.sink.memory_usage(),
+            RangeJoinState::Probe(probe_state) => Some(probe_state.memory.size()),
+            RangeJoinState::Done => Some(0),
+        }
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(recv_ports.len() == 2);
+        assert!(send_ports.len() == 1);
+
+        let build_idx = self.params.build_idx();
+        let probe_idx = 1 - build_idx;
+
+        match &mut self.state {
+            RangeJoinState::Sample(sample_state) => {
+                assert!(send_ports[0].is_none());
+                sample_state.spawn(scope, recv_ports, join_handles);
+            },
+            RangeJoinState::Build(build_state) => {
+                assert!(send_ports[0].is_none());
+                assert!(recv_ports[probe_idx].is_none());
+                build_state.sink.spawn(
+                    scope,
+                    &mut recv_ports[build_idx..build_idx + 1],
+                    &mut [],
+                    state,
+                    join_handles,
+                );
+            },
+            RangeJoinState::Probe(probe_state) => {
+                assert!(recv_ports[build_idx].is_none());
+                let senders = send_ports[0].take().unwrap().parallel();
+                let receivers = probe_state
+                    .sampled_probe_morsels
+                    .reinsert(
+                        state.num_pipelines,
+                        recv_ports[probe_idx].take(),
+                        scope,
+                        join_handles,
+                    )
+                    .unwrap();
+
+                for (recv, send) in receivers.into_iter().zip(senders) {
+                    join_handles.push(scope.spawn_task(
+                        TaskPriority::High,
+                        ProbeState::probe(
+                            recv,
+                            send,
+                            &probe_state.build_df,
+                            &probe_state.index,
+                            &self.params,
+                            &state.in_memory_exec_state,
+                        ),
+                    ));
+                }
+            },
+            RangeJoinState::Done => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! Filters on the keys of the probe side of a join, which the join publishes
+//! once it has seen its entire build side. Scans feeding the probe side use
+//! them to skip data that can't have a match.
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use arrow::array::BinaryArray;
+use polars_core::chunked_array::ops::row_encode::_get_rows_encoded_arr;
+use polars_core::config;
+use polars_core::prelude::*;
+use polars_core::scalar::Scalar;
+use polars_core::schema::Schema;
+use polars_error::PolarsResult;
+use polars_parquet::parquet::bloom_filter::{hash_byte, insert, is_in_set};
+use polars_utils::format_pl_smallstr;
+use polars_utils::itertools::Itertools;
+use polars_utils::pl_str::PlSmallStr;
+
+use crate::execute::MemoryReservation;
+
+/// The number of bloom filter bits per build row. With the split block filter
+/// this gives a false positive rate of well under one percent.
+const BLOOM_FILTER_BITS_PER_ROW: usize = 16;
+
+/// Beyond this many build rows, summed over all builders, only the key ranges
+/// are published.
+const MAX_BLOOM_FILTER_ROWS: usize = 1 << 24;
+
+/// Whether the row encoding of this key type is equal exactly when the keys
+/// are equal in a join.
+pub fn supports_runtime_filter(dtype: &DataType) -> bool {
+    dtype.is_integer()
+        || dtype.is_temporal()
+        || matches!(
+            dtype,
+            DataType::Boolean | DataType::String | DataType::Binary
+        )
+}
+
+fn encode_keys(keys: &[Column]) -> PolarsResult<BinaryArray<i64>> {
+    let descending = vec![false; keys.len()];
+    let nulls_last = vec![false; keys.len()];
+    _get_rows_encoded_arr(keys, &descending, &nulls_last)
+}
+
+/// Filter on the key columns of the probe side of a join. It is shared by the
+/// join, which publishes it, and the scan producing the probe side.
+#[derive(Debug)]
+pub struct RuntimeJoinFilter {
+    /// The key columns, as named in the output of the probe-side scan.
+    key_schema: Schema,
+    published: OnceLock<PublishedFilter>,
+}
+
+#[derive(Debug)]
+struct PublishedFilter {
+    /// The smallest and largest value of every key on the build side, or
+    /// `None` if the build side has no non-null keys.
+    bounds: Option<Vec<(Scalar, Scalar)>>,
+    /// A split block bloom filter over the row-encoded keys of the build side.
+    bloom_filter: Option<Vec<u8>>,
+}
+
+impl RuntimeJoinFilter {
+    pub fn new(key_schema: Schema) -> Self {
+        Self {
+            key_schema,
+            published: OnceLock::new(),
+        }
+    }
+
+    pub fn key_schema(&self) -> &Schema {
+        &self.key_schema
+    }
+
+    pub fn is_published(&self) -> bool {
+        self.published.get().is_some()
+    }
+
+    /// Whether this filter can be evaluated on data with the given schema.
+    pub fn applies_to(&self, schema: &Schema) -> bool {
+        self.key_schema
+            .iter()
+            .all(|(name, dtype)| schema.get(name) == Some(dtype))
+    }
+
+    /// Publishes the filter built from the entire build side of the join.
+    pub fn publish(
+        &self,
+        builders: impl IntoIterator<Item = RuntimeFilterBuilder>,
+        budget: &RuntimeFilterHashBudget,
+    ) -> PolarsResult<()> {
+        let builders = builders.into_iter().collect_vec();
+        let num_keys = self.key_schema.len();
+
+        let mut bounds = Vec::with_capacity(num_keys);
+        for (i, (name, dtype)) in self.key_schema.iter().enumerate() {
+            let reduce = |values: Vec<AnyValue>, min: bool| {
+                let s = Series::from_any_values_and_dtype(name.clone(), &values, dtype, false)?;
+                if min { s.min_reduce() } else { s.max_reduce() }
+            };
+            let mins = builders
+                .iter()
+                .flat_map(|b| b.mins[i].iter().map(|s| s.as_any_value()))
+                .collect_vec();
+            let maxs = builders
+                .iter()
+                .flat_map(|b| b.maxs[i].iter().map(|s| s.as_any_value()))
+                .collect_vec();
+            bounds.push((reduce(mins, true)?, reduce(maxs, false)?));
+        }
+        let bounds =
+            (!bounds.iter().any(|(lo, hi)| lo.is_null() || hi.is_null())).then_some(bounds);
+
+        let bloom_filter = builders
+            .iter()
+            .map(|b| b.hashes.as_ref())
+            .collect::<Option<Vec<_>>>()
+            .filter(|_| bounds.is_some() && !budget.is_exhausted())
+            .map(|hashes| {
+                let num_rows: usize = hashes.iter().map(|h| h.len()).sum();
+                let num_bytes = (num_rows * BLOOM_FILTER_BITS_PER_ROW / 8)
+                    .next_power_of_two()
+                    .max(32);
+                let mut bitset = vec![0; num_bytes];
+                for hash in hashes.into_iter().flatten() {
+                    insert(&mut bitset, *hash);
+                }
+                bitset
+            });
+
+        if config::verbose() {
+            eprintln!(
+                "publishing runtime join filter on {:?}: bounds: {:?}, bloom filter: {} bytes",
+                self.key_schema.iter_names().collect_vec(),
+                bounds,
+                bloom_filter.as_ref().map_or(0, |b| b.len()),
+            );
+        }
+        _ = self.published.set(PublishedFilter {
+            bounds,
+            bloom_filter,
+        });
+        // The hashes are dropped together with the builders.
+        budget.memory.clear();
+        Ok(())
+    }
+
+    /// Removes the rows whose keys can't have a match on the build side, if
+    /// the filter has been published.
+    pub fn filter(&self, df: DataFrame) -> PolarsResult<DataFrame> {
+        let Some(published) = self.published.get() else {
+            return Ok(df);
+        };
+        if df.height() == 0 {
+            return Ok(df);
+        }
+
+        let Some(bounds) = &published.bounds else {
+            return Ok(df.clear());
+        };
+        let keys = self
+            .key_schema
+            .iter_names()
+            .map(|name| df.column(name).cloned())
+            .try_collect_vec()?;
+
+        let mut mask: Option<BooleanChunked> = None;
+        for (key, (lo, hi)) in keys.iter().zip(bounds) {
+            let key = key.as_materialized_series();
+            let lo = lo.clone().into_series(PlSmallStr::EMPTY);
+            let hi = hi.clone().into_series(PlSmallStr::EMPTY);
+            let in_range = &key.gt_eq(&lo)? & &key.lt_eq(&hi)?;
+            mask = Some(match mask {
+                Some(mask) => &mask & &in_range,
+                None => in_range,
+            });
+        }
+        let mut mask = mask.unwrap();
+
+        if let Some(bloom_filter) = &published.bloom_filter {
+            let rows = encode_keys(&keys)?;
+            let in_set = BooleanChunked::from_iter_values(
+                PlSmallStr::EMPTY,
+                rows.values_iter()
+                    .map(|row| is_in_set(bloom_filter, hash_byte(row))),
+            );
+            mask = &mask & &in_set;
+        }
+
+        df.filter(&mask)
+    }
+
+    /// Determines which batches can't contain a matching row given their
+    /// statistics, if the filter has been published. The statistics contain a
+    /// `{key}_min` and `{key}_max` column for the keys, with a row per batch.
+    pub fn skip_batch_mask(&self, statistics: &DataFrame) -> PolarsResult<Option<BooleanChunked>> {
+        let Some(published) = self.published.get() else {
+            return Ok(None);
+        };
+        let height = statistics.height();
+        let Some(bounds) = &published.bounds else {
+            return Ok(Some(BooleanChunked::full(PlSmallStr::EMPTY, true, height)));
+        };
+
+        let mut skip = BooleanChunked::full(PlSmallStr::EMPTY, false, height);
+        for ((name, dtype), (lo, hi)) in self.key_schema.iter().zip(bounds) {
+            let (Ok(min), Ok(max)) = (
+                statistics.column(&format_pl_smallstr!("{name}_min")),
+                statistics.column(&format_pl_smallstr!("{name}_max")),
+            ) else {
+                continue;
+            };
+            let min = min.as_materialized_series().cast(dtype)?;
+            let max = max.as_materialized_series().cast(dtype)?;
+            let lo = lo.clone().into_series(PlSmallStr::EMPTY);
+            let hi = hi.clone().into_series(PlSmallStr::EMPTY);
+
+            // Missing statistics are null, which means we can't skip.
+            let disjoint = &max.lt(&lo)? | &min.gt(&hi)?;
+            skip = &skip | &disjoint.fill_null_with_values(false)?;
+        }
+        Ok(Some(skip))
+    }
+}
+
+/// Bounds the key hashes collected by all [`RuntimeFilterBuilder`]s of a join
+/// together, both in number and against the memory budget of the query.
+pub struct RuntimeFilterHashBudget {
+    memory: MemoryReservation,
+    exhausted: AtomicBool,
+}
+
+impl RuntimeFilterHashBudget {
+    pub fn new(memory: MemoryReservation) -> Self {
+        Self {
+            memory,
+            exhausted: AtomicBool::new(false),
+        }
+    }
+
+    /// Reserves room for the hashes of another `num_rows` keys. Returns false
+    /// once the build side has too many keys for a bloom filter, or the
+    /// hashes don't fit in the memory budget, after which the builders drop
+    /// their hashes.
+    fn try_reserve(&self, num_rows: usize) -> bool {
+        if self.is_exhausted() {
+            return false;
+        }
+        let max_bytes = MAX_BLOOM_FILTER_ROWS * size_of::<u64>();
+        if self.memory.grow(num_rows * size_of::<u64>()).is_err() || self.memory.size() > max_bytes
+        {
+            self.exhausted.store(true, Ordering::Relaxed);
+            self.memory.clear();
+            return false;
+        }
+        true
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.exhausted.load(Ordering::Relaxed)
+    }
+}
+
+/// Collects the keys of part of the build side of a join, to publish a
+/// [`RuntimeJoinFilter`] from.
+pub struct RuntimeFilterBuilder {
+    mins: Vec<Vec<Scalar>>,
+    maxs: Vec<Vec<Scalar>>,
+    /// The hashes of the row-encoded keys, `None` once the
+    /// [`RuntimeFilterHashBudget`] is exhausted.
+    hashes: Option<Vec<u64>>,
+}
+
+impl RuntimeFilterBuilder {
+    pub fn new(num_keys: usize) -> Self {
+        Self {
+            mins: vec![Vec::new(); num_keys],
+            maxs: vec![Vec::new(); num_keys],
+            hashes: Some(Vec::new()),
+        }
+    }
+
+    pub fn insert(
+        &mut self,
+        keys: &[Column],
+        budget: &RuntimeFilterHashBudget,
+    ) -> PolarsResult<()> {
+        for (i, key) in keys.iter().enumerate() {
+            self.mins[i].push(key.min_reduce()?);
+            self.maxs[i].push(key.max_reduce()?);
+        }
+
+        let height = keys.first().map_or(0, |k| k.len());
+        if self.hashes.is_some() && !budget.try_reserve(height) {
+            self.hashes = None;
+        }
+        if let Some(hashes) = &mut self.hashes {
+            let rows = encode_keys(keys)?;
+            hashes.extend(rows.values_iter().map(hash_byte));
+        }
+        Ok(())
+    }
+}
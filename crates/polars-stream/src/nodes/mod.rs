@@ -1,3 +1,6 @@
+pub mod distinct;
+#[cfg(feature = "dynamic_group_by")]
+pub mod dynamic_group_by;
 pub mod filter;
 pub mod group_by;
 pub mod in_memory_map;
@@ -16,7 +19,10 @@ pub mod ordered_union;
 pub mod reduce;
 pub mod select;
 pub mod simple_projection;
+pub mod sort;
+pub mod sorted_partition_map;
 pub mod streaming_slice;
+pub mod top_k;
 pub mod with_row_index;
 pub mod zip;
 
@@ -77,6 +83,12 @@ pub trait ComputeNode: Send {
         join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
     );
 
+    /// The estimated size in bytes of the data this node holds on to, if it
+    /// keeps track of it. Only used when profiling.
+    fn memory_usage(&self) -> Option<usize> {
+        None
+    }
+
     /// Called once after the last execution phase to extract output from
     /// in-memory nodes.
     fn get_output(&mut self) -> PolarsResult<Option<DataFrame>> {
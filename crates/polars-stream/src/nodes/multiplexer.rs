@@ -1,14 +1,95 @@
 use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use polars_core::schema::Schema;
 use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
 
 use super::compute_node_prelude::*;
 use crate::async_primitives::wait_group::WaitGroup;
+use crate::execute::MemoryReservation;
 use crate::morsel::SourceToken;
+use crate::utils::spill::{LazySpillDir, SpillFile, can_spill, get_spill_threshold};
+
+/// The morsels the multiplexer holds for outputs which haven't consumed them
+/// yet. Once more than the spill threshold is buffered, newly arriving morsels
+/// are written to disk instead.
+struct MorselBuffer {
+    spill_threshold: usize,
+    buffered_bytes: AtomicUsize,
+    memory: MemoryReservation,
+    spill_dir: LazySpillDir,
+}
+
+impl MorselBuffer {
+    fn buffer(self: &Arc<Self>, morsel: Morsel) -> PolarsResult<BufferedMorsel> {
+        let (mut df, seq, source_token, _) = morsel.into_inner();
+        let size = df.estimated_size();
+        let total = self.buffered_bytes.fetch_add(size, Ordering::Relaxed) + size;
+
+        // Spill if we're over our own threshold or (close to) the memory budget
+        // of the query, the morsel isn't reserved in that case.
+        let spill_now = if self.spill_threshold != usize::MAX {
+            total > self.spill_threshold
+                || self.memory.should_spill()
+                || self.memory.grow(size).is_err()
+        } else {
+            self.memory.grow(size)?;
+            false
+        };
+
+        let data = if spill_now {
+            self.buffered_bytes.fetch_sub(size, Ordering::Relaxed);
+            BufferedData::Spilled(self.spill_dir.get()?.spill(&mut df)?)
+        } else {
+            BufferedData::InMemory(df, size)
+        };
+        Ok(BufferedMorsel {
+            data,
+            seq,
+            source_token,
+            buffer: self.clone(),
+        })
+    }
+}
+
+enum BufferedData {
+    InMemory(DataFrame, usize),
+    Spilled(SpillFile),
+}
+
+/// A morsel shared by all outputs it is buffered for. Its memory is released,
+/// or its spill file removed, once the last of them has consumed it.
+struct BufferedMorsel {
+    data: BufferedData,
+    seq: MorselSeq,
+    source_token: SourceToken,
+    buffer: Arc<MorselBuffer>,
+}
+
+impl BufferedMorsel {
+    fn to_morsel(&self) -> PolarsResult<Morsel> {
+        let df = match &self.data {
+            BufferedData::InMemory(df, _) => df.clone(),
+            BufferedData::Spilled(file) => file.read()?,
+        };
+        Ok(Morsel::new(df, self.seq, self.source_token.clone()))
+    }
+}
+
+impl Drop for BufferedMorsel {
+    fn drop(&mut self) {
+        if let BufferedData::InMemory(_, size) = self.data {
+            self.buffer
+                .buffered_bytes
+                .fetch_sub(size, Ordering::Relaxed);
+            self.buffer.memory.shrink(size);
+        }
+    }
+}
 
-// TODO: replace this with an out-of-core buffering solution.
 enum BufferedStream {
-    Open(VecDeque<Morsel>),
+    Open(VecDeque<Arc<BufferedMorsel>>),
     Closed,
 }
 
@@ -18,14 +99,26 @@ impl BufferedStream {
     }
 }
 
+/// Sends its input to all its outputs. Morsels are buffered for outputs that
+/// consume them slower than the others, or that only start consuming in a later
+/// phase, which spills to disk past a threshold.
 pub struct MultiplexerNode {
     buffers: Vec<BufferedStream>,
+    spill_threshold: usize,
+    morsel_buffer: Option<Arc<MorselBuffer>>,
 }
 
 impl MultiplexerNode {
-    pub fn new() -> Self {
+    pub fn new(schema: &Schema) -> Self {
+        let spill_threshold = if can_spill(schema) {
+            get_spill_threshold()
+        } else {
+            usize::MAX
+        };
         Self {
             buffers: Vec::default(),
+            spill_threshold,
+            morsel_buffer: None,
         }
     }
 }
@@ -95,24 +188,46 @@ impl ComputeNode for MultiplexerNode {
         Ok(())
     }
 
+    fn memory_usage(&self) -> Option<usize> {
+        let buffered = self
+            .morsel_buffer
+            .as_ref()
+            .map_or(0, |b| b.buffered_bytes.load(Ordering::Relaxed));
+        Some(buffered)
+    }
+
     fn spawn<'env, 's>(
         &'env mut self,
         scope: &'s TaskScope<'s, 'env>,
         recv_ports: &mut [Option<RecvPort<'_>>],
         send_ports: &mut [Option<SendPort<'_>>],
-        _state: &'s StreamingExecutionState,
+        state: &'s StreamingExecutionState,
         join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
     ) {
         assert!(recv_ports.len() == 1 && !send_ports.is_empty());
         assert!(self.buffers.len() == send_ports.len());
 
         enum Listener<'a> {
-            Active(UnboundedSender<Morsel>),
-            Buffering(&'a mut VecDeque<Morsel>),
+            Active(UnboundedSender<Arc<BufferedMorsel>>),
+            Buffering(&'a mut VecDeque<Arc<BufferedMorsel>>),
             Inactive,
         }
 
         let buffered_source_token = SourceToken::new();
+        let spill_threshold = self.spill_threshold;
+        let morsel_buffer = self
+            .morsel_buffer
+            .get_or_insert_with(|| {
+                Arc::new(MorselBuffer {
+                    spill_threshold,
+                    buffered_bytes: AtomicUsize::new(0),
+                    memory: state
+                        .memory_manager
+                        .reservation(state.node_key, "multiplexer"),
+                    spill_dir: LazySpillDir::new("multiplexer"),
+                })
+            })
+            .clone();
 
         let (mut buf_senders, buf_receivers): (Vec<_>, Vec<_>) = self
             .buffers
@@ -121,8 +236,8 @@ impl ComputeNode for MultiplexerNode {
             .map(|(port_idx, buffer)| {
                 if let BufferedStream::Open(buf) = buffer {
                     if send_ports[port_idx].is_some() {
-                        // TODO: replace with a bounded channel and store data
-                        // out-of-core beyond a certain size.
+                        // Morsels waiting in the channel count towards the
+                        // spill threshold as well.
                         let (rx, tx) = unbounded_channel();
                         (Listener::Active(rx), Some((buf, tx)))
                     } else {
@@ -143,12 +258,15 @@ impl ComputeNode for MultiplexerNode {
                         break;
                     };
                     drop(morsel.take_consume_token());
+                    let source_token = morsel.source_token().clone();
+
+                    let buffered = Arc::new(morsel_buffer.buffer(morsel)?);
 
                     let mut anyone_interested = false;
                     let mut active_listener_interested = false;
                     for buf_sender in &mut buf_senders {
                         match buf_sender {
-                            Listener::Active(s) => match s.send(morsel.clone()) {
+                            Listener::Active(s) => match s.send(buffered.clone()) {
                                 Ok(_) => {
                                     anyone_interested = true;
                                     active_listener_interested = true;
@@ -156,7 +274,7 @@ impl ComputeNode for MultiplexerNode {
                                 Err(_) => *buf_sender = Listener::Inactive,
                             },
                             Listener::Buffering(b) => {
-                                b.push_front(morsel.clone());
+                                b.push_front(buffered.clone());
                                 anyone_interested = true;
                             },
                             Listener::Inactive => {},
@@ -171,7 +289,7 @@ impl ComputeNode for MultiplexerNode {
                     // request from an input reading from old buffered data,
                     // request a stop from the source.
                     if !active_listener_interested || buffered_source_token.stop_requested() {
-                        morsel.source_token().stop();
+                        source_token.stop();
                     }
                 }
 
@@ -187,7 +305,9 @@ impl ComputeNode for MultiplexerNode {
                 let buffered_source_token = buffered_source_token.clone();
                 join_handles.push(scope.spawn_task(TaskPriority::High, async move {
                     // First we try to flush all the old buffered data.
-                    while let Some(mut morsel) = buf.pop_back() {
+                    while let Some(buffered) = buf.pop_back() {
+                        let mut morsel = buffered.to_morsel()?;
+                        drop(buffered);
                         morsel.replace_source_token(buffered_source_token.clone());
                         morsel.set_consume_token(wait_group.token());
                         if sender.send(morsel).await.is_err()
@@ -199,7 +319,9 @@ impl ComputeNode for MultiplexerNode {
                     }
 
                     // Then send along data from the multiplexer.
-                    while let Some(mut morsel) = rx.recv().await {
+                    while let Some(buffered) = rx.recv().await {
+                        let mut morsel = buffered.to_morsel()?;
+                        drop(buffered);
                         morsel.set_consume_token(wait_group.token());
                         if sender.send(morsel).await.is_err() {
                             break;
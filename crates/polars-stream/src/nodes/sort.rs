@@ -0,0 +1,652 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use arrow::array::BinaryViewArray;
+use polars_core::prelude::row_encode::_get_rows_encoded;
+use polars_core::prelude::sort::_broadcast_bools;
+use polars_core::prelude::{
+    BinaryChunked, ChunkSort, IntoColumn, NewChunkedArray, SortMultipleOptions, SortOptions,
+    UInt64Chunked,
+};
+use polars_core::schema::Schema;
+use polars_core::utils::{accumulate_dataframes_vertical_unchecked, slice_offsets};
+use polars_error::polars_ensure;
+use polars_utils::format_pl_smallstr;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::compute_node_prelude::*;
+use crate::async_primitives::connector::{Receiver, Sender};
+use crate::async_primitives::wait_group::WaitGroup;
+use crate::execute::MemoryReservation;
+use crate::expression::StreamExpr;
+use crate::morsel::{SourceToken, get_ideal_morsel_size};
+use crate::nodes::in_memory_source::InMemorySourceNode;
+use crate::utils::in_memory_linearize::linearize;
+use crate::utils::spill::{LazySpillDir, MaybeSpilled, SpillDir, can_spill, get_spill_threshold};
+
+const ROW_ENCODED_NAME: PlSmallStr = PlSmallStr::from_static("__POLARS_SORT_ROW");
+
+/// A column to sort by.
+pub struct SortKey {
+    /// If `None` the key is an existing column of the input with the given name,
+    /// otherwise it gets computed and stored under that name.
+    pub selector: Option<StreamExpr>,
+    pub name: PlSmallStr,
+}
+
+impl SortKey {
+    pub fn column(name: PlSmallStr) -> Self {
+        Self {
+            selector: None,
+            name,
+        }
+    }
+
+    pub fn expr(selector: StreamExpr, idx: usize) -> Self {
+        Self {
+            selector: Some(selector),
+            name: format_pl_smallstr!("__POLARS_SORT_KEY_{idx}"),
+        }
+    }
+}
+
+/// Computes the keys that aren't plain columns and adds them to the frame.
+pub async fn add_computed_keys(
+    keys: &[SortKey],
+    df: &mut DataFrame,
+    state: &ExecutionState,
+) -> PolarsResult<()> {
+    let height = df.height();
+    for key in keys {
+        if let Some(selector) = &key.selector {
+            let mut c = selector.evaluate(df, state).await?;
+            if c.len() == 1 && height != 1 {
+                c = c.new_from_index(0, height);
+            }
+            polars_ensure!(
+                c.len() == height,
+                ShapeMismatch: "sort expressions must have same \
+                length as DataFrame, got DataFrame height: {} and Series length: {}",
+                height, c.len()
+            );
+            df.with_column(c.with_name(key.name.clone()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Parameters shared by all phases of the sort.
+struct SortParams {
+    keys: Vec<SortKey>,
+    sort_options: SortMultipleOptions,
+    slice: Option<(i64, usize)>,
+    output_schema: Arc<Schema>,
+    spill_threshold: usize,
+}
+
+impl SortParams {
+    /// Sorts the given buffered morsels into a run, with a row-encoded column
+    /// that can be used for merging.
+    ///
+    /// The morsels must be given in the order they were received.
+    fn sort_into_run(&self, morsels: Vec<Morsel>) -> PolarsResult<DataFrame> {
+        let mut seqs = Vec::with_capacity(morsels.len());
+        let df = accumulate_dataframes_vertical_unchecked(morsels.into_iter().map(|m| {
+            let (df, seq, _, _) = m.into_inner();
+            seqs.push((seq.to_u64(), df.height()));
+            df
+        }));
+
+        let mut key_columns = self
+            .keys
+            .iter()
+            .map(|k| df.column(&k.name).unwrap().clone())
+            .collect::<Vec<_>>();
+        let mut descending = self.sort_options.descending.clone();
+        let mut nulls_last = self.sort_options.nulls_last.clone();
+
+        // Break ties by the position in the input stream so the merge is stable.
+        if self.sort_options.maintain_order {
+            let seq = UInt64Chunked::from_iter_values(
+                PlSmallStr::EMPTY,
+                seqs.iter()
+                    .flat_map(|(seq, len)| std::iter::repeat_n(*seq, *len)),
+            );
+            let row = UInt64Chunked::from_iter_values(PlSmallStr::EMPTY, 0..df.height() as u64);
+            key_columns.push(seq.into_column());
+            key_columns.push(row.into_column());
+            descending.extend([false, false]);
+            nulls_last.extend([false, false]);
+        }
+
+        let rows = _get_rows_encoded(&key_columns, &descending, &nulls_last)?.into_binview();
+        let rows = BinaryChunked::with_chunk(ROW_ENCODED_NAME, rows);
+        let idx = rows.arg_sort(SortOptions {
+            maintain_order: false,
+            multithreaded: false,
+            ..Default::default()
+        });
+
+        let mut out = df._select_impl(
+            self.output_schema
+                .iter_names_cloned()
+                .collect::<Vec<_>>()
+                .as_slice(),
+        )?;
+        unsafe {
+            out.with_column_unchecked(rows.into_column());
+            Ok(out.take_unchecked_impl(&idx, false))
+        }
+    }
+}
+
+struct LocalSortSinkState {
+    morsels: Vec<Morsel>,
+    buffered_bytes: usize,
+    runs: Vec<SortedRun>,
+}
+
+struct SortSinkState {
+    params: Arc<SortParams>,
+    locals: Vec<LocalSortSinkState>,
+    buffered_bytes: AtomicUsize,
+    spill_dir: LazySpillDir,
+    memory: Option<MemoryReservation>,
+}
+
+impl SortSinkState {
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        receivers: Vec<Receiver<Morsel>>,
+        state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        let params = &*self.params;
+        let buffered_bytes = &self.buffered_bytes;
+        let spill_dir = &self.spill_dir;
+        let memory = &*self
+            .memory
+            .get_or_insert_with(|| state.memory_manager.reservation(state.node_key, "sort"));
+        for (mut recv, local) in receivers.into_iter().zip(self.locals.iter_mut()) {
+            join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+                while let Ok(mut morsel) = recv.recv().await {
+                    drop(morsel.take_consume_token());
+
+                    let df = morsel.df_mut();
+                    add_computed_keys(&params.keys, df, &state.in_memory_exec_state).await?;
+
+                    let size = df.estimated_size();
+                    local.buffered_bytes += size;
+                    local.morsels.push(morsel);
+                    let total = buffered_bytes.fetch_add(size, Ordering::Relaxed) + size;
+
+                    // Spill if we're over our own threshold or (close to) the
+                    // memory budget of the query, the morsel isn't reserved
+                    // in that case.
+                    let spill_now = if params.spill_threshold != usize::MAX {
+                        total > params.spill_threshold
+                            || memory.should_spill()
+                            || memory.grow(size).is_err()
+                    } else {
+                        memory.grow(size)?;
+                        false
+                    };
+
+                    if spill_now {
+                        let spill_dir = spill_dir.get()?;
+                        let run = params.sort_into_run(std::mem::take(&mut local.morsels))?;
+                        local.runs.push(SortedRun::spill(run, &spill_dir)?);
+                        buffered_bytes.fetch_sub(local.buffered_bytes, Ordering::Relaxed);
+                        memory.shrink(local.buffered_bytes - size);
+                        local.buffered_bytes = 0;
+                    }
+                }
+
+                Ok(())
+            }));
+        }
+    }
+
+    fn finalize(self) -> PolarsResult<SortState> {
+        let params = self.params;
+        let has_spilled = self.locals.iter().any(|l| !l.runs.is_empty());
+
+        if !has_spilled {
+            // Everything fit in memory, fall back to the regular in-memory sort.
+            let morsels_per_pipe = self.locals.into_iter().map(|l| l.morsels).collect();
+            let dataframes = linearize(morsels_per_pipe);
+            if dataframes.is_empty() {
+                let df = DataFrame::empty_with_schema(&params.output_schema);
+                let source = InMemorySourceNode::new(Arc::new(df), MorselSeq::default());
+                return Ok(SortState::Source(source));
+            }
+            let df = accumulate_dataframes_vertical_unchecked(dataframes);
+
+            let by_column = params
+                .keys
+                .iter()
+                .map(|k| df.column(&k.name).unwrap().clone())
+                .collect();
+            let df =
+                df._select_impl(&params.output_schema.iter_names_cloned().collect::<Vec<_>>())?;
+            let df = df.sort_impl(by_column, params.sort_options.clone(), params.slice)?;
+            let source = InMemorySourceNode::new(Arc::new(df), MorselSeq::default());
+            return Ok(SortState::Source(source));
+        }
+
+        // Turn the remaining buffered data into runs, these can stay in memory.
+        let mut runs = Vec::new();
+        for local in self.locals {
+            runs.extend(local.runs);
+            if !local.morsels.is_empty() {
+                runs.push(SortedRun::in_memory(params.sort_into_run(local.morsels)?));
+            }
+        }
+
+        let total_height = runs.iter().map(|r| r.height).sum();
+        let (skip, remaining) = match params.slice {
+            None => (0, total_height),
+            Some((offset, len)) => slice_offsets(offset, len, total_height),
+        };
+
+        if polars_core::config::verbose() {
+            eprintln!(
+                "[sort]: merging {} sorted runs with {total_height} rows",
+                runs.len()
+            );
+        }
+
+        Ok(SortState::Merge(MergeSourceState {
+            runs: runs.into_iter().map(RunCursor::new).collect(),
+            pending: VecDeque::new(),
+            skip,
+            remaining,
+            seq: MorselSeq::default(),
+            _spill_dir: self.spill_dir.into_inner(),
+        }))
+    }
+}
+
+/// A sorted sequence of rows, split into chunks that are possibly on disk.
+struct SortedRun {
+    chunks: VecDeque<MaybeSpilled>,
+    height: usize,
+}
+
+impl SortedRun {
+    fn in_memory(df: DataFrame) -> Self {
+        let height = df.height();
+        let chunks = split_in_chunks(df)
+            .into_iter()
+            .map(MaybeSpilled::InMemory)
+            .collect();
+        Self { chunks, height }
+    }
+
+    fn spill(df: DataFrame, spill_dir: &SpillDir) -> PolarsResult<Self> {
+        let height = df.height();
+        let chunks = split_in_chunks(df)
+            .into_iter()
+            .map(|mut df| Ok(MaybeSpilled::Spilled(spill_dir.spill(&mut df)?)))
+            .collect::<PolarsResult<_>>()?;
+        Ok(Self { chunks, height })
+    }
+}
+
+fn split_in_chunks(df: DataFrame) -> Vec<DataFrame> {
+    let chunk_size = get_ideal_morsel_size();
+    let mut chunks = Vec::with_capacity(df.height().div_ceil(chunk_size));
+    let mut offset = 0;
+    while offset < df.height() {
+        chunks.push(df.slice(offset as i64, chunk_size));
+        offset += chunk_size;
+    }
+    chunks
+}
+
+/// The chunk of a run that is currently being merged.
+struct RunHead {
+    df: DataFrame,
+    rows: BinaryViewArray,
+    offset: usize,
+}
+
+impl RunHead {
+    fn last_row(&self) -> &[u8] {
+        self.rows.value(self.rows.len() - 1)
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.offset == self.rows.len()
+    }
+}
+
+struct RunCursor {
+    run: SortedRun,
+    head: Option<RunHead>,
+}
+
+impl RunCursor {
+    fn new(run: SortedRun) -> Self {
+        Self { run, head: None }
+    }
+
+    /// Ensures the head has rows left, loading the next chunk if necessary.
+    /// The head is `None` afterwards if the run is exhausted.
+    fn fill_head(&mut self) -> PolarsResult<()> {
+        while self.head.as_ref().is_none_or(|h| h.is_exhausted()) {
+            let Some(chunk) = self.run.chunks.pop_front() else {
+                self.head = None;
+                return Ok(());
+            };
+            let df = chunk.load()?;
+            let rows = df.column(&ROW_ENCODED_NAME)?.binary()?.rechunk();
+            let rows = rows.downcast_as_array().clone();
+            self.head = Some(RunHead {
+                df,
+                rows,
+                offset: 0,
+            });
+        }
+        Ok(())
+    }
+}
+
+struct MergeSourceState {
+    runs: Vec<RunCursor>,
+    /// Merged data that still has to be sent.
+    pending: VecDeque<DataFrame>,
+    /// The number of rows we still have to skip (for the slice offset).
+    skip: usize,
+    /// The number of rows we still have to send (for the slice length).
+    remaining: usize,
+    seq: MorselSeq,
+    // Keeps the spilled files alive until we're done merging.
+    _spill_dir: Option<Arc<SpillDir>>,
+}
+
+impl MergeSourceState {
+    fn is_exhausted(&self) -> bool {
+        self.pending.is_empty()
+            && (self.remaining == 0
+                || self.runs.iter().all(|r| {
+                    r.head.as_ref().is_none_or(|h| h.is_exhausted()) && r.run.chunks.is_empty()
+                }))
+    }
+
+    /// Merges the next batch of rows from the runs, returns `None` if all runs
+    /// are exhausted.
+    ///
+    /// Every row up to the smallest last row of the current chunks of all runs
+    /// can be safely merged, as all rows that come after it in any run are larger.
+    fn merge_next_batch(&mut self) -> PolarsResult<Option<DataFrame>> {
+        for run in self.runs.iter_mut() {
+            run.fill_head()?;
+        }
+        let Some(bound) = self
+            .runs
+            .iter()
+            .filter_map(|r| r.head.as_ref().map(|h| h.last_row()))
+            .min()
+        else {
+            return Ok(None);
+        };
+
+        let bound = bound.to_vec();
+        let mut parts = Vec::new();
+        for run in self.runs.iter_mut() {
+            let Some(head) = run.head.as_mut() else {
+                continue;
+            };
+            let start = head.offset;
+            let end = if head.last_row() <= bound.as_slice() {
+                head.rows.len()
+            } else {
+                start + partition_point(&head.rows, start, |row| row <= bound.as_slice())
+            };
+            if end > start {
+                parts.push(head.df.slice(start as i64, end - start));
+                head.offset = end;
+            }
+        }
+
+        let needs_sort = parts.len() > 1;
+        let mut df = accumulate_dataframes_vertical_unchecked(parts);
+        let rows = df.drop_in_place(&ROW_ENCODED_NAME)?;
+        if needs_sort {
+            let idx = rows.binary()?.arg_sort(SortOptions {
+                maintain_order: false,
+                multithreaded: false,
+                ..Default::default()
+            });
+            df = unsafe { df.take_unchecked_impl(&idx, false) };
+        }
+        Ok(Some(df))
+    }
+
+    /// Fills the pending queue with the next morsel-sized pieces of output,
+    /// applying the slice. Returns false if there is nothing left to send.
+    fn fill_pending(&mut self) -> PolarsResult<bool> {
+        while self.pending.is_empty() && self.remaining > 0 {
+            let Some(mut df) = self.merge_next_batch()? else {
+                self.remaining = 0;
+                break;
+            };
+
+            if self.skip > 0 {
+                let n = self.skip.min(df.height());
+                df = df.slice(n as i64, df.height() - n);
+                self.skip -= n;
+            }
+            if df.height() > self.remaining {
+                df = df.slice(0, self.remaining);
+            }
+            self.remaining -= df.height();
+            self.pending.extend(split_in_chunks(df));
+        }
+        Ok(!self.pending.is_empty())
+    }
+
+    async fn send_morsels(&mut self, mut send: Sender<Morsel>) -> PolarsResult<()> {
+        let source_token = SourceToken::new();
+        let wait_group = WaitGroup::default();
+        while self.fill_pending()? {
+            let df = self.pending.pop_front().unwrap();
+            let mut morsel = Morsel::new(df, self.seq, source_token.clone());
+            self.seq = self.seq.successor();
+            morsel.set_consume_token(wait_group.token());
+            if send.send(morsel).await.is_err() {
+                break;
+            }
+
+            wait_group.wait().await;
+            if source_token.stop_requested() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the number of rows starting at `start` for which `pred` holds,
+/// assuming the rows are partitioned by `pred`.
+fn partition_point(rows: &BinaryViewArray, start: usize, pred: impl Fn(&[u8]) -> bool) -> usize {
+    let (mut lo, mut hi) = (start, rows.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(rows.value(mid)) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo - start
+}
+
+enum SortState {
+    Sink(SortSinkState),
+    Source(InMemorySourceNode),
+    Merge(MergeSourceState),
+    Done,
+}
+
+/// Sorts its input, spilling sorted runs to disk if the buffered data exceeds
+/// the spill threshold and merging those runs when producing output.
+pub struct SortNode {
+    state: SortState,
+}
+
+impl SortNode {
+    pub fn new(
+        keys: Vec<SortKey>,
+        mut sort_options: SortMultipleOptions,
+        slice: Option<(i64, usize)>,
+        output_schema: Arc<Schema>,
+        num_pipelines: usize,
+    ) -> Self {
+        _broadcast_bools(keys.len(), &mut sort_options.descending);
+        _broadcast_bools(keys.len(), &mut sort_options.nulls_last);
+
+        let spill_threshold = if can_spill(&output_schema) {
+            get_spill_threshold()
+        } else {
+            usize::MAX
+        };
+
+        let params = Arc::new(SortParams {
+            keys,
+            sort_options,
+            slice,
+            output_schema,
+            spill_threshold,
+        });
+        let locals = (0..num_pipelines)
+            .map(|_| LocalSortSinkState {
+                morsels: Vec::new(),
+                buffered_bytes: 0,
+                runs: Vec::new(),
+            })
+            .collect();
+        Self {
+            state: SortState::Sink(SortSinkState {
+                params,
+                locals,
+                buffered_bytes: AtomicUsize::new(0),
+                spill_dir: LazySpillDir::new("sort"),
+                memory: None,
+            }),
+        }
+    }
+}
+
+impl ComputeNode for SortNode {
+    fn name(&self) -> &str {
+        "sort"
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 1 && send.len() == 1);
+
+        // State transitions.
+        match &mut self.state {
+            // If the output doesn't want any more data, transition to being done.
+            _ if send[0] == PortState::Done => {
+                self.state = SortState::Done;
+            },
+            // Input is done, transition to being a source.
+            SortState::Sink(_) if matches!(recv[0], PortState::Done) => {
+                let SortState::Sink(sink) = core::mem::replace(&mut self.state, SortState::Done)
+                else {
+                    unreachable!()
+                };
+                self.state = sink.finalize()?;
+                if let SortState::Source(src) = &mut self.state {
+                    src.update_state(&mut [], send, state)?;
+                }
+            },
+            // Defer to source node implementation.
+            SortState::Source(src) => {
+                src.update_state(&mut [], send, state)?;
+                if send[0] == PortState::Done {
+                    self.state = SortState::Done;
+                }
+            },
+            SortState::Merge(merge) => {
+                if merge.is_exhausted() {
+                    self.state = SortState::Done;
+                }
+            },
+            // Nothing to change.
+            SortState::Done | SortState::Sink(_) => {},
+        }
+
+        // Communicate our state.
+        match &self.state {
+            SortState::Sink { .. } => {
+                send[0] = PortState::Blocked;
+                recv[0] = PortState::Ready;
+            },
+            SortState::Source(..) | SortState::Merge(..) => {
+                recv[0] = PortState::Done;
+                send[0] = PortState::Ready;
+            },
+            SortState::Done => {
+                recv[0] = PortState::Done;
+                send[0] = PortState::Done;
+            },
+        }
+        Ok(())
+    }
+
+    fn is_memory_intensive_pipeline_blocker(&self) -> bool {
+        matches!(self.state, SortState::Sink { .. })
+    }
+
+    fn memory_usage(&self) -> Option<usize> {
+        match &self.state {
+            SortState::Sink(sink) => Some(sink.buffered_bytes.load(Ordering::Relaxed)),
+            SortState::Source(source) => source.memory_usage(),
+            SortState::Merge(_) => None,
+            SortState::Done => Some(0),
+        }
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(send_ports.len() == 1 && recv_ports.len() == 1);
+        match &mut self.state {
+            SortState::Sink(sink) => {
+                assert!(send_ports[0].is_none());
+                sink.spawn(
+                    scope,
+                    recv_ports[0].take().unwrap().parallel(),
+                    state,
+                    join_handles,
+                )
+            },
+            SortState::Source(source) => {
+                assert!(recv_ports[0].is_none());
+                source.spawn(scope, &mut [], send_ports, state, join_handles);
+            },
+            SortState::Merge(merge) => {
+                assert!(recv_ports[0].is_none());
+                let send = send_ports[0].take().unwrap().serial();
+                join_handles.push(scope.spawn_task(TaskPriority::High, merge.send_morsels(send)));
+            },
+            SortState::Done => unreachable!(),
+        }
+    }
+}
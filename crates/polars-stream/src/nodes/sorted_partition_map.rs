@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_plan::plans::DataFrameUdf;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::compute_node_prelude::*;
+use crate::DEFAULT_DISTRIBUTOR_BUFFER_SIZE;
+use crate::async_primitives::distributor_channel::distributor_channel;
+use crate::async_primitives::wait_group::WaitGroup;
+use crate::morsel::SourceToken;
+
+/// Applies a map to whole partitions of an input in which all rows with equal
+/// keys are consecutive, e.g. because it is sorted by those keys.
+///
+/// Rows are buffered until the keys change, after which all complete partitions
+/// are passed to the map at once. The last partition is only known to be
+/// complete once the input is exhausted.
+pub struct SortedPartitionMapNode {
+    keys: Vec<PlSmallStr>,
+    map: Arc<dyn DataFrameUdf>,
+    /// The rows of the (possibly incomplete) last partition seen so far.
+    buffer: Vec<DataFrame>,
+    seq: MorselSeq,
+}
+
+impl SortedPartitionMapNode {
+    pub fn new(keys: Vec<PlSmallStr>, map: Arc<dyn DataFrameUdf>) -> Self {
+        Self {
+            keys,
+            map,
+            buffer: Vec::new(),
+            seq: MorselSeq::new(0),
+        }
+    }
+
+    /// Returns the index of the first row of the last partition in df.
+    fn last_partition_start(&self, df: &DataFrame) -> PolarsResult<usize> {
+        let len = df.height();
+        let mut same_as_last = BooleanChunked::full(PlSmallStr::EMPTY, true, len);
+        for key in &self.keys {
+            let col = df.column(key)?;
+            same_as_last = &same_as_last & &col.equal_missing(&col.slice(len as i64 - 1, 1))?;
+        }
+        Ok(same_as_last
+            .reverse()
+            .first_false_idx()
+            .map_or(0, |n_from_end| len - n_from_end))
+    }
+
+    /// Adds the given rows, returning all rows of partitions which are now
+    /// known to be complete.
+    fn add(&mut self, df: DataFrame) -> PolarsResult<Option<DataFrame>> {
+        if df.height() == 0 {
+            return Ok(None);
+        }
+
+        let split = self.last_partition_start(&df)?;
+        if split == 0 {
+            // The whole frame might still belong to the buffered partition.
+            let Some(last) = self.buffer.last() else {
+                self.buffer.push(df);
+                return Ok(None);
+            };
+            let mut boundary = last.slice(last.height() as i64 - 1, 1);
+            boundary.vstack_mut_owned_unchecked(df.slice(0, 1));
+            if self.last_partition_start(&boundary)? == 0 {
+                self.buffer.push(df);
+                return Ok(None);
+            }
+        }
+
+        let (complete, rest) = df.split_at(split as i64);
+        let mut complete_parts = std::mem::take(&mut self.buffer);
+        complete_parts.push(complete);
+        self.buffer.push(rest);
+        Ok(Some(accumulate_dataframes_vertical_unchecked(
+            complete_parts,
+        )))
+    }
+}
+
+impl ComputeNode for SortedPartitionMapNode {
+    fn name(&self) -> &str {
+        "sorted-partition-map"
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        _state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 1 && send.len() == 1);
+
+        if send[0] == PortState::Done || (recv[0] == PortState::Done && self.buffer.is_empty()) {
+            self.buffer.clear();
+            recv[0] = PortState::Done;
+            send[0] = PortState::Done;
+        } else if recv[0] != PortState::Done {
+            recv.swap_with_slice(send);
+        }
+        // Otherwise the input is done but we still have to flush the last
+        // partition, so we keep the state of the output.
+        Ok(())
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        _state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(recv_ports.len() == 1 && send_ports.len() == 1);
+        let receiver = recv_ports[0].take().map(|recv| recv.serial());
+        let senders = send_ports[0].take().unwrap().parallel();
+
+        let (mut distributor, distr_receivers) =
+            distributor_channel(senders.len(), *DEFAULT_DISTRIBUTOR_BUFFER_SIZE);
+
+        // Finding the partition boundaries has to be done serially.
+        let slf = &mut *self;
+        let map = slf.map.clone();
+        join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+            if let Some(mut receiver) = receiver {
+                while let Ok(morsel) = receiver.recv().await {
+                    let (df, _seq, source_token, _consume_token) = morsel.into_inner();
+                    if let Some(complete) = slf.add(df)? {
+                        let seq = slf.seq;
+                        slf.seq = seq.successor();
+                        if distributor
+                            .send((complete, seq, source_token.clone()))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+
+                    // The morsel is buffered, so we can stop consuming the
+                    // input as soon as that is requested.
+                    if source_token.stop_requested() {
+                        break;
+                    }
+                }
+            } else if !slf.buffer.is_empty() {
+                // The input is exhausted, so the buffered partition is complete.
+                let complete = accumulate_dataframes_vertical_unchecked(slf.buffer.drain(..));
+                let seq = slf.seq;
+                slf.seq = seq.successor();
+                _ = distributor.send((complete, seq, SourceToken::new())).await;
+            }
+
+            Ok(())
+        }));
+
+        // But the map itself can be applied in parallel.
+        for (mut send, mut recv) in senders.into_iter().zip(distr_receivers) {
+            let map = map.clone();
+            join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+                let wait_group = WaitGroup::default();
+                while let Ok((df, seq, source_token)) = recv.recv().await {
+                    let mut morsel = Morsel::new(map.call_udf(df)?, seq, source_token);
+                    morsel.set_consume_token(wait_group.token());
+                    if send.send(morsel).await.is_err() {
+                        break;
+                    }
+                    wait_group.wait().await;
+                }
+
+                Ok(())
+            }));
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use std::sync::Arc;
+
+use polars_core::prelude::row_encode::_get_rows_encoded;
+use polars_core::prelude::sort::_broadcast_bools;
+use polars_core::prelude::{
+    BinaryChunked, BooleanChunked, ChunkFull, ChunkSort, IntoColumn, NewChunkedArray,
+    SortMultipleOptions, SortOptions, UInt64Chunked,
+};
+use polars_core::schema::Schema;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_utils::IdxSize;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::compute_node_prelude::*;
+use crate::async_primitives::connector::Receiver;
+use crate::morsel::get_ideal_morsel_size;
+use crate::nodes::in_memory_source::InMemorySourceNode;
+use crate::nodes::sort::{SortKey, add_computed_keys};
+
+const ROW_ENCODED_NAME: PlSmallStr = PlSmallStr::from_static("__POLARS_TOP_K_ROW");
+
+struct TopKParams {
+    keys: Vec<SortKey>,
+    sort_options: SortMultipleOptions,
+    /// The number of rows to skip from the top.
+    offset: usize,
+    /// The number of rows to output after the offset.
+    len: usize,
+    output_schema: Arc<Schema>,
+}
+
+impl TopKParams {
+    /// The number of rows each pipeline has to keep around.
+    fn k(&self) -> usize {
+        self.offset.saturating_add(self.len)
+    }
+
+    /// Returns the output columns of the frame together with the row-encoded
+    /// keys, rows with smaller encodings come first in the sort order.
+    ///
+    /// If the sort has to maintain order, ties are broken by the position in
+    /// the input stream.
+    fn encode(&self, df: &DataFrame, seq: MorselSeq, row_offset: u64) -> PolarsResult<DataFrame> {
+        let mut key_columns = self
+            .keys
+            .iter()
+            .map(|k| df.column(&k.name).unwrap().clone())
+            .collect::<Vec<_>>();
+        let mut descending = self.sort_options.descending.clone();
+        let mut nulls_last = self.sort_options.nulls_last.clone();
+
+        if self.sort_options.maintain_order {
+            let seq = UInt64Chunked::full(PlSmallStr::EMPTY, seq.to_u64(), df.height());
+            let row = UInt64Chunked::from_iter_values(
+                PlSmallStr::EMPTY,
+                row_offset..row_offset + df.height() as u64,
+            );
+            key_columns.push(seq.into_column());
+            key_columns.push(row.into_column());
+            descending.extend([false, false]);
+            nulls_last.extend([false, false]);
+        }
+
+        let rows = _get_rows_encoded(&key_columns, &descending, &nulls_last)?.into_binview();
+        let rows = BinaryChunked::with_chunk(ROW_ENCODED_NAME, rows);
+
+        let mut out = df._select_impl(
+            self.output_schema
+                .iter_names_cloned()
+                .collect::<Vec<_>>()
+                .as_slice(),
+        )?;
+        unsafe { out.with_column_unchecked(rows.into_column()) };
+        Ok(out)
+    }
+
+    /// Returns the (at most) k smallest rows of the frame in sorted order.
+    fn select_top_k(&self, df: DataFrame) -> PolarsResult<DataFrame> {
+        let idx = df
+            .column(&ROW_ENCODED_NAME)?
+            .binary()?
+            .arg_sort(SortOptions {
+                maintain_order: false,
+                multithreaded: false,
+                limit: Some(self.k().min(df.height()) as IdxSize),
+                ..Default::default()
+            });
+        let idx = idx.slice(0, self.k());
+        Ok(unsafe { df.take_unchecked_impl(&idx, false) })
+    }
+}
+
+#[derive(Default)]
+struct LocalTopKState {
+    /// The best k rows seen so far by this pipeline, in sorted order.
+    top: Option<DataFrame>,
+    /// Rows that still have to be compared against the current top.
+    pending: Vec<DataFrame>,
+    pending_rows: usize,
+    /// The encoding of the k-th best row, once we've seen k rows. Rows that
+    /// don't sort before it can't be part of the result.
+    threshold: Option<Vec<u8>>,
+    rows_seen: u64,
+}
+
+impl LocalTopKState {
+    fn compact(&mut self, params: &TopKParams) -> PolarsResult<()> {
+        let parts = self.top.take().into_iter().chain(self.pending.drain(..));
+        let df = accumulate_dataframes_vertical_unchecked(parts);
+        let top = params.select_top_k(df)?;
+        if top.height() == params.k() {
+            let rows = top.column(&ROW_ENCODED_NAME)?.binary()?;
+            self.threshold = rows.get(top.height() - 1).map(|r| r.to_vec());
+        }
+        self.top = Some(top);
+        self.pending_rows = 0;
+        Ok(())
+    }
+
+    fn estimated_size(&self) -> usize {
+        let top = self.top.iter();
+        top.chain(&self.pending).map(|df| df.estimated_size()).sum()
+    }
+}
+
+impl TopKParams {
+    async fn sink(
+        &self,
+        mut recv: Receiver<Morsel>,
+        local: &mut LocalTopKState,
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        let compact_at = self.k().max(get_ideal_morsel_size());
+        while let Ok(morsel) = recv.recv().await {
+            let (mut df, seq, _src_token, _wait_token) = morsel.into_inner();
+            if df.height() == 0 || self.k() == 0 {
+                continue;
+            }
+
+            add_computed_keys(&self.keys, &mut df, &state.in_memory_exec_state).await?;
+            let mut df = self.encode(&df, seq, local.rows_seen)?;
+            local.rows_seen += df.height() as u64;
+
+            if let Some(threshold) = &local.threshold {
+                let rows = df.column(&ROW_ENCODED_NAME)?.binary()?;
+                let mask: BooleanChunked = rows
+                    .downcast_iter()
+                    .flat_map(|arr| arr.values_iter())
+                    .map(|row| row < threshold.as_slice())
+                    .collect();
+                df = df.filter(&mask)?;
+            }
+
+            if df.height() > 0 {
+                local.pending_rows += df.height();
+                local.pending.push(df);
+                if local.pending_rows >= compact_at {
+                    local.compact(self)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Combines the tops of all pipelines into the final output.
+    fn finalize(&self, locals: Vec<LocalTopKState>) -> PolarsResult<DataFrame> {
+        let parts = locals
+            .into_iter()
+            .flat_map(|l| l.top.into_iter().chain(l.pending))
+            .collect::<Vec<_>>();
+        if parts.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&self.output_schema));
+        }
+        let df = accumulate_dataframes_vertical_unchecked(parts);
+        let mut df = self.select_top_k(df)?;
+        df.drop_in_place(&ROW_ENCODED_NAME)?;
+        Ok(df.slice(self.offset as i64, self.len))
+    }
+}
+
+enum TopKState {
+    Sink(Vec<LocalTopKState>),
+    Source(InMemorySourceNode),
+    Done,
+}
+
+/// Sorts its input and outputs a slice from the top of it, keeping only the
+/// rows that can still end up in that slice.
+///
+/// Each pipeline keeps its best k rows (where k is the end of the slice)
+/// together with the encoding of the k-th best row, morsels are filtered
+/// against that threshold and merged into the top once enough rows are
+/// pending. This keeps the memory usage in O(k) regardless of the input size.
+pub struct TopKNode {
+    state: TopKState,
+    params: TopKParams,
+}
+
+impl TopKNode {
+    pub fn new(
+        keys: Vec<SortKey>,
+        mut sort_options: SortMultipleOptions,
+        offset: usize,
+        len: usize,
+        output_schema: Arc<Schema>,
+        num_pipelines: usize,
+    ) -> Self {
+        _broadcast_bools(keys.len(), &mut sort_options.descending);
+        _broadcast_bools(keys.len(), &mut sort_options.nulls_last);
+
+        let locals = (0..num_pipelines)
+            .map(|_| LocalTopKState::default())
+            .collect();
+        Self {
+            state: TopKState::Sink(locals),
+            params: TopKParams {
+                keys,
+                sort_options,
+                offset,
+                len,
+                output_schema,
+            },
+        }
+    }
+}
+
+impl ComputeNode for TopKNode {
+    fn name(&self) -> &str {
+        "top-k"
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 1 && send.len() == 1);
+
+        // State transitions.
+        match &mut self.state {
+            // If the output doesn't want any more data, transition to being done.
+            _ if send[0] == PortState::Done => {
+                self.state = TopKState::Done;
+            },
+            // Input is done, transition to being a source.
+            TopKState::Sink(_) if matches!(recv[0], PortState::Done) => {
+                let TopKState::Sink(locals) = core::mem::replace(&mut self.state, TopKState::Done)
+                else {
+                    unreachable!()
+                };
+                let df = self.params.finalize(locals)?;
+                let source = InMemorySourceNode::new(Arc::new(df), MorselSeq::default());
+                self.state = TopKState::Source(source);
+            },
+            // Defer to source node implementation.
+            TopKState::Source(src) => {
+                src.update_state(&mut [], send, state)?;
+                if send[0] == PortState::Done {
+                    self.state = TopKState::Done;
+                }
+            },
+            // Nothing to change.
+            TopKState::Done | TopKState::Sink(_) => {},
+        }
+
+        // Communicate our state.
+        match &self.state {
+            TopKState::Sink { .. } => {
+                send[0] = PortState::Blocked;
+                recv[0] = PortState::Ready;
+            },
+            TopKState::Source(..) => {
+                recv[0] = PortState::Done;
+                send[0] = PortState::Ready;
+            },
+            TopKState::Done => {
+                recv[0] = PortState::Done;
+                send[0] = PortState::Done;
+            },
+        }
+        Ok(())
+    }
+
+    fn memory_usage(&self) -> Option<usize> {
+        match &self.state {
+            TopKState::Sink(locals) => Some(locals.iter().map(|l| l.estimated_size()).sum()),
+            TopKState::Source(source) => source.memory_usage(),
+            TopKState::Done => Some(0),
+        }
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(send_ports.len() == 1 && recv_ports.len() == 1);
+        match &mut self.state {
+            TopKState::Sink(locals) => {
+                assert!(send_ports[0].is_none());
+                let receivers = recv_ports[0].take().unwrap().parallel();
+                let params = &self.params;
+                for (local, recv) in locals.iter_mut().zip(receivers) {
+                    join_handles.push(
+                        scope.spawn_task(TaskPriority::High, params.sink(recv, local, state)),
+                    );
+                }
+            },
+            TopKState::Source(source) => {
+                assert!(recv_ports[0].is_none());
+                source.spawn(scope, &mut [], send_ports, state, join_handles);
+            },
+            TopKState::Done => unreachable!(),
+        }
+    }
+}
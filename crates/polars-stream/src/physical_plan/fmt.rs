@@ -32,10 +32,13 @@ impl NodeStyle {
             | K::InMemorySink { .. }
             | K::Sort { .. }
             | K::GroupBy { .. }
+            | K::DistinctLast { .. }
             | K::EquiJoin { .. }
             | K::SemiAntiJoin { .. }
             | K::InMemoryJoin { .. }
             | K::Multiplexer { .. } => Self::MemoryIntensive,
+            #[cfg(feature = "iejoin")]
+            K::RangeJoin { .. } => Self::MemoryIntensive,
             #[cfg(feature = "merge_sorted")]
             K::MergeSorted { .. } => Self::MemoryIntensive,
             _ => Self::Generic,
@@ -150,6 +153,7 @@ fn visualize_plan_rec(
     node_key: PhysNodeKey,
     phys_sm: &SlotMap<PhysNodeKey, PhysNode>,
     expr_arena: &Arena<AExpr>,
+    annotations: &SecondaryMap<PhysNodeKey, String>,
     visited: &mut SecondaryMap<PhysNodeKey, ()>,
     out: &mut Vec<String>,
 ) {
@@ -161,7 +165,7 @@ fn visualize_plan_rec(
     let kind = &phys_sm[node_key].kind;
 
     use std::slice::from_ref;
-    let (label, inputs) = match kind {
+    let (mut label, inputs) = match kind {
         PhysNodeKind::InMemorySource { df } => (
             format!(
                 "in-memory-source\\ncols: {}",
@@ -173,7 +177,7 @@ fn visualize_plan_rec(
         PhysNodeKind::PythonScan { .. } => ("python-scan".to_string(), &[][..]),
         PhysNodeKind::SinkMultiple { sinks } => {
             for sink in sinks {
-                visualize_plan_rec(*sink, phys_sm, expr_arena, visited, out);
+                visualize_plan_rec(*sink, phys_sm, expr_arena, annotations, visited, out);
             }
             return;
         },
@@ -256,6 +260,8 @@ fn visualize_plan_rec(
             FileType::Csv(_) => ("csv-sink".to_string(), from_ref(input)),
             #[cfg(feature = "json")]
             FileType::Json(_) => ("ndjson-sink".to_string(), from_ref(input)),
+            #[cfg(feature = "avro")]
+            FileType::Avro(_) => ("avro-sink".to_string(), from_ref(input)),
             #[allow(unreachable_patterns)]
             _ => todo!(),
         },
@@ -280,6 +286,8 @@ fn visualize_plan_rec(
                 FileType::Csv(_) => (format!("{variant}[csv]"), from_ref(input)),
                 #[cfg(feature = "json")]
                 FileType::Json(_) => (format!("{variant}[ndjson]"), from_ref(input)),
+                #[cfg(feature = "avro")]
+                FileType::Avro(_) => (format!("{variant}[avro]"), from_ref(input)),
                 #[allow(unreachable_patterns)]
                 _ => todo!(),
             }
@@ -300,6 +308,22 @@ fn visualize_plan_rec(
             (label, from_ref(input))
         },
         PhysNodeKind::Map { input, map: _ } => ("map".to_string(), from_ref(input)),
+        PhysNodeKind::SortedPartitionMap {
+            input,
+            keys,
+            map: _,
+            format_str,
+        } => {
+            let mut label = String::new();
+            write!(label, "sorted-partition-map\\nby: {}", keys.join(", ")).unwrap();
+            if let Some(format_str) = format_str {
+                label.push_str("\\n");
+
+                let mut f = EscapeLabel(&mut label);
+                write!(f, "{format_str}").unwrap();
+            }
+            (label, from_ref(input))
+        },
         PhysNodeKind::Sort {
             input,
             by_column,
@@ -312,6 +336,19 @@ fn visualize_plan_rec(
             ),
             from_ref(input),
         ),
+        PhysNodeKind::TopK {
+            input,
+            by_column,
+            offset,
+            len,
+            sort_options: _,
+        } => (
+            format!(
+                "top-k\\n{}\\noffset: {offset}\\nlen: {len}",
+                fmt_exprs_to_label(by_column, expr_arena, FormatExprStyle::NoAliases)
+            ),
+            from_ref(input),
+        ),
         PhysNodeKind::OrderedUnion { inputs } => ("ordered-union".to_string(), inputs.as_slice()),
         PhysNodeKind::Zip {
             inputs,
@@ -340,6 +377,7 @@ fn visualize_plan_rec(
             missing_columns_policy: _,
             extra_columns_policy: _,
             file_schema: _,
+            runtime_filters,
         } => {
             let mut out = format!("multi-scan[{}]", file_reader_builder.reader_name());
             let mut f = EscapeLabel(&mut out);
@@ -393,6 +431,11 @@ fn visualize_plan_rec(
                 }
             }
 
+            for filter in runtime_filters {
+                let keys = filter.key_schema().iter_names().collect::<Vec<_>>();
+                write!(f, "\nruntime join filter: {keys:?}").unwrap();
+            }
+
             (out, &[][..])
         },
         PhysNodeKind::GroupBy { input, key, aggs } => (
@@ -403,6 +446,64 @@ fn visualize_plan_rec(
             ),
             from_ref(input),
         ),
+        #[cfg(feature = "dynamic_group_by")]
+        PhysNodeKind::DynamicGroupBy {
+            input,
+            key,
+            aggs,
+            options,
+        } => {
+            let mut out = "group-by-dynamic".to_string();
+            let mut f = EscapeLabel(&mut out);
+            write!(
+                f,
+                "\nindex: {}\nevery: {}\nperiod: {}\noffset: {}\nclosed: {:?}",
+                options.index_column,
+                options.every,
+                options.period,
+                options.offset,
+                options.closed_window
+            )
+            .unwrap();
+            write!(
+                out,
+                "\\nkey:\\n{}\\naggs:\\n{}",
+                fmt_exprs_to_label(key, expr_arena, FormatExprStyle::Select),
+                fmt_exprs_to_label(aggs, expr_arena, FormatExprStyle::Select)
+            )
+            .unwrap();
+            (out, from_ref(input))
+        },
+        #[cfg(feature = "dynamic_group_by")]
+        PhysNodeKind::RollingGroupBy {
+            input,
+            key,
+            aggs,
+            options,
+        } => {
+            let mut out = "rolling-group-by".to_string();
+            let mut f = EscapeLabel(&mut out);
+            write!(
+                f,
+                "\nindex: {}\nperiod: {}\noffset: {}\nclosed: {:?}",
+                options.index_column, options.period, options.offset, options.closed_window
+            )
+            .unwrap();
+            write!(
+                out,
+                "\\nkey:\\n{}\\naggs:\\n{}",
+                fmt_exprs_to_label(key, expr_arena, FormatExprStyle::Select),
+                fmt_exprs_to_label(aggs, expr_arena, FormatExprStyle::Select)
+            )
+            .unwrap();
+            (out, from_ref(input))
+        },
+        PhysNodeKind::DistinctLast { input, keys } => {
+            let mut out = "distinct".to_string();
+            let mut f = EscapeLabel(&mut out);
+            write!(f, "\nkeys: {}\nkeep: last", keys.join(", ")).unwrap();
+            (out, from_ref(input))
+        },
         PhysNodeKind::InMemoryJoin {
             input_left,
             input_right,
@@ -417,6 +518,7 @@ fn visualize_plan_rec(
             left_on,
             right_on,
             args,
+            ..
         }
         | PhysNodeKind::SemiAntiJoin {
             input_left,
@@ -475,6 +577,84 @@ fn visualize_plan_rec(
             input_right,
             args: _,
         } => ("cross-join".to_string(), &[*input_left, *input_right][..]),
+        PhysNodeKind::MergeJoin {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            args,
+        } => {
+            let mut out = "merge-join".to_string();
+            let mut f = EscapeLabel(&mut out);
+
+            write!(
+                f,
+                "\nleft_on: {left_on}\nright_on: {right_on}\nhow: {:?}",
+                args.how
+            )
+            .unwrap();
+
+            (out, &[*input_left, *input_right][..])
+        },
+        #[cfg(feature = "asof_join")]
+        PhysNodeKind::AsOfJoin {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            args,
+        } => {
+            let mut out = "asof-join".to_string();
+            let mut f = EscapeLabel(&mut out);
+
+            write!(f, "\nleft_on: {left_on}\nright_on: {right_on}").unwrap();
+            if let polars_ops::frame::JoinType::AsOf(options) = &args.how {
+                if let (Some(left_by), Some(right_by)) = (&options.left_by, &options.right_by) {
+                    write!(
+                        f,
+                        "\nleft_by: {}\nright_by: {}",
+                        left_by.join(", "),
+                        right_by.join(", ")
+                    )
+                    .unwrap();
+                }
+                write!(f, "\nstrategy: {:?}", options.strategy).unwrap();
+                if let Some(tolerance) = &options.tolerance {
+                    write!(f, "\ntolerance: {tolerance}").unwrap();
+                }
+            }
+
+            (out, &[*input_left, *input_right][..])
+        },
+        #[cfg(feature = "iejoin")]
+        PhysNodeKind::RangeJoin {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            options,
+            args: _,
+        } => {
+            let mut label = "range-join".to_string();
+            write!(
+                label,
+                r"\nleft_on:\n{}",
+                fmt_exprs_to_label(left_on, expr_arena, FormatExprStyle::NoAliases)
+            )
+            .unwrap();
+            write!(
+                label,
+                r"\nright_on:\n{}",
+                fmt_exprs_to_label(right_on, expr_arena, FormatExprStyle::NoAliases)
+            )
+            .unwrap();
+            let mut f = EscapeLabel(&mut label);
+            write!(f, "\noperator1: {:?}", options.operator1).unwrap();
+            if let Some(operator2) = options.operator2 {
+                write!(f, "\noperator2: {operator2:?}").unwrap();
+            }
+            (label, &[*input_left, *input_right][..])
+        },
         #[cfg(feature = "merge_sorted")]
         PhysNodeKind::MergeSorted {
             input_left,
@@ -490,6 +670,10 @@ fn visualize_plan_rec(
         },
     };
 
+    if let Some(annotation) = annotations.get(node_key) {
+        label.push_str(&escape_graphviz(annotation));
+    }
+
     let node_id = node_key.data().as_ffi();
     let style = NodeStyle::for_node_kind(kind);
 
@@ -499,7 +683,7 @@ fn visualize_plan_rec(
         out.push(format!("{node_id} [label=\"{label}\"];"));
     }
     for input in inputs {
-        visualize_plan_rec(input.node, phys_sm, expr_arena, visited, out);
+        visualize_plan_rec(input.node, phys_sm, expr_arena, annotations, visited, out);
         out.push(format!(
             "{} -> {};",
             input.node.data().as_ffi(),
@@ -512,12 +696,30 @@ pub fn visualize_plan(
     root: PhysNodeKey,
     phys_sm: &SlotMap<PhysNodeKey, PhysNode>,
     expr_arena: &Arena<AExpr>,
+) -> String {
+    visualize_annotated_plan(root, phys_sm, expr_arena, &SecondaryMap::new())
+}
+
+/// Visualizes the plan, appending the given annotation to the label of each
+/// node that has one.
+pub fn visualize_annotated_plan(
+    root: PhysNodeKey,
+    phys_sm: &SlotMap<PhysNodeKey, PhysNode>,
+    expr_arena: &Arena<AExpr>,
+    annotations: &SecondaryMap<PhysNodeKey, String>,
 ) -> String {
     let mut visited: SecondaryMap<PhysNodeKey, ()> = SecondaryMap::new();
     let mut out = Vec::with_capacity(phys_sm.len() + 3);
     out.push("digraph polars {\nrankdir=\"BT\"\nnode [fontname=\"Monospace\"]".to_string());
     out.push(NodeStyle::legend());
-    visualize_plan_rec(root, phys_sm, expr_arena, &mut visited, &mut out);
+    visualize_plan_rec(
+        root,
+        phys_sm,
+        expr_arena,
+        annotations,
+        &mut visited,
+        &mut out,
+    );
     out.push("}".to_string());
     out.join("\n")
 }
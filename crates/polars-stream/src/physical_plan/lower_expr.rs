@@ -1,12 +1,15 @@
 use std::sync::Arc;
 
 use polars_core::frame::DataFrame;
-use polars_core::prelude::{DataType, Field, InitHashMaps, PlHashMap, PlHashSet};
+use polars_core::prelude::{
+    DataType, Field, IDX_DTYPE, InitHashMaps, PlHashMap, PlHashSet, SortMultipleOptions,
+};
 use polars_core::schema::{Schema, SchemaExt};
+use polars_core::series::IsSorted;
 use polars_error::PolarsResult;
 use polars_expr::state::ExecutionState;
 use polars_expr::{ExpressionConversionState, create_physical_expr};
-use polars_ops::frame::{JoinArgs, JoinType};
+use polars_ops::frame::{JoinArgs, JoinType, MaintainOrderJoin};
 use polars_plan::plans::AExpr;
 use polars_plan::plans::expr_ir::{ExprIR, OutputName};
 use polars_plan::prelude::*;
@@ -252,6 +255,11 @@ pub fn is_input_independent(
     is_input_independent_rec(expr_key, expr_arena, &mut cache.is_input_independent)
 }
 
+/// Whether the expression is a literal with a single value.
+pub fn is_scalar_literal(expr_key: ExprNodeKey, expr_arena: &Arena<AExpr>) -> bool {
+    matches!(expr_arena.get(expr_key), AExpr::Literal(lv) if lv.is_scalar())
+}
+
 fn is_input_independent_ctx(expr_key: ExprNodeKey, ctx: &mut LowerExprContext) -> bool {
     is_input_independent_rec(
         expr_key,
@@ -372,11 +380,22 @@ fn is_length_preserving_ctx(expr_key: ExprNodeKey, ctx: &mut LowerExprContext) -
     )
 }
 
-fn build_fallback_node_with_ctx(
+/// A map evaluating expressions with the in-memory engine.
+struct InMemoryExprMap {
+    /// The stream the map should be applied to.
+    input: PhysStream,
+    output_schema: Arc<Schema>,
+    map: Arc<dyn DataFrameUdf>,
+    format_str: Option<String>,
+}
+
+/// Builds a map evaluating the given expressions on (parts of) the input with
+/// the in-memory engine.
+fn build_in_memory_map_with_ctx(
     input: PhysStream,
     exprs: &[ExprIR],
     ctx: &mut LowerExprContext,
-) -> PolarsResult<PhysNodeKey> {
+) -> PolarsResult<InMemoryExprMap> {
     // Pre-select only the columns that are needed for this fallback expression.
     let input_schema = &ctx.phys_sm[input.node].output_schema;
     let mut select_names: PlHashSet<_> = exprs
@@ -443,12 +462,377 @@ fn build_fallback_node_with_ctx(
         buffer.push(']');
         buffer
     });
-    let kind = PhysNodeKind::InMemoryMap {
+    Ok(InMemoryExprMap {
         input: input_stream,
+        output_schema,
         map: Arc::new(map),
         format_str,
+    })
+}
+
+fn build_fallback_node_with_ctx(
+    input: PhysStream,
+    exprs: &[ExprIR],
+    ctx: &mut LowerExprContext,
+) -> PolarsResult<PhysNodeKey> {
+    let map = build_in_memory_map_with_ctx(input, exprs, ctx)?;
+    let kind = PhysNodeKind::InMemoryMap {
+        input: map.input,
+        map: map.map,
+        format_str: map.format_str,
     };
-    Ok(ctx.phys_sm.insert(PhysNode::new(output_schema, kind)))
+    Ok(ctx.phys_sm.insert(PhysNode::new(map.output_schema, kind)))
+}
+
+/// Whether all rows with equal values in the given key columns are known to be
+/// consecutive in the given stream.
+fn is_grouped_by_keys(stream: PhysStream, keys: &[PlSmallStr], ctx: &LowerExprContext) -> bool {
+    let mut keys = keys.to_vec();
+    let mut node = stream.node;
+    loop {
+        match &ctx.phys_sm[node].kind {
+            PhysNodeKind::InMemorySource { df } => {
+                // If each key column is sorted the rows are sorted lexicographically by the keys.
+                return keys.iter().all(|key| {
+                    df.column(key)
+                        .is_ok_and(|c| c.is_sorted_flag() != IsSorted::Not)
+                });
+            },
+            PhysNodeKind::Sort { by_column, .. } | PhysNodeKind::TopK { by_column, .. } => {
+                // Equal keys are consecutive if the keys make up a prefix of
+                // the sort columns.
+                let Some(prefix) = by_column.get(..keys.len()) else {
+                    return false;
+                };
+                let prefix_names: Option<Vec<&PlSmallStr>> = prefix
+                    .iter()
+                    .map(|e| match ctx.expr_arena.get(e.node()) {
+                        AExpr::Column(name) => Some(name),
+                        _ => None,
+                    })
+                    .collect();
+                return prefix_names.is_some_and(|names| {
+                    names.iter().all(|name| keys.contains(name))
+                        && keys.iter().all(|key| names.contains(&key))
+                });
+            },
+            PhysNodeKind::Select {
+                input,
+                selectors,
+                extend_original,
+            } => {
+                for key in keys.iter_mut() {
+                    match selectors.iter().find(|e| e.output_name() == key) {
+                        Some(e) => match ctx.expr_arena.get(e.node()) {
+                            AExpr::Column(name) => *key = name.clone(),
+                            _ => return false,
+                        },
+                        None if *extend_original => {},
+                        None => return false,
+                    }
+                }
+                node = input.node;
+            },
+            PhysNodeKind::WithRowIndex { input, name, .. } => {
+                if keys.contains(name) {
+                    return false;
+                }
+                node = input.node;
+            },
+            PhysNodeKind::SimpleProjection { input, .. }
+            | PhysNodeKind::Filter { input, .. }
+            | PhysNodeKind::StreamingSlice { input, .. } => node = input.node,
+            _ => return false,
+        }
+    }
+}
+
+/// Replaces the scalar subexpressions of a window function that depend on the
+/// input (i.e. the aggregations) by columns, adding their definitions to aggs.
+#[recursive::recursive]
+fn extract_window_aggs(expr: Node, aggs: &mut Vec<ExprIR>, ctx: &mut LowerExprContext) -> Node {
+    if is_input_independent_ctx(expr, ctx) {
+        return expr;
+    }
+
+    if is_scalar_ae(expr, ctx.expr_arena) {
+        let name = unique_column_name();
+        aggs.push(ExprIR::new(expr, OutputName::Alias(name.clone())));
+        return ctx.expr_arena.add(AExpr::Column(name));
+    }
+
+    let ae = ctx.expr_arena.get(expr).clone();
+    let mut inputs = Vec::new();
+    ae.inputs_rev(&mut inputs);
+    inputs.reverse();
+    let new_inputs = inputs
+        .iter()
+        .map(|input| extract_window_aggs(*input, aggs, ctx))
+        .collect_vec();
+    if new_inputs == inputs {
+        expr
+    } else {
+        ctx.expr_arena.add(ae.replace_inputs(&new_inputs))
+    }
+}
+
+/// Computes the aggregations per group of keys.
+fn build_window_group_by_stream(
+    input: PhysStream,
+    keys: &[ExprIR],
+    aggs: &[ExprIR],
+    ctx: &mut LowerExprContext,
+) -> PolarsResult<PhysStream> {
+    let group_by_exprs = keys.iter().chain(aggs).cloned().collect_vec();
+    let group_by_output_schema = schema_for_select(input, &group_by_exprs, ctx)?;
+    build_group_by_stream(
+        input,
+        keys,
+        aggs,
+        group_by_output_schema,
+        false,
+        Arc::new(GroupbyOptions::default()),
+        None,
+        ctx.expr_arena,
+        ctx.phys_sm,
+        ctx.cache,
+        StreamingLowerIRContext::from(&*ctx),
+    )
+}
+
+/// Computes the aggregations per group of keys, and joins them back onto the
+/// given columns of the input, keeping the order of the input.
+fn build_window_join_back_stream(
+    input: PhysStream,
+    keys: &[ExprIR],
+    aggs: &[ExprIR],
+    columns: impl IntoIterator<Item = PlSmallStr>,
+    ctx: &mut LowerExprContext,
+) -> PolarsResult<PhysStream> {
+    let group_by_stream = build_window_group_by_stream(input, keys, aggs, ctx)?;
+    let group_by_output_schema = ctx.phys_sm[group_by_stream.node].output_schema.clone();
+
+    let mut left_exprs = keys.to_vec();
+    left_exprs.extend(columns.into_iter().map(|name| {
+        ExprIR::new(
+            ctx.expr_arena.add(AExpr::Column(name.clone())),
+            OutputName::ColumnLhs(name),
+        )
+    }));
+    let left_stream = build_select_stream_with_ctx(input, &left_exprs, ctx)?;
+
+    let key_exprs = keys
+        .iter()
+        .map(|key| {
+            let name = key.output_name().clone();
+            ExprIR::new(
+                ctx.expr_arena.add(AExpr::Column(name.clone())),
+                OutputName::Alias(name),
+            )
+        })
+        .collect_vec();
+    let mut output_schema = ctx.phys_sm[left_stream.node].output_schema.as_ref().clone();
+    output_schema.extend(group_by_output_schema.iter_fields().skip(keys.len()));
+    let kind = PhysNodeKind::EquiJoin {
+        input_left: left_stream,
+        input_right: group_by_stream,
+        left_on: key_exprs.clone(),
+        right_on: key_exprs,
+        args: JoinArgs {
+            how: JoinType::Left,
+            validation: Default::default(),
+            suffix: None,
+            slice: None,
+            nulls_equal: true,
+            coalesce: Default::default(),
+            maintain_order: MaintainOrderJoin::Left,
+        },
+        left_runtime_filter: None,
+        right_runtime_filter: None,
+    };
+    let join_node_key = ctx
+        .phys_sm
+        .insert(PhysNode::new(Arc::new(output_schema), kind));
+    Ok(PhysStream::first(join_node_key))
+}
+
+/// Tries to lower a window expression without falling back to the in-memory
+/// engine. On success returns a stream with the result as its only column.
+fn try_build_window_stream(
+    input: PhysStream,
+    expr: Node,
+    out_name: &PlSmallStr,
+    ctx: &mut LowerExprContext,
+) -> PolarsResult<Option<PhysStream>> {
+    let AExpr::Window {
+        function,
+        partition_by,
+        order_by,
+        options: WindowType::Over(mapping),
+    } = ctx.expr_arena.get(expr).clone()
+    else {
+        return Ok(None);
+    };
+    if partition_by.is_empty() {
+        return Ok(None);
+    }
+
+    // If all rows of a partition are consecutive we can simply evaluate the
+    // window on a batch of whole partitions at a time.
+    let key_names: Option<Vec<PlSmallStr>> = partition_by
+        .iter()
+        .map(|key| match ctx.expr_arena.get(*key) {
+            AExpr::Column(name) => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+    if let Some(key_names) = key_names {
+        if is_grouped_by_keys(input, &key_names, ctx) {
+            let expr_ir = ExprIR::new(expr, OutputName::Alias(out_name.clone()));
+            let map = build_in_memory_map_with_ctx(input, std::slice::from_ref(&expr_ir), ctx)?;
+            let kind = PhysNodeKind::SortedPartitionMap {
+                input: map.input,
+                keys: key_names,
+                map: map.map,
+                format_str: map.format_str,
+            };
+            let node_key = ctx.phys_sm.insert(PhysNode::new(map.output_schema, kind));
+            return Ok(Some(PhysStream::first(node_key)));
+        }
+    }
+
+    // Otherwise we compute the aggregations with a group-by and join them back
+    // onto the rows.
+    if order_by.is_some()
+        || is_input_independent_ctx(function, ctx)
+        || !partition_by.iter().all(|key| {
+            is_elementwise_rec_cached(*key, ctx.expr_arena, ctx.cache)
+                && !is_input_independent_ctx(*key, ctx)
+        })
+    {
+        return Ok(None);
+    }
+
+    let keys = partition_by
+        .iter()
+        .map(|key| ExprIR::new(*key, OutputName::Alias(unique_column_name())))
+        .collect_vec();
+    let mut aggs = Vec::new();
+    let mut row_expr = extract_window_aggs(function, &mut aggs, ctx);
+    if !is_elementwise_rec_cached(row_expr, ctx.expr_arena, ctx.cache) {
+        return Ok(None);
+    }
+
+    // Aggregations resulting in lists are flattened with the explode mapping.
+    let input_schema = ctx.phys_sm[input.node].output_schema.clone();
+    for agg in &aggs {
+        let dtype = agg.dtype(&input_schema, Context::Default, ctx.expr_arena)?;
+        if dtype.is_list() && mapping == WindowMapping::Explode {
+            return Ok(None);
+        }
+    }
+
+    // With the join mapping the values of each group are collected into a list.
+    let is_scalar = is_scalar_ae(function, ctx.expr_arena);
+    if mapping == WindowMapping::Join && !is_scalar {
+        if !aggs.is_empty() {
+            return Ok(None);
+        }
+        let implode_name = unique_column_name();
+        let implode = ctx.expr_arena.add(AExpr::Agg(IRAggExpr::Implode(function)));
+        aggs.push(ExprIR::new(
+            implode,
+            OutputName::Alias(implode_name.clone()),
+        ));
+        row_expr = ctx.expr_arena.add(AExpr::Column(implode_name));
+    }
+
+    let out_expr = ExprIR::new(row_expr, OutputName::Alias(out_name.clone()));
+    let row_columns = polars_plan::utils::aexpr_to_leaf_names_iter(row_expr, ctx.expr_arena)
+        .filter(|name| input_schema.contains(name))
+        .collect::<PlHashSet<_>>();
+
+    if mapping != WindowMapping::Explode {
+        if aggs.is_empty() {
+            // Without aggregations the window is just an elementwise expression.
+            return Ok(Some(build_select_stream_with_ctx(input, &[out_expr], ctx)?));
+        }
+
+        let join_stream = build_window_join_back_stream(input, &keys, &aggs, row_columns, ctx)?;
+        return Ok(Some(build_select_stream_with_ctx(
+            join_stream,
+            &[out_expr],
+            ctx,
+        )?));
+    }
+
+    // The explode mapping orders the rows by group, with the groups in order of
+    // their first occurrence, so we sort on the first row index of each group.
+    let row_index_name = unique_column_name();
+    let mut row_index_schema = input_schema.as_ref().clone();
+    row_index_schema.insert_at_index(0, row_index_name.clone(), IDX_DTYPE)?;
+    let row_index_kind = PhysNodeKind::WithRowIndex {
+        input,
+        name: row_index_name.clone(),
+        offset: None,
+    };
+    let row_index_stream = PhysStream::first(
+        ctx.phys_sm
+            .insert(PhysNode::new(Arc::new(row_index_schema), row_index_kind)),
+    );
+
+    let first_row_name = unique_column_name();
+    let row_index_col = ctx.expr_arena.add(AExpr::Column(row_index_name.clone()));
+    let first_row = ctx.expr_arena.add(AExpr::Agg(IRAggExpr::Min {
+        input: row_index_col,
+        propagate_nans: false,
+    }));
+    aggs.push(ExprIR::new(
+        first_row,
+        OutputName::Alias(first_row_name.clone()),
+    ));
+    let (stream, sort_by) = if is_scalar {
+        // Every group results in a single row.
+        let group_by_stream = build_window_group_by_stream(row_index_stream, &keys, &aggs, ctx)?;
+        (group_by_stream, vec![first_row_name])
+    } else {
+        let join_stream = build_window_join_back_stream(
+            row_index_stream,
+            &keys,
+            &aggs,
+            row_columns.into_iter().chain([row_index_name.clone()]),
+            ctx,
+        )?;
+        (join_stream, vec![first_row_name, row_index_name])
+    };
+
+    let sort_by = sort_by
+        .into_iter()
+        .map(|name| {
+            ExprIR::new(
+                ctx.expr_arena.add(AExpr::Column(name.clone())),
+                OutputName::Alias(name),
+            )
+        })
+        .collect_vec();
+    let mut select_exprs = vec![out_expr];
+    select_exprs.extend(sort_by.iter().cloned());
+    let select_stream = build_select_stream_with_ctx(stream, &select_exprs, ctx)?;
+    let kind = PhysNodeKind::Sort {
+        input: select_stream,
+        by_column: sort_by,
+        slice: None,
+        sort_options: SortMultipleOptions::default(),
+    };
+    let output_schema = ctx.phys_sm[select_stream.node].output_schema.clone();
+    let sort_stream = PhysStream::first(ctx.phys_sm.insert(PhysNode::new(output_schema, kind)));
+    let out_col = ctx.expr_arena.add(AExpr::Column(out_name.clone()));
+    let out_expr = ExprIR::new(out_col, OutputName::Alias(out_name.clone()));
+    Ok(Some(build_select_stream_with_ctx(
+        sort_stream,
+        &[out_expr],
+        ctx,
+    )?))
 }
 
 fn simplify_input_streams(
@@ -879,6 +1263,15 @@ fn lower_exprs_with_ctx(
                 transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
             },
             AExpr::Agg(mut agg) => match agg {
+                // We can only reduce quantiles in a streaming fashion if the
+                // quantile is known up front.
+                IRAggExpr::Quantile { quantile, .. }
+                    if !is_scalar_literal(quantile, ctx.expr_arena) =>
+                {
+                    let out_name = unique_column_name();
+                    fallback_subset.push(ExprIR::new(expr, OutputName::Alias(out_name.clone())));
+                    transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
+                },
                 // Change agg mutably so we can share the codepath for all of these.
                 IRAggExpr::Min {
                     input: ref mut inner,
@@ -894,7 +1287,13 @@ fn lower_exprs_with_ctx(
                 | IRAggExpr::Mean(ref mut inner)
                 | IRAggExpr::Var(ref mut inner, _ /* ddof */)
                 | IRAggExpr::Std(ref mut inner, _ /* ddof */)
-                | IRAggExpr::Count(ref mut inner, _ /* count_nulls */) => {
+                | IRAggExpr::Count(ref mut inner, _ /* count_nulls */)
+                | IRAggExpr::Median(ref mut inner)
+                | IRAggExpr::Quantile {
+                    expr: ref mut inner,
+                    ..
+                }
+                | IRAggExpr::Implode(ref mut inner) => {
                     let (trans_input, trans_exprs) = lower_exprs_with_ctx(input, &[*inner], ctx)?;
                     *inner = trans_exprs[0];
 
@@ -953,10 +1352,7 @@ fn lower_exprs_with_ctx(
                     input_streams.insert(PhysStream::first(reduce_node_key));
                     transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(tmp_name)));
                 },
-                IRAggExpr::Median(_)
-                | IRAggExpr::Implode(_)
-                | IRAggExpr::Quantile { .. }
-                | IRAggExpr::AggGroups(_) => {
+                IRAggExpr::AggGroups(_) => {
                     let out_name = unique_column_name();
                     fallback_subset.push(ExprIR::new(expr, OutputName::Alias(out_name.clone())));
                     transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
@@ -974,10 +1370,18 @@ fn lower_exprs_with_ctx(
                 input_streams.insert(PhysStream::first(reduce_node_key));
                 transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
             },
+            AExpr::Window { .. } => {
+                let out_name = unique_column_name();
+                if let Some(window_stream) = try_build_window_stream(input, expr, &out_name, ctx)? {
+                    input_streams.insert(window_stream);
+                } else {
+                    fallback_subset.push(ExprIR::new(expr, OutputName::Alias(out_name.clone())));
+                }
+                transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
+            },
             AExpr::AnonymousFunction { .. }
             | AExpr::Function { .. }
             | AExpr::Slice { .. }
-            | AExpr::Window { .. }
             | AExpr::Gather { .. } => {
                 let out_name = unique_column_name();
                 fallback_subset.push(ExprIR::new(expr, OutputName::Alias(out_name.clone())));
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use parking_lot::Mutex;
-use polars_core::prelude::{InitHashMaps, PlIndexMap};
+use polars_core::prelude::{IDX_DTYPE, InitHashMaps, PlIndexMap};
 use polars_core::schema::Schema;
 use polars_error::{PolarsResult, polars_err};
 use polars_expr::state::ExecutionState;
@@ -18,7 +18,7 @@ use slotmap::SlotMap;
 use super::{ExprCache, PhysNode, PhysNodeKey, PhysNodeKind, PhysStream, StreamingLowerIRContext};
 use crate::physical_plan::lower_expr::{
     build_select_stream, compute_output_schema, is_elementwise_rec_cached,
-    is_fake_elementwise_function, is_input_independent,
+    is_fake_elementwise_function, is_input_independent, is_scalar_literal,
 };
 use crate::physical_plan::lower_ir::build_slice_stream;
 use crate::utils::late_materialized_df::LateMaterializedDataFrame;
@@ -77,6 +77,7 @@ fn build_group_by_fallback(
 /// Such an expression is defined as the elementwise combination of scalar
 /// aggregations of elementwise combinations of the input columns / scalar literals.
 #[recursive]
+#[allow(clippy::too_many_arguments)]
 fn try_lower_elementwise_scalar_agg_expr(
     expr: Node,
     outer_name: Option<PlSmallStr>,
@@ -85,6 +86,7 @@ fn try_lower_elementwise_scalar_agg_expr(
     expr_arena: &mut Arena<AExpr>,
     agg_exprs: &mut Vec<ExprIR>,
     uniq_input_exprs: &mut PlIndexMap<u32, PlSmallStr>,
+    row_index_name: &mut Option<PlSmallStr>,
 ) -> Option<Node> {
     // Helper macro to simplify recursive calls.
     macro_rules! lower_rec {
@@ -97,6 +99,7 @@ fn try_lower_elementwise_scalar_agg_expr(
                 expr_arena,
                 agg_exprs,
                 uniq_input_exprs,
+                row_index_name,
             )
         };
     }
@@ -105,7 +108,8 @@ fn try_lower_elementwise_scalar_agg_expr(
         AExpr::Alias(..) => unreachable!("alias found in physical plan"),
 
         AExpr::Column(_) => {
-            // Implicit implode not yet supported.
+            // Implicit implode is only supported at the top-level of an
+            // aggregation, see try_build_streaming_group_by.
             None
         },
 
@@ -212,7 +216,17 @@ fn try_lower_elementwise_scalar_agg_expr(
                 | IRAggExpr::Sum(input)
                 | IRAggExpr::Var(input, ..)
                 | IRAggExpr::Std(input, ..)
-                | IRAggExpr::Count(input, ..) => {
+                | IRAggExpr::Count(input, ..)
+                | IRAggExpr::Median(input)
+                | IRAggExpr::Quantile { expr: input, .. }
+                | IRAggExpr::Implode(input)
+                | IRAggExpr::AggGroups(input) => {
+                    if let IRAggExpr::Quantile { quantile, .. } = agg {
+                        if !is_scalar_literal(*quantile, expr_arena) {
+                            return None;
+                        }
+                    }
+
                     if is_input_independent(*input, expr_arena, expr_cache) {
                         // TODO: we could simply return expr here, but we first need an is_scalar function, because if
                         // it is not a scalar we need to return expr.implode().
@@ -224,11 +238,18 @@ fn try_lower_elementwise_scalar_agg_expr(
                     }
 
                     let mut trans_agg = agg.clone();
-                    let input_id = expr_merger.get_uniq_id(*input).unwrap();
-                    let input_col = uniq_input_exprs
-                        .entry(input_id)
-                        .or_insert_with(unique_column_name)
-                        .clone();
+                    let input_col = if let IRAggExpr::AggGroups(_) = agg {
+                        // The group indices are the imploded row index.
+                        row_index_name
+                            .get_or_insert_with(unique_column_name)
+                            .clone()
+                    } else {
+                        let input_id = expr_merger.get_uniq_id(*input).unwrap();
+                        uniq_input_exprs
+                            .entry(input_id)
+                            .or_insert_with(unique_column_name)
+                            .clone()
+                    };
                     let input_col_node = expr_arena.add(AExpr::Column(input_col.clone()));
                     trans_agg.set_input(input_col_node);
                     let trans_agg_node = expr_arena.add(AExpr::Agg(trans_agg));
@@ -243,11 +264,7 @@ fn try_lower_elementwise_scalar_agg_expr(
                     agg_exprs.push(agg_expr);
                     Some(result_node)
                 },
-                IRAggExpr::Median(..)
-                | IRAggExpr::NUnique(..)
-                | IRAggExpr::Implode(..)
-                | IRAggExpr::Quantile { .. }
-                | IRAggExpr::AggGroups(..) => None, // TODO: allow all aggregates,
+                IRAggExpr::NUnique(..) => None, // TODO: allow all aggregates,
             }
         },
         AExpr::Len => {
@@ -276,16 +293,22 @@ fn try_build_streaming_group_by(
     expr_cache: &mut ExprCache,
     ctx: StreamingLowerIRContext,
 ) -> Option<PolarsResult<PhysStream>> {
-    if apply.is_some() || maintain_order {
-        return None; // TODO
-    }
-
     #[cfg(feature = "dynamic_group_by")]
-    if options.dynamic.is_some() || options.rolling.is_some() {
+    let index_column = match (&options.dynamic, &options.rolling) {
+        (Some(dynamic), _) => Some(dynamic.index_column.clone()),
+        (_, Some(rolling)) => Some(rolling.index_column.clone()),
+        _ => None,
+    };
+    #[cfg(not(feature = "dynamic_group_by"))]
+    let index_column: Option<PlSmallStr> = None;
+
+    // Windows are emitted in order of the index column within each key, the
+    // keys themselves are interleaved in the order their windows complete.
+    if apply.is_some() || (maintain_order && index_column.is_none()) {
         return None; // TODO
     }
 
-    if keys.is_empty() {
+    if keys.is_empty() && index_column.is_none() {
         return Some(Err(
             polars_err!(ComputeError: "at least one key is required in a group_by operation"),
         ));
@@ -314,6 +337,7 @@ fn try_build_streaming_group_by(
     let mut trans_agg_exprs = Vec::new();
     let mut trans_keys = Vec::new();
     let mut trans_output_exprs = Vec::new();
+    let mut row_index_name = None;
     for key in keys {
         let key_id = expr_merger.get_uniq_id(key.node()).unwrap();
         let uniq_col = uniq_input_exprs
@@ -333,14 +357,23 @@ fn try_build_streaming_group_by(
         trans_output_exprs.push(ExprIR::new(trans_output_node, output_name));
     }
     for agg in aggs {
+        // Elementwise expressions are implicitly imploded.
+        let mut agg_node = agg.node();
+        if is_elementwise_rec_cached(agg_node, expr_arena, expr_cache)
+            && !is_input_independent(agg_node, expr_arena, expr_cache)
+        {
+            agg_node = expr_arena.add(AExpr::Agg(IRAggExpr::Implode(agg_node)));
+        }
+
         let trans_node = try_lower_elementwise_scalar_agg_expr(
-            agg.node(),
+            agg_node,
             Some(agg.output_name().clone()),
             &expr_merger,
             expr_cache,
             expr_arena,
             &mut trans_agg_exprs,
             &mut uniq_input_exprs,
+            &mut row_index_name,
         )?;
         let output_name = OutputName::Alias(agg.output_name().clone());
         trans_output_exprs.push(ExprIR::new(trans_node, output_name));
@@ -353,24 +386,96 @@ fn try_build_streaming_group_by(
         input_exprs.push(ExprIR::new(node, OutputName::Alias(name.clone())));
     }
 
+    let mut input = input;
+    if let Some(name) = row_index_name {
+        let mut schema = phys_sm[input.node].output_schema.as_ref().clone();
+        schema.insert_at_index(0, name.clone(), IDX_DTYPE).ok()?;
+        input = PhysStream::first(phys_sm.insert(PhysNode::new(
+            Arc::new(schema),
+            PhysNodeKind::WithRowIndex {
+                input,
+                name: name.clone(),
+                offset: None,
+            },
+        )));
+        let node = expr_arena.add(AExpr::Column(name.clone()));
+        input_exprs.push(ExprIR::new(node, OutputName::Alias(name)));
+    }
+
+    // The windows are computed from the index column, it is passed through
+    // to the output (after the keys) under a unique name.
+    #[cfg(feature = "dynamic_group_by")]
+    let trans_index_column = index_column.as_ref().map(|name| {
+        let uniq_name = unique_column_name();
+        let node = expr_arena.add(AExpr::Column(name.clone()));
+        input_exprs.push(ExprIR::new(node, OutputName::Alias(uniq_name.clone())));
+        uniq_name
+    });
+
     let pre_select =
         build_select_stream(input, &input_exprs, expr_arena, phys_sm, expr_cache, ctx).ok()?;
 
     let input_schema = &phys_sm[pre_select.node].output_schema;
-    let group_by_output_schema = compute_output_schema(
-        input_schema,
-        &[trans_keys.as_slice(), trans_agg_exprs.as_slice()].concat(),
-        expr_arena,
-    )
-    .unwrap();
-    let agg_node = phys_sm.insert(PhysNode::new(
-        group_by_output_schema,
-        PhysNodeKind::GroupBy {
-            input: pre_select,
-            key: trans_keys,
-            aggs: trans_agg_exprs,
-        },
-    ));
+    let key_schema = compute_output_schema(input_schema, &trans_keys, expr_arena).unwrap();
+    let agg_schema = compute_output_schema(input_schema, &trans_agg_exprs, expr_arena).unwrap();
+    let mut group_by_output_schema = key_schema.as_ref().clone();
+    #[allow(unused_mut)]
+    let mut kind = PhysNodeKind::GroupBy {
+        input: pre_select,
+        key: trans_keys,
+        aggs: trans_agg_exprs,
+    };
+    #[cfg(feature = "dynamic_group_by")]
+    if let Some(index_name) = &trans_index_column {
+        use crate::nodes::dynamic_group_by::WindowKind;
+
+        let PhysNodeKind::GroupBy { input, key, aggs } = kind else {
+            unreachable!()
+        };
+        let window_kind = if let Some(dynamic) = &options.dynamic {
+            let mut dynamic = dynamic.clone();
+            dynamic.index_column = index_name.clone();
+            WindowKind::Dynamic(dynamic)
+        } else {
+            let mut rolling = options.rolling.clone().unwrap();
+            rolling.index_column = index_name.clone();
+            WindowKind::Rolling(rolling)
+        };
+
+        let index_dtype = input_schema.get(index_name).unwrap();
+        let window_fields = window_kind.output_fields(index_dtype);
+        let key_len = key.len();
+        for (i, field) in window_fields.iter().enumerate() {
+            let output_name = if field.name() == index_name {
+                index_column.clone().unwrap()
+            } else {
+                field.name().clone()
+            };
+            let node = expr_arena.add(AExpr::Column(field.name().clone()));
+            trans_output_exprs.insert(
+                key_len + i,
+                ExprIR::new(node, OutputName::Alias(output_name)),
+            );
+        }
+        group_by_output_schema.extend(window_fields);
+
+        kind = match window_kind {
+            WindowKind::Dynamic(options) => PhysNodeKind::DynamicGroupBy {
+                input,
+                key,
+                aggs,
+                options,
+            },
+            WindowKind::Rolling(options) => PhysNodeKind::RollingGroupBy {
+                input,
+                key,
+                aggs,
+                options,
+            },
+        };
+    }
+    group_by_output_schema.merge(agg_schema.as_ref().clone());
+    let agg_node = phys_sm.insert(PhysNode::new(Arc::new(group_by_output_schema), kind));
 
     let post_select = build_select_stream(
         PhysStream::first(agg_node),
@@ -1,13 +1,13 @@
 use std::sync::Arc;
 
-use parking_lot::Mutex;
 use polars_core::config;
 use polars_core::frame::{DataFrame, UniqueKeepStrategy};
 use polars_core::prelude::{DataType, InitHashMaps, PlHashMap, PlHashSet, PlIndexMap};
 use polars_core::schema::Schema;
+use polars_core::series::IsSorted;
 use polars_error::{PolarsResult, polars_bail};
-use polars_expr::state::ExecutionState;
-use polars_mem_engine::create_physical_plan;
+#[cfg(feature = "iejoin")]
+use polars_plan::dsl::JoinTypeOptionsIR;
 use polars_plan::dsl::{
     ExtraColumnsPolicy, FileScan, FileSinkType, PartitionSinkTypeIR, PartitionVariantIR, SinkTypeIR,
 };
@@ -18,6 +18,7 @@ use polars_plan::plans::{
 use polars_plan::prelude::GroupbyOptions;
 use polars_utils::arena::{Arena, Node};
 use polars_utils::itertools::Itertools;
+use polars_utils::pl_str::PlSmallStr;
 use polars_utils::slice_enum::Slice;
 use polars_utils::unique_id::UniqueId;
 use polars_utils::{IdxSize, unique_column_name};
@@ -26,12 +27,16 @@ use slotmap::SlotMap;
 use super::{PhysNode, PhysNodeKey, PhysNodeKind, PhysStream};
 use crate::nodes::io_sources::multi_file_reader;
 use crate::nodes::io_sources::multi_file_reader::reader_interface::builder::FileReaderBuilder;
+use crate::nodes::joins::runtime_filter::{RuntimeJoinFilter, supports_runtime_filter};
 use crate::physical_plan::lower_expr::{
     ExprCache, build_length_preserving_select_stream, build_select_stream,
     is_elementwise_rec_cached, lower_exprs,
 };
 use crate::physical_plan::lower_group_by::build_group_by_stream;
-use crate::utils::late_materialized_df::LateMaterializedDataFrame;
+
+/// The largest number of rows (offset + length) of a sorted slice for which we
+/// use the top-k node, which keeps all these rows in memory.
+const TOP_K_MAX_ROWS: usize = 1 << 16;
 
 /// Creates a new PhysStream which outputs a slice of the input stream.
 pub fn build_slice_stream(
@@ -111,6 +116,374 @@ fn build_filter_stream(
     )
 }
 
+/// Lowers the key expressions of one side of a join, returning the lowered
+/// input and the keys in terms of its output.
+fn lower_join_keys(
+    input: PhysStream,
+    on: &[ExprIR],
+    expr_arena: &mut Arena<AExpr>,
+    phys_sm: &mut SlotMap<PhysNodeKey, PhysNode>,
+    expr_cache: &mut ExprCache,
+    ctx: StreamingLowerIRContext,
+) -> PolarsResult<(PhysStream, Vec<ExprIR>)> {
+    // When lowering the expressions for the keys we need to ensure we keep around the
+    // payload columns, otherwise the input nodes can get replaced by input-independent
+    // nodes since the lowering code does not see we access any non-literal expressions.
+    // So we add dummy expressions before lowering and remove them afterwards.
+    let mut aug_on = on.to_vec();
+    for name in phys_sm[input.node].output_schema.iter_names() {
+        let col_expr = expr_arena.add(AExpr::Column(name.clone()));
+        aug_on.push(ExprIR::new(col_expr, OutputName::ColumnLhs(name.clone())));
+    }
+    let (trans_input, mut trans_on) =
+        lower_exprs(input, &aug_on, expr_arena, phys_sm, expr_cache, ctx)?;
+    trans_on.drain(on.len()..);
+    Ok((trans_input, trans_on))
+}
+
+/// Creates a new PhysStream which as-of joins the input streams, if the join is
+/// on plain columns of a supported key type. Both inputs are assumed to be
+/// sorted by their key, which the node verifies.
+#[cfg(feature = "asof_join")]
+#[allow(clippy::too_many_arguments)]
+fn try_build_asof_join_stream(
+    input_left: PhysStream,
+    input_right: PhysStream,
+    left_on: &[ExprIR],
+    right_on: &[ExprIR],
+    args: &polars_ops::frame::JoinArgs,
+    output_schema: Arc<Schema>,
+    expr_arena: &Arena<AExpr>,
+    phys_sm: &mut SlotMap<PhysNodeKey, PhysNode>,
+) -> PolarsResult<Option<PhysStream>> {
+    use polars_core::prelude::{AnyValue, TimeUnit};
+    use polars_core::utils::arrow::temporal_conversions::MILLISECONDS_IN_DAY;
+    use polars_error::polars_ensure;
+    use polars_ops::frame::JoinType;
+
+    let JoinType::AsOf(options) = &args.how else {
+        return Ok(None);
+    };
+    let ([left_on], [right_on]) = (left_on, right_on) else {
+        return Ok(None);
+    };
+    let (AExpr::Column(left_col), AExpr::Column(right_col)) = (
+        expr_arena.get(left_on.node()),
+        expr_arena.get(right_on.node()),
+    ) else {
+        return Ok(None);
+    };
+    // Without the sortedness check the in-memory engine joins unsorted inputs
+    // as is, which we can't reproduce while streaming.
+    if args.validation.needs_checks()
+        || !options.check_sortedness
+        || left_on.output_name() != left_col
+        || right_on.output_name() != right_col
+    {
+        return Ok(None);
+    }
+
+    let key_dtype = phys_sm[input_left.node].output_schema.try_get(left_col)?;
+    if key_dtype != phys_sm[input_right.node].output_schema.try_get(right_col)?
+        || !matches!(
+            key_dtype.to_physical(),
+            DataType::Int32
+                | DataType::Int64
+                | DataType::UInt32
+                | DataType::UInt64
+                | DataType::Float32
+                | DataType::Float64
+        )
+    {
+        return Ok(None);
+    }
+
+    // The `by` groups of both inputs are assigned by a single grouper.
+    let left_schema = &phys_sm[input_left.node].output_schema;
+    let right_schema = &phys_sm[input_right.node].output_schema;
+    let left_by = options.left_by.as_deref().unwrap_or_default();
+    let right_by = options.right_by.as_deref().unwrap_or_default();
+    if left_by.len() != right_by.len() {
+        return Ok(None);
+    }
+    for (l, r) in left_by.iter().zip(right_by) {
+        if left_schema.try_get(l)?.to_physical() != right_schema.try_get(r)?.to_physical() {
+            return Ok(None);
+        }
+    }
+
+    // Resolve the tolerance to the unit of the key, like the in-memory engine
+    // does.
+    let mut options = options.clone();
+    if let Some(tol) = options.tolerance_str.take() {
+        let duration = polars_time::Duration::try_parse(&tol)?;
+        polars_ensure!(
+            duration.months() == 0,
+            ComputeError: "cannot use month offset in timedelta of an asof join; \
+            consider using 4 weeks"
+        );
+        options.tolerance = Some(match key_dtype {
+            DataType::Datetime(tu, _) | DataType::Duration(tu) => AnyValue::from(match tu {
+                TimeUnit::Nanoseconds => duration.duration_ns(),
+                TimeUnit::Microseconds => duration.duration_us(),
+                TimeUnit::Milliseconds => duration.duration_ms(),
+            }),
+            DataType::Date => AnyValue::from((duration.duration_ms() / MILLISECONDS_IN_DAY) as i32),
+            DataType::Time => AnyValue::from(duration.duration_ns()),
+            _ => return Ok(None),
+        });
+    }
+
+    let mut node_args = args.clone();
+    node_args.how = JoinType::AsOf(options);
+    node_args.slice = None;
+    let node = phys_sm.insert(PhysNode::new(
+        output_schema,
+        PhysNodeKind::AsOfJoin {
+            input_left,
+            input_right,
+            left_on: left_col.clone(),
+            right_on: right_col.clone(),
+            args: node_args,
+        },
+    ));
+    let mut stream = PhysStream::first(node);
+    if let Some((offset, len)) = args.slice {
+        stream = build_slice_stream(stream, offset, len, phys_sm);
+    }
+    Ok(Some(stream))
+}
+
+/// Returns whether the output of an IR node is known to be sorted ascendingly
+/// by a column, regardless of where its nulls are placed.
+fn is_sorted_ascending_by(
+    node: Node,
+    column: &PlSmallStr,
+    ir_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+) -> PolarsResult<bool> {
+    let is_column = |e: &ExprIR| {
+        e.output_name() == column
+            && matches!(expr_arena.get(e.node()), AExpr::Column(c) if c == column)
+    };
+    let recurse = |input: &Node| is_sorted_ascending_by(*input, column, ir_arena, expr_arena);
+
+    match ir_arena.get(node) {
+        IR::Sort {
+            by_column,
+            sort_options,
+            ..
+        } => Ok(by_column.first().is_some_and(is_column) && !sort_options.descending[0]),
+        IR::Filter { input, .. } | IR::Slice { input, .. } | IR::Cache { input, .. } => {
+            recurse(input)
+        },
+        IR::SimpleProjection { input, columns } => Ok(columns.contains(column) && recurse(input)?),
+        IR::Select { input, expr, .. } => match expr.iter().find(|e| e.output_name() == column) {
+            Some(e) if is_column(e) => recurse(input),
+            _ => Ok(false),
+        },
+        IR::HStack { input, exprs, .. } => match exprs.iter().find(|e| e.output_name() == column) {
+            Some(e) if !is_column(e) => Ok(false),
+            _ => recurse(input),
+        },
+        IR::MapFunction {
+            input,
+            function: FunctionIR::RowIndex { name, .. },
+        } => Ok(name == column || recurse(input)?),
+        IR::DataFrameScan { df, .. } => Ok(df
+            .column(column)
+            .is_ok_and(|c| c.is_sorted_flag() == IsSorted::Ascending)),
+        IR::Scan {
+            sources,
+            file_info,
+            scan_type,
+            unified_scan_args,
+            ..
+        } => {
+            if unified_scan_args
+                .row_index
+                .as_ref()
+                .is_some_and(|ri| ri.name == column)
+            {
+                return Ok(true);
+            }
+
+            #[cfg(feature = "parquet")]
+            if let (
+                1,
+                FileScan::Parquet {
+                    metadata: Some(metadata),
+                    ..
+                },
+                Some(reader_schema),
+            ) = (
+                sources.len(),
+                scan_type.as_ref(),
+                file_info
+                    .reader_schema
+                    .as_ref()
+                    .and_then(|s| s.as_ref().left()),
+            ) {
+                return polars_io::parquet::read::is_sorted_ascending_by(
+                    metadata,
+                    reader_schema,
+                    column,
+                );
+            }
+
+            let _ = (sources, file_info, scan_type);
+            Ok(false)
+        },
+        _ => Ok(false),
+    }
+}
+
+/// Builds a merge join if both inputs are known to be sorted by their join
+/// key, returning `None` if we can't.
+#[allow(clippy::too_many_arguments)]
+fn try_build_merge_join_stream(
+    input_left: PhysStream,
+    input_right: PhysStream,
+    ir_left: Node,
+    ir_right: Node,
+    left_on: &[ExprIR],
+    right_on: &[ExprIR],
+    args: &polars_ops::frame::JoinArgs,
+    output_schema: Arc<Schema>,
+    ir_arena: &Arena<IR>,
+    expr_arena: &Arena<AExpr>,
+    phys_sm: &mut SlotMap<PhysNodeKey, PhysNode>,
+) -> PolarsResult<Option<PhysStream>> {
+    use crate::nodes::joins::merge_join;
+
+    let ([left_on], [right_on]) = (left_on, right_on) else {
+        return Ok(None);
+    };
+    let (AExpr::Column(left_col), AExpr::Column(right_col)) = (
+        expr_arena.get(left_on.node()),
+        expr_arena.get(right_on.node()),
+    ) else {
+        return Ok(None);
+    };
+    if args.nulls_equal
+        || args.validation.needs_checks()
+        || !merge_join::can_maintain_order(&args.how, args.maintain_order)
+        || left_on.output_name() != left_col
+        || right_on.output_name() != right_col
+    {
+        return Ok(None);
+    }
+
+    let key_dtype = phys_sm[input_left.node].output_schema.try_get(left_col)?;
+    if key_dtype != phys_sm[input_right.node].output_schema.try_get(right_col)?
+        || !merge_join::is_supported_key_dtype(key_dtype)
+        || !is_sorted_ascending_by(ir_left, left_col, ir_arena, expr_arena)?
+        || !is_sorted_ascending_by(ir_right, right_col, ir_arena, expr_arena)?
+    {
+        return Ok(None);
+    }
+
+    let mut node_args = args.clone();
+    node_args.slice = None;
+    let node = phys_sm.insert(PhysNode::new(
+        output_schema,
+        PhysNodeKind::MergeJoin {
+            input_left,
+            input_right,
+            left_on: left_col.clone(),
+            right_on: right_col.clone(),
+            args: node_args,
+        },
+    ));
+    let mut stream = PhysStream::first(node);
+    if let Some((offset, len)) = args.slice {
+        stream = build_slice_stream(stream, offset, len, phys_sm);
+    }
+    Ok(Some(stream))
+}
+
+/// Creates the filters an equi-join publishes on its probe side, for the inputs
+/// which are scans that can skip data with them. Rows of an input may only be
+/// skipped if the join drops them when they don't have a match.
+#[allow(clippy::too_many_arguments)]
+fn add_runtime_join_filters(
+    input_left: PhysStream,
+    input_right: PhysStream,
+    left_on: &[ExprIR],
+    right_on: &[ExprIR],
+    args: &polars_ops::frame::JoinArgs,
+    expr_arena: &Arena<AExpr>,
+    phys_sm: &mut SlotMap<PhysNodeKey, PhysNode>,
+    cache_nodes: &PlHashMap<UniqueId, PhysStream>,
+) -> PolarsResult<(
+    Option<Arc<RuntimeJoinFilter>>,
+    Option<Arc<RuntimeJoinFilter>>,
+)> {
+    use polars_ops::frame::JoinType;
+
+    if !args.how.is_equi() || args.nulls_equal {
+        return Ok((None, None));
+    }
+    let (filter_left, filter_right) = match args.how {
+        JoinType::Inner => (true, true),
+        JoinType::Left => (false, true),
+        JoinType::Right => (true, false),
+        _ => (false, false),
+    };
+
+    let key_columns = |on: &[ExprIR]| {
+        on.iter()
+            .map(|e| match expr_arena.get(e.node()) {
+                AExpr::Column(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+    let (Some(left_cols), Some(right_cols)) = (key_columns(left_on), key_columns(right_on)) else {
+        return Ok((None, None));
+    };
+    for (l, r) in left_cols.iter().zip(&right_cols) {
+        let dtype = phys_sm[input_left.node].output_schema.try_get(l)?;
+        if dtype != phys_sm[input_right.node].output_schema.try_get(r)?
+            || !supports_runtime_filter(dtype)
+        {
+            return Ok((None, None));
+        }
+    }
+
+    let mut add_filter = |input: PhysStream, cols: &[PlSmallStr]| {
+        // The scan must feed nothing but this join.
+        if cache_nodes.values().any(|s| s.node == input.node) {
+            return None;
+        }
+        // Skipping rows changes the row positions a slice or row index refer to.
+        let PhysNodeKind::MultiScan {
+            projected_file_schema,
+            row_index: None,
+            pre_slice: None,
+            runtime_filters,
+            ..
+        } = &mut phys_sm[input.node].kind
+        else {
+            return None;
+        };
+        let key_schema = cols
+            .iter()
+            .map(|c| Some((c.clone(), projected_file_schema.get(c)?.clone())))
+            .collect::<Option<Schema>>()?;
+        let filter = Arc::new(RuntimeJoinFilter::new(key_schema));
+        runtime_filters.push(filter.clone());
+        Some(filter)
+    };
+    let left_filter = filter_left
+        .then(|| add_filter(input_left, &left_cols))
+        .flatten();
+    let right_filter = filter_right
+        .then(|| add_filter(input_right, &right_cols))
+        .flatten();
+    Ok((left_filter, right_filter))
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct StreamingLowerIRContext {
     pub prepare_visualization: bool,
@@ -428,11 +801,50 @@ pub fn lower_ir(
             by_column,
             slice,
             sort_options,
-        } => PhysNodeKind::Sort {
-            by_column: by_column.clone(),
-            slice: *slice,
-            sort_options: sort_options.clone(),
-            input: lower_ir!(*input)?,
+        } => {
+            let by_column = by_column.clone();
+            let slice = *slice;
+            let sort_options = sort_options.clone();
+            let phys_input = lower_ir!(*input)?;
+
+            // Like for the join keys we add dummy expressions for the payload
+            // columns to keep them around while lowering the sort keys.
+            let mut aug_by_column = by_column.clone();
+            for name in phys_sm[phys_input.node].output_schema.iter_names() {
+                let col_expr = expr_arena.add(AExpr::Column(name.clone()));
+                aug_by_column.push(ExprIR::new(col_expr, OutputName::ColumnLhs(name.clone())));
+            }
+            let (trans_input, mut trans_by_column) = lower_exprs(
+                phys_input,
+                &aug_by_column,
+                expr_arena,
+                phys_sm,
+                expr_cache,
+                ctx,
+            )?;
+            trans_by_column.drain(by_column.len()..);
+
+            match slice {
+                // A small slice from the top only needs the first offset + len
+                // rows, larger slices are better served by a (spilling) sort.
+                Some((offset, len))
+                    if offset >= 0 && (offset as usize).saturating_add(len) <= TOP_K_MAX_ROWS =>
+                {
+                    PhysNodeKind::TopK {
+                        input: trans_input,
+                        by_column: trans_by_column,
+                        offset: offset as usize,
+                        len,
+                        sort_options,
+                    }
+                },
+                _ => PhysNodeKind::Sort {
+                    input: trans_input,
+                    by_column: trans_by_column,
+                    slice,
+                    sort_options,
+                },
+            }
         },
 
         IR::Union { inputs, options } => {
@@ -526,6 +938,11 @@ pub fn lower_ir(
                         Arc::new(Arc::new(options.clone())) as Arc<dyn FileReaderBuilder>
                     },
 
+                    #[cfg(feature = "avro")]
+                    FileScan::Avro { options } => {
+                        Arc::new(Arc::new(options.clone())) as Arc<dyn FileReaderBuilder>
+                    },
+
                     #[cfg(feature = "json")]
                     FileScan::NDJson { options } => {
                         Arc::new(Arc::new(options.clone())) as Arc<dyn FileReaderBuilder>
@@ -612,6 +1029,7 @@ pub fn lower_ir(
                         extra_columns_policy,
                         include_file_paths: unified_scan_args.include_file_paths,
                         file_schema,
+                        runtime_filters: Vec::new(),
                     };
 
                     let PhysNodeKind::MultiScan {
@@ -845,40 +1263,38 @@ pub fn lower_ir(
             let phys_left = lower_ir!(input_left)?;
             let phys_right = lower_ir!(input_right)?;
             if (args.how.is_equi() || args.how.is_semi_anti()) && !args.validation.needs_checks() {
-                // When lowering the expressions for the keys we need to ensure we keep around the
-                // payload columns, otherwise the input nodes can get replaced by input-independent
-                // nodes since the lowering code does not see we access any non-literal expressions.
-                // So we add dummy expressions before lowering and remove them afterwards.
-                let mut aug_left_on = left_on.clone();
-                for name in phys_sm[phys_left.node].output_schema.iter_names() {
-                    let col_expr = expr_arena.add(AExpr::Column(name.clone()));
-                    aug_left_on.push(ExprIR::new(col_expr, OutputName::ColumnLhs(name.clone())));
-                }
-                let mut aug_right_on = right_on.clone();
-                for name in phys_sm[phys_right.node].output_schema.iter_names() {
-                    let col_expr = expr_arena.add(AExpr::Column(name.clone()));
-                    aug_right_on.push(ExprIR::new(col_expr, OutputName::ColumnLhs(name.clone())));
-                }
-                let (trans_input_left, mut trans_left_on) = lower_exprs(
+                if let Some(stream) = try_build_merge_join_stream(
                     phys_left,
-                    &aug_left_on,
-                    expr_arena,
-                    phys_sm,
-                    expr_cache,
-                    ctx,
-                )?;
-                let (trans_input_right, mut trans_right_on) = lower_exprs(
                     phys_right,
-                    &aug_right_on,
+                    input_left,
+                    input_right,
+                    &left_on,
+                    &right_on,
+                    &args,
+                    output_schema.clone(),
+                    ir_arena,
                     expr_arena,
                     phys_sm,
-                    expr_cache,
-                    ctx,
-                )?;
-                trans_left_on.drain(left_on.len()..);
-                trans_right_on.drain(right_on.len()..);
+                )? {
+                    return Ok(stream);
+                }
+
+                let (trans_input_left, trans_left_on) =
+                    lower_join_keys(phys_left, &left_on, expr_arena, phys_sm, expr_cache, ctx)?;
+                let (trans_input_right, trans_right_on) =
+                    lower_join_keys(phys_right, &right_on, expr_arena, phys_sm, expr_cache, ctx)?;
 
                 let node = if args.how.is_equi() {
+                    let (left_runtime_filter, right_runtime_filter) = add_runtime_join_filters(
+                        trans_input_left,
+                        trans_input_right,
+                        &trans_left_on,
+                        &trans_right_on,
+                        &args,
+                        expr_arena,
+                        phys_sm,
+                        cache_nodes,
+                    )?;
                     phys_sm.insert(PhysNode::new(
                         output_schema,
                         PhysNodeKind::EquiJoin {
@@ -887,6 +1303,8 @@ pub fn lower_ir(
                             left_on: trans_left_on,
                             right_on: trans_right_on,
                             args: args.clone(),
+                            left_runtime_filter,
+                            right_runtime_filter,
                         },
                     ))
                 } else {
@@ -922,6 +1340,50 @@ pub fn lower_ir(
                 }
                 return Ok(stream);
             } else {
+                #[cfg(feature = "iejoin")]
+                if let Some(JoinTypeOptionsIR::IEJoin(ie_options)) = &options {
+                    if args.how.is_ie() && !args.validation.needs_checks() {
+                        let (trans_input_left, trans_left_on) = lower_join_keys(
+                            phys_left, &left_on, expr_arena, phys_sm, expr_cache, ctx,
+                        )?;
+                        let (trans_input_right, trans_right_on) = lower_join_keys(
+                            phys_right, &right_on, expr_arena, phys_sm, expr_cache, ctx,
+                        )?;
+                        let mut node_args = args.clone();
+                        node_args.slice = None;
+                        let node = phys_sm.insert(PhysNode::new(
+                            output_schema,
+                            PhysNodeKind::RangeJoin {
+                                input_left: trans_input_left,
+                                input_right: trans_input_right,
+                                left_on: trans_left_on,
+                                right_on: trans_right_on,
+                                options: ie_options.clone(),
+                                args: node_args,
+                            },
+                        ));
+                        let mut stream = PhysStream::first(node);
+                        if let Some((offset, len)) = args.slice {
+                            stream = build_slice_stream(stream, offset, len, phys_sm);
+                        }
+                        return Ok(stream);
+                    }
+                }
+
+                #[cfg(feature = "asof_join")]
+                if let Some(stream) = try_build_asof_join_stream(
+                    phys_left,
+                    phys_right,
+                    &left_on,
+                    &right_on,
+                    &args,
+                    output_schema.clone(),
+                    expr_arena,
+                    phys_sm,
+                )? {
+                    return Ok(stream);
+                }
+
                 PhysNodeKind::InMemoryJoin {
                     input_left: phys_left,
                     input_right: phys_right,
@@ -937,67 +1399,37 @@ pub fn lower_ir(
             let options = options.clone();
             let phys_input = lower_ir!(*input)?;
 
-            // We don't have a dedicated distinct operator (yet), lower to group
-            // by with an aggregate for each column.
-            let input_schema = &phys_sm[phys_input.node].output_schema;
+            let input_schema = phys_sm[phys_input.node].output_schema.clone();
             if input_schema.is_empty() {
                 // Can't group (or have duplicates) if dataframe has zero-width.
                 return Ok(phys_input);
             }
 
-            if options.maintain_order && options.keep_strategy == UniqueKeepStrategy::Last {
-                // Unfortunately the order-preserving groupby always orders by the first occurrence
-                // of the group so we can't lower this and have to fallback.
-                let input_schema = phys_sm[phys_input.node].output_schema.clone();
-                let lmdf = Arc::new(LateMaterializedDataFrame::default());
-                let mut lp_arena = Arena::default();
-                let input_lp_node = lp_arena.add(lmdf.clone().as_ir_node(input_schema.clone()));
-                let distinct_lp_node = lp_arena.add(IR::Distinct {
-                    input: input_lp_node,
-                    options,
-                });
-                let executor = Mutex::new(create_physical_plan(
-                    distinct_lp_node,
-                    &mut lp_arena,
-                    expr_arena,
-                    None,
-                )?);
-
-                let format_str = ctx.prepare_visualization.then(|| {
-                    let mut buffer = String::new();
-                    write_ir_non_recursive(
-                        &mut buffer,
-                        ir_arena.get(node),
-                        expr_arena,
-                        phys_sm.get(phys_input.node).unwrap().output_schema.as_ref(),
-                        0,
-                    )
-                    .unwrap();
-                    buffer
-                });
-                let distinct_node = PhysNode {
-                    output_schema,
-                    kind: PhysNodeKind::InMemoryMap {
-                        input: phys_input,
-                        map: Arc::new(move |df| {
-                            lmdf.set_materialized_dataframe(df);
-                            let mut state = ExecutionState::new();
-                            executor.lock().execute(&mut state)
-                        }),
-                        format_str,
-                    },
-                };
-
-                return Ok(PhysStream::first(phys_sm.insert(distinct_node)));
-            }
-
-            // Create the key and aggregate expressions.
             let all_col_names = input_schema.iter_names().cloned().collect_vec();
             let key_names = if let Some(subset) = options.subset {
                 subset.to_vec()
             } else {
                 all_col_names.clone()
             };
+
+            if options.maintain_order && options.keep_strategy == UniqueKeepStrategy::Last {
+                // The order-preserving group by always orders by the first
+                // occurrence of a group, use the dedicated distinct node which
+                // keeps the last occurrence.
+                let mut stream = PhysStream::first(phys_sm.insert(PhysNode::new(
+                    input_schema.clone(),
+                    PhysNodeKind::DistinctLast {
+                        input: phys_input,
+                        keys: key_names,
+                    },
+                )));
+                if let Some((offset, length)) = options.slice {
+                    stream = build_slice_stream(stream, offset, length, phys_sm);
+                }
+                return Ok(stream);
+            }
+
+            // Otherwise lower to a group by with an aggregate for each column.
             let key_name_set: PlHashSet<_> = key_names.iter().cloned().collect();
 
             let mut group_by_output_schema = Schema::with_capacity(all_col_names.len() + 1);
@@ -1087,7 +1519,56 @@ pub fn lower_ir(
 
             return Ok(stream);
         },
-        IR::ExtContext { .. } => todo!(),
+        IR::ExtContext {
+            input,
+            contexts,
+            schema: _,
+        } => {
+            let input = *input;
+            let contexts = contexts.clone();
+
+            // Columns are resolved from the input first, and then from the
+            // contexts in order, so every context only contributes the columns
+            // which weren't seen before.
+            let mut seen_columns: PlHashSet<PlSmallStr> =
+                IR::schema_with_cache(input, ir_arena, schema_cache)
+                    .iter_names_cloned()
+                    .collect();
+            let mut inputs = vec![lower_ir!(input)?];
+            for context in contexts {
+                let context_schema = IR::schema_with_cache(context, ir_arena, schema_cache);
+                let columns = context_schema
+                    .iter_names()
+                    .filter(|name| seen_columns.insert((*name).clone()))
+                    .cloned()
+                    .collect_vec();
+                if columns.is_empty() {
+                    continue;
+                }
+
+                let phys_context = lower_ir!(context)?;
+                let projected_schema = Arc::new(context_schema.try_project(&columns)?);
+                let projection = phys_sm.insert(PhysNode::new(
+                    projected_schema,
+                    PhysNodeKind::SimpleProjection {
+                        input: phys_context,
+                        columns,
+                    },
+                ));
+                inputs.push(PhysStream::first(projection));
+            }
+
+            if inputs.len() == 1 {
+                return Ok(inputs.pop().unwrap());
+            }
+
+            // Contexts of a single row, e.g. aggregates, are broadcast, others
+            // must have the same length as the input.
+            PhysNodeKind::Zip {
+                inputs,
+                null_extend: false,
+            }
+        },
         IR::Invalid => unreachable!(),
     };
 
@@ -7,6 +7,8 @@ use polars_core::schema::{Schema, SchemaRef};
 use polars_error::PolarsResult;
 use polars_io::RowIndex;
 use polars_io::cloud::CloudOptions;
+#[cfg(feature = "iejoin")]
+use polars_ops::frame::IEJoinOptions;
 use polars_ops::frame::JoinArgs;
 use polars_plan::dsl::{
     CastColumnsPolicy, JoinTypeOptionsIR, MissingColumnsPolicy, PartitionTargetCallback,
@@ -23,9 +25,11 @@ mod lower_group_by;
 mod lower_ir;
 mod to_graph;
 
-pub use fmt::visualize_plan;
+pub use fmt::{visualize_annotated_plan, visualize_plan};
 use polars_plan::dsl::ExtraColumnsPolicy;
 use polars_plan::prelude::FileType;
+#[cfg(feature = "dynamic_group_by")]
+use polars_time::{DynamicGroupOptions, RollingGroupOptions};
 use polars_utils::arena::{Arena, Node};
 use polars_utils::pl_str::PlSmallStr;
 use polars_utils::slice_enum::Slice;
@@ -34,6 +38,7 @@ pub use to_graph::physical_plan_to_graph;
 
 pub use self::lower_ir::StreamingLowerIRContext;
 use crate::nodes::io_sources::multi_file_reader::reader_interface::builder::FileReaderBuilder;
+use crate::nodes::joins::runtime_filter::RuntimeJoinFilter;
 use crate::physical_plan::lower_expr::ExprCache;
 
 slotmap::new_key_type! {
@@ -178,6 +183,17 @@ pub enum PhysNodeKind {
         map: Arc<dyn DataFrameUdf>,
     },
 
+    /// Applies a map to batches of whole partitions of an input in which all
+    /// rows with equal keys are consecutive (e.g. because it is sorted by them).
+    SortedPartitionMap {
+        input: PhysStream,
+        keys: Vec<PlSmallStr>,
+        map: Arc<dyn DataFrameUdf>,
+
+        /// A formatted explain of the map.
+        format_str: Option<String>,
+    },
+
     Sort {
         input: PhysStream,
         by_column: Vec<ExprIR>,
@@ -185,6 +201,16 @@ pub enum PhysNodeKind {
         sort_options: SortMultipleOptions,
     },
 
+    /// Sort with a slice from the top, only keeps the rows which can still
+    /// end up in the slice.
+    TopK {
+        input: PhysStream,
+        by_column: Vec<ExprIR>,
+        offset: usize,
+        len: usize,
+        sort_options: SortMultipleOptions,
+    },
+
     OrderedUnion {
         inputs: Vec<PhysStream>,
     },
@@ -225,6 +251,9 @@ pub enum PhysNodeKind {
 
         /// Schema of columns contained in the file. Does not contain external columns (e.g. hive / row_index).
         file_schema: SchemaRef,
+
+        /// Filters on the output that joins publish while the query runs.
+        runtime_filters: Vec<Arc<RuntimeJoinFilter>>,
     },
 
     #[cfg(feature = "python")]
@@ -239,12 +268,42 @@ pub enum PhysNodeKind {
         aggs: Vec<ExprIR>,
     },
 
+    /// Group-by over windows of an index column which is sorted (per key).
+    #[cfg(feature = "dynamic_group_by")]
+    DynamicGroupBy {
+        input: PhysStream,
+        key: Vec<ExprIR>,
+        // Same restrictions as the aggregations of GroupBy.
+        aggs: Vec<ExprIR>,
+        options: DynamicGroupOptions,
+    },
+
+    /// Group-by over a window per row of an index column which is sorted (per key).
+    #[cfg(feature = "dynamic_group_by")]
+    RollingGroupBy {
+        input: PhysStream,
+        key: Vec<ExprIR>,
+        // Same restrictions as the aggregations of GroupBy.
+        aggs: Vec<ExprIR>,
+        options: RollingGroupOptions,
+    },
+
+    /// Keeps the last row of each distinct key, in the order of the input.
+    DistinctLast {
+        input: PhysStream,
+        keys: Vec<PlSmallStr>,
+    },
+
     EquiJoin {
         input_left: PhysStream,
         input_right: PhysStream,
         left_on: Vec<ExprIR>,
         right_on: Vec<ExprIR>,
         args: JoinArgs,
+        /// Filters this join publishes on the keys of an input, if it turns
+        /// out to be the probe side.
+        left_runtime_filter: Option<Arc<RuntimeJoinFilter>>,
+        right_runtime_filter: Option<Arc<RuntimeJoinFilter>>,
     },
 
     SemiAntiJoin {
@@ -262,6 +321,38 @@ pub enum PhysNodeKind {
         args: JoinArgs,
     },
 
+    /// Equi-join of two inputs which are both sorted by their join key.
+    MergeJoin {
+        input_left: PhysStream,
+        input_right: PhysStream,
+        left_on: PlSmallStr,
+        right_on: PlSmallStr,
+        args: JoinArgs,
+    },
+
+    /// As-of join of two inputs which are both sorted by their as-of key.
+    #[cfg(feature = "asof_join")]
+    AsOfJoin {
+        input_left: PhysStream,
+        input_right: PhysStream,
+        left_on: PlSmallStr,
+        right_on: PlSmallStr,
+        args: JoinArgs,
+    },
+
+    /// Inner join on one or two inequality predicates between the keys. Builds
+    /// an index sorted on the keys of the smaller input and streams the other
+    /// input through it.
+    #[cfg(feature = "iejoin")]
+    RangeJoin {
+        input_left: PhysStream,
+        input_right: PhysStream,
+        left_on: Vec<ExprIR>,
+        right_on: Vec<ExprIR>,
+        options: IEJoinOptions,
+        args: JoinArgs,
+    },
+
     /// Generic fallback for (as-of-yet) unsupported streaming joins.
     /// Fully sinks all data to in-memory data frames and uses the in-memory
     /// engine to perform the join.
@@ -318,9 +409,19 @@ fn visit_node_inputs_mut(
             | PhysNodeKind::PartitionSink { input, .. }
             | PhysNodeKind::InMemoryMap { input, .. }
             | PhysNodeKind::Map { input, .. }
+            | PhysNodeKind::SortedPartitionMap { input, .. }
             | PhysNodeKind::Sort { input, .. }
+            | PhysNodeKind::TopK { input, .. }
             | PhysNodeKind::Multiplexer { input }
-            | PhysNodeKind::GroupBy { input, .. } => {
+            | PhysNodeKind::GroupBy { input, .. }
+            | PhysNodeKind::DistinctLast { input, .. } => {
+                rec!(input.node);
+                visit(input);
+            },
+
+            #[cfg(feature = "dynamic_group_by")]
+            PhysNodeKind::DynamicGroupBy { input, .. }
+            | PhysNodeKind::RollingGroupBy { input, .. } => {
                 rec!(input.node);
                 visit(input);
             },
@@ -344,6 +445,35 @@ fn visit_node_inputs_mut(
                 input_left,
                 input_right,
                 ..
+            }
+            | PhysNodeKind::MergeJoin {
+                input_left,
+                input_right,
+                ..
+            } => {
+                rec!(input_left.node);
+                rec!(input_right.node);
+                visit(input_left);
+                visit(input_right);
+            },
+
+            #[cfg(feature = "asof_join")]
+            PhysNodeKind::AsOfJoin {
+                input_left,
+                input_right,
+                ..
+            } => {
+                rec!(input_left.node);
+                rec!(input_right.node);
+                visit(input_left);
+                visit(input_right);
+            },
+
+            #[cfg(feature = "iejoin")]
+            PhysNodeKind::RangeJoin {
+                input_left,
+                input_right,
+                ..
             } => {
                 rec!(input_left.node);
                 rec!(input_right.node);
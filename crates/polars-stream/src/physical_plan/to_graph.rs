@@ -1,15 +1,17 @@
 use std::path::PathBuf;
-use std::sync::Arc;
 use std::sync::atomic::AtomicUsize;
+use std::sync::{Arc, OnceLock};
 
 use parking_lot::Mutex;
 use polars_core::prelude::PlRandomState;
 use polars_core::schema::Schema;
+#[cfg(feature = "iejoin")]
+use polars_core::utils::try_get_supertype;
 use polars_core::{POOL, config};
 use polars_error::{PolarsResult, polars_bail, polars_ensure, polars_err};
 use polars_expr::groups::new_hash_grouper;
 use polars_expr::planner::{ExpressionConversionState, create_physical_expr};
-use polars_expr::reduce::into_reduction;
+use polars_expr::reduce::{GroupedReduction, into_reduction};
 use polars_expr::state::ExecutionState;
 use polars_mem_engine::{create_physical_plan, create_scan_predicate};
 use polars_plan::dsl::{JoinOptionsIR, PartitionVariantIR, ScanSources};
@@ -62,6 +64,76 @@ fn create_stream_expr(
     Ok(StreamExpr::new(phys, reentrant))
 }
 
+/// Sort keys which are plain columns of the input are used as is, others get
+/// computed by the node.
+fn create_sort_keys(
+    by_column: &[ExprIR],
+    input_schema: &Arc<Schema>,
+    ctx: &mut GraphConversionContext<'_>,
+) -> PolarsResult<Vec<nodes::sort::SortKey>> {
+    by_column
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            if let AExpr::Column(name) = ctx.expr_arena.get(e.node()) {
+                if input_schema.contains(name) {
+                    return Ok(nodes::sort::SortKey::column(name.clone()));
+                }
+            }
+            let selector = create_stream_expr(e, ctx, input_schema)?;
+            Ok(nodes::sort::SortKey::expr(selector, i))
+        })
+        .collect()
+}
+
+/// Creates the grouped reductions for simple aggregations, together with the
+/// input columns they aggregate.
+#[allow(clippy::type_complexity)]
+fn create_grouped_reductions(
+    aggs: &[ExprIR],
+    input_schema: &Schema,
+    ctx: &mut GraphConversionContext<'_>,
+) -> PolarsResult<(Vec<Box<dyn GroupedReduction>>, Vec<PlSmallStr>)> {
+    let mut grouped_reductions = Vec::new();
+    let mut grouped_reduction_cols = Vec::new();
+    for agg in aggs {
+        let (reduction, input_node) = into_reduction(agg.node(), ctx.expr_arena, input_schema)?;
+        let AExpr::Column(col) = ctx.expr_arena.get(input_node) else {
+            unreachable!()
+        };
+        grouped_reductions.push(reduction);
+        grouped_reduction_cols.push(col.clone());
+    }
+    Ok((grouped_reductions, grouped_reduction_cols))
+}
+
+#[cfg(feature = "dynamic_group_by")]
+fn create_dynamic_group_by_node(
+    input_schema: &Arc<Schema>,
+    key: &[ExprIR],
+    aggs: &[ExprIR],
+    kind: nodes::dynamic_group_by::WindowKind,
+    output_schema: Arc<Schema>,
+    ctx: &mut GraphConversionContext<'_>,
+) -> PolarsResult<nodes::dynamic_group_by::DynamicGroupByNode> {
+    let key_schema = compute_output_schema(input_schema, key, ctx.expr_arena)?;
+    let key_selectors = key
+        .iter()
+        .map(|e| create_stream_expr(e, ctx, input_schema))
+        .try_collect_vec()?;
+    let (grouped_reductions, grouped_reduction_cols) =
+        create_grouped_reductions(aggs, input_schema, ctx)?;
+    nodes::dynamic_group_by::DynamicGroupByNode::new(
+        input_schema,
+        kind,
+        key_selectors,
+        (!key.is_empty()).then(|| new_hash_grouper(key_schema)),
+        grouped_reduction_cols,
+        grouped_reductions,
+        output_schema,
+    )
+}
+
 struct GraphConversionContext<'a> {
     phys_sm: &'a SlotMap<PhysNodeKey, PhysNode>,
     expr_arena: &'a mut Arena<AExpr>,
@@ -296,11 +368,23 @@ fn to_graph_rec<'a>(
                     )),
                     [(input_key, input.port)],
                 ),
+                #[cfg(feature = "avro")]
+                FileType::Avro(avro_writer_options) => ctx.graph.add_node(
+                    SinkComputeNode::from(nodes::io_sinks::avro::AvroSinkNode::new(
+                        target.clone(),
+                        input_schema,
+                        sink_options,
+                        avro_writer_options.clone(),
+                        cloud_options.clone(),
+                    )?),
+                    [(input_key, input.port)],
+                ),
                 #[cfg(not(any(
                     feature = "csv",
                     feature = "parquet",
                     feature = "json",
-                    feature = "ipc"
+                    feature = "ipc",
+                    feature = "avro"
                 )))]
                 _ => {
                     panic!("activate source feature")
@@ -430,6 +514,19 @@ fn to_graph_rec<'a>(
             )
         },
 
+        SortedPartitionMap {
+            input,
+            keys,
+            map,
+            format_str: _,
+        } => {
+            let input_key = to_graph_rec(input.node, ctx)?;
+            ctx.graph.add_node(
+                nodes::sorted_partition_map::SortedPartitionMapNode::new(keys.clone(), map.clone()),
+                [(input_key, input.port)],
+            )
+        },
+
         Sort {
             input,
             by_column,
@@ -437,36 +534,57 @@ fn to_graph_rec<'a>(
             sort_options,
         } => {
             let input_schema = ctx.phys_sm[input.node].output_schema.clone();
-            let lmdf = Arc::new(LateMaterializedDataFrame::default());
-            let mut lp_arena = Arena::default();
-            let df_node = lp_arena.add(lmdf.clone().as_ir_node(input_schema.clone()));
-            let sort_node = lp_arena.add(IR::Sort {
-                input: df_node,
-                by_column: by_column.clone(),
-                slice: *slice,
-                sort_options: sort_options.clone(),
-            });
-            let executor = Mutex::new(create_physical_plan(
-                sort_node,
-                &mut lp_arena,
-                ctx.expr_arena,
-                None,
-            )?);
+            let keys = create_sort_keys(by_column, &input_schema, ctx)?;
 
             let input_key = to_graph_rec(input.node, ctx)?;
             ctx.graph.add_node(
-                nodes::in_memory_map::InMemoryMapNode::new(
-                    input_schema,
-                    Arc::new(move |df| {
-                        lmdf.set_materialized_dataframe(df);
-                        let mut state = ExecutionState::new();
-                        executor.lock().execute(&mut state)
-                    }),
+                nodes::sort::SortNode::new(
+                    keys,
+                    sort_options.clone(),
+                    *slice,
+                    node.output_schema.clone(),
+                    ctx.num_pipelines,
+                ),
+                [(input_key, input.port)],
+            )
+        },
+
+        TopK {
+            input,
+            by_column,
+            offset,
+            len,
+            sort_options,
+        } => {
+            let input_schema = ctx.phys_sm[input.node].output_schema.clone();
+            let keys = create_sort_keys(by_column, &input_schema, ctx)?;
+
+            let input_key = to_graph_rec(input.node, ctx)?;
+            ctx.graph.add_node(
+                nodes::top_k::TopKNode::new(
+                    keys,
+                    sort_options.clone(),
+                    *offset,
+                    *len,
+                    node.output_schema.clone(),
+                    ctx.num_pipelines,
                 ),
                 [(input_key, input.port)],
             )
         },
 
+        DistinctLast { input, keys } => {
+            let input_key = to_graph_rec(input.node, ctx)?;
+            ctx.graph.add_node(
+                nodes::distinct::DistinctNode::new(
+                    node.output_schema.clone(),
+                    keys.clone(),
+                    ctx.num_pipelines,
+                )?,
+                [(input_key, input.port)],
+            )
+        },
+
         OrderedUnion { inputs } => {
             let input_keys = inputs
                 .iter()
@@ -497,7 +615,7 @@ fn to_graph_rec<'a>(
         Multiplexer { input } => {
             let input_key = to_graph_rec(input.node, ctx)?;
             ctx.graph.add_node(
-                nodes::multiplexer::MultiplexerNode::new(),
+                nodes::multiplexer::MultiplexerNode::new(&node.output_schema),
                 [(input_key, input.port)],
             )
         },
@@ -517,6 +635,7 @@ fn to_graph_rec<'a>(
             cast_columns_policy,
             include_file_paths,
             file_schema,
+            runtime_filters,
         } => {
             let hive_parts = hive_parts.clone();
 
@@ -552,6 +671,7 @@ fn to_graph_rec<'a>(
             let missing_columns_policy = *missing_columns_policy;
             let extra_columns_policy = *extra_columns_policy;
             let cast_columns_policy = cast_columns_policy.clone();
+            let runtime_filters = runtime_filters.clone();
 
             let verbose = config::verbose();
 
@@ -572,10 +692,12 @@ fn to_graph_rec<'a>(
                         missing_columns_policy,
                         extra_columns_policy,
                         cast_columns_policy,
+                        runtime_filters,
                         // Initialized later
                         num_pipelines: AtomicUsize::new(0),
                         n_readers_pre_init: AtomicUsize::new(0),
                         max_concurrent_scans: AtomicUsize::new(0),
+                        cancel_token: OnceLock::new(),
                         verbose,
                     },
                 )),
@@ -595,20 +717,12 @@ fn to_graph_rec<'a>(
                 .map(|e| create_stream_expr(e, ctx, input_schema))
                 .try_collect_vec()?;
 
-            let mut grouped_reductions = Vec::new();
-            let mut grouped_reduction_cols = Vec::new();
-            for agg in aggs {
-                let (reduction, input_node) =
-                    into_reduction(agg.node(), ctx.expr_arena, input_schema)?;
-                let AExpr::Column(col) = ctx.expr_arena.get(input_node) else {
-                    unreachable!()
-                };
-                grouped_reductions.push(reduction);
-                grouped_reduction_cols.push(col.clone());
-            }
+            let (grouped_reductions, grouped_reduction_cols) =
+                create_grouped_reductions(aggs, input_schema, ctx)?;
 
             ctx.graph.add_node(
                 nodes::group_by::GroupByNode::new(
+                    input_schema,
                     key_schema,
                     key_selectors,
                     grouper,
@@ -622,6 +736,48 @@ fn to_graph_rec<'a>(
             )
         },
 
+        #[cfg(feature = "dynamic_group_by")]
+        DynamicGroupBy {
+            input,
+            key,
+            aggs,
+            options,
+        } => {
+            let input_key = to_graph_rec(input.node, ctx)?;
+            let input_schema = &ctx.phys_sm[input.node].output_schema;
+            let kind = nodes::dynamic_group_by::WindowKind::Dynamic(options.clone());
+            let node = create_dynamic_group_by_node(
+                input_schema,
+                key,
+                aggs,
+                kind,
+                node.output_schema.clone(),
+                ctx,
+            )?;
+            ctx.graph.add_node(node, [(input_key, input.port)])
+        },
+
+        #[cfg(feature = "dynamic_group_by")]
+        RollingGroupBy {
+            input,
+            key,
+            aggs,
+            options,
+        } => {
+            let input_key = to_graph_rec(input.node, ctx)?;
+            let input_schema = &ctx.phys_sm[input.node].output_schema;
+            let kind = nodes::dynamic_group_by::WindowKind::Rolling(options.clone());
+            let node = create_dynamic_group_by_node(
+                input_schema,
+                key,
+                aggs,
+                kind,
+                node.output_schema.clone(),
+                ctx,
+            )?;
+            ctx.graph.add_node(node, [(input_key, input.port)])
+        },
+
         InMemoryJoin {
             input_left,
             input_right,
@@ -689,6 +845,7 @@ fn to_graph_rec<'a>(
             left_on,
             right_on,
             args,
+            ..
         }
         | SemiAntiJoin {
             input_left,
@@ -746,7 +903,7 @@ fn to_graph_rec<'a>(
             let unique_key_schema =
                 compute_output_schema(&right_input_schema, &unique_left_on, ctx.expr_arena)?;
 
-            match node.kind {
+            match &node.kind {
                 #[cfg(feature = "semi_anti_join")]
                 SemiAntiJoin { output_bool, .. } => ctx.graph.add_node(
                     nodes::joins::semi_anti_join::SemiAntiJoinNode::new(
@@ -754,7 +911,7 @@ fn to_graph_rec<'a>(
                         left_key_selectors,
                         right_key_selectors,
                         args,
-                        output_bool,
+                        *output_bool,
                         ctx.num_pipelines,
                     )?,
                     [
@@ -762,7 +919,11 @@ fn to_graph_rec<'a>(
                         (right_input_key, input_right.port),
                     ],
                 ),
-                _ => ctx.graph.add_node(
+                EquiJoin {
+                    left_runtime_filter,
+                    right_runtime_filter,
+                    ..
+                } => ctx.graph.add_node(
                     nodes::joins::equi_join::EquiJoinNode::new(
                         left_input_schema,
                         right_input_schema,
@@ -772,6 +933,8 @@ fn to_graph_rec<'a>(
                         left_key_selectors,
                         right_key_selectors,
                         args,
+                        left_runtime_filter.clone(),
+                        right_runtime_filter.clone(),
                         ctx.num_pipelines,
                     )?,
                     [
@@ -779,6 +942,7 @@ fn to_graph_rec<'a>(
                         (right_input_key, input_right.port),
                     ],
                 ),
+                _ => unreachable!(),
             }
         },
 
@@ -806,6 +970,117 @@ fn to_graph_rec<'a>(
             )
         },
 
+        MergeJoin {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            args,
+        } => {
+            let left_input_key = to_graph_rec(input_left.node, ctx)?;
+            let right_input_key = to_graph_rec(input_right.node, ctx)?;
+            let left_input_schema = ctx.phys_sm[input_left.node].output_schema.clone();
+            let right_input_schema = ctx.phys_sm[input_right.node].output_schema.clone();
+
+            ctx.graph.add_node(
+                nodes::joins::merge_join::MergeJoinNode::new(
+                    left_input_schema,
+                    right_input_schema,
+                    left_on.clone(),
+                    right_on.clone(),
+                    args.clone(),
+                )?,
+                [
+                    (left_input_key, input_left.port),
+                    (right_input_key, input_right.port),
+                ],
+            )
+        },
+
+        #[cfg(feature = "asof_join")]
+        AsOfJoin {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            args,
+        } => {
+            let left_input_key = to_graph_rec(input_left.node, ctx)?;
+            let right_input_key = to_graph_rec(input_right.node, ctx)?;
+            let left_input_schema = ctx.phys_sm[input_left.node].output_schema.clone();
+            let right_input_schema = ctx.phys_sm[input_right.node].output_schema.clone();
+
+            ctx.graph.add_node(
+                nodes::joins::asof::AsOfJoinNode::new(
+                    left_input_schema,
+                    right_input_schema,
+                    left_on.clone(),
+                    right_on.clone(),
+                    args.clone(),
+                )?,
+                [
+                    (left_input_key, input_left.port),
+                    (right_input_key, input_right.port),
+                ],
+            )
+        },
+
+        #[cfg(feature = "iejoin")]
+        RangeJoin {
+            input_left,
+            input_right,
+            left_on,
+            right_on,
+            options,
+            args,
+        } => {
+            let left_input_key = to_graph_rec(input_left.node, ctx)?;
+            let right_input_key = to_graph_rec(input_right.node, ctx)?;
+            let left_input_schema = ctx.phys_sm[input_left.node].output_schema.clone();
+            let right_input_schema = ctx.phys_sm[input_right.node].output_schema.clone();
+
+            let left_key_schema =
+                compute_output_schema(&left_input_schema, left_on, ctx.expr_arena)?;
+            let right_key_schema =
+                compute_output_schema(&right_input_schema, right_on, ctx.expr_arena)?;
+
+            // Both keys of a predicate are compared in their common supertype.
+            let key_dtypes = left_on
+                .iter()
+                .zip(right_on)
+                .map(|(l, r)| {
+                    let l_dtype = left_key_schema.get(l.output_name()).unwrap();
+                    let r_dtype = right_key_schema.get(r.output_name()).unwrap();
+                    try_get_supertype(l_dtype, r_dtype)
+                })
+                .try_collect_vec()?;
+
+            let left_key_selectors = left_on
+                .iter()
+                .map(|e| create_stream_expr(e, ctx, &left_input_schema))
+                .try_collect_vec()?;
+            let right_key_selectors = right_on
+                .iter()
+                .map(|e| create_stream_expr(e, ctx, &right_input_schema))
+                .try_collect_vec()?;
+
+            ctx.graph.add_node(
+                nodes::joins::range_join::RangeJoinNode::new(
+                    left_input_schema,
+                    right_input_schema,
+                    left_key_selectors,
+                    right_key_selectors,
+                    key_dtypes,
+                    options.clone(),
+                    args.clone(),
+                ),
+                [
+                    (left_input_key, input_left.port),
+                    (right_input_key, input_right.port),
+                ],
+            )
+        },
+
         #[cfg(feature = "merge_sorted")]
         MergeSorted {
             input_left,
@@ -1004,6 +1279,7 @@ fn to_graph_rec<'a>(
             let missing_columns_policy = MissingColumnsPolicy::Raise;
             let extra_columns_policy = ExtraColumnsPolicy::Ignore;
             let cast_columns_policy = CastColumnsPolicy::ERROR_ON_MISMATCH;
+            let runtime_filters = Vec::new();
             let verbose = config::verbose();
 
             ctx.graph.add_node(
@@ -1023,10 +1299,12 @@ fn to_graph_rec<'a>(
                         missing_columns_policy,
                         extra_columns_policy,
                         cast_columns_policy,
+                        runtime_filters,
                         // Initialized later
                         num_pipelines: AtomicUsize::new(0),
                         n_readers_pre_init: AtomicUsize::new(0),
                         max_concurrent_scans: AtomicUsize::new(0),
+                        cancel_token: OnceLock::new(),
                         verbose,
                     },
                 )),
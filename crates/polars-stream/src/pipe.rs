@@ -1,10 +1,12 @@
 use std::cmp::Reverse;
+use std::sync::Arc;
 
 use polars_error::PolarsResult;
+use polars_error::signals::CancellationToken;
 use polars_utils::priority::Priority;
 
 use crate::async_executor::{JoinHandle, TaskPriority, TaskScope};
-use crate::async_primitives::connector::{Receiver, Sender, connector};
+use crate::async_primitives::connector::{ConnectorObserver, Receiver, Sender, connector};
 use crate::async_primitives::distributor_channel::distributor_channel;
 use crate::async_primitives::linearizer::Linearizer;
 use crate::async_primitives::wait_group::WaitGroup;
@@ -22,10 +24,29 @@ pub enum PhysicalPipe {
     Initialized,
 }
 
-pub struct SendPort<'a>(&'a mut PhysicalPipe);
-pub struct RecvPort<'a>(&'a mut PhysicalPipe);
+pub type MorselObserver = Arc<dyn ConnectorObserver<Morsel>>;
+
+pub struct SendPort<'a>(&'a mut PhysicalPipe, Option<MorselObserver>);
+pub struct RecvPort<'a>(
+    &'a mut PhysicalPipe,
+    Option<MorselObserver>,
+    Option<CancellationToken>,
+);
 
 impl RecvPort<'_> {
+    /// Reports every morsel received through this port to `observer`.
+    pub fn with_observer(mut self, observer: Option<MorselObserver>) -> Self {
+        self.1 = observer;
+        self
+    }
+
+    /// Stops handing out morsels through this port once `cancel_token` is
+    /// cancelled, as if the sending side was done.
+    pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
+        self.2 = Some(cancel_token);
+        self
+    }
+
     pub fn serial(self) -> Receiver<Morsel> {
         self.serial_with_maintain_order(true)
     }
@@ -34,7 +55,9 @@ impl RecvPort<'_> {
         let PhysicalPipe::Uninit(num_pipelines) = self.0 else {
             unreachable!()
         };
-        let (send, recv) = connector();
+        let (send, mut recv) = connector();
+        recv.set_observer(self.1);
+        recv.set_cancel_token(self.2);
         *self.0 = PhysicalPipe::SerialReceiver(*num_pipelines, send, maintain_order);
         recv
     }
@@ -43,21 +66,32 @@ impl RecvPort<'_> {
         let PhysicalPipe::Uninit(num_pipelines) = self.0 else {
             unreachable!()
         };
-        let (senders, receivers): (Vec<Sender<Morsel>>, Vec<Receiver<Morsel>>) =
+        let (senders, mut receivers): (Vec<Sender<Morsel>>, Vec<Receiver<Morsel>>) =
             (0..*num_pipelines).map(|_| connector()).unzip();
+        for recv in &mut receivers {
+            recv.set_observer(self.1.clone());
+            recv.set_cancel_token(self.2.clone());
+        }
         *self.0 = PhysicalPipe::ParallelReceiver(senders);
         receivers
     }
 }
 
 impl SendPort<'_> {
+    /// Reports the time spent waiting on back-pressure when sending through
+    /// this port to `observer`.
+    pub fn with_observer(mut self, observer: Option<MorselObserver>) -> Self {
+        self.1 = observer;
+        self
+    }
+
     #[allow(unused)]
     pub fn is_receiver_serial(&self) -> bool {
         matches!(self.0, PhysicalPipe::SerialReceiver(..))
     }
 
     pub fn serial(self) -> Sender<Morsel> {
-        match core::mem::replace(self.0, PhysicalPipe::Uninit(0)) {
+        let mut send = match core::mem::replace(self.0, PhysicalPipe::Uninit(0)) {
             PhysicalPipe::SerialReceiver(_, send, _) => {
                 *self.0 = PhysicalPipe::Initialized;
                 send
@@ -68,11 +102,13 @@ impl SendPort<'_> {
                 send
             },
             _ => unreachable!(),
-        }
+        };
+        send.set_observer(self.1);
+        send
     }
 
     pub fn parallel(self) -> Vec<Sender<Morsel>> {
-        match core::mem::replace(self.0, PhysicalPipe::Uninit(0)) {
+        let mut senders = match core::mem::replace(self.0, PhysicalPipe::Uninit(0)) {
             PhysicalPipe::SerialReceiver(num_pipelines, send, maintain_order) => {
                 let (senders, receivers): (Vec<Sender<Morsel>>, Vec<Receiver<Morsel>>) =
                     (0..num_pipelines).map(|_| connector()).unzip();
@@ -84,7 +120,11 @@ impl SendPort<'_> {
                 senders
             },
             _ => unreachable!(),
+        };
+        for send in &mut senders {
+            send.set_observer(self.1.clone());
         }
+        senders
     }
 }
 
@@ -98,7 +138,7 @@ impl PhysicalPipe {
             matches!(self, Self::Uninit(_)),
             "PhysicalPipe::recv_port can only be called on an uninitialized pipe"
         );
-        RecvPort(self)
+        RecvPort(self, None, None)
     }
 
     pub fn send_port(&mut self) -> SendPort<'_> {
@@ -106,7 +146,7 @@ impl PhysicalPipe {
             matches!(self, Self::SerialReceiver(..) | Self::ParallelReceiver(..)),
             "PhysicalPipe::send_port must be called on a pipe which only has its receive port initialized"
         );
-        SendPort(self)
+        SendPort(self, None)
     }
 
     pub fn spawn<'env, 's>(
@@ -2,15 +2,17 @@
 use std::cmp::Reverse;
 
 use polars_core::POOL;
+use polars_core::error::signals::CancellationToken;
 use polars_core::prelude::*;
 use polars_expr::planner::{ExpressionConversionState, create_physical_expr, get_expr_depth_limit};
 use polars_plan::plans::{Context, IR, IRPlan};
 use polars_plan::prelude::AExpr;
 use polars_plan::prelude::expr_ir::ExprIR;
 use polars_utils::arena::{Arena, Node};
-use slotmap::{SecondaryMap, SlotMap};
+use slotmap::{Key, SecondaryMap, SlotMap, SparseSecondaryMap};
 
 use crate::graph::{Graph, GraphNodeKey};
+use crate::metrics::GraphMetrics;
 use crate::physical_plan::{PhysNode, PhysNodeKey, PhysNodeKind, StreamingLowerIRContext};
 
 /// Executes the IR with the streaming engine.
@@ -24,12 +26,31 @@ use crate::physical_plan::{PhysNode, PhysNodeKey, PhysNodeKind, StreamingLowerIR
 ///
 /// Returned `DataFrame`s contain data only for memory sinks,
 /// `DataFrame`s corresponding to file sinks are empty.
+///
+/// The query stops with a `Cancelled` error once `cancel_token` is cancelled,
+/// and fails once its nodes hold more than `memory_limit` bytes. Without a
+/// limit it is taken from `POLARS_STREAMING_MEMORY_LIMIT`, if set.
 pub fn run_query(
     node: Node,
     ir_arena: &mut Arena<IR>,
     expr_arena: &mut Arena<AExpr>,
+    cancel_token: CancellationToken,
+    memory_limit: Option<usize>,
 ) -> PolarsResult<QueryResult> {
-    StreamingQuery::build(node, ir_arena, expr_arena)?.execute()
+    StreamingQuery::build(node, ir_arena, expr_arena)?
+        .with_cancel_token(cancel_token)
+        .with_memory_limit(memory_limit)
+        .execute()
+}
+
+/// Executes the IR with the streaming engine, collecting runtime statistics of
+/// every node in the physical plan.
+pub fn profile_query(
+    node: Node,
+    ir_arena: &mut Arena<IR>,
+    expr_arena: &mut Arena<AExpr>,
+) -> PolarsResult<(QueryResult, QueryProfile)> {
+    StreamingQuery::build(node, ir_arena, expr_arena)?.execute_with_profile(expr_arena)
 }
 
 /// Visualizes the physical plan as a dot graph.
@@ -57,6 +78,8 @@ pub struct StreamingQuery {
     root_phys_node: PhysNodeKey,
     phys_sm: SlotMap<PhysNodeKey, PhysNode>,
     phys_to_graph: SecondaryMap<PhysNodeKey, GraphNodeKey>,
+    cancel_token: CancellationToken,
+    memory_limit: Option<usize>,
 }
 
 impl StreamingQuery {
@@ -102,22 +125,69 @@ impl StreamingQuery {
             root_phys_node,
             phys_sm,
             phys_to_graph,
+            cancel_token: CancellationToken::new(),
+            memory_limit: None,
         };
 
         Ok(out)
     }
 
-    pub fn execute(self) -> PolarsResult<QueryResult> {
-        let StreamingQuery {
-            top_ir,
-            mut graph,
-            root_phys_node,
-            phys_sm,
-            phys_to_graph,
-        } = self;
+    /// Runs the query under `cancel_token`.
+    pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
+        self.cancel_token = cancel_token;
+        self
+    }
+
+    /// Limits the memory (in bytes) the nodes of the query may hold.
+    pub fn with_memory_limit(mut self, memory_limit: Option<usize>) -> Self {
+        self.memory_limit = memory_limit;
+        self
+    }
+
+    pub fn execute(mut self) -> PolarsResult<QueryResult> {
+        let results = self.execute_graph(None)?;
+        Ok(self.into_result(results))
+    }
 
+    /// Executes the query while profiling each node, the profile refers to
+    /// nodes by their ids in the physical plan.
+    pub fn execute_with_profile(
+        mut self,
+        expr_arena: &Arena<AExpr>,
+    ) -> PolarsResult<(QueryResult, QueryProfile)> {
+        let mut metrics = GraphMetrics::new(&self.graph);
+        let results = self.execute_graph(Some(&mut metrics))?;
+
+        let mut profiles = Vec::with_capacity(self.phys_to_graph.len());
+        let mut annotations = SecondaryMap::new();
+        for (phys_key, graph_key) in self.phys_to_graph.iter() {
+            let profile = metrics.node_profile(&self.graph, *graph_key);
+            annotations.insert(phys_key, profile.annotation());
+            profiles.push((phys_key.data().as_ffi(), profile));
+        }
+        let profile = QueryProfile {
+            nodes: crate::metrics::profiles_to_df(&profiles)?,
+            annotated_plan: crate::physical_plan::visualize_annotated_plan(
+                self.root_phys_node,
+                &self.phys_sm,
+                expr_arena,
+                &annotations,
+            ),
+        };
+        Ok((self.into_result(results), profile))
+    }
+
+    fn execute_graph(
+        &mut self,
+        metrics: Option<&mut GraphMetrics>,
+    ) -> PolarsResult<SparseSecondaryMap<GraphNodeKey, DataFrame>> {
         crate::async_executor::clear_task_wait_statistics();
-        let mut results = crate::execute::execute_graph(&mut graph)?;
+        let results = crate::execute::execute_graph(
+            &mut self.graph,
+            metrics,
+            self.cancel_token.clone(),
+            self.memory_limit,
+        )?;
 
         if std::env::var("POLARS_TRACK_WAIT_STATS").as_deref() == Ok("1") {
             let mut stats = crate::async_executor::get_task_wait_statistics();
@@ -127,6 +197,17 @@ impl StreamingQuery {
                 eprintln!("{}:{} - {:?}", loc.file(), loc.line(), wait_time);
             }
         }
+        Ok(results)
+    }
+
+    fn into_result(self, mut results: SparseSecondaryMap<GraphNodeKey, DataFrame>) -> QueryResult {
+        let StreamingQuery {
+            top_ir,
+            root_phys_node,
+            phys_sm,
+            phys_to_graph,
+            ..
+        } = self;
 
         match top_ir {
             IR::SinkMultiple { inputs } => {
@@ -135,7 +216,7 @@ impl StreamingQuery {
                     unreachable!();
                 };
 
-                Ok(QueryResult::Multiple(
+                QueryResult::Multiple(
                     sinks
                         .iter()
                         .map(|phys_node_key| {
@@ -144,17 +225,28 @@ impl StreamingQuery {
                                 .unwrap_or_else(DataFrame::empty)
                         })
                         .collect(),
-                ))
+                )
             },
-            _ => Ok(QueryResult::Single(
+            _ => QueryResult::Single(
                 results
                     .remove(phys_to_graph[root_phys_node])
                     .unwrap_or_else(DataFrame::empty),
-            )),
+            ),
         }
     }
 }
 
+/// The runtime profile of a query executed with the streaming engine.
+pub struct QueryProfile {
+    /// One row per node of the physical plan with its rows and morsels in
+    /// and out, start and end times, time spent running and blocked on
+    /// back-pressure, and peak memory usage. Times are in microseconds.
+    pub nodes: DataFrame,
+    /// The physical plan as a dot graph, with each node annotated with its
+    /// statistics.
+    pub annotated_plan: String,
+}
+
 pub enum QueryResult {
     Single(DataFrame),
     /// Collected to multiple in-memory sinks
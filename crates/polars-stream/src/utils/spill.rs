@@ -0,0 +1,176 @@
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::Mutex;
+use polars_core::config;
+use polars_core::frame::DataFrame;
+use polars_core::schema::Schema;
+use polars_error::{PolarsResult, polars_err, polars_warn};
+use polars_io::ipc::{IpcReader, IpcWriter};
+use polars_io::path_utils::POLARS_TEMP_DIR_BASE_PATH;
+use polars_io::{SerReader, SerWriter};
+use polars_utils::sys::MEMINFO;
+
+/// Setting this environment variable forces every spill-capable node to spill,
+/// this is mostly useful for testing.
+const FORCE_OOC: &str = "POLARS_FORCE_OOC";
+
+/// Returns the amount of bytes a single spill-capable node may keep buffered
+/// in memory before it starts spilling to disk.
+///
+/// Can be configured with `POLARS_STREAMING_SPILL_THRESHOLD`, otherwise (or if
+/// the variable isn't a valid integer) the [`default_spill_threshold`] is used.
+pub fn get_spill_threshold() -> usize {
+    if std::env::var(FORCE_OOC).is_ok() {
+        return 0;
+    }
+
+    if let Ok(v) = std::env::var("POLARS_STREAMING_SPILL_THRESHOLD") {
+        match v.parse() {
+            Ok(threshold) => return threshold,
+            Err(_) => polars_warn!(
+                "POLARS_STREAMING_SPILL_THRESHOLD must be an integer, got '{}'; \
+                falling back to the default",
+                v
+            ),
+        }
+    }
+
+    default_spill_threshold()
+}
+
+static DEFAULT_SPILL_THRESHOLD: OnceLock<usize> = OnceLock::new();
+
+/// Half of the system memory that was available when this was first called.
+///
+/// This is shared by the whole process: without a memory limit, the memory
+/// manager of each query compares what all of its nodes hold against it, so
+/// spill-capable nodes don't each get their own share.
+pub fn default_spill_threshold() -> usize {
+    *DEFAULT_SPILL_THRESHOLD.get_or_init(|| (MEMINFO.free() / 2) as usize)
+}
+
+/// Whether data with this schema can be written to a spill file.
+pub fn can_spill(schema: &Schema) -> bool {
+    // Objects can't be serialized to IPC.
+    !schema.iter_values().any(|dtype| dtype.contains_objects())
+}
+
+static SPILL_DIR_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A temporary directory that holds the spilled data of a single node.
+///
+/// The directory and everything in it is removed when this is dropped.
+pub struct SpillDir {
+    path: PathBuf,
+    file_counter: AtomicU64,
+}
+
+impl SpillDir {
+    pub fn try_new(operation_name: &str) -> PolarsResult<Self> {
+        let id = SPILL_DIR_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = POLARS_TEMP_DIR_BASE_PATH
+            .join("spill")
+            .join(operation_name)
+            .join(format!("{}-{id}", std::process::id()));
+
+        std::fs::create_dir_all(&path).map_err(|err| {
+            polars_err!(ComputeError: "failed to create spill directory {}: {err}", path.display())
+        })?;
+
+        if config::verbose() {
+            eprintln!("[{operation_name}]: spilling to {}", path.display());
+        }
+
+        Ok(Self {
+            path,
+            file_counter: AtomicU64::new(0),
+        })
+    }
+
+    /// Writes the [`DataFrame`] to a new file in this directory.
+    pub fn spill(&self, df: &mut DataFrame) -> PolarsResult<SpillFile> {
+        let id = self.file_counter.fetch_add(1, Ordering::Relaxed);
+        let path = self.path.join(format!("{id}.ipc"));
+        let file = File::create(&path)?;
+        IpcWriter::new(file).with_parallel(false).finish(df)?;
+        Ok(SpillFile { path })
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        // This is best-effort, a failure to clean up shouldn't fail the query.
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// A [`SpillDir`] that is only created once something actually gets spilled.
+pub struct LazySpillDir {
+    operation_name: &'static str,
+    dir: Mutex<Option<Arc<SpillDir>>>,
+}
+
+impl LazySpillDir {
+    pub fn new(operation_name: &'static str) -> Self {
+        Self {
+            operation_name,
+            dir: Mutex::new(None),
+        }
+    }
+
+    pub fn get(&self) -> PolarsResult<Arc<SpillDir>> {
+        let mut dir = self.dir.lock();
+        if dir.is_none() {
+            *dir = Some(Arc::new(SpillDir::try_new(self.operation_name)?));
+        }
+        Ok(dir.clone().unwrap())
+    }
+
+    /// Returns the directory if anything was spilled.
+    pub fn into_inner(self) -> Option<Arc<SpillDir>> {
+        self.dir.into_inner()
+    }
+}
+
+/// A [`DataFrame`] that was written to disk by a [`SpillDir`].
+pub struct SpillFile {
+    path: PathBuf,
+}
+
+impl SpillFile {
+    /// Reads the [`DataFrame`] back into memory and removes the file.
+    pub fn load(self) -> PolarsResult<DataFrame> {
+        self.read()
+    }
+
+    /// Reads the [`DataFrame`] back into memory, the file is kept until this
+    /// is dropped.
+    pub fn read(&self) -> PolarsResult<DataFrame> {
+        let file = File::open(&self.path)?;
+        IpcReader::new(file).set_rechunk(false).finish()
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// A [`DataFrame`] that might have been spilled to disk.
+pub enum MaybeSpilled {
+    InMemory(DataFrame),
+    Spilled(SpillFile),
+}
+
+impl MaybeSpilled {
+    pub fn load(self) -> PolarsResult<DataFrame> {
+        match self {
+            Self::InMemory(df) => Ok(df),
+            Self::Spilled(file) => file.load(),
+        }
+    }
+}
@@ -18,10 +18,22 @@ impl Bounds {
     }
 
     /// Create a new [`Bounds`] without checking input correctness.
-    pub(crate) fn new(start: i64, stop: i64) -> Self {
+    pub fn new(start: i64, stop: i64) -> Self {
         Bounds { start, stop }
     }
 
+    /// The start of the window.
+    #[inline]
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    /// The end of the window.
+    #[inline]
+    pub fn stop(&self) -> i64 {
+        self.stop
+    }
+
     /// Duration in unit for this Boundary
     #[inline]
     pub(crate) fn duration(&self) -> i64 {
@@ -40,7 +52,7 @@ impl Bounds {
     }
 
     #[inline]
-    pub(crate) fn is_member_entry(&self, t: i64, closed: ClosedWindow) -> bool {
+    pub fn is_member_entry(&self, t: i64, closed: ClosedWindow) -> bool {
         match closed {
             ClosedWindow::Right => t > self.start,
             ClosedWindow::Left => t >= self.start,
@@ -50,7 +62,7 @@ impl Bounds {
     }
 
     #[inline]
-    pub(crate) fn is_member_exit(&self, t: i64, closed: ClosedWindow) -> bool {
+    pub fn is_member_exit(&self, t: i64, closed: ClosedWindow) -> bool {
         match closed {
             ClosedWindow::Right => t <= self.stop,
             ClosedWindow::Left => t < self.stop,
@@ -59,14 +71,16 @@ impl Bounds {
         }
     }
 
+    /// Whether the window lies entirely before `t`.
     #[inline]
-    pub(crate) fn is_future(&self, t: i64, closed: ClosedWindow) -> bool {
+    pub fn is_future(&self, t: i64, closed: ClosedWindow) -> bool {
         match closed {
             ClosedWindow::Left | ClosedWindow::None => self.stop <= t,
             ClosedWindow::Both | ClosedWindow::Right => self.stop < t,
         }
     }
 
+    /// Whether the window lies entirely after `t`.
     #[inline]
     pub(crate) fn is_past(&self, t: i64, closed: ClosedWindow) -> bool {
         match closed {
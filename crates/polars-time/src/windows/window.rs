@@ -180,47 +180,31 @@ impl Window {
     ) -> PolarsResult<BoundsIter<'a>> {
         BoundsIter::new(*self, closed_window, boundary, tu, tz, start_by)
     }
-}
 
-pub struct BoundsIter<'a> {
-    window: Window,
-    // wrapping boundary
-    boundary: Bounds,
-    // boundary per window iterator
-    bi: Bounds,
-    tu: TimeUnit,
-    tz: Option<&'a Tz>,
-}
-impl<'a> BoundsIter<'a> {
-    fn new(
-        window: Window,
+    /// Returns the bounds of the first window for a time column that starts at `t`.
+    ///
+    /// The bounds of the windows after it are given by [`Window::get_next_bounds`].
+    pub fn get_first_bounds(
+        &self,
+        t: i64,
         closed_window: ClosedWindow,
-        boundary: Bounds,
         tu: TimeUnit,
-        tz: Option<&'a Tz>,
+        tz: Option<&Tz>,
         start_by: StartBy,
-    ) -> PolarsResult<Self> {
+    ) -> PolarsResult<Bounds> {
         let bi = match start_by {
             StartBy::DataPoint => {
-                let mut boundary = boundary;
                 let offset_fn = match tu {
                     TimeUnit::Nanoseconds => Duration::add_ns,
                     TimeUnit::Microseconds => Duration::add_us,
                     TimeUnit::Milliseconds => Duration::add_ms,
                 };
-                boundary.stop = offset_fn(&window.period, boundary.start, tz)?;
-                boundary
+                Bounds::new(t, offset_fn(&self.period, t, tz)?)
             },
             StartBy::WindowBound => match tu {
-                TimeUnit::Nanoseconds => {
-                    window.get_earliest_bounds_ns(boundary.start, closed_window, tz)?
-                },
-                TimeUnit::Microseconds => {
-                    window.get_earliest_bounds_us(boundary.start, closed_window, tz)?
-                },
-                TimeUnit::Milliseconds => {
-                    window.get_earliest_bounds_ms(boundary.start, closed_window, tz)?
-                },
+                TimeUnit::Nanoseconds => self.get_earliest_bounds_ns(t, closed_window, tz)?,
+                TimeUnit::Microseconds => self.get_earliest_bounds_us(t, closed_window, tz)?,
+                TimeUnit::Milliseconds => self.get_earliest_bounds_ms(t, closed_window, tz)?,
             },
             _ => {
                 {
@@ -247,7 +231,7 @@ impl<'a> BoundsIter<'a> {
                         ),
                     };
                     // find beginning of the week.
-                    let dt = from(boundary.start);
+                    let dt = from(t);
                     match tz {
                         #[cfg(feature = "timezones")]
                         Some(tz) => {
@@ -262,14 +246,14 @@ impl<'a> BoundsIter<'a> {
                                 Some(tz),
                             )?;
                             // apply the 'offset'
-                            let start = offset_fn(&window.offset, start, Some(tz))?;
+                            let start = offset_fn(&self.offset, start, Some(tz))?;
                             // make sure the first datapoint has a chance to be included
                             // and compute the end of the window defined by the 'period'
                             ensure_t_in_or_in_front_of_window(
-                                window.every,
-                                boundary.start,
+                                self.every,
+                                t,
                                 offset_fn,
-                                window.period,
+                                self.period,
                                 start,
                                 closed_window,
                                 Some(tz),
@@ -289,14 +273,14 @@ impl<'a> BoundsIter<'a> {
                             )
                             .unwrap();
                             // apply the 'offset'
-                            let start = offset_fn(&window.offset, start, None).unwrap();
+                            let start = offset_fn(&self.offset, start, None).unwrap();
                             // make sure the first datapoint has a chance to be included
                             // and compute the end of the window defined by the 'period'
                             ensure_t_in_or_in_front_of_window(
-                                window.every,
-                                boundary.start,
+                                self.every,
+                                t,
                                 offset_fn,
-                                window.period,
+                                self.period,
                                 start,
                                 closed_window,
                                 None,
@@ -306,6 +290,46 @@ impl<'a> BoundsIter<'a> {
                 }
             },
         };
+        Ok(bi)
+    }
+
+    /// Returns the bounds of the window that follows the given one.
+    pub fn get_next_bounds(
+        &self,
+        bounds: Bounds,
+        tu: TimeUnit,
+        tz: Option<&Tz>,
+    ) -> PolarsResult<Bounds> {
+        let offset_fn = match tu {
+            TimeUnit::Nanoseconds => Duration::add_ns,
+            TimeUnit::Microseconds => Duration::add_us,
+            TimeUnit::Milliseconds => Duration::add_ms,
+        };
+        let start = offset_fn(&self.every, bounds.start, tz)?;
+        let stop = offset_fn(&self.period, start, tz)?;
+        Ok(Bounds::new(start, stop))
+    }
+}
+
+pub struct BoundsIter<'a> {
+    window: Window,
+    // wrapping boundary
+    boundary: Bounds,
+    // boundary per window iterator
+    bi: Bounds,
+    tu: TimeUnit,
+    tz: Option<&'a Tz>,
+}
+impl<'a> BoundsIter<'a> {
+    fn new(
+        window: Window,
+        closed_window: ClosedWindow,
+        boundary: Bounds,
+        tu: TimeUnit,
+        tz: Option<&'a Tz>,
+        start_by: StartBy,
+    ) -> PolarsResult<Self> {
+        let bi = window.get_first_bounds(boundary.start, closed_window, tu, tz, start_by)?;
         Ok(Self {
             window,
             boundary,
@@ -322,22 +346,12 @@ impl Iterator for BoundsIter<'_> {
     fn next(&mut self) -> Option<Self::Item> {
         if self.bi.start < self.boundary.stop {
             let out = self.bi;
-            match self.tu {
-                // TODO: find some way to propagate error instead of unwrapping?
-                // Issue is that `next` needs to return `Option`.
-                TimeUnit::Nanoseconds => {
-                    self.bi.start = self.window.every.add_ns(self.bi.start, self.tz).unwrap();
-                    self.bi.stop = self.window.period.add_ns(self.bi.start, self.tz).unwrap();
-                },
-                TimeUnit::Microseconds => {
-                    self.bi.start = self.window.every.add_us(self.bi.start, self.tz).unwrap();
-                    self.bi.stop = self.window.period.add_us(self.bi.start, self.tz).unwrap();
-                },
-                TimeUnit::Milliseconds => {
-                    self.bi.start = self.window.every.add_ms(self.bi.start, self.tz).unwrap();
-                    self.bi.stop = self.window.period.add_ms(self.bi.start, self.tz).unwrap();
-                },
-            }
+            // TODO: find some way to propagate error instead of unwrapping?
+            // Issue is that `next` needs to return `Option`.
+            self.bi = self
+                .window
+                .get_next_bounds(self.bi, self.tu, self.tz)
+                .unwrap();
             Some(out)
         } else {
             None
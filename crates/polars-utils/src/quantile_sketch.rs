@@ -0,0 +1,191 @@
+use std::f64::consts::PI;
+
+/// Computing Extremely Accurate Quantiles Using t-Digests
+/// Ted Dunning, Otmar Ertl
+///
+/// This is the merging variant with the k1 scale function, which keeps the
+/// estimates most accurate near the tails. Sketches can be combined, so they
+/// can be built in parallel and merged afterwards.
+#[derive(Clone, Default)]
+pub struct QuantileSketch {
+    // Sorted by mean.
+    centroids: Vec<Centroid>,
+    // Not yet merged into the centroids, in arbitrary order.
+    unmerged: Vec<Centroid>,
+    min: f64,
+    max: f64,
+}
+
+#[derive(Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+const COMPRESSION: f64 = 200.0;
+const MAX_UNMERGED: usize = 1024;
+
+impl QuantileSketch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.centroids.is_empty() && self.unmerged.is_empty()
+    }
+
+    /// Add a value to the sketch, NaNs are ignored.
+    pub fn insert(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+
+        if self.is_empty() {
+            self.min = x;
+            self.max = x;
+        } else {
+            self.min = self.min.min(x);
+            self.max = self.max.max(x);
+        }
+        self.unmerged.push(Centroid {
+            mean: x,
+            weight: 1.0,
+        });
+        if self.unmerged.len() >= MAX_UNMERGED {
+            self.compress();
+        }
+    }
+
+    pub fn combine(&mut self, other: &QuantileSketch) {
+        if other.is_empty() {
+            return;
+        }
+
+        if self.is_empty() {
+            self.min = other.min;
+            self.max = other.max;
+        } else {
+            self.min = self.min.min(other.min);
+            self.max = self.max.max(other.max);
+        }
+        self.unmerged.extend_from_slice(&other.centroids);
+        self.unmerged.extend_from_slice(&other.unmerged);
+        if self.unmerged.len() >= MAX_UNMERGED {
+            self.compress();
+        }
+    }
+
+    /// Estimates the given quantile, returns None if the sketch is empty.
+    pub fn quantile(&mut self, q: f64) -> Option<f64> {
+        self.compress();
+        let (first, last) = (self.centroids.first()?, self.centroids.last()?);
+        if q <= 0.0 {
+            return Some(self.min);
+        }
+        if q >= 1.0 {
+            return Some(self.max);
+        }
+
+        let total: f64 = self.centroids.iter().map(|c| c.weight).sum();
+        let target = q * total;
+
+        // Values are assumed to be spread evenly around each centroid mean, so
+        // we interpolate between the centers of consecutive centroids and
+        // between the extremes and the outer centroids.
+        if target < first.weight / 2.0 {
+            let frac = target / (first.weight / 2.0);
+            return Some(self.min + frac * (first.mean - self.min));
+        }
+
+        let mut center = first.weight / 2.0;
+        for w in self.centroids.windows(2) {
+            let next_center = center + w[0].weight / 2.0 + w[1].weight / 2.0;
+            if target < next_center {
+                let frac = (target - center) / (next_center - center);
+                return Some(w[0].mean + frac * (w[1].mean - w[0].mean));
+            }
+            center = next_center;
+        }
+
+        let frac = ((target - center) / (last.weight / 2.0)).min(1.0);
+        Some(last.mean + frac * (self.max - last.mean))
+    }
+
+    fn compress(&mut self) {
+        if self.unmerged.is_empty() {
+            return;
+        }
+
+        let mut all = std::mem::take(&mut self.unmerged);
+        all.extend_from_slice(&self.centroids);
+        all.sort_unstable_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total: f64 = all.iter().map(|c| c.weight).sum();
+
+        // The k1 scale function, a centroid may span at most one unit of k.
+        let k = |q: f64| COMPRESSION / (2.0 * PI) * (2.0 * q - 1.0).asin();
+        let k_inv = |k: f64| ((k * 2.0 * PI / COMPRESSION).sin() + 1.0) / 2.0;
+        let q_limit = |q: f64| {
+            let next_k = k(q) + 1.0;
+            if next_k >= COMPRESSION / 4.0 {
+                1.0
+            } else {
+                k_inv(next_k)
+            }
+        };
+
+        self.centroids.clear();
+        let mut cur = all[0];
+        let mut weight_so_far = 0.0;
+        let mut limit = q_limit(0.0);
+        for next in &all[1..] {
+            let q = (weight_so_far + cur.weight + next.weight) / total;
+            if q <= limit {
+                let weight = cur.weight + next.weight;
+                cur.mean += (next.mean - cur.mean) * next.weight / weight;
+                cur.weight = weight;
+            } else {
+                weight_so_far += cur.weight;
+                self.centroids.push(cur);
+                cur = *next;
+                limit = q_limit(weight_so_far / total);
+            }
+        }
+        self.centroids.push(cur);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_quantile_sketch() {
+        let n = 100_000;
+        // A deterministic permutation of 0..n.
+        let values = (0..n).map(|i| ((i * 7919) % n) as f64);
+
+        let mut left = QuantileSketch::new();
+        let mut right = QuantileSketch::new();
+        for (i, v) in values.enumerate() {
+            if i % 3 == 0 {
+                left.insert(v);
+            } else {
+                right.insert(v);
+            }
+        }
+        left.combine(&right);
+
+        assert_eq!(left.quantile(0.0), Some(0.0));
+        assert_eq!(left.quantile(1.0), Some((n - 1) as f64));
+        for q in [0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999] {
+            let exact = q * (n - 1) as f64;
+            let est = left.quantile(q).unwrap();
+            assert!(
+                (est - exact).abs() <= 0.005 * n as f64,
+                "q={q}: {est} vs {exact}"
+            );
+        }
+
+        assert_eq!(QuantileSketch::new().quantile(0.5), None);
+    }
+}
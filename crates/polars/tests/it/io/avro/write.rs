@@ -8,7 +8,7 @@ use arrow::io::avro::avro_schema::write::{compress, write_block, write_metadata}
 use arrow::io::avro::write;
 use arrow::record_batch::RecordBatchT;
 use avro_schema::schema::{Field as AvroField, Record, Schema as AvroSchema};
-use polars::io::avro::{AvroReader, AvroWriter};
+use polars::io::avro::{AvroCodec, AvroReader, AvroWriter};
 use polars::io::{SerReader, SerWriter};
 use polars::prelude::df;
 use polars_error::PolarsResult;
@@ -432,6 +432,26 @@ fn test_write_and_read_with_compression() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_write_and_read_with_zstd() -> PolarsResult<()> {
+    let mut write_df = df!(
+        "i64" => &[1, 2],
+        "f64" => &[0.1, 0.2],
+        "string" => &["a", "b"]
+    )?;
+
+    let mut buf: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    AvroWriter::new(&mut buf)
+        .with_codec(Some(AvroCodec::Zstd))
+        .finish(&mut write_df)?;
+    buf.set_position(0);
+
+    let read_df = AvroReader::new(buf).finish()?;
+    assert!(write_df.equals(&read_df));
+
+    Ok(())
+}
+
 #[test]
 fn test_with_projection() -> PolarsResult<()> {
     let mut df = df!(
@@ -102,7 +102,7 @@ ColumnNameOrSelector: TypeAlias = Union[str, SelectorType]
 # User-facing string literal types
 # The following all have an equivalent Rust enum with the same name
 Ambiguous: TypeAlias = Literal["earliest", "latest", "raise", "null"]
-AvroCompression: TypeAlias = Literal["uncompressed", "snappy", "deflate"]
+AvroCompression: TypeAlias = Literal["uncompressed", "snappy", "deflate", "zstd"]
 CsvQuoteStyle: TypeAlias = Literal["necessary", "always", "non_numeric", "never"]
 CategoricalOrdering: TypeAlias = Literal["physical", "lexical"]
 CsvEncoding: TypeAlias = Literal["utf8", "utf8-lossy"]
@@ -129,7 +129,7 @@ PivotAgg: TypeAlias = Literal[
     "min", "max", "first", "last", "sum", "mean", "median", "len"
 ]
 QuantileMethod: TypeAlias = Literal[
-    "nearest", "higher", "lower", "midpoint", "linear", "equiprobable"
+    "nearest", "higher", "lower", "midpoint", "linear", "equiprobable", "approximate"
 ]
 RankMethod: TypeAlias = Literal["average", "min", "max", "dense", "ordinal", "random"]
 Roll: TypeAlias = Literal["raise", "forward", "backward"]
@@ -314,7 +314,7 @@ EngineType: TypeAlias = Union[
     Literal["auto", "in-memory", "streaming", "gpu"], "GPUEngine"
 ]
 
-PlanStage: TypeAlias = Literal["ir", "physical"]
+PlanStage: TypeAlias = Literal["ir", "physical", "profile"]
 
 FileSource: TypeAlias = Union[
     str,
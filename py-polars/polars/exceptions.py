@@ -12,6 +12,7 @@ try:
         PerformanceWarning,
         PolarsError,
         PolarsWarning,
+        QueryCancelledError,
         SchemaError,
         SchemaFieldNotFoundError,
         ShapeError,
@@ -71,6 +72,9 @@ except ImportError:
     class PanicException(PolarsError):  # type: ignore[no-redef, misc]
         """Exception raised when an unexpected state causes a panic in the underlying Rust library."""  # noqa: W505
 
+    class QueryCancelledError(ComputeError):  # type: ignore[no-redef, misc]
+        """Exception raised when a query is cancelled before it completes."""
+
     class SchemaError(PolarsError):  # type: ignore[no-redef, misc]
         """Exception raised when an unexpected schema mismatch causes an error."""
 
@@ -205,6 +209,7 @@ __all__ = [
     "NoRowsReturnedError",
     "OutOfBoundsError",
     "ParameterCollisionError",
+    "QueryCancelledError",
     "RowsError",
     "SQLInterfaceError",
     "SQLSyntaxError",
@@ -3888,9 +3888,13 @@ class Expr:
         ----------
         quantile
             Quantile between 0.0 and 1.0.
-        interpolation : {'nearest', 'higher', 'lower', 'midpoint', 'linear', 'equiprobable'}
+        interpolation : {'nearest', 'higher', 'lower', 'midpoint', 'linear', 'equiprobable', 'approximate'}
             Interpolation method.
 
+            With 'approximate' the streaming engine estimates the quantile of
+            numeric columns with bounded memory per group. Other engines may
+            compute it exactly, the same as 'nearest'.
+
         Examples
         --------
         >>> df = pl.DataFrame({"a": [0, 1, 2, 3, 4, 5]})
@@ -46,6 +46,10 @@ def _parquet_field_overwrites_to_dict(pqo: ParquetFieldOverwrites) -> dict[str,
     if pqo.required is not None:
         d["required"] = pqo.required
 
+    if pqo.bloom_filter:
+        fpp = pqo.bloom_filter_fpp if pqo.bloom_filter_fpp is not None else 0.05
+        d["bloom_filter"] = (pqo.bloom_filter_ndv, fpp)
+
     return d
 
 
@@ -108,6 +112,16 @@ class ParquetFieldOverwrites:
         dict[str, None | str] | None
     )  #: Arrow metadata added to the field before writing
     required: bool | None = None  #: Is the field not allowed to have missing values
+    bloom_filter: bool | None = None  #: Write a bloom filter for every row group
+    #
+    # Bloom filters allow readers to skip row groups that cannot contain a value. They
+    # can only be written for non-nested fields.
+    bloom_filter_ndv: int | None = None  #: Distinct values to size the filters for
+    #
+    # By default, every filter is sized for the distinct values in its row group.
+    bloom_filter_fpp: float | None = None  #: False positive probability of filters
+    #
+    # Defaults to 0.05.
 
     def __init__(
         self,
@@ -122,6 +136,9 @@ class ParquetFieldOverwrites:
         field_id: int | None = None,
         metadata: Mapping[str, None | str] | None = None,
         required: bool | None = None,
+        bloom_filter: bool | None = None,
+        bloom_filter_ndv: int | None = None,
+        bloom_filter_fpp: float | None = None,
     ) -> None:
         self.name = name
 
@@ -138,3 +155,12 @@ class ParquetFieldOverwrites:
         else:
             self.metadata = metadata
         self.required = required
+
+        if not bloom_filter and (
+            bloom_filter_ndv is not None or bloom_filter_fpp is not None
+        ):
+            msg = "`bloom_filter_ndv` and `bloom_filter_fpp` need `bloom_filter=True`"
+            raise ValueError(msg)
+        self.bloom_filter = bloom_filter
+        self.bloom_filter_ndv = bloom_filter_ndv
+        self.bloom_filter_fpp = bloom_filter_fpp
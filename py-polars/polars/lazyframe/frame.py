@@ -115,9 +115,10 @@ if TYPE_CHECKING:
     with contextlib.suppress(ImportError):  # Module not available when building docs
         from polars.polars import PyExpr, PyPartitioning
 
-    from polars import DataFrame, DataType, Expr
+    from polars import DataFrame, DataType, Expr, Series
     from polars._typing import (
         AsofJoinStrategy,
+        AvroCompression,
         ClosedInterval,
         ColumnNameOrSelector,
         CsvQuoteStyle,
@@ -134,6 +135,7 @@ if TYPE_CHECKING:
         MaintainOrderJoin,
         Orientation,
         ParquetMetadata,
+        PivotAgg,
         PlanStage,
         PolarsDataType,
         PythonDataType,
@@ -1413,10 +1415,12 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
             .. note::
                The GPU engine does not support streaming, if streaming
                is enabled then GPU execution is switched off.
-        plan_stage : {'ir', 'physical'}
+        plan_stage : {'ir', 'physical', 'profile'}
             Select the stage to display. Currently only the streaming engine has a
             separate physical stage, for the other engines both IR and physical are the
-            same.
+            same. The `'profile'` stage runs the (optimized) query on the streaming
+            engine and shows its physical plan, with every node annotated with the
+            runtime statistics that :meth:`profile` reports.
 
 
         Examples
@@ -1449,6 +1453,11 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
                 dot = _ldf.to_dot_streaming_phys(optimized)
             else:
                 dot = _ldf.to_dot(optimized)
+        elif plan_stage == "profile":
+            if engine != "streaming":
+                error_msg = "plan stage 'profile' requires engine='streaming'"
+                raise ValueError(error_msg)
+            dot = _ldf.to_dot_streaming_profile()
         else:
             error_msg = f"invalid plan stage '{plan_stage}'"
             raise TypeError(error_msg)
@@ -1877,6 +1886,13 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
 
         The units of the timings are microseconds.
 
+        With `engine="streaming"` the profiling information is collected per
+        node of the physical plan. Besides the `start` and `end` of each node,
+        it then contains the total time spent running the node (`busy`), the
+        time it spent blocked on its consumers (`blocked`), the number of rows
+        and morsels it received and sent, and its peak memory usage in bytes
+        if the node keeps track of it.
+
         Parameters
         ----------
         type_coercion
@@ -2022,7 +2038,10 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
         if _kwargs.get("post_opt_callback") is not None:
             # Only for testing
             callback = _kwargs.get("post_opt_callback")
-        df, timings = ldf.profile(callback)
+        if engine == "streaming" and callback is None:
+            df, timings = ldf.profile_streaming()
+        else:
+            df, timings = ldf.profile(callback)
         (df, timings) = wrap_df(df), wrap_df(timings)
 
         if show_plot:
@@ -2034,8 +2053,9 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
 
             _fig, ax = plt.subplots(1, figsize=figsize)
 
-            max_val = timings["end"][-1]
-            timings_ = timings.reverse()
+            timings_ = timings.filter(F.col("start").is_not_null())
+            max_val = timings_["end"].max()
+            timings_ = timings_.reverse()
 
             if max_val > 1e9:
                 unit = "s"
@@ -2050,7 +2070,7 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
                     F.col("node").str.slice(0, truncate_nodes) + "..."
                 )
 
-            max_in_unit = timings_["end"][0]
+            max_in_unit = timings_["end"].max()
             ax.barh(
                 timings_["node"],
                 width=timings_["end"] - timings_["start"],
@@ -3017,6 +3037,203 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
             return None
         return LazyFrame._from_pyldf(ldf)
 
+    @overload
+    def sink_avro(
+        self,
+        path: str | Path | IO[bytes] | PartitioningScheme,
+        *,
+        compression: AvroCompression | None = "uncompressed",
+        name: str = "",
+        maintain_order: bool = True,
+        storage_options: dict[str, Any] | None = None,
+        credential_provider: CredentialProviderFunction
+        | Literal["auto"]
+        | None = "auto",
+        retries: int = 2,
+        sync_on_close: SyncOnCloseMethod | None = None,
+        mkdir: bool = False,
+        lazy: Literal[False] = ...,
+        engine: EngineType = "auto",
+        optimizations: QueryOptFlags = DEFAULT_QUERY_OPT_FLAGS,
+    ) -> None: ...
+
+    @overload
+    def sink_avro(
+        self,
+        path: str | Path | IO[bytes] | PartitioningScheme,
+        *,
+        compression: AvroCompression | None = "uncompressed",
+        name: str = "",
+        maintain_order: bool = True,
+        storage_options: dict[str, Any] | None = None,
+        credential_provider: CredentialProviderFunction
+        | Literal["auto"]
+        | None = "auto",
+        retries: int = 2,
+        sync_on_close: SyncOnCloseMethod | None = None,
+        mkdir: bool = False,
+        lazy: Literal[True],
+        engine: EngineType = "auto",
+        optimizations: QueryOptFlags = DEFAULT_QUERY_OPT_FLAGS,
+    ) -> LazyFrame: ...
+
+    def sink_avro(
+        self,
+        path: str | Path | IO[bytes] | PartitioningScheme,
+        *,
+        compression: AvroCompression | None = "uncompressed",
+        name: str = "",
+        maintain_order: bool = True,
+        storage_options: dict[str, Any] | None = None,
+        credential_provider: CredentialProviderFunction
+        | Literal["auto"]
+        | None = "auto",
+        retries: int = 2,
+        sync_on_close: SyncOnCloseMethod | None = None,
+        mkdir: bool = False,
+        lazy: bool = False,
+        engine: EngineType = "auto",
+        optimizations: QueryOptFlags = DEFAULT_QUERY_OPT_FLAGS,
+    ) -> LazyFrame | None:
+        """
+        Evaluate the query in streaming mode and write to an Avro file.
+
+        This allows streaming results that are larger than RAM to be written to disk.
+
+        Parameters
+        ----------
+        path
+            File path to which the file should be written.
+        compression : {'uncompressed', 'snappy', 'deflate', 'zstd'}
+            Compression method. Defaults to "uncompressed".
+        name
+            Schema name. Defaults to empty string.
+        maintain_order
+            Maintain the order in which data is processed.
+            Setting this to `False` will be slightly faster.
+
+            .. warning::
+                This functionality is considered **unstable**. It may be changed at any
+                point without it being considered a breaking change.
+        storage_options
+            Options that indicate how to connect to a cloud provider.
+
+            The cloud providers currently supported are AWS, GCP, and Azure.
+            See supported keys here:
+
+            * `aws <https://docs.rs/object_store/latest/object_store/aws/enum.AmazonS3ConfigKey.html>`_
+            * `gcp <https://docs.rs/object_store/latest/object_store/gcp/enum.GoogleConfigKey.html>`_
+            * `azure <https://docs.rs/object_store/latest/object_store/azure/enum.AzureConfigKey.html>`_
+            * Hugging Face (`hf://`): Accepts an API key under the `token` parameter: \
+            `{'token': '...'}`, or by setting the `HF_TOKEN` environment variable.
+
+            If `storage_options` is not provided, Polars will try to infer the
+            information from environment variables.
+        credential_provider
+            Provide a function that can be called to provide cloud storage
+            credentials. The function is expected to return a dictionary of
+            credential keys along with an optional credential expiry time.
+
+            .. warning::
+                This functionality is considered **unstable**. It may be changed
+                at any point without it being considered a breaking change.
+        retries
+            Number of retries if accessing a cloud instance fails.
+        sync_on_close: { None, 'data', 'all' }
+            Sync to disk when before closing a file.
+
+            * `None` does not sync.
+            * `data` syncs the file contents.
+            * `all` syncs the file contents and metadata.
+
+            .. warning::
+                This functionality is considered **unstable**. It may be changed at any
+                point without it being considered a breaking change.
+        mkdir: bool
+            Recursively create all the directories in the path.
+
+            .. warning::
+                This functionality is considered **unstable**. It may be changed at any
+                point without it being considered a breaking change.
+        lazy: bool
+            Wait to start execution until `collect` is called.
+
+            .. warning::
+                This functionality is considered **unstable**. It may be changed at any
+                point without it being considered a breaking change.
+        engine
+            Select the engine used to process the query, optional.
+            At the moment, if set to `"auto"` (default), the query is run
+            using the polars streaming engine. Polars will also
+            attempt to use the engine set by the `POLARS_ENGINE_AFFINITY`
+            environment variable. If it cannot run the query using the
+            selected engine, the query is run using the polars streaming
+            engine.
+
+            .. note::
+               The GPU engine is currently not supported.
+        optimizations
+            The optimization passes done during query optimization.
+
+            This has no effect if `lazy` is set to `True`.
+
+            .. warning::
+                This functionality is considered **unstable**. It may be changed
+                at any point without it being considered a breaking change.
+
+        Returns
+        -------
+        DataFrame
+
+        Examples
+        --------
+        >>> lf = pl.scan_csv("/path/to/my_larger_than_ram_file.csv")  # doctest: +SKIP
+        >>> lf.sink_avro("out.avro")  # doctest: +SKIP
+        """
+        engine = _select_engine(engine)
+
+        from polars.io.cloud.credential_provider._builder import (
+            _init_credential_provider_builder,
+        )
+
+        credential_provider_builder = _init_credential_provider_builder(
+            credential_provider, path, storage_options, "sink_avro"
+        )
+        del credential_provider
+
+        if storage_options:
+            storage_options = list(storage_options.items())  # type: ignore[assignment]
+        else:
+            # Handle empty dict input
+            storage_options = None
+
+        target = _to_sink_target(path)
+        sink_options = {
+            "sync_on_close": sync_on_close or "none",
+            "maintain_order": maintain_order,
+            "mkdir": mkdir,
+        }
+
+        if compression is None:
+            compression = "uncompressed"
+
+        ldf = self._ldf.sink_avro(
+            target=target,
+            compression=compression,
+            name=name,
+            cloud_options=storage_options,
+            credential_provider=credential_provider_builder,
+            retries=retries,
+            sink_options=sink_options,
+        )
+
+        if not lazy:
+            ldf = ldf.with_optimizations(optimizations._pyoptflags)
+            ldf = LazyFrame._from_pyldf(ldf)
+            ldf.collect(engine=engine)
+            return None
+        return LazyFrame._from_pyldf(ldf)
+
     @overload
     def sink_csv(
         self,
@@ -7473,6 +7690,140 @@ naive plan: (run LazyFrame.explain(optimized=True) to see the optimized plan)
 
         return self._from_pyldf(self._ldf.unpivot(on, index, value_name, variable_name))
 
+    @unstable()
+    def pivot(
+        self,
+        on: str | Sequence[str],
+        on_columns: DataFrame | Series | Sequence[Any],
+        *,
+        index: str | Sequence[str],
+        values: str | Sequence[str] | None = None,
+        aggregate_function: PivotAgg | Expr | Sequence[Expr],
+        maintain_order: bool = True,
+        separator: str = "_",
+        unseen_on_values: Literal["raise", "ignore"] = "raise",
+    ) -> LazyFrame:
+        """
+        Create a spreadsheet-style pivot table over declared `on` values.
+
+        Unlike :meth:`DataFrame.pivot`, the values of `on` that become columns are
+        declared up front, so the schema of the result is known without reading
+        the data. The pivot is executed as a group-by on `index`, which the
+        streaming engine can run.
+
+        .. warning::
+            This functionality is considered **unstable**. It may be changed
+            at any point without it being considered a breaking change.
+
+        Parameters
+        ----------
+        on
+            The column(s) whose values will be used as the new columns of the output.
+        on_columns
+            The values of `on` to create columns for. A DataFrame with the `on`
+            columns, or a Series or sequence of values if there is a single `on`
+            column. The output columns are in this order.
+        index
+            The column(s) that remain from the input to the output. The output will
+            have one row for each unique combination of the `index`'s values.
+        values
+            The existing column(s) of values to aggregate under the new columns. If
+            None, all remaining columns not specified on `on` and `index` will be used.
+        aggregate_function
+            Choose from:
+
+            - A predefined aggregate function string, one of
+              {'min', 'max', 'sum', 'mean', 'median', 'len'}
+            - An expression to do the aggregation.
+            - Several expressions, each given a distinct name with `alias` that
+              is used as a prefix of the column names.
+
+            Aggregations that depend on the order of the rows, such as `first`,
+            are not supported.
+        maintain_order
+            Ensure the values of `index` are sorted by discovery order.
+        separator
+            Used as separator/delimiter in generated column names in case of multiple
+            `values` columns or aggregations.
+        unseen_on_values
+            What to do with rows whose `on` values were not declared in
+            `on_columns`.
+
+            - 'raise': Raise an error when they are encountered.
+            - 'ignore': Leave them out of the result.
+
+        Examples
+        --------
+        >>> lf = pl.LazyFrame(
+        ...     {
+        ...         "name": ["Cady", "Cady", "Karen", "Karen"],
+        ...         "subject": ["maths", "physics", "maths", "physics"],
+        ...         "test_1": [98, 99, 61, 58],
+        ...     }
+        ... )
+        >>> lf.pivot(
+        ...     "subject",
+        ...     ["maths", "physics"],
+        ...     index="name",
+        ...     aggregate_function="sum",
+        ... ).collect()
+        shape: (2, 3)
+        ┌───────┬───────┬─────────┐
+        │ name  ┆ maths ┆ physics │
+        │ ---   ┆ ---   ┆ ---     │
+        │ str   ┆ i64   ┆ i64     │
+        ╞═══════╪═══════╪═════════╡
+        │ Cady  ┆ 98    ┆ 99      │
+        │ Karen ┆ 61    ┆ 58      │
+        └───────┴───────┴─────────┘
+        """
+        on = [on] if isinstance(on, str) else list(on)
+        index = [index] if isinstance(index, str) else list(index)
+        if values is not None:
+            values = [values] if isinstance(values, str) else list(values)
+
+        if isinstance(on_columns, pl.Series):
+            on_columns = on_columns.to_frame(on[0])
+        elif not isinstance(on_columns, pl.DataFrame):
+            if len(on) == 1:
+                on_columns = pl.DataFrame({on[0]: on_columns})
+            else:
+                on_columns = pl.DataFrame(on_columns, schema=on, orient="row")
+
+        if isinstance(aggregate_function, str):
+            if aggregate_function == "sum":
+                aggregate_exprs = [F.element().sum()]
+            elif aggregate_function == "max":
+                aggregate_exprs = [F.element().max()]
+            elif aggregate_function == "min":
+                aggregate_exprs = [F.element().min()]
+            elif aggregate_function == "mean":
+                aggregate_exprs = [F.element().mean()]
+            elif aggregate_function == "median":
+                aggregate_exprs = [F.element().median()]
+            elif aggregate_function == "len":
+                aggregate_exprs = [F.len()]
+            else:
+                msg = f"invalid input for `aggregate_function` argument: {aggregate_function!r}"
+                raise ValueError(msg)
+        elif isinstance(aggregate_function, pl.Expr):
+            aggregate_exprs = [aggregate_function]
+        else:
+            aggregate_exprs = list(aggregate_function)
+
+        return self._from_pyldf(
+            self._ldf.pivot(
+                on,
+                on_columns._df,
+                index,
+                values,
+                [e._pyexpr for e in aggregate_exprs],
+                maintain_order,
+                separator,
+                unseen_on_values,
+            )
+        )
+
     def map_batches(
         self,
         function: Callable[[DataFrame], DataFrame],
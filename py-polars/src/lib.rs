@@ -341,6 +341,11 @@ fn polars(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
         py.get_type::<exceptions::OutOfBoundsError>(),
     )
     .unwrap();
+    m.add(
+        "QueryCancelledError",
+        py.get_type::<exceptions::QueryCancelledError>(),
+    )
+    .unwrap();
     m.add(
         "SQLInterfaceError",
         py.get_type::<exceptions::SQLInterfaceError>(),
@@ -1,12 +1,14 @@
 from __future__ import annotations
 
 import io
+from datetime import date, datetime
+from decimal import Decimal
 from typing import TYPE_CHECKING
 
 import pytest
 
 import polars as pl
-from polars.testing import assert_frame_equal
+from polars.testing import assert_frame_equal, assert_series_equal
 
 if TYPE_CHECKING:
     from pathlib import Path
@@ -14,7 +16,7 @@ if TYPE_CHECKING:
     from polars._typing import AvroCompression
 
 
-COMPRESSIONS = ["uncompressed", "snappy", "deflate"]
+COMPRESSIONS = ["uncompressed", "snappy", "deflate", "zstd"]
 
 
 @pytest.fixture
@@ -89,3 +91,210 @@ def test_with_name() -> None:
     read_df = pl.read_json(raw[raw.find(b"{") : raw.rfind(b"}") + 1])
 
     assert_frame_equal(expected, read_df)
+
+
+@pytest.mark.parametrize("compression", COMPRESSIONS)
+def test_scan_avro(compression: AvroCompression) -> None:
+    df = pl.DataFrame({"a": [1, 2, 3], "b": [True, False, None], "c": ["x", "y", "z"]})
+
+    f = io.BytesIO()
+    df.write_avro(f, compression=compression)
+    data = f.getvalue()
+
+    assert_frame_equal(pl.scan_avro(data).collect(), df)
+    assert_frame_equal(
+        pl.scan_avro(data).select("c", "a").collect(), df.select("c", "a")
+    )
+    assert_frame_equal(pl.scan_avro(data).head(2).collect(), df.head(2))
+    assert_frame_equal(pl.scan_avro(data).tail(1).collect(), df.tail(1))
+    assert_frame_equal(
+        pl.scan_avro(data, row_index_name="idx", row_index_offset=10)
+        .slice(1, 1)
+        .collect(),
+        df.with_row_index("idx", offset=10).slice(1, 1),
+    )
+    assert pl.scan_avro(data).select(pl.len()).item() == 3
+    assert pl.scan_avro(data).collect_schema() == df.schema
+
+
+@pytest.mark.write_disk
+def test_scan_avro_multiple_files(tmp_path: Path) -> None:
+    dfs = [
+        pl.DataFrame({"a": [1, 2], "b": ["x", "y"]}),
+        pl.DataFrame({"a": [3, 4, 5], "b": ["z", None, "w"]}),
+    ]
+    paths = [tmp_path / f"{i}.avro" for i in range(len(dfs))]
+    for df, path in zip(dfs, paths):
+        df.write_avro(path)
+
+    expected = pl.concat(dfs)
+    lf = pl.scan_avro(tmp_path / "*.avro")
+    assert_frame_equal(lf.collect(), expected)
+    assert_frame_equal(lf.slice(1, 3).collect(), expected.slice(1, 3))
+    assert_frame_equal(
+        pl.scan_avro(paths, n_rows=3, row_index_name="idx").collect(),
+        expected.head(3).with_row_index("idx"),
+    )
+    assert_frame_equal(
+        lf.filter(pl.col("a") > 2).collect(), expected.filter(pl.col("a") > 2)
+    )
+
+    out = pl.scan_avro(paths, include_file_paths="path").select("path").collect()
+    assert out["path"].to_list() == [str(paths[0])] * 2 + [str(paths[1])] * 3
+
+
+@pytest.mark.write_disk
+def test_scan_avro_hive(tmp_path: Path) -> None:
+    for part in [1, 2]:
+        (tmp_path / f"part={part}").mkdir()
+        df = pl.DataFrame({"a": [part * 10, part * 10 + 1]})
+        df.write_avro(tmp_path / f"part={part}" / "data.avro")
+
+    out = pl.scan_avro(tmp_path).filter(pl.col("part") == 2).collect()
+    expected = pl.DataFrame({"a": [20, 21], "part": [2, 2]})
+    assert_frame_equal(out, expected)
+
+
+@pytest.mark.parametrize("compression", COMPRESSIONS)
+def test_sink_avro(compression: AvroCompression) -> None:
+    df = pl.DataFrame(
+        {"a": list(range(1000)), "b": [str(i) if i % 3 else None for i in range(1000)]}
+    )
+
+    f = io.BytesIO()
+    df.lazy().sink_avro(f, compression=compression, name="my_record")
+    f.seek(0)
+
+    assert_frame_equal(pl.read_avro(f), df)
+    assert b'"name":"my_record"' in f.getvalue()
+
+
+def test_sink_avro_zstd_roundtrip() -> None:
+    df = pl.DataFrame(
+        {"a": list(range(1000)), "b": [str(i) if i % 3 else None for i in range(1000)]}
+    )
+
+    f = io.BytesIO()
+    df.lazy().sink_avro(f, compression="zstd")
+    data = f.getvalue()
+
+    assert b"zstandard" in data
+    assert_frame_equal(pl.read_avro(io.BytesIO(data)), df)
+    assert_frame_equal(pl.scan_avro(data).collect(), df)
+    assert_frame_equal(pl.scan_avro(data).slice(500, 10).collect(), df.slice(500, 10))
+    assert pl.scan_avro(data).select(pl.len()).item() == 1000
+
+
+@pytest.mark.parametrize("compression", COMPRESSIONS)
+def test_sink_avro_in_memory_engine(compression: AvroCompression) -> None:
+    df = pl.DataFrame({"a": [1, 2, 3], "b": ["x", None, "z"]})
+
+    streaming = io.BytesIO()
+    in_memory = io.BytesIO()
+    df.lazy().sink_avro(streaming, compression=compression)
+    df.lazy().sink_avro(in_memory, compression=compression, engine="in-memory")
+    streaming.seek(0)
+    in_memory.seek(0)
+
+    assert_frame_equal(pl.read_avro(streaming), df)
+    assert_frame_equal(pl.read_avro(in_memory), df)
+
+
+def test_sink_avro_sync_marker_per_file() -> None:
+    lf = pl.LazyFrame({"a": [1, 2, 3]})
+
+    # The sync marker makes up the last 16 bytes of a file.
+    markers = set()
+    for _ in range(2):
+        f = io.BytesIO()
+        lf.sink_avro(f)
+        markers.add(f.getvalue()[-16:])
+
+    assert len(markers) == 2
+
+
+def test_sink_avro_logical_types() -> None:
+    df = pl.DataFrame(
+        {
+            "date": [date(2020, 1, 1), None],
+            "datetime": [datetime(2020, 1, 1, 12), None],
+            "datetime_tz": [datetime(2020, 1, 1, 12), None],
+            "decimal": [Decimal("1.25"), None],
+            "struct": [{"x": 1, "y": "a"}, None],
+            "list": [[1, 2], None],
+        },
+        schema_overrides={
+            "datetime": pl.Datetime("ms"),
+            "datetime_tz": pl.Datetime("us", "UTC"),
+            "decimal": pl.Decimal(10, 2),
+        },
+    )
+
+    f = io.BytesIO()
+    df.lazy().sink_avro(f)
+    f.seek(0)
+
+    out = pl.read_avro(f)
+
+    # Time zone aware timestamps are written as UTC instants.
+    assert_frame_equal(out.drop("datetime_tz"), df.drop("datetime_tz"))
+    assert_series_equal(
+        out["datetime_tz"].dt.epoch("us"), df["datetime_tz"].dt.epoch("us")
+    )
+
+
+@pytest.mark.parametrize("time_zone", [None, "Europe/Amsterdam"])
+def test_sink_avro_nanosecond_datetime_raises(time_zone: str | None) -> None:
+    lf = pl.LazyFrame(
+        {"a": [datetime(2020, 1, 1, 12)]},
+        schema={"a": pl.Datetime("ns", time_zone)},
+    )
+
+    with pytest.raises(pl.exceptions.InvalidOperationError, match="Datetime"):
+        lf.sink_avro(io.BytesIO())
+
+    # Casting to microseconds first is the way out.
+    f = io.BytesIO()
+    lf.with_columns(pl.col("a").dt.cast_time_unit("us")).sink_avro(f)
+    f.seek(0)
+    assert_series_equal(
+        pl.read_avro(f)["a"].dt.epoch("us"), lf.collect()["a"].dt.epoch("us")
+    )
+
+
+def test_sink_avro_enum() -> None:
+    dtype = pl.Enum(["low", "mid", "high"])
+    df = pl.DataFrame({"a": ["high", None, "low"]}, schema={"a": dtype})
+
+    f = io.BytesIO()
+    df.lazy().sink_avro(f)
+    data = f.getvalue()
+
+    assert b'"symbols":["low","mid","high"]' in data
+    f.seek(0)
+    assert pl.read_avro(f)["a"].cast(pl.String).to_list() == ["high", None, "low"]
+
+
+def test_sink_avro_enum_invalid_symbol() -> None:
+    df = pl.DataFrame({"a": ["x y"]}, schema={"a": pl.Enum(["x y"])})
+
+    with pytest.raises(pl.exceptions.InvalidOperationError, match="Avro enum"):
+        df.lazy().sink_avro(io.BytesIO())
+
+
+@pytest.mark.write_disk
+def test_sink_avro_partitioned(tmp_path: Path) -> None:
+    df = pl.DataFrame({"k": [1, 2, 1, 2], "v": ["a", "b", "c", "d"]})
+
+    df.lazy().sink_avro(
+        pl.PartitionByKey(tmp_path, by="k", include_key=False),
+        compression="snappy",
+    )
+
+    for k in [1, 2]:
+        files = list((tmp_path / f"k={k}").iterdir())
+        assert [p.suffix for p in files] == [".avro"]
+        assert_frame_equal(
+            pl.read_avro(files[0]),
+            df.filter(pl.col("k") == k).drop("k"),
+        )
@@ -3,6 +3,7 @@ from __future__ import annotations
 import decimal
 import functools
 import io
+import re
 import warnings
 from datetime import date, datetime, time, timezone
 from decimal import Decimal
@@ -3358,3 +3359,168 @@ def test_field_overwrites_metadata() -> None:
     assert schema[2].metadata[b"struct"] == b"true"
     assert schema[2].type.fields[0].metadata[b"md"] == b"yes"
     assert schema[2].type.fields[1].metadata[b"md2"] == b"Yes!"
+
+
+@pytest.mark.parametrize(
+    ("predicate", "uses_bloom_filter"),
+    [
+        (pl.col.a == 12, True),
+        (pl.col.a == 50, True),
+        (pl.col.a.is_in([10, 14, 50]), True),
+        (pl.col.a.is_in([]), False),
+        (pl.col.a.is_in([11, None], nulls_equal=True), False),
+        (pl.col.a.eq_missing(None), False),
+        (pl.col.a == None, False),  # noqa: E711
+        (pl.col.b == "x3", True),
+        (pl.col.b.is_in(["x1", "y"]) & (pl.col.a == 11), True),
+    ],
+)
+def test_scan_parquet_membership_predicate(
+    monkeypatch: pytest.MonkeyPatch,
+    capfd: pytest.CaptureFixture[str],
+    predicate: pl.Expr,
+    uses_bloom_filter: bool,
+) -> None:
+    # Every row group holds the minimum and maximum of the column, so only the bloom
+    # filters can skip them.
+    df = pl.DataFrame(
+        {
+            "a": [v for i in range(5) for v in (0, 100, None if i == 2 else 10 + i)],
+            "b": [v for i in range(5) for v in ("a", "z0", f"x{i}")],
+        }
+    )
+    f = io.BytesIO()
+    df.lazy().sink_parquet(
+        f,
+        row_group_size=3,
+        field_overwrites=[
+            ParquetFieldOverwrites(name="a", bloom_filter=True),
+            ParquetFieldOverwrites(name="b", bloom_filter=True),
+        ],
+    )
+
+    monkeypatch.setenv("POLARS_VERBOSE", "1")
+    f.seek(0)
+    result = pl.scan_parquet(f).filter(predicate).collect()
+    assert_frame_equal(result, df.filter(predicate))
+
+    if uses_bloom_filter:
+        assert "Bloom filter pushdown" in capfd.readouterr().err
+
+
+@pytest.mark.parametrize(
+    ("predicate", "expected_row_groups"),
+    [
+        (pl.col.a == 15, 1),
+        (pl.col.a.is_in([12, 17, 1000]), 2),
+        (pl.col.b == "x3", 1),
+        (pl.col.b.is_in(["y", "z"]), 0),
+    ],
+)
+def test_parquet_bloom_filter(
+    monkeypatch: pytest.MonkeyPatch,
+    capfd: pytest.CaptureFixture[str],
+    predicate: pl.Expr,
+    expected_row_groups: int,
+) -> None:
+    # Every row group holds the minimum and maximum of the column, so the statistics
+    # can't be used to skip any of them.
+    df = pl.DataFrame(
+        {
+            "a": [v for i in range(10) for v in (0, 100, 10 + i)],
+            "b": [v for i in range(10) for v in ("a", "z0", f"x{i}")],
+        }
+    )
+    f = io.BytesIO()
+    df.lazy().sink_parquet(
+        f,
+        row_group_size=3,
+        field_overwrites=[
+            ParquetFieldOverwrites(name="a", bloom_filter=True),
+            ParquetFieldOverwrites(
+                name="b", bloom_filter=True, bloom_filter_ndv=8, bloom_filter_fpp=0.01
+            ),
+        ],
+    )
+
+    monkeypatch.setenv("POLARS_VERBOSE", "1")
+    f.seek(0)
+    result = pl.scan_parquet(f).filter(predicate).collect()
+    assert_frame_equal(result, df.filter(predicate))
+
+    captured = capfd.readouterr().err
+    assert (
+        f"Bloom filter pushdown: reading {expected_row_groups} / 10 row groups"
+        in captured
+    )
+
+
+@pytest.mark.parametrize(
+    "s",
+    [
+        pl.Series("a", [[1, 2], [3]]),
+        pl.Series("a", [True, False]),
+        pl.Series("a", [1, 2], dtype=pl.Decimal(10, 2)),
+        pl.Series("a", [1, 2], dtype=pl.Int128),
+        pl.Series("a", [None, None]),
+    ],
+)
+def test_parquet_bloom_filter_unsupported_type_raises(s: pl.Series) -> None:
+    with pytest.raises(pl.exceptions.InvalidOperationError, match="bloom filter"):
+        s.to_frame().lazy().sink_parquet(
+            io.BytesIO(),
+            field_overwrites=ParquetFieldOverwrites(name="a", bloom_filter=True),
+        )
+
+
+def test_parquet_bloom_filter_fpp_without_filter_raises() -> None:
+
+    with pytest.raises(ValueError, match="bloom_filter=True"):
+        ParquetFieldOverwrites(name="a", bloom_filter_fpp=0.1)
+
+
+@pytest.mark.parametrize(
+    "predicate",
+    [
+        pl.col.a == 5000,
+        pl.col.a.is_between(4000, 4200),
+        (pl.col.a < 100) | (pl.col.a > 9900),
+        pl.col.b == "5000",
+    ],
+)
+@pytest.mark.parametrize("parallel", ["columns", "prefiltered"])
+def test_parquet_page_index_pushdown(
+    monkeypatch: pytest.MonkeyPatch,
+    capfd: pytest.CaptureFixture[str],
+    predicate: pl.Expr,
+    parallel: ParallelStrategy,
+) -> None:
+    # Both columns are split into many pages, which have different boundaries.
+    df = pl.DataFrame(
+        {
+            "a": range(10_000),
+            "b": pl.Series(range(10_000)).cast(pl.String).sort(),
+            "c": [[i] for i in range(10_000)],
+        }
+    )
+    f = io.BytesIO()
+    df.write_parquet(f, data_page_size=1024, row_group_size=10_000)
+
+    monkeypatch.setenv("POLARS_VERBOSE", "1")
+    f.seek(0)
+    result = pl.scan_parquet(f, parallel=parallel).filter(predicate).collect()
+    expected = df.filter(predicate)
+    assert_frame_equal(result, expected)
+
+    captured = capfd.readouterr().err
+    m = re.search(r"Page index pushdown: reading (\d+) / 10000 rows", captured)
+    assert m is not None
+    assert expected.height <= int(m.group(1)) < 10_000
+
+    f.seek(0)
+    result = (
+        pl.scan_parquet(f, row_index_name="idx", parallel=parallel)
+        .filter(predicate)
+        .collect()
+    )
+    assert_frame_equal(result, expected.with_row_index("idx"))
@@ -34,6 +34,7 @@ io_types: list[IOType] = [
     {"ext": "jsonl", "scan": pl.scan_ndjson, "sink": pl.LazyFrame.sink_ndjson},
     {"ext": "parquet", "scan": pl.scan_parquet, "sink": pl.LazyFrame.sink_parquet},
     {"ext": "ipc", "scan": pl.scan_ipc, "sink": pl.LazyFrame.sink_ipc},
+    {"ext": "avro", "scan": pl.scan_avro, "sink": pl.LazyFrame.sink_avro},
 ]
 
 
@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+import threading
 from datetime import date, datetime
 from functools import reduce
 from inspect import signature
@@ -17,6 +18,7 @@ from polars.exceptions import (
     InvalidOperationError,
     PerformanceWarning,
     PolarsInefficientMapWarning,
+    QueryCancelledError,
 )
 from polars.testing import assert_frame_equal, assert_series_equal
 from tests.unit.conftest import FLOAT_DTYPES, NUMERIC_DTYPES
@@ -1611,3 +1613,26 @@ def test_join_where_bad_input_type() -> None:
         pl.col("dur") < pl.col("time"),
         pl.col("rev") < pl.col("cost"),
     ).collect()
+
+
+def test_collect_background_cancel() -> None:
+    started = threading.Event()
+    release = threading.Event()
+
+    def wait(s: pl.Series) -> pl.Series:
+        started.set()
+        release.wait(timeout=10)
+        return s
+
+    lf = pl.LazyFrame({"a": [1, 2, 3]}).select(
+        pl.col("a").map_batches(wait, return_dtype=pl.Int64)
+    )
+    query = lf.collect(background=True)
+    started.wait(timeout=10)
+    query.cancel()
+    release.set()
+
+    # Cancelling while the last operation is running still discards the result.
+    with pytest.raises(QueryCancelledError):
+        query.fetch_blocking()
+    assert issubclass(QueryCancelledError, pl.exceptions.ComputeError)
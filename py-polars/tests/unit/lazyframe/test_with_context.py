@@ -77,6 +77,28 @@ def test_streaming_11219() -> None:
     ).to_dict(as_series=False) == {"b": ["afoo", "cfoo", None]}
 
 
+@pytest.mark.xdist_group("streaming")
+def test_with_context_streaming() -> None:
+    lf = pl.LazyFrame({"a": [1, 2, 3], "b": ["a", "c", None]})
+    same_len = pl.LazyFrame({"a": [7, 8, 9], "c": [10, 20, 30]})
+    agg = pl.LazyFrame({"d": [1, 2, 5]}).select(pl.col("d").sum())
+
+    with pytest.deprecated_call():
+        context = lf.with_context([same_len, agg])
+    q = context.select("a", "b", pl.col("c") + pl.col("d"))
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+    assert q.collect(engine="streaming").to_dict(as_series=False) == {
+        "a": [1, 2, 3],
+        "b": ["a", "c", None],
+        "c": [18, 28, 38],
+    }
+
+    with pytest.deprecated_call():
+        context = lf.with_context(pl.LazyFrame({"c": [1, 2]}))
+    with pytest.raises(pl.exceptions.ShapeError, match="non-equal length"):
+        context.select("a", "c").collect(engine="streaming")
+
+
 def test_no_cse_in_with_context() -> None:
     df1 = pl.DataFrame(
         {
@@ -11,7 +11,12 @@ from polars.exceptions import ComputeError, DuplicateError
 from polars.testing import assert_frame_equal
 
 if TYPE_CHECKING:
-    from polars._typing import PivotAgg, PolarsIntegerType, PolarsTemporalType
+    from polars._typing import (
+        EngineType,
+        PivotAgg,
+        PolarsIntegerType,
+        PolarsTemporalType,
+    )
 
 
 def test_pivot() -> None:
@@ -577,3 +582,117 @@ def test_pivot_empty_index_dtypes(dtype: PolarsIntegerType) -> None:
     result = df.pivot(index="index", on="on", values="values")
     expected = pl.DataFrame({"index": index})
     assert_frame_equal(result, expected)
+
+
+@pytest.mark.parametrize("agg", ["sum", "min", "max", "mean", "median", "len"])
+@pytest.mark.parametrize("engine", ["in-memory", "streaming"])
+def test_pivot_lazy(agg: PivotAgg, engine: EngineType) -> None:
+    df = pl.DataFrame(
+        {
+            "ix": [1, 1, 2, 2, 1, 3, None],
+            "col": ["a", "a", "a", "b", "b", None, "b"],
+            "foo": [0, 1, 2, 2, 7, 1, None],
+            "bar": [0.5, 2.0, 0.0, None, 9.0, 4.0, 1.0],
+        }
+    )
+    expected = df.pivot("col", index="ix", aggregate_function=agg)
+    result = (
+        df.lazy()
+        .pivot("col", ["a", "b", None], index="ix", aggregate_function=agg)
+        .collect(engine=engine)
+    )
+    assert_frame_equal(result, expected)
+
+
+def test_pivot_lazy_multiple_on() -> None:
+    df = pl.DataFrame(
+        {
+            "ix": [1, 1, 2, 2],
+            "x": ["a", "b", "a", "b"],
+            "y": [1, 1, 1, 2],
+            "v": [1, 2, 3, 4],
+        }
+    )
+    expected = df.pivot(["x", "y"], index="ix", aggregate_function="sum")
+    result = (
+        df.lazy()
+        .pivot(
+            ["x", "y"],
+            [("a", 1), ("b", 1), ("b", 2)],
+            index="ix",
+            aggregate_function="sum",
+        )
+        .collect(engine="streaming")
+    )
+    assert_frame_equal(result, expected)
+
+
+def test_pivot_lazy_multiple_aggregations() -> None:
+    lf = pl.LazyFrame(
+        {
+            "ix": [1, 1, 2, 2, 2],
+            "col": ["a", "b", "a", "a", "b"],
+            "v": [1, 2, 3, 4, 5],
+        }
+    )
+    result = lf.pivot(
+        "col",
+        pl.Series(["a", "b"]),
+        index="ix",
+        aggregate_function=[
+            pl.element().sum().alias("sum"),
+            pl.element().max().alias("max"),
+        ],
+    )
+    expected = pl.DataFrame(
+        {
+            "ix": [1, 2],
+            "sum_a": [1, 7],
+            "sum_b": [2, 5],
+            "max_a": [1, 4],
+            "max_b": [2, 5],
+        }
+    )
+    assert result.collect_schema() == expected.schema
+    assert_frame_equal(result.collect(engine="streaming"), expected)
+
+    with pytest.raises(
+        pl.exceptions.InvalidOperationError, match="must be named with `alias`"
+    ):
+        lf.pivot(
+            "col",
+            ["a", "b"],
+            index="ix",
+            aggregate_function=[pl.element().sum(), pl.element().max()],
+        )
+
+    with pytest.raises(pl.exceptions.InvalidOperationError, match="not supported"):
+        lf.pivot(
+            "col", ["a", "b"], index="ix", aggregate_function=pl.element().first()
+        )
+
+
+@pytest.mark.parametrize("engine", ["in-memory", "streaming"])
+def test_pivot_lazy_unseen_on_values(engine: EngineType) -> None:
+    lf = pl.LazyFrame(
+        {
+            "ix": [1, 1, 2, 2],
+            "col": ["a", "b", "a", "c"],
+            "v": [1, 2, 3, 4],
+        }
+    )
+
+    with pytest.raises(ComputeError, match="were not declared"):
+        lf.pivot("col", ["a", "b"], index="ix", aggregate_function="sum").collect(
+            engine=engine
+        )
+
+    result = lf.pivot(
+        "col",
+        ["a", "b"],
+        index="ix",
+        aggregate_function="sum",
+        unseen_on_values="ignore",
+    ).collect(engine=engine)
+    expected = pl.DataFrame({"ix": [1, 2], "a": [1, 3], "b": [2, None]})
+    assert_frame_equal(result, expected)
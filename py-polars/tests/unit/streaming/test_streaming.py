@@ -14,7 +14,7 @@ from polars.exceptions import PolarsInefficientMapWarning
 from polars.testing import assert_frame_equal
 
 if TYPE_CHECKING:
-    from polars._typing import JoinStrategy
+    from polars._typing import JoinStrategy, WindowMappingStrategy
 
 pytestmark = pytest.mark.xdist_group("streaming")
 
@@ -411,3 +411,118 @@ def test_streaming_flag_21799() -> None:
         pl.LazyFrame({"a": 1}).collect(streaming=False)  # type: ignore[call-overload]
     with pytest.raises(DeprecationWarning):
         pl.LazyFrame({"a": 1}).collect(streaming=True)  # type: ignore[call-overload]
+
+
+@pytest.mark.parametrize("mapping_strategy", ["group_to_rows", "join", "explode"])
+def test_streaming_window_over(mapping_strategy: WindowMappingStrategy) -> None:
+    lf = pl.LazyFrame(
+        {
+            "g": [1, None, 2, 1, 3, None, 2, 1] * 100,
+            "h": ["a", "b"] * 400,
+            "x": list(range(800)),
+        }
+    )
+
+    for q in [
+        lf.select(
+            pl.col("x").sum().over("g", mapping_strategy=mapping_strategy),
+            pl.len().over("g", "h", mapping_strategy=mapping_strategy).alias("len"),
+        ),
+        lf.select(
+            (pl.col("x") - pl.col("x").min()).over(
+                pl.col("g") * 2, mapping_strategy=mapping_strategy
+            )
+        ),
+        lf.select((pl.col("x") * 2).over("g", "h", mapping_strategy=mapping_strategy)),
+    ]:
+        assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+@pytest.mark.parametrize("mapping_strategy", ["group_to_rows", "join", "explode"])
+def test_streaming_window_over_sorted(mapping_strategy: WindowMappingStrategy) -> None:
+    lf = pl.LazyFrame(
+        {
+            "g": [1, None, 2, 1, 3, None, 2, 1] * 100,
+            "x": list(range(800)),
+        }
+    ).sort("g", maintain_order=True)
+
+    q = lf.select(
+        a=pl.col("x").shift(1).over("g", mapping_strategy=mapping_strategy),
+        b=pl.col("x")
+        .cum_sum()
+        .over("g", order_by=-pl.col("x"), mapping_strategy=mapping_strategy),
+    )
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+    # Stops consuming its input once the slice is satisfied.
+    q = q.head(10)
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+def test_streaming_profile() -> None:
+    lf = pl.LazyFrame({"a": range(1000), "b": [1, 2, 3, 4] * 250})
+    q = lf.filter(pl.col("a") % 2 == 0).select(pl.col("a") * pl.col("b"))
+
+    df, profile = q.profile(engine="streaming")
+    assert_frame_equal(df, q.collect(engine="in-memory"))
+
+    assert profile.columns == [
+        "node",
+        "id",
+        "start",
+        "end",
+        "busy",
+        "blocked",
+        "rows_in",
+        "morsels_in",
+        "rows_out",
+        "morsels_out",
+        "peak_memory",
+    ]
+    assert profile.schema["start"] == pl.UInt64
+
+    nodes = {row["node"]: row for row in profile.iter_rows(named=True)}
+    assert nodes["in-memory-source"]["rows_in"] == 0
+    assert nodes["in-memory-source"]["rows_out"] == 1000
+    assert nodes["filter"]["rows_in"] == 1000
+    assert nodes["filter"]["rows_out"] == 500
+    assert nodes["in-memory-sink"]["rows_in"] == 500
+    assert nodes["in-memory-sink"]["rows_out"] == 0
+    assert nodes["in-memory-sink"]["peak_memory"] > 0
+    assert (profile["morsels_in"] <= profile["rows_in"]).all()
+    assert (profile["end"] >= profile["start"]).all()
+
+
+def test_streaming_memory_limit_exceeded(monkeypatch: Any) -> None:
+    monkeypatch.setenv("POLARS_STREAMING_MEMORY_LIMIT", "100000")
+    lf = pl.LazyFrame({"a": range(1_000_000)}).with_columns(b=pl.col("a") * 2)
+    with pytest.raises(
+        pl.exceptions.ComputeError, match="memory budget.*'in-memory-sink'"
+    ):
+        lf.collect(engine="streaming")
+
+
+def test_streaming_memory_limit_invalid(monkeypatch: Any) -> None:
+    monkeypatch.setenv("POLARS_STREAMING_MEMORY_LIMIT", "1GB")
+    lf = pl.LazyFrame({"a": [1, 2, 3]})
+    with pytest.raises(
+        pl.exceptions.InvalidOperationError, match="POLARS_STREAMING_MEMORY_LIMIT"
+    ):
+        lf.collect(engine="streaming")
+
+
+@pytest.mark.write_disk
+def test_streaming_memory_limit_spills(tmp_path: Path, monkeypatch: Any) -> None:
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_STREAMING_MEMORY_LIMIT", "2000000")
+    df = pl.DataFrame({"a": pl.int_range(1_000_000, eager=True).shuffle(seed=0)})
+    q = (
+        df.lazy()
+        .sort("a")
+        .with_row_index()
+        .filter(pl.col("index") % 1_000 == 0)
+        .drop("index")
+    )
+    expected = pl.DataFrame({"a": pl.int_range(0, 1_000_000, 1_000, eager=True)})
+    assert_frame_equal(q.collect(engine="streaming"), expected)
@@ -1,12 +1,15 @@
 from __future__ import annotations
 
-from typing import Any
+from typing import TYPE_CHECKING, Any
 
 import pytest
 
 import polars as pl
 from polars.testing import assert_frame_equal
 
+if TYPE_CHECKING:
+    from pathlib import Path
+
 pytestmark = pytest.mark.xdist_group("streaming")
 
 
@@ -89,3 +92,28 @@ def test_cse_expr_group_by() -> None:
             engine="streaming" if streaming else "in-memory",
         )
         assert_frame_equal(out, expected)
+
+
+@pytest.mark.write_disk
+def test_cse_cache_spill_streaming(tmp_path: Path, monkeypatch: Any) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "100")
+
+    n = 10_000
+    lf = pl.LazyFrame(
+        {"a": pl.int_range(n, eager=True) % 97, "b": pl.int_range(n, eager=True)}
+    ).with_columns(c=pl.col("b") * 2)
+
+    # The aggregated branch consumes the shared input in an earlier phase than
+    # the other branch, which has to buffer it in the meantime.
+    q = lf.join(lf.group_by("a").agg(pl.col("c").sum().alias("total")), on="a")
+    assert_frame_equal(
+        q.collect(engine="streaming"),
+        q.collect(engine="in-memory"),
+        check_row_order=False,
+    )
+
+    q = pl.concat([lf, lf.filter(pl.col("a") > 50).reverse()])
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
@@ -1,6 +1,6 @@
 from __future__ import annotations
 
-from datetime import date
+from datetime import date, datetime, timedelta
 from typing import TYPE_CHECKING, Any
 
 import numpy as np
@@ -14,6 +14,8 @@ from tests.unit.conftest import INTEGER_DTYPES
 if TYPE_CHECKING:
     from pathlib import Path
 
+    from polars._typing import ClosedInterval, Label, QuantileMethod, StartBy
+
 pytestmark = pytest.mark.xdist_group("streaming")
 
 
@@ -531,3 +533,232 @@ def test_streaming_group_by_all_null_21593() -> None:
 
     out = df.lazy().group_by(pl.all()).min().collect(engine="streaming")
     assert_frame_equal(df, out, check_row_order=False)
+
+
+@pytest.mark.write_disk
+def test_streaming_group_by_ooc_high_cardinality(
+    tmp_path: Path,
+    monkeypatch: Any,
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "1000")
+
+    n = 100_000
+    lf = pl.LazyFrame(
+        {
+            "k": pl.int_range(n, eager=True) * 7919 % 20_000,
+            "s": (pl.int_range(n, eager=True) % 5_000).cast(pl.String),
+            "v": pl.Series([None if i % 11 == 0 else i for i in range(n)]),
+        }
+    )
+    q = lf.group_by("k", "s").agg(
+        pl.col("v").sum().alias("sum"),
+        pl.col("v").mean().alias("mean"),
+        pl.col("v").min().alias("min"),
+        pl.col("v").first().alias("first"),
+        pl.len(),
+    )
+
+    assert_frame_equal(
+        q.collect(engine="streaming").sort("k", "s"),
+        q.collect(engine="in-memory").sort("k", "s"),
+    )
+
+
+@pytest.mark.write_disk
+def test_streaming_group_by_ooc_reduction_states(
+    tmp_path: Path,
+    monkeypatch: Any,
+    capfd: Any,
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "1000")
+    monkeypatch.setenv("POLARS_VERBOSE", "1")
+
+    # None of these reductions are order-sensitive, so the pre-aggregated
+    # groups get spilled rather than the rows.
+    n = 100_000
+    lf = pl.LazyFrame(
+        {
+            "k": pl.int_range(n, eager=True) * 7919 % 20_000,
+            "v": pl.Series([None if i % 11 == 0 else i for i in range(n)]),
+            "f": pl.Series([i * 37 % 1001 / 7 for i in range(n)]),
+            "b": pl.Series([None if i % 7 == 0 else i % 3 == 0 for i in range(n)]),
+        }
+    )
+    q = lf.group_by("k").agg(
+        pl.col("v").sum().alias("sum"),
+        pl.col("v").mean().alias("mean"),
+        pl.col("v").min().alias("min"),
+        pl.col("f").max().alias("max"),
+        pl.col("v").count().alias("count"),
+        pl.col("b").sum().alias("b_sum"),
+        pl.col("b").mean().alias("b_mean"),
+        pl.col("v").median().alias("median"),
+        pl.col("f").quantile(0.3, "nearest").alias("quantile"),
+        pl.len(),
+    )
+
+    assert_frame_equal(
+        q.collect(engine="streaming").sort("k"),
+        q.collect(engine="in-memory").sort("k"),
+    )
+    assert "[group_by]: spilling to" in capfd.readouterr().err
+
+
+@pytest.mark.parametrize("method", ["nearest", "higher", "lower", "midpoint", "linear"])
+def test_streaming_group_by_median_quantile(method: QuantileMethod) -> None:
+    n = 10_000
+    lf = pl.LazyFrame(
+        {
+            "k": pl.int_range(n, eager=True) * 7919 % 100,
+            "i": pl.Series([None if i % 13 == 0 else i * 31 % 977 for i in range(n)]),
+            "f": pl.Series([i * 37 % 1001 / 7 for i in range(n)], dtype=pl.Float32),
+            "b": pl.Series([i % 3 == 0 for i in range(n)]),
+        }
+    ).with_columns(
+        d=pl.col("i").cast(pl.Date),
+        du=pl.col("i").cast(pl.Duration("us")),
+    )
+    aggs = [
+        pl.all().exclude("k").median().name.suffix("_median"),
+        pl.all()
+        .exclude("k", "b")
+        .quantile(0.3, interpolation=method)
+        .name.suffix("_quantile"),
+    ]
+
+    for q in [lf.select(aggs), lf.group_by("k").agg(aggs)]:
+        assert_frame_equal(
+            q.collect(engine="streaming"),
+            q.collect(engine="in-memory"),
+            check_row_order=False,
+        )
+
+
+def test_streaming_approx_quantile() -> None:
+    n = 100_000
+    lf = pl.LazyFrame({"k": pl.int_range(n) % 3, "v": pl.int_range(n) * 7919 % n})
+    q = lf.group_by("k").agg(
+        pl.col("v").quantile(0.5, "approximate").alias("median"),
+        pl.col("v").quantile(0.9, "approximate").alias("quantile"),
+    )
+
+    out = q.collect(engine="streaming").sort("k")
+    expected = q.collect(engine="in-memory").sort("k")
+    assert_frame_equal(out, expected, check_exact=False, rtol=1e-2)
+
+    # Without the explicit method the streaming engine stays exact.
+    q = lf.group_by("k").agg(pl.col("v").quantile(0.9).alias("quantile"))
+    assert_frame_equal(
+        q.collect(engine="streaming"),
+        q.collect(engine="in-memory"),
+        check_row_order=False,
+    )
+
+
+def test_streaming_group_by_implode() -> None:
+    n = 10_000
+    lf = pl.LazyFrame(
+        {
+            "k": pl.int_range(n, eager=True) * 7919 % 1_000,
+            "i": pl.Series([None if i % 13 == 0 else i for i in range(n)]),
+            "s": (pl.int_range(n, eager=True) % 101).cast(pl.String),
+        }
+    )
+    q = lf.group_by("k").agg(
+        "i",
+        (pl.col("i") * 2).alias("i2"),
+        pl.col("s").implode(),
+        pl.col("i").agg_groups().alias("groups"),
+        pl.col("i").sum().alias("sum"),
+    )
+
+    assert_frame_equal(
+        q.collect(engine="streaming").sort("k"),
+        q.collect(engine="in-memory").sort("k"),
+    )
+
+    q = lf.select(pl.col("i").implode())
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+@pytest.mark.parametrize("closed", ["left", "right", "both", "none"])
+@pytest.mark.parametrize("label", ["left", "right", "datapoint"])
+@pytest.mark.parametrize("start_by", ["window", "datapoint", "monday"])
+@pytest.mark.parametrize("group_by", [None, "k"])
+def test_streaming_group_by_dynamic(
+    closed: ClosedInterval,
+    label: Label,
+    start_by: StartBy,
+    group_by: str | None,
+) -> None:
+    n = 5_000
+    lf = pl.LazyFrame(
+        {
+            "t": pl.datetime_range(
+                datetime(2024, 1, 1),
+                datetime(2024, 1, 1) + timedelta(minutes=17 * (n - 1)),
+                "17m",
+                eager=True,
+            ),
+            "k": pl.int_range(n, eager=True) % 3,
+            "v": pl.Series([None if i % 11 == 0 else i for i in range(n)]),
+        }
+    )
+    q = lf.group_by_dynamic(
+        "t",
+        every="1h",
+        period="3h",
+        offset="10m",
+        closed=closed,
+        label=label,
+        start_by=start_by,
+        include_boundaries=True,
+        group_by=group_by,
+    ).agg(
+        pl.col("v").sum().alias("sum"),
+        pl.col("v").mean().alias("mean"),
+        pl.col("v").first().alias("first"),
+        pl.col("v").max().alias("max"),
+        pl.len(),
+    )
+
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+@pytest.mark.parametrize("closed", ["left", "right", "both", "none"])
+@pytest.mark.parametrize("offset", [None, "-2d", "1d"])
+@pytest.mark.parametrize("group_by", [None, "k"])
+def test_streaming_rolling(
+    closed: ClosedInterval, offset: str | None, group_by: str | None
+) -> None:
+    n = 5_000
+    lf = pl.LazyFrame(
+        {
+            "t": pl.Series([i // 3 for i in range(n)]).cast(pl.Int64),
+            "k": pl.int_range(n, eager=True) % 2,
+            "v": pl.Series([None if i % 7 == 0 else i * 7919 % 100 for i in range(n)]),
+        }
+    ).with_columns(pl.col("t").cast(pl.Date))
+    q = lf.rolling(
+        "t", period="2d", offset=offset, closed=closed, group_by=group_by
+    ).agg(
+        pl.col("v").sum().alias("sum"),
+        pl.col("v").min().alias("min"),
+        pl.col("v").last().alias("last"),
+        pl.len(),
+    )
+
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+def test_streaming_group_by_dynamic_unsorted() -> None:
+    lf = pl.LazyFrame({"t": [3, 1, 2], "v": [1, 2, 3]})
+    q = lf.group_by_dynamic("t", every="1i").agg(pl.col("v").sum())
+    with pytest.raises(pl.exceptions.InvalidOperationError, match="not sorted"):
+        q.collect(engine="streaming")
@@ -13,7 +13,7 @@ from polars.testing import assert_frame_equal
 if TYPE_CHECKING:
     from pathlib import Path
 
-    from polars._typing import JoinStrategy
+    from polars._typing import AsofJoinStrategy, JoinStrategy, PolarsDataType
 
 pytestmark = pytest.mark.xdist_group("streaming")
 
@@ -358,3 +358,288 @@ def test_invert_order_full_join_22295() -> None:
     lf.join(lf, on=["value", "value_at"], how="full", coalesce=True).collect(
         engine="streaming"
     )
+
+
+@pytest.mark.write_disk
+@pytest.mark.parametrize("how", ["inner", "left", "right", "full"])
+@pytest.mark.parametrize("nulls_equal", [False, True])
+@pytest.mark.parametrize("coalesce", [None, True, False])
+def test_streaming_join_spill(
+    how: JoinStrategy,
+    nulls_equal: bool,
+    coalesce: bool | None,
+    tmp_path: Path,
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "1000")
+
+    n = 20_000
+    left = pl.LazyFrame(
+        {
+            "a": pl.Series(
+                [None if i % 1001 == 0 else i * 7919 % 6_000 for i in range(n)]
+            ),
+            "s": (pl.int_range(n, eager=True) * 17 % 2_000).cast(pl.String),
+            "c": pl.int_range(n, eager=True),
+        }
+    )
+    right = pl.LazyFrame(
+        {
+            "a": pl.Series(
+                [None if i % 997 == 0 else i * 13 % 8_000 for i in range(n)]
+            ),
+            "s": (pl.int_range(n, eager=True) * 3 % 3_000).cast(pl.String),
+            "d": pl.int_range(n, eager=True) * 2,
+        }
+    )
+
+    for on in ["a", ["s", "a"]]:
+        q = left.join(right, on=on, how=how, nulls_equal=nulls_equal, coalesce=coalesce)
+        assert_frame_equal(
+            q.collect(engine="streaming"),
+            q.collect(engine="in-memory"),
+            check_row_order=False,
+        )
+
+
+@pytest.mark.parametrize("strategy", ["backward", "forward", "nearest"])
+@pytest.mark.parametrize("by", [None, "g"])
+@pytest.mark.parametrize("tolerance", [None, 3])
+def test_streaming_join_asof(
+    strategy: AsofJoinStrategy,
+    by: str | None,
+    tolerance: int | None,
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "100")
+
+    n = 5_000
+    left = pl.LazyFrame(
+        {
+            "t": pl.int_range(n, eager=True) * 7 // 3,
+            "g": pl.int_range(n, eager=True) * 7919 % 5,
+            "a": pl.int_range(n, eager=True),
+        }
+    )
+    right = pl.LazyFrame(
+        {
+            "t": pl.int_range(n, eager=True) * 5 // 2,
+            "g": pl.int_range(n, eager=True) * 31 % 5,
+            "a": pl.int_range(n, eager=True) * 2,
+        }
+    )
+
+    q = left.join_asof(right, on="t", by=by, strategy=strategy, tolerance=tolerance)
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+@pytest.mark.parametrize("strategy", ["backward", "forward", "nearest"])
+def test_streaming_join_asof_sorted_per_group(
+    strategy: AsofJoinStrategy, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "100")
+
+    # The keys are only sorted within each group, not as a whole.
+    n = 2_000
+    left = pl.LazyFrame(
+        {
+            "g": pl.int_range(n, eager=True) // 500,
+            "t": pl.int_range(n, eager=True) % 500 * 3,
+            "a": pl.int_range(n, eager=True),
+        }
+    )
+    right = pl.LazyFrame(
+        {
+            "g": pl.int_range(n, eager=True) % 4,
+            "t": pl.int_range(n, eager=True) // 4 * 2,
+            "b": pl.int_range(n, eager=True),
+        }
+    )
+
+    q = left.join_asof(right, on="t", by="g", strategy=strategy, tolerance=5)
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+def test_streaming_join_asof_unsorted() -> None:
+    left = pl.LazyFrame({"t": [1, 3, 2], "a": [1, 2, 3]})
+    right = pl.LazyFrame({"t": [1, 2, 3], "b": [1, 2, 3]})
+
+    q = left.join_asof(right, on="t")
+    with pytest.raises(pl.exceptions.InvalidOperationError, match="sorted"):
+        q.collect(engine="in-memory")
+    with pytest.raises(pl.exceptions.InvalidOperationError, match="sorted"):
+        q.collect(engine="streaming")
+
+    # Without the check the streaming engine joins like the in-memory engine.
+    q = left.join_asof(right, on="t", check_sortedness=False)
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+def _sorted_join_inputs(
+    dtype: PolarsDataType, nulls_last: bool
+) -> tuple[pl.LazyFrame, pl.LazyFrame]:
+    def side(n: int, mul: int, mod: int) -> pl.LazyFrame:
+        i = pl.int_range(n)
+        k = pl.when(i % 37 == 0).then(None).otherwise(i * mul % mod)
+        return (
+            pl.LazyFrame({"i": pl.int_range(n, eager=True)})
+            .with_columns(k=k.cast(dtype))
+            .sort("k", nulls_last=nulls_last)
+        )
+
+    return side(3_000, 7919, 500), side(2_000, 31, 700)
+
+
+@pytest.mark.parametrize("how", ["inner", "left", "right", "full", "semi", "anti"])
+@pytest.mark.parametrize("nulls_last", [False, True])
+@pytest.mark.parametrize("dtype", [pl.Int64, pl.String])
+def test_streaming_merge_join(
+    how: JoinStrategy,
+    nulls_last: bool,
+    dtype: PolarsDataType,
+    monkeypatch: pytest.MonkeyPatch,
+) -> None:
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "100")
+    left, right = _sorted_join_inputs(dtype, nulls_last)
+
+    q = left.join(right, on="k", how=how)
+    graph = q.show_graph(raw_output=True, plan_stage="physical", engine="streaming")
+    assert isinstance(graph, str)
+    assert "merge-join" in graph
+    assert_frame_equal(
+        q.collect(engine="streaming"),
+        q.collect(engine="in-memory"),
+        check_row_order=False,
+    )
+
+
+@pytest.mark.parametrize("how", ["inner", "left", "semi", "anti"])
+def test_streaming_merge_join_maintain_order(
+    how: JoinStrategy, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "100")
+    left, right = _sorted_join_inputs(pl.Int64, nulls_last=True)
+
+    q = left.join(right, on="k", how=how, maintain_order="left")
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+def test_streaming_merge_join_unsorted() -> None:
+    left = pl.DataFrame({"k": [1, 2, 3], "a": [1, 2, 3]}).set_sorted("k").lazy()
+    right = pl.DataFrame({"k": [3, 1, 2], "b": [1, 2, 3]}).set_sorted("k").lazy()
+
+    q = left.join(right, on="k")
+    with pytest.raises(pl.exceptions.InvalidOperationError, match="sorted"):
+        q.collect(engine="streaming")
+
+
+@pytest.mark.parametrize("two_predicates", [False, True])
+def test_streaming_join_where(
+    two_predicates: bool, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "100")
+    rng = np.random.default_rng(0)
+    east = pl.LazyFrame(
+        {
+            "id": range(500),
+            "dur": rng.integers(0, 100, 500),
+            "rev": pl.Series(rng.integers(0, 100, 500)).scatter([3, 7], None),
+        }
+    )
+    west = pl.LazyFrame(
+        {
+            "id": range(300),
+            "time": rng.integers(0, 100, 300),
+            "cost": rng.integers(0, 100, 300),
+        }
+    )
+
+    predicates = [pl.col("dur") < pl.col("time")]
+    if two_predicates:
+        predicates.append(pl.col("rev") >= pl.col("cost"))
+    q = east.join_where(west, *predicates)
+
+    graph = q.show_graph(raw_output=True, plan_stage="physical", engine="streaming")
+    assert isinstance(graph, str)
+    assert "range-join" in graph
+    assert_frame_equal(
+        q.collect(engine="streaming"),
+        q.collect(engine="in-memory"),
+        check_row_order=False,
+    )
+
+
+@pytest.mark.write_disk
+@pytest.mark.parametrize("how", ["inner", "left", "right"])
+def test_streaming_join_runtime_filter(
+    how: JoinStrategy, tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "100")
+    n = 10_000
+    fact = pl.DataFrame(
+        {
+            "k": pl.int_range(n, eager=True) // 10,
+            "s": (pl.int_range(n, eager=True) * 7 % 300).cast(pl.String),
+            "v": pl.int_range(n, eager=True),
+        }
+    )
+    path = tmp_path / "fact.parquet"
+    fact.write_parquet(path, row_group_size=500)
+    dim = pl.LazyFrame(
+        {
+            "k": pl.Series([3, 5, 8, 412, 413, None, 5_000], dtype=pl.Int64),
+            "s": ["21", "35", "x", "84", "91", "10", "0"],
+            "w": range(7),
+        }
+    )
+
+    for on in ["k", ["k", "s"]]:
+        q = pl.scan_parquet(path).join(dim, on=on, how=how)
+        graph = q.show_graph(raw_output=True, plan_stage="physical", engine="streaming")
+        assert isinstance(graph, str)
+        assert ("runtime join filter" in graph) == (how != "left")
+        assert_frame_equal(
+            q.collect(engine="streaming"),
+            q.collect(engine="in-memory"),
+            check_row_order=False,
+        )
+
+
+@pytest.mark.write_disk
+@pytest.mark.parametrize("how", ["inner", "left", "full"])
+def test_streaming_join_spill_repartition(
+    how: JoinStrategy, tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+    # Spill partitions hold more rows than a morsel, so they get split again.
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "100")
+
+    n = 20_000
+    left = pl.LazyFrame(
+        {"a": pl.int_range(n, eager=True) * 7 % 5_000, "b": pl.int_range(n, eager=True)}
+    )
+    right = pl.LazyFrame(
+        {"a": pl.int_range(n, eager=True) * 3 % 7_000, "c": pl.int_range(n, eager=True)}
+    )
+
+    q = left.join(right, on="a", how=how)
+    assert_frame_equal(
+        q.collect(engine="streaming"),
+        q.collect(engine="in-memory"),
+        check_row_order=False,
+    )
+
+    # Spilled rows keep their computed keys rather than the key inputs.
+    q = left.join(
+        right, left_on=pl.col("a") % 1_000, right_on=pl.col("a") // 7, how=how
+    )
+    assert_frame_equal(
+        q.collect(engine="streaming"),
+        q.collect(engine="in-memory"),
+        check_row_order=False,
+    )
@@ -296,3 +296,87 @@ def test_sort_descending_nulls_last(descending: bool, nulls_last: bool) -> None:
         .collect(engine="streaming"),
         pl.DataFrame({"x": ref_x, "y": ref_y}),
     )
+
+
+@pytest.mark.write_disk
+@pytest.mark.parametrize("slice", [None, (1_000, 5_000), (-300, 200)])
+def test_ooc_sort_multiple_keys_maintain_order(
+    tmp_path: Path, monkeypatch: Any, slice: tuple[int, int] | None
+) -> None:
+    tmp_path.mkdir(exist_ok=True)
+    monkeypatch.setenv("POLARS_TEMP_DIR", str(tmp_path))
+    monkeypatch.setenv("POLARS_FORCE_OOC", "1")
+    monkeypatch.setenv("POLARS_IDEAL_MORSEL_SIZE", "1000")
+
+    n = 50_000
+    df = pl.DataFrame(
+        {
+            "a": pl.int_range(n, eager=True) * 7919 % 100,
+            "b": pl.Series([None if i % 13 == 0 else i % 17 for i in range(n)]),
+            "c": pl.int_range(n, eager=True).cast(pl.String),
+        }
+    )
+
+    q = df.lazy().sort(
+        [pl.col("b"), pl.col("a") % 10],
+        descending=[True, False],
+        nulls_last=True,
+        maintain_order=True,
+    )
+    if slice is not None:
+        q = q.slice(*slice)
+
+    assert_frame_equal(
+        q.collect(engine="streaming"),
+        q.collect(engine="in-memory"),
+    )
+
+
+@pytest.mark.parametrize("descending", [False, True, [True, False]])
+@pytest.mark.parametrize("nulls_last", [False, True])
+@pytest.mark.parametrize("slice", [(0, 10), (100, 1_000), (0, 0), (0, 100_000)])
+def test_streaming_top_k(
+    descending: bool | list[bool], nulls_last: bool, slice: tuple[int, int]
+) -> None:
+    n = 20_000
+    lf = pl.LazyFrame(
+        {
+            "a": [None if i % 13 == 0 else i * 7919 % 100 for i in range(n)],
+            "b": [None if i % 11 == 0 else i % 17 / 3 for i in range(n)],
+            "c": pl.int_range(n, eager=True).cast(pl.String),
+        }
+    )
+    q = lf.sort(
+        ["a", "b"], descending=descending, nulls_last=nulls_last, maintain_order=True
+    ).slice(*slice)
+
+    graph = q.show_graph(raw_output=True, plan_stage="physical", engine="streaming")
+    assert isinstance(graph, str)
+    # Large slices are sorted instead, as the top-k node keeps them in memory.
+    assert ("top-k" in graph) == (sum(slice) <= 1 << 16)
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+def test_streaming_top_k_bottom_k() -> None:
+    lf = pl.LazyFrame(
+        {
+            "a": [i * 7919 % 1000 for i in range(10_000)],
+            "b": pl.int_range(10_000, eager=True).cast(pl.String),
+        }
+    )
+    for q in [
+        lf.top_k(20, by=["a", "b"]),
+        lf.bottom_k(20, by=["a", "b"]),
+        lf.top_k(20, by=[pl.col("a") % 7 * 1000 + pl.col("a"), "b"]),
+        lf.sort("a", "b").head(5),
+    ]:
+        assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+def test_sort_invalid_spill_threshold(monkeypatch: Any) -> None:
+    monkeypatch.setenv("POLARS_STREAMING_SPILL_THRESHOLD", "1GB")
+
+    df = pl.DataFrame({"a": [3, 1, 2]})
+    with pytest.warns(UserWarning, match="POLARS_STREAMING_SPILL_THRESHOLD"):
+        out = df.lazy().sort("a").collect(engine="streaming")
+    assert_frame_equal(out, df.sort("a"))
@@ -10,6 +10,8 @@ from polars.testing import assert_frame_equal
 if TYPE_CHECKING:
     from pathlib import Path
 
+    from polars._typing import UniqueKeepStrategy
+
 pytestmark = pytest.mark.xdist_group("streaming")
 
 
@@ -53,3 +55,57 @@ def test_streaming_unique(monkeypatch: Any, capfd: Any) -> None:
     assert_frame_equal(q.collect(engine="old-streaming"), q.collect(engine="in-memory"))  # type: ignore[call-overload]
     (_, err) = capfd.readouterr()
     assert "df -> re-project-sink -> sort_multiple" in err
+
+
+@pytest.mark.parametrize("keep", ["first", "last", "any", "none"])
+@pytest.mark.parametrize("subset", [None, ["k"], ["k", "v"]])
+def test_streaming_unique_maintain_order(
+    keep: UniqueKeepStrategy, subset: list[str] | None
+) -> None:
+    n = 10_000
+    lf = pl.LazyFrame(
+        {
+            "k": [
+                i if i >= n - 20 else None if i % 17 == 0 else (i * 7) % 113
+                for i in range(n)
+            ],
+            "v": [i % 3 for i in range(n)],
+            "i": range(n),
+        }
+    )
+    if subset is None:
+        lf = lf.select("k", "v")
+    q = lf.unique(subset=subset, keep=keep, maintain_order=True)
+
+    # Only keeping the last row needs the dedicated distinct node, the others
+    # are lowered to an order-preserving group by.
+    graph = q.show_graph(raw_output=True, plan_stage="physical", engine="streaming")
+    assert isinstance(graph, str)
+    assert ("distinct" in graph) == (keep == "last")
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+    q = q.slice(3, 20)
+    assert_frame_equal(q.collect(engine="streaming"), q.collect(engine="in-memory"))
+
+
+def test_streaming_unique_keep_last_event_log() -> None:
+    lf = pl.LazyFrame(
+        {
+            "id": [1, 2, 1, 3, 2, 1],
+            "event": ["a", "b", "c", "d", "e", "f"],
+        }
+    )
+    q = lf.unique(subset="id", keep="last", maintain_order=True)
+    assert_frame_equal(
+        q.collect(engine="streaming"),
+        pl.DataFrame({"id": [3, 2, 1], "event": ["d", "e", "f"]}),
+    )
+
+
+def test_streaming_unique_keep_last_many_morsels() -> None:
+    # Every key is superseded many times, so the kept rows get compacted.
+    n = 200_000
+    lf = pl.LazyFrame({"k": [i % 1000 for i in range(n)], "i": range(n)})
+    q = lf.unique(subset="k", keep="last", maintain_order=True)
+    expected = pl.DataFrame({"k": range(1000), "i": range(n - 1000, n)})
+    assert_frame_equal(q.collect(engine="streaming"), expected)
@@ -42,6 +42,19 @@ def test_show_graph_phys_not_streaming(query: pl.LazyFrame) -> None:
     assert out_ir == out_phys
 
 
+def test_show_graph_profile_streaming(query: pl.LazyFrame) -> None:
+    out = query.show_graph(raw_output=True, plan_stage="profile", engine="streaming")
+    assert isinstance(out, str)
+    assert "in-memory-source" in out
+    assert "rows: 0 -> 6" in out
+    assert "busy: " in out
+
+
+def test_show_graph_profile_requires_streaming(query: pl.LazyFrame) -> None:
+    with pytest.raises(ValueError, match="requires engine='streaming'"):
+        query.show_graph(raw_output=True, plan_stage="profile", engine="in-memory")
+
+
 def test_show_graph_invalid_stage(query: pl.LazyFrame) -> None:
     with pytest.raises(TypeError, match="invalid plan stage 'invalid-stage'"):
         query.show_graph(raw_output=True, plan_stage="invalid-stage")  # type: ignore[arg-type]